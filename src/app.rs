@@ -1,25 +1,43 @@
+pub mod batch;
+pub mod commands;
 pub mod data;
+pub mod export;
 pub mod filter;
+pub mod graph_cursor;
 pub mod layout_manager;
 pub mod states;
+pub mod time_travel;
 
-use std::time::Instant;
+use std::{path::PathBuf, time::Instant};
 
 use concat_string::concat_string;
 use data::*;
 use filter::*;
+use graph_cursor::GraphCursorState;
 use layout_manager::*;
 use rustc_hash::FxHashMap as HashMap;
 pub use states::*;
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
+#[cfg(target_os = "linux")]
+use crate::collection::SystemRoots;
 use crate::{
     canvas::{
-        components::time_graph::LegendPosition, dialogs::process_kill_dialog::ProcessKillDialog,
+        capture::CaptureFormat,
+        components::{data_table::ColumnPicker, time_graph::LegendPosition},
+        dialogs::process_kill_dialog::ProcessKillDialog,
+    },
+    collection::{
+        custom::CustomMetric,
+        processes::{Pid, ProcessFields},
+        schedule::CollectionRates,
     },
     constants,
-    utils::data_units::DataUnit,
-    widgets::{ProcWidgetColumn, ProcWidgetMode, TreeCollapsed},
+    utils::{
+        clipboard::{ClipboardSettings, CopyTarget},
+        data_units::DataUnit,
+    },
+    widgets::{DiskColumn, ProcWidgetColumn, ProcWidgetMode, TempWidgetColumn, TreeCollapsed},
 };
 
 const STALE_MIN_MILLISECONDS: u64 = 30 * 1000; // Lowest is 30 seconds
@@ -33,9 +51,10 @@ pub enum AxisScaling {
 
 /// AppConfigFields is meant to cover basic fields that would normally be set
 /// by config files or launch options.
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct AppConfigFields {
     pub update_rate: u64,
+    pub collection_rates: CollectionRates,
     pub temperature_type: TemperatureType,
     pub use_dot: bool,
     pub cpu_left_legend: bool,
@@ -44,6 +63,13 @@ pub struct AppConfigFields {
     pub unnormalized_cpu: bool,
     pub get_process_threads: bool,
     pub use_basic_mode: bool,
+
+    /// How many lines to draw in below the cursor, if not using the whole
+    /// terminal.
+    pub inline_height: Option<u16>,
+
+    /// The basic widgets shown in inline mode, from left to right.
+    pub inline_widgets: Vec<BottomWidgetType>,
     pub default_time_value: u64,
     pub time_interval: u64,
     pub hide_time: bool,
@@ -60,6 +86,9 @@ pub struct AppConfigFields {
     pub is_read_only: bool,
     #[cfg(target_os = "linux")]
     pub hide_k_threads: bool,
+    #[cfg(target_os = "linux")]
+    pub system_roots: SystemRoots,
+    pub custom_metrics: Vec<CustomMetric>,
     #[cfg(feature = "zfs")]
     pub free_arc: bool,
     pub memory_legend_position: Option<LegendPosition>,
@@ -71,6 +100,8 @@ pub struct AppConfigFields {
     pub retention_ms: u64,
     pub dedicated_average_row: bool,
     pub default_tree_collapse: bool,
+    pub remember_settings: bool,
+    pub clipboard: ClipboardSettings,
 }
 
 /// For filtering out information
@@ -89,6 +120,10 @@ pub struct App {
     last_key_press: Instant,
     pub(crate) process_kill_dialog: ProcessKillDialog,
     pub help_dialog_state: AppHelpDialogState,
+    pub diagnostics_dialog_state: AppDiagnosticsDialogState,
+    pub column_dialog_state: AppColumnDialogState,
+    pub command_state: AppCommandState,
+    pub tab_state: AppTabState,
     pub is_expanded: bool,
     pub is_force_redraw: bool,
     pub is_determining_widget_boundary: bool,
@@ -99,6 +134,36 @@ pub struct App {
     pub current_widget: BottomWidget,
     pub used_widgets: UsedWidgets,
     pub filters: DataFilters,
+
+    /// The error from the last attempt to reload the config or save settings,
+    /// shown as a banner.
+    pub config_error: Option<String>,
+
+    /// Whether the config should be reloaded after handling the current event.
+    pub is_config_reload_requested: bool,
+
+    /// Whether the settings changed while running should be saved after
+    /// handling the current event.
+    pub is_settings_save_requested: bool,
+
+    /// Text to copy to the clipboard after handling the current event.
+    pub pending_copy: Option<String>,
+
+    /// Where to capture the next drawn frame to, and in what format.
+    pub pending_capture: Option<(CaptureFormat, PathBuf)>,
+
+    /// The cursor for reading off values on a time graph.
+    pub graph_cursor: GraphCursorState,
+
+    /// The earlier time picked for the process tables to show while frozen.
+    pub process_time: Option<Instant>,
+
+    /// The earlier time the process tables currently show, if any.
+    shown_process_time: Option<Instant>,
+
+    /// The processes shown by process widgets that need their detailed
+    /// memory, sorted, as last sent to the collection thread.
+    pub visible_pids: Vec<Pid>,
 }
 
 impl App {
@@ -115,6 +180,10 @@ impl App {
             last_key_press: Instant::now(),
             process_kill_dialog: ProcessKillDialog::default(),
             help_dialog_state: AppHelpDialogState::default(),
+            diagnostics_dialog_state: AppDiagnosticsDialogState::default(),
+            column_dialog_state: AppColumnDialogState::default(),
+            command_state: AppCommandState::default(),
+            tab_state: AppTabState::default(),
             is_expanded,
             is_force_redraw: false,
             is_determining_widget_boundary: false,
@@ -125,19 +194,236 @@ impl App {
             current_widget,
             used_widgets,
             filters,
+            config_error: None,
+            is_config_reload_requested: false,
+            is_settings_save_requested: false,
+            pending_copy: None,
+            pending_capture: None,
+            graph_cursor: GraphCursorState::default(),
+            process_time: None,
+            shown_process_time: None,
+            visible_pids: Vec::new(),
+        }
+    }
+
+    /// Replaces the settings, styles, and widgets with those of `new`, which was
+    /// built from a reloaded config. Collected data is kept, as are the selected
+    /// widget and tab, and the state of each widget that is still in the layout,
+    /// such as its search, sorting, and scroll position.
+    pub fn apply_reloaded_config(&mut self, new: App) {
+        let old = std::mem::replace(self, new);
+        self.data_store = old.data_store;
+        self.diagnostics_dialog_state = old.diagnostics_dialog_state;
+        self.basic_mode_use_percent = old.basic_mode_use_percent;
+        self.states.keep_runtime_state(old.states);
+
+        let temperature_type = self.app_config_fields.temperature_type;
+        if temperature_type != old.app_config_fields.temperature_type {
+            self.data_store.set_temperature_type(temperature_type);
+        }
+
+        for (index, widget_id) in old.tab_state.selected_widgets.iter().enumerate() {
+            if self.tab_state.widget_tabs.get(widget_id) == Some(&index) {
+                self.tab_state.selected_widgets[index] = *widget_id;
+            }
+        }
+
+        match self.widget_map.get(&old.current_widget.widget_id) {
+            Some(widget) if widget.widget_type == old.current_widget.widget_type => {
+                self.show_widget(widget.clone());
+                self.is_expanded = old.is_expanded;
+            }
+            _ => self.select_tab(old.tab_state.current),
+        }
+
+        self.is_force_redraw = true;
+        self.force_widget_data_update();
+        self.update_data();
+    }
+
+    /// Selects `widget`, switching to the layout tab it is in.
+    pub fn show_widget(&mut self, widget: BottomWidget) {
+        if let Some(&tab) = self.tab_state.widget_tabs.get(&widget.widget_id) {
+            self.tab_state.current = tab;
+        }
+        self.current_widget = widget;
+    }
+
+    /// Switches to the layout tab at `index`, selecting the widget that was
+    /// last selected in it.
+    pub fn select_tab(&mut self, index: usize) {
+        let tab_state = &mut self.tab_state;
+        if index == tab_state.current || index >= tab_state.selected_widgets.len() {
+            return;
+        }
+
+        tab_state.selected_widgets[tab_state.current] = self.current_widget.widget_id;
+        tab_state.current = index;
+
+        if let Some(widget) = self.widget_map.get(&tab_state.selected_widgets[index]) {
+            self.current_widget = widget.clone();
+        }
+
+        self.reset_multi_tap_keys();
+        self.is_force_redraw = true;
+    }
+
+    /// Makes `layout` the one used for moving between and selecting widgets,
+    /// such as when the terminal is resized past a layout breakpoint. If the
+    /// selected widget isn't in `layout`, a widget of the same type is
+    /// selected instead, or else the first widget.
+    pub fn use_layout(&mut self, layout: &BottomLayout) {
+        for widget in layout.widgets() {
+            self.widget_map.insert(widget.widget_id, widget.clone());
+        }
+
+        let current = &self.current_widget;
+        let widget_id = layout
+            .widgets()
+            .find(|widget| widget.widget_id == current.widget_id)
+            .or_else(|| {
+                layout
+                    .widgets()
+                    .find(|widget| widget.widget_type == current.widget_type)
+            })
+            .map(|widget| widget.widget_id)
+            .or_else(|| layout.first_widget_id());
+
+        if let Some(widget) = widget_id.and_then(|id| self.widget_map.get(&id)) {
+            self.current_widget = widget.clone();
+        }
+
+        self.is_force_redraw = true;
+    }
+
+    /// Updates the slower process details to collect from the columns shown
+    /// and searches used by process widgets. Returns whether they changed, in
+    /// which case the collection thread should be told.
+    pub fn refresh_process_fields(&mut self) -> bool {
+        let fields = self
+            .states
+            .proc_state
+            .widget_states
+            .values()
+            .fold(ProcessFields::default(), |fields, proc_state| {
+                fields.union(proc_state.process_fields())
+            });
+
+        let is_changed = fields != self.used_widgets.process_fields;
+        self.used_widgets.process_fields = fields;
+        is_changed
+    }
+
+    /// Updates the processes shown by process widgets that need their detailed
+    /// memory. Returns whether they changed, in which case the collection
+    /// thread should be told.
+    pub fn refresh_visible_pids(&mut self) -> bool {
+        let mut pids = self
+            .states
+            .proc_state
+            .widget_states
+            .values()
+            .filter(|proc_state| proc_state.process_fields().detailed_memory)
+            .flat_map(|proc_state| proc_state.visible_pids())
+            .collect::<Vec<_>>();
+        pids.sort_unstable();
+        pids.dedup();
+
+        let is_changed = pids != self.visible_pids;
+        self.visible_pids = pids;
+        is_changed
+    }
+
+    /// Makes every widget update its data from the current data the next time
+    /// [`App::update_data`] is called.
+    pub fn force_widget_data_update(&mut self) {
+        if self.used_widgets.use_disk {
+            for disk in self.states.disk_state.widget_states.values_mut() {
+                disk.force_data_update();
+            }
+        }
+
+        if self.used_widgets.use_temp {
+            for temp in self.states.temp_state.widget_states.values_mut() {
+                temp.force_data_update();
+            }
+        }
+
+        if self.used_widgets.use_proc {
+            for proc in self.states.proc_state.widget_states.values_mut() {
+                proc.force_data_update();
+            }
+
+            for proc_log in self.states.proc_log_state.widget_states.values_mut() {
+                proc_log.force_data_update();
+            }
+        }
+
+        if self.used_widgets.use_connections {
+            for conn in self.states.conn_state.widget_states.values_mut() {
+                conn.force_data_update();
+            }
+        }
+
+        if self.used_widgets.use_cpu {
+            for cpu in self.states.cpu_state.widget_states.values_mut() {
+                cpu.force_data_update();
+            }
+        }
+
+        #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+        if self.used_widgets.use_gpu {
+            for gpu in self.states.gpu_state.widget_states.values_mut() {
+                gpu.force_data_update();
+            }
         }
     }
 
     /// Update the data in the [`App`].
     pub fn update_data(&mut self) {
+        let snapshot_time = self.process_snapshot_time();
+        if snapshot_time != self.shown_process_time {
+            self.shown_process_time = snapshot_time;
+            for proc in self.states.proc_state.widget_states.values_mut() {
+                proc.force_data_update();
+            }
+        }
+        let snapshot_label = snapshot_time.map(|time| self.process_time_label(time));
+
         let data_source = self.data_store.get_data();
+        let past_process_data = snapshot_time
+            .filter(|_| {
+                self.states
+                    .proc_state
+                    .widget_states
+                    .values()
+                    .any(|proc| proc.force_update_data)
+            })
+            .and_then(|time| data_source.process_history.process_data_at(time));
 
         // FIXME: (points_rework_v1) maybe separate PR but would it make more sense to store references of data?
         // Would it also make more sense to move the "data set" step to the draw step, and make it only set if force
         // update is set here?
         for proc in self.states.proc_state.widget_states.values_mut() {
             if proc.force_update_data {
-                proc.set_table_data(data_source);
+                proc.set_shown_time(snapshot_label.as_deref());
+                proc.set_table_data(
+                    past_process_data
+                        .as_ref()
+                        .unwrap_or(&data_source.process_data),
+                );
+            }
+        }
+
+        for proc_log in self.states.proc_log_state.widget_states.values_mut() {
+            if proc_log.force_update_data {
+                proc_log.set_table_data(&data_source.process_log);
+            }
+        }
+
+        for conn in self.states.conn_state.widget_states.values_mut() {
+            if conn.force_update_data {
+                conn.set_table_data(&data_source.connection_harvest);
             }
         }
 
@@ -173,6 +459,9 @@ impl App {
 
         // Reset dialog state
         self.help_dialog_state.is_showing_help = false;
+        self.diagnostics_dialog_state.is_showing = false;
+        self.column_dialog_state.is_showing = false;
+        self.command_state.is_showing = false;
         self.process_kill_dialog.reset();
 
         // Close all searches and reset it
@@ -190,6 +479,8 @@ impl App {
         self.reset_cpu_zoom();
         self.reset_mem_zoom();
         self.reset_net_zoom();
+        self.reset_power_zoom();
+        self.reset_custom_zoom();
     }
 
     pub fn should_get_widget_bounds(&self) -> bool {
@@ -199,13 +490,24 @@ impl App {
     pub fn on_esc(&mut self) {
         self.reset_multi_tap_keys();
 
-        if self.process_kill_dialog.is_open() {
+        if self.config_error.is_some() {
+            self.config_error = None;
+            self.is_force_redraw = true;
+        } else if self.process_kill_dialog.is_open() {
             self.process_kill_dialog.on_esc();
             self.is_force_redraw = true;
         } else if self.help_dialog_state.is_showing_help {
             self.help_dialog_state.is_showing_help = false;
             self.help_dialog_state.scroll_state.current_scroll_index = 0;
             self.is_force_redraw = true;
+        } else if self.diagnostics_dialog_state.is_showing {
+            self.diagnostics_dialog_state.is_showing = false;
+            self.is_force_redraw = true;
+        } else if self.column_dialog_state.is_showing {
+            self.column_dialog_state.is_showing = false;
+            self.is_force_redraw = true;
+        } else if self.hide_graph_cursor() {
+            self.is_force_redraw = true;
         } else {
             match self.current_widget.widget_type {
                 BottomWidgetType::Proc => {
@@ -273,7 +575,11 @@ impl App {
     }
 
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.process_kill_dialog.is_open()
+        self.help_dialog_state.is_showing_help
+            || self.diagnostics_dialog_state.is_showing
+            || self.column_dialog_state.is_showing
+            || self.command_state.is_showing
+            || self.process_kill_dialog.is_open()
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
@@ -313,11 +619,189 @@ impl App {
                         self.is_force_redraw = true;
                     }
                 }
+                BottomWidgetType::ProcLog => {
+                    let query = self
+                        .states
+                        .proc_log_state
+                        .get_widget_state(self.current_widget.widget_id)
+                        .and_then(|state| state.query())
+                        .unwrap_or_default()
+                        .to_string();
+                    self.open_command_prompt();
+                    self.edit_command_input(|input| input.set_query(format!("filter {query}")));
+                }
+                BottomWidgetType::Conn => {
+                    let query = self
+                        .states
+                        .conn_state
+                        .get_widget_state(self.current_widget.widget_id)
+                        .map(|state| state.query())
+                        .unwrap_or_default()
+                        .to_string();
+                    self.open_command_prompt();
+                    self.edit_command_input(|input| input.set_query(format!("filter {query}")));
+                }
                 _ => {}
             }
         }
     }
 
+    /// Opens or closes the diagnostics dialog. It won't open over another dialog.
+    pub fn toggle_diagnostics(&mut self) {
+        if self.diagnostics_dialog_state.is_showing {
+            self.diagnostics_dialog_state.is_showing = false;
+            self.is_force_redraw = true;
+        } else {
+            self.open_diagnostics();
+        }
+    }
+
+    /// Opens the diagnostics dialog, unless another dialog is open.
+    pub fn open_diagnostics(&mut self) {
+        if !self.is_in_dialog() {
+            self.diagnostics_dialog_state.is_showing = true;
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Returns the column picker for the table widget with `widget_id`, if it
+    /// is a table whose columns can be picked.
+    pub fn column_picker(&self, widget_id: u64) -> Option<&dyn ColumnPicker> {
+        if let Some(state) = self.states.proc_state.get_widget_state(widget_id) {
+            Some(state)
+        } else if let Some(state) = self.states.disk_state.get_widget_state(widget_id) {
+            Some(state)
+        } else if let Some(state) = self.states.temp_state.get_widget_state(widget_id) {
+            Some(state)
+        } else {
+            None
+        }
+    }
+
+    /// Copies `target` from the selected row of the current process, process
+    /// log, connection, disk, or temperature table. Returns whether there was anything to
+    /// copy.
+    pub fn copy_selection(&mut self, target: CopyTarget) -> bool {
+        let widget_id = match self.current_widget.widget_type {
+            BottomWidgetType::ProcSearch => self.current_widget.widget_id - 1,
+            BottomWidgetType::ProcSort => self.current_widget.widget_id - 2,
+            _ => self.current_widget.widget_id,
+        };
+
+        let text = if let Some(state) = self.states.proc_state.get_widget_state(widget_id) {
+            state.copy_text(target)
+        } else if let Some(state) = self.states.proc_log_state.get_widget_state(widget_id) {
+            state.copy_text(target)
+        } else if let Some(state) = self.states.conn_state.get_widget_state(widget_id) {
+            state.copy_text(target)
+        } else if let Some(state) = self.states.disk_state.get_widget_state(widget_id) {
+            state.copy_text(target)
+        } else if let Some(state) = self.states.temp_state.get_widget_state(widget_id) {
+            state.copy_text(target)
+        } else {
+            None
+        };
+
+        self.pending_copy = text;
+        self.pending_copy.is_some()
+    }
+
+    /// Opens the command prompt with a capture command, so that the format and
+    /// path can be changed before running it.
+    pub fn open_capture_prompt(&mut self) {
+        let path = format!("bottom-{}.txt", export::file_timestamp());
+        self.open_command_prompt();
+        self.edit_command_input(|input| input.set_query(format!("capture text {path}")));
+    }
+
+    fn column_picker_mut(&mut self, widget_id: u64) -> Option<&mut dyn ColumnPicker> {
+        if let Some(state) = self.states.proc_state.get_mut_widget_state(widget_id) {
+            Some(state)
+        } else if let Some(state) = self.states.disk_state.get_mut_widget_state(widget_id) {
+            Some(state)
+        } else if let Some(state) = self.states.temp_state.get_mut_widget_state(widget_id) {
+            Some(state)
+        } else {
+            None
+        }
+    }
+
+    /// Opens the column picker for the current table widget.
+    pub fn open_column_dialog(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+        let is_table = matches!(
+            self.current_widget.widget_type,
+            BottomWidgetType::Proc | BottomWidgetType::Disk | BottomWidgetType::Temp
+        );
+
+        if is_table && !self.is_in_dialog() && self.column_picker(widget_id).is_some() {
+            self.column_dialog_state = AppColumnDialogState {
+                is_showing: true,
+                widget_id,
+                selected: 0,
+            };
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Moves the column picker's selection up or down.
+    fn column_dialog_select(&mut self, up: bool) {
+        let count = self
+            .column_picker(self.column_dialog_state.widget_id)
+            .map(|picker| picker.picker_columns().len())
+            .unwrap_or(0);
+
+        let selected = &mut self.column_dialog_state.selected;
+        if up {
+            *selected = selected.saturating_sub(1);
+        } else if *selected + 1 < count {
+            *selected += 1;
+        }
+        self.is_force_redraw = true;
+    }
+
+    /// Shows or hides the column selected in the column picker.
+    fn column_dialog_toggle(&mut self) {
+        let AppColumnDialogState {
+            widget_id,
+            selected,
+            ..
+        } = self.column_dialog_state;
+
+        if let Some(picker) = self.column_picker_mut(widget_id) {
+            self.column_dialog_state.selected = picker.toggle_picked_column(selected);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Moves the column selected in the column picker earlier or later.
+    fn column_dialog_move(&mut self, earlier: bool) {
+        let AppColumnDialogState {
+            widget_id,
+            selected,
+            ..
+        } = self.column_dialog_state;
+
+        if let Some(picker) = self.column_picker_mut(widget_id) {
+            self.column_dialog_state.selected = picker.move_picked_column(selected, earlier);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Widens or narrows the column selected in the column picker.
+    fn column_dialog_resize(&mut self, delta: i16) {
+        let AppColumnDialogState {
+            widget_id,
+            selected,
+            ..
+        } = self.column_dialog_state;
+
+        if let Some(picker) = self.column_picker_mut(widget_id) {
+            picker.resize_picked_column(selected, delta);
+            self.is_force_redraw = true;
+        }
+    }
+
     pub fn toggle_sort_menu(&mut self) {
         let widget_id = self.current_widget.widget_id
             - match &self.current_widget.widget_type {
@@ -333,7 +817,8 @@ impl App {
             // If the sort is now open, move left. Otherwise, if the proc sort was selected,
             // force move right.
             if pws.is_sort_open {
-                pws.sort_table.set_position(pws.table.sort_index());
+                pws.sort_table
+                    .set_position(pws.table.shown_index(pws.table.sort_index()).unwrap_or(0));
                 self.move_widget_selection(&WidgetDirection::Left);
             } else if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
                 self.move_widget_selection(&WidgetDirection::Right);
@@ -454,6 +939,8 @@ impl App {
         if self.process_kill_dialog.is_open() {
             // Not the best way of doing things for now but works as glue.
             self.process_kill_dialog.on_enter();
+        } else if self.column_dialog_state.is_showing {
+            self.column_dialog_toggle();
         } else if !self.is_in_dialog() {
             match self.current_widget.widget_type {
                 BottomWidgetType::ProcSearch => {
@@ -590,6 +1077,8 @@ impl App {
             self.reset_multi_tap_keys();
         } else if self.process_kill_dialog.is_open() {
             self.process_kill_dialog.on_up_key();
+        } else if self.column_dialog_state.is_showing {
+            self.column_dialog_select(true);
         }
     }
 
@@ -602,11 +1091,17 @@ impl App {
             self.reset_multi_tap_keys();
         } else if self.process_kill_dialog.is_open() {
             self.process_kill_dialog.on_down_key();
+        } else if self.column_dialog_state.is_showing {
+            self.column_dialog_select(false);
         }
     }
 
     pub fn on_left_key(&mut self) {
         if !self.is_in_dialog() {
+            if self.step_graph_cursor(true) {
+                return;
+            }
+
             match self.current_widget.widget_type {
                 BottomWidgetType::Proc => {
                     if let Some(proc_widget_state) = self
@@ -652,11 +1147,17 @@ impl App {
             }
         } else if self.process_kill_dialog.is_open() {
             self.process_kill_dialog.on_left_key();
+        } else if self.column_dialog_state.is_showing {
+            self.column_dialog_resize(-1);
         }
     }
 
     pub fn on_right_key(&mut self) {
         if !self.is_in_dialog() {
+            if self.step_graph_cursor(false) {
+                return;
+            }
+
             match self.current_widget.widget_type {
                 BottomWidgetType::Proc => {
                     if let Some(proc_widget_state) = self
@@ -707,6 +1208,8 @@ impl App {
             }
         } else if self.process_kill_dialog.is_open() {
             self.process_kill_dialog.on_right_key();
+        } else if self.column_dialog_state.is_showing {
+            self.column_dialog_resize(1);
         }
     }
 
@@ -724,6 +1227,8 @@ impl App {
         } else if self.process_kill_dialog.is_open() {
             // Either select the current option,
             // or scroll to the next one
+        } else if self.column_dialog_state.is_showing {
+            self.column_dialog_toggle();
         }
     }
 
@@ -1010,6 +1515,16 @@ impl App {
             }
         } else if self.process_kill_dialog.is_open() {
             self.process_kill_dialog.on_char(caught_char);
+        } else if self.column_dialog_state.is_showing {
+            match caught_char {
+                'k' => self.column_dialog_select(true),
+                'j' => self.column_dialog_select(false),
+                'K' => self.column_dialog_move(true),
+                'J' => self.column_dialog_move(false),
+                'h' => self.column_dialog_resize(-1),
+                'l' => self.column_dialog_resize(1),
+                _ => {}
+            }
         }
     }
 
@@ -1066,6 +1581,13 @@ impl App {
             '/' => {
                 self.on_slash();
             }
+            ':' => self.open_command_prompt(),
+            'y' => {
+                self.copy_selection(self.app_config_fields.clipboard.target);
+            }
+            'i' => self.toggle_graph_cursor(),
+            'x' => self.open_export_prompt(),
+            'X' => self.open_capture_prompt(),
             'd' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     let mut is_first_d = true;
@@ -1090,7 +1612,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(0);
+                    disk.select_column(DiskColumn::Disk);
                 }
             }
             'g' => {
@@ -1112,7 +1634,9 @@ impl App {
             'G' => self.skip_to_last(),
             'k' => self.on_up_key(),
             'j' => self.on_down_key(),
-            'f' => self.data_store.toggle_frozen(),
+            'f' => self.toggle_frozen(),
+            '[' => self.step_process_time(true),
+            ']' => self.step_process_time(false),
             'c' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
@@ -1138,7 +1662,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(1);
+                    disk.select_column(DiskColumn::Mount);
                 }
             }
             'p' => {
@@ -1155,7 +1679,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(5);
+                    disk.select_column(DiskColumn::UsedPercent);
                 }
             }
             'P' => {
@@ -1183,7 +1707,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(3);
+                    disk.select_column(DiskColumn::Free);
                 }
             }
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -1214,6 +1738,13 @@ impl App {
                 self.help_dialog_state.is_showing_help = true;
                 self.is_force_redraw = true;
             }
+            'R' => self.is_config_reload_requested = true,
+            'o' => self.open_column_dialog(),
+            '1'..='9' => {
+                if let Some(digit) = caught_char.to_digit(10) {
+                    self.select_tab(digit as usize - 1);
+                }
+            }
             'H' | 'A' => self.move_widget_selection(&WidgetDirection::Left),
             'L' | 'D' => self.move_widget_selection(&WidgetDirection::Right),
             'K' | 'W' => self.move_widget_selection(&WidgetDirection::Up),
@@ -1226,14 +1757,14 @@ impl App {
                     .temp_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    temp.table.set_sort_index(1);
+                    temp.table.select_column(&TempWidgetColumn::Temp);
                     temp.force_data_update();
                 } else if let Some(disk) = self
                     .states
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(4);
+                    disk.select_column(DiskColumn::Total);
                 }
             }
             '+' => self.on_plus(),
@@ -1248,7 +1779,7 @@ impl App {
                     .temp_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    temp.table.set_sort_index(0);
+                    temp.table.select_column(&TempWidgetColumn::Sensor);
                     temp.force_data_update();
                     self.is_force_redraw = true;
                 }
@@ -1259,7 +1790,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(2);
+                    disk.select_column(DiskColumn::Used);
                 }
             }
             'r' => {
@@ -1268,7 +1799,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(6);
+                    disk.select_column(DiskColumn::IoRead);
                 }
             }
             'w' => {
@@ -1277,7 +1808,7 @@ impl App {
                     .disk_state
                     .get_mut_widget_state(self.current_widget.widget_id)
                 {
-                    disk.set_index(7);
+                    disk.select_column(DiskColumn::IoWrite);
                 }
             }
             'I' => self.invert_sort(),
@@ -1791,6 +2322,10 @@ impl App {
 
     pub fn skip_to_first(&mut self) {
         if !self.ignore_normal_keybinds() {
+            if self.current_widget.widget_type.is_widget_graph() && self.jump_graph_cursor(true) {
+                return;
+            }
+
             match self.current_widget.widget_type {
                 BottomWidgetType::Proc => {
                     if let Some(proc_widget_state) = self
@@ -1810,6 +2345,24 @@ impl App {
                         proc_widget_state.sort_table.scroll_to_first();
                     }
                 }
+                BottomWidgetType::ProcLog => {
+                    if let Some(proc_log_widget_state) = self
+                        .states
+                        .proc_log_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        proc_log_widget_state.table.scroll_to_first();
+                    }
+                }
+                BottomWidgetType::Conn => {
+                    if let Some(conn_widget_state) = self
+                        .states
+                        .conn_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        conn_widget_state.table.scroll_to_first();
+                    }
+                }
                 BottomWidgetType::Temp => {
                     if let Some(temp_widget_state) = self
                         .states
@@ -1850,6 +2403,10 @@ impl App {
 
     pub fn skip_to_last(&mut self) {
         if !self.ignore_normal_keybinds() {
+            if self.current_widget.widget_type.is_widget_graph() && self.jump_graph_cursor(false) {
+                return;
+            }
+
             match self.current_widget.widget_type {
                 BottomWidgetType::Proc => {
                     if let Some(proc_widget_state) = self
@@ -1869,6 +2426,24 @@ impl App {
                         proc_widget_state.sort_table.scroll_to_last();
                     }
                 }
+                BottomWidgetType::ProcLog => {
+                    if let Some(proc_log_widget_state) = self
+                        .states
+                        .proc_log_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        proc_log_widget_state.table.scroll_to_last();
+                    }
+                }
+                BottomWidgetType::Conn => {
+                    if let Some(conn_widget_state) = self
+                        .states
+                        .conn_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        conn_widget_state.table.scroll_to_last();
+                    }
+                }
                 BottomWidgetType::Temp => {
                     if let Some(temp_widget_state) = self
                         .states
@@ -1924,6 +2499,8 @@ impl App {
                     self.change_process_position(amount);
                 }
                 BottomWidgetType::ProcSort => self.change_process_sort_position(amount),
+                BottomWidgetType::ProcLog => self.change_proc_log_position(amount),
+                BottomWidgetType::Conn => self.change_conn_position(amount),
                 BottomWidgetType::Temp => self.change_temp_position(amount),
                 BottomWidgetType::Disk => self.change_disk_position(amount),
                 BottomWidgetType::CpuLegend => self.change_cpu_legend_position(amount),
@@ -1968,6 +2545,30 @@ impl App {
         }
     }
 
+    fn change_proc_log_position(&mut self, num_to_change_by: i64) {
+        if let Some(proc_log_widget_state) = self
+            .states
+            .proc_log_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            proc_log_widget_state
+                .table
+                .increment_position(num_to_change_by);
+        }
+    }
+
+    fn change_conn_position(&mut self, num_to_change_by: i64) {
+        if let Some(conn_widget_state) = self
+            .states
+            .conn_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            conn_widget_state.table.increment_position(num_to_change_by);
+        }
+    }
+
     fn change_temp_position(&mut self, num_to_change_by: i64) {
         if let Some(temp_widget_state) = self
             .states
@@ -2120,6 +2721,60 @@ impl App {
                     }
                 }
             }
+            BottomWidgetType::Power => {
+                if let Some(power_widget_state) = self
+                    .states
+                    .power_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = power_widget_state
+                        .current_display_time
+                        .saturating_add(self.app_config_fields.time_interval);
+
+                    if new_time <= self.app_config_fields.retention_ms {
+                        power_widget_state.current_display_time = new_time;
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if power_widget_state.current_display_time
+                        != self.app_config_fields.retention_ms
+                    {
+                        power_widget_state.current_display_time =
+                            self.app_config_fields.retention_ms;
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Custom => {
+                if let Some(custom_widget_state) = self
+                    .states
+                    .custom_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = custom_widget_state
+                        .current_display_time
+                        .saturating_add(self.app_config_fields.time_interval);
+
+                    if new_time <= self.app_config_fields.retention_ms {
+                        custom_widget_state.current_display_time = new_time;
+                        if self.app_config_fields.autohide_time {
+                            custom_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if custom_widget_state.current_display_time
+                        != self.app_config_fields.retention_ms
+                    {
+                        custom_widget_state.current_display_time =
+                            self.app_config_fields.retention_ms;
+                        if self.app_config_fields.autohide_time {
+                            custom_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
             BottomWidgetType::Net => {
                 if let Some(net_widget_state) = self
                     .states
@@ -2227,6 +2882,54 @@ impl App {
                     }
                 }
             }
+            BottomWidgetType::Power => {
+                if let Some(power_widget_state) = self
+                    .states
+                    .power_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = power_widget_state
+                        .current_display_time
+                        .saturating_sub(self.app_config_fields.time_interval);
+
+                    if new_time >= STALE_MIN_MILLISECONDS {
+                        power_widget_state.current_display_time = new_time;
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if power_widget_state.current_display_time != STALE_MIN_MILLISECONDS {
+                        power_widget_state.current_display_time = STALE_MIN_MILLISECONDS;
+                        if self.app_config_fields.autohide_time {
+                            power_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
+            BottomWidgetType::Custom => {
+                if let Some(custom_widget_state) = self
+                    .states
+                    .custom_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = custom_widget_state
+                        .current_display_time
+                        .saturating_sub(self.app_config_fields.time_interval);
+
+                    if new_time >= STALE_MIN_MILLISECONDS {
+                        custom_widget_state.current_display_time = new_time;
+                        if self.app_config_fields.autohide_time {
+                            custom_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if custom_widget_state.current_display_time != STALE_MIN_MILLISECONDS {
+                        custom_widget_state.current_display_time = STALE_MIN_MILLISECONDS;
+                        if self.app_config_fields.autohide_time {
+                            custom_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
             BottomWidgetType::Net => {
                 if let Some(net_widget_state) = self
                     .states
@@ -2308,6 +3011,34 @@ impl App {
         }
     }
 
+    fn reset_power_zoom(&mut self) {
+        if let Some(power_widget_state) = self
+            .states
+            .power_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            power_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            if self.app_config_fields.autohide_time {
+                power_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
+    fn reset_custom_zoom(&mut self) {
+        if let Some(custom_widget_state) = self
+            .states
+            .custom_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            custom_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            if self.app_config_fields.autohide_time {
+                custom_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
     fn reset_net_zoom(&mut self) {
         if let Some(net_widget_state) = self
             .states
@@ -2327,12 +3058,85 @@ impl App {
             BottomWidgetType::Cpu => self.reset_cpu_zoom(),
             BottomWidgetType::Mem => self.reset_mem_zoom(),
             BottomWidgetType::Net => self.reset_net_zoom(),
+            BottomWidgetType::Power => self.reset_power_zoom(),
+            BottomWidgetType::Custom => self.reset_custom_zoom(),
             _ => {}
         }
     }
 
     /// Moves the mouse to the widget that was clicked on, then propagates the
     /// click down to be handled by the widget specifically.
+    /// Resizes the table column being dragged, if there is one, so that it ends
+    /// at `x`.
+    pub fn on_left_mouse_drag(&mut self, x: u16) {
+        let widget_id = self.current_widget.widget_id;
+        let is_resized = match self.current_widget.widget_type {
+            BottomWidgetType::Proc => self
+                .states
+                .proc_state
+                .get_mut_widget_state(widget_id)
+                .is_some_and(|state| state.table.drag_column_resize(x)),
+            BottomWidgetType::ProcLog => self
+                .states
+                .proc_log_state
+                .get_mut_widget_state(widget_id)
+                .is_some_and(|proc_log| proc_log.table.drag_column_resize(x)),
+            BottomWidgetType::Conn => self
+                .states
+                .conn_state
+                .get_mut_widget_state(widget_id)
+                .is_some_and(|conn| conn.table.drag_column_resize(x)),
+            BottomWidgetType::Temp => self
+                .states
+                .temp_state
+                .get_mut_widget_state(widget_id)
+                .is_some_and(|temp| temp.table.drag_column_resize(x)),
+            BottomWidgetType::Disk => self
+                .states
+                .disk_state
+                .get_mut_widget_state(widget_id)
+                .is_some_and(|disk| disk.table.drag_column_resize(x)),
+            _ => false,
+        };
+
+        if is_resized {
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Stops resizing a table column by dragging.
+    pub fn on_left_mouse_release(&mut self) {
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc => {
+                if let Some(state) = self.states.proc_state.get_mut_widget_state(widget_id) {
+                    state.table.stop_column_resize();
+                }
+            }
+            BottomWidgetType::ProcLog => {
+                if let Some(proc_log) = self.states.proc_log_state.get_mut_widget_state(widget_id) {
+                    proc_log.table.stop_column_resize();
+                }
+            }
+            BottomWidgetType::Conn => {
+                if let Some(conn) = self.states.conn_state.get_mut_widget_state(widget_id) {
+                    conn.table.stop_column_resize();
+                }
+            }
+            BottomWidgetType::Temp => {
+                if let Some(temp) = self.states.temp_state.get_mut_widget_state(widget_id) {
+                    temp.table.stop_column_resize();
+                }
+            }
+            BottomWidgetType::Disk => {
+                if let Some(disk) = self.states.disk_state.get_mut_widget_state(widget_id) {
+                    disk.table.stop_column_resize();
+                }
+            }
+            _ => {}
+        }
+    }
+
     pub fn on_left_mouse_up(&mut self, x: u16, y: u16) {
         // Pretty dead simple - iterate through the widget map and go to the widget
         // where the click is within.
@@ -2416,6 +3220,18 @@ impl App {
             return;
         }
 
+        if let Some(index) =
+            self.tab_state
+                .click_locs
+                .iter()
+                .position(|((tlc_x, tlc_y), (brc_x, brc_y))| {
+                    (x >= *tlc_x && y >= *tlc_y) && (x < *brc_x && y <= *brc_y)
+                })
+        {
+            self.select_tab(index);
+            return;
+        }
+
         let mut failed_to_get = true;
         for (new_widget_id, widget) in &self.widget_map {
             if let (Some((tlc_x, tlc_y)), Some((brc_x, brc_y))) =
@@ -2467,6 +3283,8 @@ impl App {
                     BottomWidgetType::Proc
                     | BottomWidgetType::ProcSort
                     | BottomWidgetType::CpuLegend
+                    | BottomWidgetType::ProcLog
+                    | BottomWidgetType::Conn
                     | BottomWidgetType::Temp
                     | BottomWidgetType::Disk => {
                         // Get our index...
@@ -2536,6 +3354,36 @@ impl App {
                                         }
                                     }
                                 }
+                                BottomWidgetType::ProcLog => {
+                                    if let Some(proc_log_widget_state) = self
+                                        .states
+                                        .proc_log_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            proc_log_widget_state.table.ratatui_selected()
+                                        {
+                                            self.change_proc_log_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
+                                BottomWidgetType::Conn => {
+                                    if let Some(conn_widget_state) = self
+                                        .states
+                                        .conn_state
+                                        .get_widget_state(self.current_widget.widget_id)
+                                    {
+                                        if let Some(visual_index) =
+                                            conn_widget_state.table.ratatui_selected()
+                                        {
+                                            self.change_conn_position(
+                                                offset_clicked_entry as i64 - visual_index as i64,
+                                            );
+                                        }
+                                    }
+                                }
                                 BottomWidgetType::Temp => {
                                     if let Some(temp_widget_state) = self
                                         .states
@@ -2580,18 +3428,58 @@ impl App {
                                             .proc_state
                                             .get_mut_widget_state(self.current_widget.widget_id)
                                         {
-                                            if state.table.try_select_location(x, y).is_some() {
+                                            if state.table.try_start_column_resize(x, y) {
+                                                self.is_force_redraw = true;
+                                            } else if state
+                                                .table
+                                                .try_select_location(x, y)
+                                                .is_some()
+                                            {
                                                 state.force_data_update();
                                             }
                                         }
                                     }
+                                    BottomWidgetType::ProcLog => {
+                                        if let Some(proc_log) = self
+                                            .states
+                                            .proc_log_state
+                                            .get_mut_widget_state(self.current_widget.widget_id)
+                                        {
+                                            if proc_log.table.try_start_column_resize(x, y) {
+                                                self.is_force_redraw = true;
+                                            } else if proc_log
+                                                .table
+                                                .try_select_location(x, y)
+                                                .is_some()
+                                            {
+                                                proc_log.force_data_update();
+                                            }
+                                        }
+                                    }
+                                    BottomWidgetType::Conn => {
+                                        if let Some(conn) = self
+                                            .states
+                                            .conn_state
+                                            .get_mut_widget_state(self.current_widget.widget_id)
+                                        {
+                                            if conn.table.try_start_column_resize(x, y) {
+                                                self.is_force_redraw = true;
+                                            } else if conn.table.try_select_location(x, y).is_some()
+                                            {
+                                                conn.force_data_update();
+                                            }
+                                        }
+                                    }
                                     BottomWidgetType::Temp => {
                                         if let Some(temp) = self
                                             .states
                                             .temp_state
                                             .get_mut_widget_state(self.current_widget.widget_id)
                                         {
-                                            if temp.table.try_select_location(x, y).is_some() {
+                                            if temp.table.try_start_column_resize(x, y) {
+                                                self.is_force_redraw = true;
+                                            } else if temp.table.try_select_location(x, y).is_some()
+                                            {
                                                 temp.force_data_update();
                                             }
                                         }
@@ -2602,7 +3490,10 @@ impl App {
                                             .disk_state
                                             .get_mut_widget_state(self.current_widget.widget_id)
                                         {
-                                            if disk.table.try_select_location(x, y).is_some() {
+                                            if disk.table.try_start_column_resize(x, y) {
+                                                self.is_force_redraw = true;
+                                            } else if disk.table.try_select_location(x, y).is_some()
+                                            {
                                                 disk.force_data_update();
                                             }
                                         }
@@ -2669,6 +3560,11 @@ impl App {
 
     /// A quick and dirty way to handle paste events.
     pub fn handle_paste(&mut self, paste: String) {
+        if self.command_state.is_showing {
+            self.edit_command_input(|input| input.insert_str(&paste));
+            return;
+        }
+
         // Partially copy-pasted from the single-char variant; should probably clean up
         // this process in the future. In particular, encapsulate this entire
         // logic and add some tests to make it less potentially error-prone.
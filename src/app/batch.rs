@@ -0,0 +1,110 @@
+//! Printing the process list as text, like `top -b`, instead of drawing the
+//! interface.
+
+use unicode_width::UnicodeWidthStr;
+
+use super::{App, layout_manager::BottomWidgetType};
+
+/// Lines up `rows` under `headers`, with two spaces between columns.
+fn align_columns(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut widths = headers
+        .iter()
+        .map(|header| header.width())
+        .collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+
+    std::iter::once(headers)
+        .chain(rows.iter().map(Vec::as_slice))
+        .map(|row| {
+            let mut line = String::new();
+            for (cell, width) in row.iter().zip(&widths) {
+                line.push_str(cell);
+                line.push_str(&" ".repeat(width - cell.width() + 2));
+            }
+            line.truncate(line.trim_end().len());
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+impl App {
+    /// Selects the first process widget and applies `query` and `sort` to it,
+    /// so that [`App::batch_text`] prints it.
+    pub(crate) fn prepare_batch(
+        &mut self, query: Option<&str>, sort: Option<&str>,
+    ) -> Result<(), String> {
+        let widget = self
+            .widget_map
+            .values()
+            .filter(|widget| widget.widget_type == BottomWidgetType::Proc)
+            .min_by_key(|widget| widget.widget_id)
+            .cloned()
+            .ok_or("Batch mode needs a process widget in the layout.")?;
+        self.current_widget = widget;
+
+        if let Some(query) = query {
+            self.run_command(&format!("filter {query}"))?;
+        }
+        if let Some(sort) = sort {
+            self.run_command(&format!("sort {sort}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the selected process table as aligned text, with at most `rows`
+    /// processes.
+    pub(crate) fn batch_text(&self, rows: Option<usize>) -> Option<String> {
+        let state = self
+            .states
+            .proc_state
+            .get_widget_state(self.current_widget.widget_id)?;
+        let (headers, mut shown) = state.table.shown_rows();
+        if let Some(rows) = rows {
+            shown.truncate(rows);
+        }
+
+        Some(align_columns(&headers, &shown))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::*;
+    use crate::{args::BottomArgs, options::config::Config};
+
+    #[test]
+    fn aligns_columns() {
+        let headers = vec!["PID".to_string(), "Name".to_string(), "CPU%".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "init".to_string(), "0.0%".to_string()],
+            vec!["1234".to_string(), "名前".to_string(), "12.5%".to_string()],
+        ];
+
+        assert_eq!(
+            align_columns(&headers, &rows),
+            "PID   Name  CPU%\n1     init  0.0%\n1234  名前  12.5%\n"
+        );
+    }
+
+    #[test]
+    fn prepares_batch() {
+        let args = BottomArgs::parse_from(["btm", "--batch"]);
+        let mut app = crate::options::init_app(args, Config::default()).unwrap().0;
+        app.prepare_batch(Some("cpu > 5"), Some("mem")).unwrap();
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Proc);
+
+        let text = app.batch_text(Some(10)).unwrap();
+        assert!(text.starts_with("PID"));
+
+        assert!(app.prepare_batch(Some("("), None).is_err());
+        assert!(app.prepare_batch(None, Some("nope")).is_err());
+    }
+}
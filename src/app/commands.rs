@@ -0,0 +1,838 @@
+//! The `:` command prompt, which runs actions and changes settings by name.
+
+use super::{
+    App, AppSearchState,
+    data::TemperatureType,
+    export::{ExportFormat, expand_home, file_timestamp},
+    layout_manager::BottomWidgetType,
+};
+use crate::{
+    canvas::{capture::CaptureFormat, components::time_graph::LegendPosition},
+    utils::clipboard::CopyTarget,
+};
+
+/// The most commands kept in the prompt's history.
+const MAX_HISTORY: usize = 100;
+
+/// A command that can be run from the prompt.
+pub struct CommandSpec {
+    pub name: &'static str,
+
+    /// The arguments the command takes, as shown in suggestions.
+    pub usage: &'static str,
+    pub description: &'static str,
+}
+
+impl CommandSpec {
+    const fn new(name: &'static str, usage: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            usage,
+            description,
+        }
+    }
+}
+
+pub const COMMANDS: [CommandSpec; 21] = [
+    CommandSpec::new(
+        "capture",
+        "<text|ansi|svg|html> [path]",
+        "Save what is on the screen to a file",
+    ),
+    CommandSpec::new("columns", "", "Pick the columns of the selected table"),
+    CommandSpec::new(
+        "copy",
+        "[pid|row|cell]",
+        "Copy from the selected row of a table",
+    ),
+    CommandSpec::new("diagnostics", "", "Show collection diagnostics"),
+    CommandSpec::new("expand", "", "Expand or shrink the selected widget"),
+    CommandSpec::new(
+        "export",
+        "<csv|json> [path]",
+        "Export the selected table or graph to a file",
+    ),
+    CommandSpec::new(
+        "filter",
+        "[query]",
+        "Search the selected process table or log, or clear the search",
+    ),
+    CommandSpec::new("freeze", "", "Freeze or unfreeze the data"),
+    CommandSpec::new("group", "", "Group processes with the same name"),
+    CommandSpec::new("help", "", "Show the help menu"),
+    CommandSpec::new("kill", "", "Kill the selected process"),
+    CommandSpec::new("layout", "<name>", "Switch to a layout tab"),
+    CommandSpec::new(
+        "legend",
+        "[show|hide|<position>]",
+        "Move, show, or hide the selected graph's legend",
+    ),
+    CommandSpec::new("quit", "", "Quit bottom"),
+    CommandSpec::new("reload", "", "Reload the config file"),
+    CommandSpec::new("save", "", "Save the current settings"),
+    CommandSpec::new("sort", "<column>", "Sort the selected table by a column"),
+    CommandSpec::new("temp", "<c|f|k>", "Set the temperature unit"),
+    CommandSpec::new("tree", "", "Show processes as a tree"),
+    CommandSpec::new("units", "<binary|decimal>", "Set the network unit prefixes"),
+    CommandSpec::new("zoom", "<in|out|reset>", "Zoom the selected graph"),
+];
+
+/// A suggestion for the command prompt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommandCompletion {
+    /// The whole input with the suggestion applied.
+    pub text: String,
+
+    /// Extra information shown next to the suggestion.
+    pub hint: String,
+}
+
+/// Scores how well `pattern` matches `candidate` as a subsequence, ignoring
+/// case. Lower is better, and [`None`] means it doesn't match.
+fn fuzzy_score(pattern: &str, candidate: &str) -> Option<usize> {
+    let candidate = candidate.to_lowercase();
+    let mut chars = candidate.char_indices();
+    let mut score = 0;
+    let mut last_index = None;
+
+    for pattern_char in pattern.to_lowercase().chars() {
+        let (index, _) = chars.find(|(_, c)| *c == pattern_char)?;
+        score += match last_index {
+            Some(last_index) => index - last_index - 1,
+            None => index * 2,
+        };
+        last_index = Some(index);
+    }
+
+    Some(score)
+}
+
+/// Returns the candidates matching `pattern`, best first.
+fn rank<'a>(pattern: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut matches = candidates
+        .into_iter()
+        .enumerate()
+        .filter_map(|(order, candidate)| {
+            fuzzy_score(pattern, candidate).map(|score| (score, candidate.len(), order, candidate))
+        })
+        .collect::<Vec<_>>();
+
+    matches.sort_unstable();
+    matches
+        .into_iter()
+        .map(|(.., candidate)| candidate)
+        .collect()
+}
+
+/// Finds a command by its name, or a prefix of only one command's name.
+fn find_command(name: &str) -> Option<&'static CommandSpec> {
+    let name = name.to_lowercase();
+    if let Some(spec) = COMMANDS.iter().find(|spec| spec.name == name) {
+        return Some(spec);
+    }
+
+    let mut matching = COMMANDS.iter().filter(|spec| spec.name.starts_with(&name));
+    match (matching.next(), matching.next()) {
+        (Some(spec), None) => Some(spec),
+        _ => None,
+    }
+}
+
+/// How a column name can be typed, such as "mem" for "Mem%".
+fn column_argument(name: &str) -> String {
+    name.trim_end_matches(['%', '▲', '▼']).trim().to_lowercase()
+}
+
+impl App {
+    /// Opens the command prompt.
+    pub fn open_command_prompt(&mut self) {
+        let completions = self.command_completions("");
+
+        let state = &mut self.command_state;
+        state.is_showing = true;
+        state.input = AppSearchState::default();
+        state.history_index = None;
+        state.error = None;
+        state.message = None;
+        state.selected_completion = None;
+        state.completions = completions;
+        self.is_force_redraw = true;
+    }
+
+    pub fn close_command_prompt(&mut self) {
+        self.command_state.is_showing = false;
+        self.is_force_redraw = true;
+    }
+
+    /// Edits the command being typed, and updates the suggestions for it.
+    pub fn edit_command_input(&mut self, edit: impl FnOnce(&mut AppSearchState)) {
+        edit(&mut self.command_state.input);
+        self.update_command_completions();
+        self.command_state.history_index = None;
+    }
+
+    /// Deletes the character before the cursor, or closes the prompt if there
+    /// is nothing to delete.
+    pub fn on_command_backspace(&mut self) {
+        if self.command_state.input.current_search_query.is_empty() {
+            self.close_command_prompt();
+        } else {
+            self.edit_command_input(AppSearchState::delete_backward);
+        }
+    }
+
+    fn update_command_completions(&mut self) {
+        let input = self.command_state.input.current_search_query.clone();
+        self.command_state.completions = self.command_completions(&input);
+        self.command_state.selected_completion = None;
+        self.command_state.error = None;
+        self.command_state.message = None;
+    }
+
+    /// Fills in the next or previous suggestion, cycling through them.
+    pub fn cycle_command_completion(&mut self, forward: bool) {
+        let state = &mut self.command_state;
+        let count = state.completions.len();
+        if count == 0 {
+            return;
+        }
+
+        let selected = match (state.selected_completion, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(selected), true) => (selected + 1) % count,
+            (Some(selected), false) => (selected + count - 1) % count,
+        };
+
+        state.selected_completion = Some(selected);
+        state
+            .input
+            .set_query(state.completions[selected].text.clone());
+    }
+
+    /// Shows an older or newer command from the history in the prompt.
+    pub fn browse_command_history(&mut self, older: bool) {
+        let state = &mut self.command_state;
+        let count = state.history.len();
+
+        let index = match (state.history_index, older) {
+            (None, true) if count > 0 => Some(count - 1),
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index + 1 < count => Some(index + 1),
+            _ => None,
+        };
+
+        let query = index
+            .and_then(|index| state.history.get(index))
+            .cloned()
+            .unwrap_or_default();
+        state.input.set_query(query);
+        self.update_command_completions();
+        self.command_state.history_index = index;
+    }
+
+    /// Runs the command in the prompt. The prompt closes if it succeeds, and
+    /// otherwise shows the error.
+    pub fn submit_command(&mut self) {
+        let input = self
+            .command_state
+            .input
+            .current_search_query
+            .trim()
+            .to_string();
+        if input.is_empty() {
+            self.close_command_prompt();
+            return;
+        }
+
+        let history = &mut self.command_state.history;
+        history.retain(|command| *command != input);
+        history.push(input.clone());
+        if history.len() > MAX_HISTORY {
+            history.remove(0);
+        }
+        self.command_state.history_index = None;
+
+        // Close the prompt first, as some commands open dialogs of their own.
+        self.close_command_prompt();
+        if let Err(err) = self.run_command(&input) {
+            self.command_state.is_showing = true;
+            self.command_state.error = Some(err);
+        }
+    }
+
+    /// Returns suggestions for `input`, best first.
+    pub(crate) fn command_completions(&self, input: &str) -> Vec<CommandCompletion> {
+        let input = input.trim_start();
+
+        match input.split_once(' ') {
+            None => rank(input, COMMANDS.iter().map(|spec| spec.name))
+                .into_iter()
+                .filter_map(find_command)
+                .map(|spec| CommandCompletion {
+                    text: if spec.usage.is_empty() {
+                        spec.name.to_string()
+                    } else {
+                        format!("{} ", spec.name)
+                    },
+                    hint: format!("{} {}", spec.usage, spec.description)
+                        .trim()
+                        .to_string(),
+                })
+                .collect(),
+            Some((name, argument)) => {
+                let Some(spec) = find_command(name) else {
+                    return Vec::new();
+                };
+                let arguments = self.command_arguments(spec.name);
+
+                rank(argument.trim(), arguments.iter().map(String::as_str))
+                    .into_iter()
+                    .map(|argument| CommandCompletion {
+                        text: format!("{} {argument}", spec.name),
+                        hint: String::new(),
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns the arguments that can be given to the command `name`.
+    fn command_arguments(&self, name: &str) -> Vec<String> {
+        let to_strings = |arguments: &[&str]| arguments.iter().map(|a| a.to_string()).collect();
+
+        match name {
+            "sort" => self
+                .column_picker(self.current_widget.widget_id)
+                .map(|picker| {
+                    picker
+                        .picker_columns()
+                        .into_iter()
+                        .filter(|column| column.is_shown)
+                        .map(|column| column_argument(&column.name))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            "capture" => to_strings(&["text", "ansi", "svg", "html"]),
+            "copy" => to_strings(&["pid", "row", "cell"]),
+            "export" => to_strings(&["csv", "json"]),
+            "temp" => to_strings(&["celsius", "fahrenheit", "kelvin"]),
+            "units" => to_strings(&["binary", "decimal"]),
+            "zoom" => to_strings(&["in", "out", "reset"]),
+            "legend" => ["show", "hide"]
+                .into_iter()
+                .chain(LegendPosition::ALL.iter().map(LegendPosition::config_name))
+                .map(str::to_string)
+                .collect(),
+            "layout" => self
+                .tab_state
+                .names
+                .iter()
+                .filter(|name| !name.is_empty())
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the ID of the selected process table, including if its search
+    /// or sort widget is selected.
+    fn selected_proc_widget_id(&self) -> Option<u64> {
+        let widget_id = self.current_widget.widget_id;
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc => Some(widget_id),
+            BottomWidgetType::ProcSearch => Some(widget_id - 1),
+            BottomWidgetType::ProcSort => Some(widget_id - 2),
+            _ => None,
+        }
+    }
+
+    /// Runs a command, such as `sort mem`.
+    pub(crate) fn run_command(&mut self, input: &str) -> Result<(), String> {
+        let input = input.trim();
+        let (name, argument) = input.split_once(' ').unwrap_or((input, ""));
+        let argument = argument.trim();
+
+        let spec = find_command(name).ok_or_else(|| format!("'{name}' is not a command."))?;
+        if spec.usage.is_empty() && !argument.is_empty() {
+            return Err(format!("'{}' doesn't take any arguments.", spec.name));
+        } else if spec.usage.starts_with('<') && argument.is_empty() {
+            return Err(format!("Usage: {} {}", spec.name, spec.usage));
+        }
+
+        const SELECT_PROCESSES: &str = "Select a process table first.";
+
+        match spec.name {
+            "capture" => {
+                let (format, path) = argument.split_once(' ').unwrap_or((argument, ""));
+                let format = format
+                    .to_lowercase()
+                    .parse::<CaptureFormat>()
+                    .map_err(|_| "Use 'text', 'ansi', 'svg', or 'html' to capture.".to_string())?;
+                let path = match path.trim() {
+                    "" => format!("bottom-{}.{}", file_timestamp(), format.extension()),
+                    path => path.to_string(),
+                };
+
+                // The frame is captured the next time it is drawn, which is
+                // after the prompt has closed.
+                self.pending_capture = Some((format, expand_home(&path)));
+            }
+            "columns" => {
+                self.open_column_dialog();
+                if !self.column_dialog_state.is_showing {
+                    return Err("Select a table first.".to_string());
+                }
+            }
+            "copy" => {
+                let target = if argument.is_empty() {
+                    self.app_config_fields.clipboard.target
+                } else {
+                    argument
+                        .to_lowercase()
+                        .parse::<CopyTarget>()
+                        .map_err(|_| "Use 'pid', 'row', or 'cell' to copy.".to_string())?
+                };
+
+                if !self.copy_selection(target) {
+                    return Err(
+                        "Select a row of a process, disk, or temperature table first.".to_string(),
+                    );
+                }
+            }
+            "export" => {
+                let (format, path) = argument.split_once(' ').unwrap_or((argument, ""));
+                let format = format
+                    .to_lowercase()
+                    .parse::<ExportFormat>()
+                    .map_err(|_| "Use 'csv' or 'json' to export.".to_string())?;
+                let path = path.trim();
+
+                let message = self.export_selected(format, (!path.is_empty()).then_some(path))?;
+
+                // Keep the prompt open to say where the file went.
+                self.open_command_prompt();
+                self.command_state.message = Some(message);
+            }
+            "diagnostics" => self.open_diagnostics(),
+            "expand" => self.toggle_expand_widget(),
+            "filter" if self.current_widget.widget_type == BottomWidgetType::ProcLog => {
+                if let Some(proc_log) = self
+                    .states
+                    .proc_log_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    proc_log.set_query(argument)?;
+                }
+            }
+            "filter" if self.current_widget.widget_type == BottomWidgetType::Conn => {
+                if let Some(conn) = self
+                    .states
+                    .conn_state
+                    .get_mut_widget_state(self.current_widget.widget_id)
+                {
+                    conn.set_query(argument);
+                }
+            }
+            "filter" => {
+                let widget_id = self
+                    .selected_proc_widget_id()
+                    .ok_or(SELECT_PROCESSES.to_string())?;
+                let Some(proc_state) = self.states.proc_state.get_mut_widget_state(widget_id)
+                else {
+                    return Err(SELECT_PROCESSES.to_string());
+                };
+
+                if argument.is_empty() {
+                    proc_state.clear_search();
+                } else {
+                    proc_state.proc_search.search_state.is_enabled = true;
+                    proc_state
+                        .proc_search
+                        .search_state
+                        .set_query(argument.to_string());
+                    proc_state.update_query();
+
+                    if let Some(err) = &proc_state.proc_search.search_state.error_message {
+                        return Err(err.clone());
+                    }
+                }
+            }
+            "freeze" => self.toggle_frozen(),
+            "group" | "tree" => {
+                if self.current_widget.widget_type != BottomWidgetType::Proc {
+                    return Err(SELECT_PROCESSES.to_string());
+                }
+
+                if spec.name == "group" {
+                    self.on_tab();
+                } else {
+                    self.toggle_tree_mode();
+                }
+            }
+            "help" => self.help_dialog_state.is_showing_help = true,
+            "kill" => {
+                if self.current_widget.widget_type != BottomWidgetType::Proc {
+                    return Err(SELECT_PROCESSES.to_string());
+                } else if self.app_config_fields.is_read_only {
+                    return Err("Processes can't be killed in read-only mode.".to_string());
+                }
+
+                self.kill_current_process();
+            }
+            "layout" => {
+                let index = self
+                    .tab_state
+                    .names
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(argument))
+                    .ok_or_else(|| format!("There is no layout named '{argument}'."))?;
+                self.select_tab(index);
+            }
+            "legend" => {
+                let legend = self
+                    .states
+                    .graph_legend_mut(self.current_widget.widget_id)
+                    .ok_or_else(|| {
+                        "Select a memory, network, power, or custom graph first.".to_string()
+                    })?;
+
+                match argument.to_lowercase().as_str() {
+                    "" => legend.is_hidden = !legend.is_hidden,
+                    "show" => legend.is_hidden = false,
+                    "hide" => legend.is_hidden = true,
+                    position => {
+                        legend.position = position
+                            .parse()
+                            .map_err(|_| format!("'{position}' is not a legend position."))?;
+                        legend.is_hidden = false;
+                    }
+                }
+            }
+            "quit" => self.command_state.is_quit_requested = true,
+            "reload" => self.is_config_reload_requested = true,
+            "save" => self.is_settings_save_requested = true,
+            "sort" => {
+                let widget_id = self.current_widget.widget_id;
+                let picker = self
+                    .column_picker_mut(widget_id)
+                    .ok_or("Select a table first.".to_string())?;
+
+                let argument = column_argument(argument);
+                let columns = picker
+                    .picker_columns()
+                    .into_iter()
+                    .take_while(|column| column.is_shown)
+                    .map(|column| column_argument(&column.name))
+                    .collect::<Vec<_>>();
+
+                // Use an exact match if there is one, and otherwise the best match.
+                let index = columns
+                    .iter()
+                    .position(|column| *column == argument)
+                    .or_else(|| {
+                        columns
+                            .iter()
+                            .enumerate()
+                            .filter_map(|(index, column)| {
+                                fuzzy_score(&argument, column).map(|score| (score, index))
+                            })
+                            .min()
+                            .map(|(_, index)| index)
+                    })
+                    .ok_or_else(|| format!("There is no '{argument}' column to sort by."))?;
+
+                picker.sort_by_picked_column(index);
+            }
+            "temp" => {
+                let temperature_type = argument
+                    .to_lowercase()
+                    .parse::<TemperatureType>()
+                    .map_err(|_| "Use 'c', 'f', or 'k' for the temperature unit.".to_string())?;
+                self.app_config_fields.temperature_type = temperature_type;
+                self.data_store.set_temperature_type(temperature_type);
+                self.force_widget_data_update();
+            }
+            "units" => match argument.to_lowercase().as_str() {
+                "binary" => self.app_config_fields.network_use_binary_prefix = true,
+                "decimal" => self.app_config_fields.network_use_binary_prefix = false,
+                _ => return Err("Use 'binary' or 'decimal' for the units.".to_string()),
+            },
+            "zoom" => {
+                let is_graph = matches!(
+                    self.current_widget.widget_type,
+                    BottomWidgetType::Cpu
+                        | BottomWidgetType::Mem
+                        | BottomWidgetType::Net
+                        | BottomWidgetType::Power
+                        | BottomWidgetType::Custom
+                );
+                if !is_graph {
+                    return Err("Select a graph first.".to_string());
+                }
+
+                match argument.to_lowercase().as_str() {
+                    "in" => self.zoom_in(),
+                    "out" => self.zoom_out(),
+                    "reset" => self.reset_zoom(),
+                    _ => return Err("Use 'in', 'out', or 'reset' to zoom.".to_string()),
+                }
+            }
+            _ => {}
+        }
+
+        self.is_force_redraw = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::*;
+    use crate::{
+        args::BottomArgs,
+        options::config::Config,
+        widgets::{GraphLegend, ProcWidgetColumn},
+    };
+
+    fn create_app() -> App {
+        crate::options::init_app(BottomArgs::parse_from(["btm"]), Config::default())
+            .unwrap()
+            .0
+    }
+
+    /// Selects the first widget of type `widget_type`.
+    fn select(app: &mut App, widget_type: BottomWidgetType) {
+        let widget = app
+            .widget_map
+            .values()
+            .filter(|widget| widget.widget_type == widget_type)
+            .min_by_key(|widget| widget.widget_id)
+            .unwrap()
+            .clone();
+        app.current_widget = widget;
+    }
+
+    #[test]
+    fn fuzzy_matching() {
+        assert_eq!(fuzzy_score("", "sort"), Some(0));
+        assert_eq!(fuzzy_score("st", "sort"), Some(2));
+        assert_eq!(fuzzy_score("SO", "sort"), Some(0));
+        assert_eq!(fuzzy_score("ts", "sort"), None);
+
+        assert_eq!(
+            rank("re", ["freeze", "reload", "tree"]),
+            vec!["reload", "tree", "freeze"]
+        );
+        assert_eq!(rank("fz", ["freeze", "filter"]), vec!["freeze"]);
+    }
+
+    #[test]
+    fn finding_commands() {
+        assert_eq!(find_command("sort").map(|spec| spec.name), Some("sort"));
+        assert_eq!(find_command("fr").map(|spec| spec.name), Some("freeze"));
+        assert_eq!(find_command("Kill").map(|spec| spec.name), Some("kill"));
+
+        // Ambiguous or unknown names don't match.
+        assert!(find_command("f").is_none());
+        assert!(find_command("nope").is_none());
+
+        assert_eq!(column_argument("Mem%"), "mem");
+        assert_eq!(column_argument("CPU%▼"), "cpu");
+    }
+
+    #[test]
+    fn completing_commands() {
+        let mut app = create_app();
+        select(&mut app, BottomWidgetType::Proc);
+
+        let texts = |input: &str| {
+            app.command_completions(input)
+                .into_iter()
+                .map(|completion| completion.text)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(texts("").len(), COMMANDS.len());
+        assert_eq!(texts("fre"), vec!["freeze"]);
+        assert_eq!(texts("so")[0], "sort ");
+        assert_eq!(texts("temp f"), vec!["temp fahrenheit"]);
+        assert_eq!(texts("un b"), vec!["units binary"]);
+        assert!(texts("sort mem").contains(&"sort mem".to_string()));
+        assert!(texts("nope x").is_empty());
+    }
+
+    #[test]
+    fn running_commands() {
+        let mut app = create_app();
+
+        assert!(app.run_command("nope").is_err());
+        assert!(app.run_command("freeze now").is_err());
+        assert!(app.run_command("temp").is_err());
+        assert!(app.run_command("temp hot").is_err());
+
+        app.run_command("temp f").unwrap();
+        assert_eq!(
+            app.app_config_fields.temperature_type,
+            TemperatureType::Fahrenheit
+        );
+        app.run_command("te kelvin").unwrap();
+        assert_eq!(
+            app.app_config_fields.temperature_type,
+            TemperatureType::Kelvin
+        );
+
+        app.run_command("units binary").unwrap();
+        assert!(app.app_config_fields.network_use_binary_prefix);
+        app.run_command("units decimal").unwrap();
+        assert!(!app.app_config_fields.network_use_binary_prefix);
+
+        assert!(!app.data_store.is_frozen());
+        app.run_command("freeze").unwrap();
+        assert!(app.data_store.is_frozen());
+
+        app.run_command("quit").unwrap();
+        assert!(app.command_state.is_quit_requested);
+
+        assert!(app.run_command("layout io").is_err());
+    }
+
+    #[test]
+    fn running_legend_commands() {
+        let mut app = create_app();
+        select(&mut app, BottomWidgetType::Proc);
+        assert!(app.run_command("legend hide").is_err());
+
+        select(&mut app, BottomWidgetType::Mem);
+        let widget_id = app.current_widget.widget_id;
+        let legend = |app: &mut App| *app.states.graph_legend_mut(widget_id).unwrap();
+
+        app.run_command("legend").unwrap();
+        assert!(legend(&mut app).is_hidden);
+        app.run_command("legend bottom-left").unwrap();
+        assert_eq!(
+            legend(&mut app),
+            GraphLegend {
+                position: LegendPosition::BottomLeft,
+                is_hidden: false,
+            }
+        );
+        assert!(app.run_command("legend middle").is_err());
+    }
+
+    #[test]
+    fn running_process_commands() {
+        let mut app = create_app();
+        select(&mut app, BottomWidgetType::Cpu);
+        assert!(app.run_command("filter btm").is_err());
+        assert!(app.run_command("sort mem").is_err());
+
+        select(&mut app, BottomWidgetType::Proc);
+        let widget_id = app.current_widget.widget_id;
+
+        app.run_command("sort mem").unwrap();
+        let proc_state = app.states.proc_state.get_widget_state(widget_id).unwrap();
+        assert_eq!(
+            proc_state.table.sort_index(),
+            proc_state
+                .column_mapping
+                .get_index_of(&ProcWidgetColumn::Mem)
+                .unwrap()
+        );
+        assert!(app.run_command("sort nope").is_err());
+
+        app.run_command("filter user=postgres").unwrap();
+        let search_state = &app
+            .states
+            .proc_state
+            .get_widget_state(widget_id)
+            .unwrap()
+            .proc_search
+            .search_state;
+        assert!(search_state.is_enabled);
+        assert_eq!(search_state.current_search_query, "user=postgres");
+
+        assert!(app.run_command("filter (").is_err());
+
+        app.run_command("filter").unwrap();
+        let search_state = &app
+            .states
+            .proc_state
+            .get_widget_state(widget_id)
+            .unwrap()
+            .proc_search
+            .search_state;
+        assert!(search_state.current_search_query.is_empty());
+    }
+
+    #[test]
+    fn running_export_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("processes.json");
+
+        let mut app = create_app();
+        select(&mut app, BottomWidgetType::Proc);
+        assert!(app.run_command("export").is_err());
+        assert!(app.run_command("export xml").is_err());
+
+        app.open_command_prompt();
+        app.edit_command_input(|input| {
+            input.insert_str(&format!("export json {}", path.display()))
+        });
+        app.submit_command();
+        assert!(app.command_state.is_showing);
+        assert!(app.command_state.error.is_none());
+        assert!(app.command_state.message.is_some());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[]");
+
+        app.open_export_prompt();
+        assert!(
+            app.command_state
+                .input
+                .current_search_query
+                .starts_with("export csv bottom-processes-")
+        );
+    }
+
+    #[test]
+    fn running_capture_commands() {
+        let mut app = create_app();
+        assert!(app.run_command("capture png").is_err());
+
+        app.run_command("capture svg ~/frame.svg").unwrap();
+        let (format, path) = app.pending_capture.take().unwrap();
+        assert_eq!(format, CaptureFormat::Svg);
+        assert!(path.ends_with("frame.svg"));
+
+        app.run_command("capture ansi").unwrap();
+        let (format, path) = app.pending_capture.take().unwrap();
+        assert_eq!(format, CaptureFormat::Ansi);
+        assert_eq!(path.extension().unwrap(), "ansi");
+    }
+
+    #[test]
+    fn browsing_history() {
+        let mut app = create_app();
+        app.open_command_prompt();
+        app.edit_command_input(|input| input.insert_str("temp c"));
+        app.submit_command();
+        assert!(!app.command_state.is_showing);
+
+        app.open_command_prompt();
+        app.edit_command_input(|input| input.insert_str("oops"));
+        app.submit_command();
+        assert!(app.command_state.is_showing);
+        assert!(app.command_state.error.is_some());
+
+        assert_eq!(app.command_state.history, vec!["temp c", "oops"]);
+        app.browse_command_history(true);
+        assert_eq!(app.command_state.input.current_search_query, "oops");
+        app.browse_command_history(true);
+        assert_eq!(app.command_state.input.current_search_query, "temp c");
+        app.browse_command_history(false);
+        app.browse_command_history(false);
+        assert_eq!(app.command_state.input.current_search_query, "");
+    }
+}
@@ -0,0 +1,296 @@
+//! Downsampled time series data, used to keep long retention periods cheap.
+//!
+//! Only the most recent [`RAW_RETENTION`] of samples are kept at full resolution.
+//! Older samples are rolled up into coarser buckets, each keeping the average
+//! and maximum of the samples within it. The samples of a bucket that is not yet
+//! complete are kept as-is after the completed buckets, so the newest data is
+//! always shown.
+
+use std::time::Duration;
+
+use super::TimeSeriesData;
+use crate::collection::Data;
+
+/// How long samples are kept at full resolution if the retention period is longer.
+pub const RAW_RETENTION: Duration = Duration::from_secs(10 * 60);
+
+/// The bucket width and maximum age of each downsampled tier, from finest to coarsest.
+const TIERS: [(Duration, Duration); 2] = [
+    (Duration::from_secs(5), Duration::from_secs(60 * 60)),
+    (Duration::from_secs(60), Duration::MAX),
+];
+
+/// How to combine the samples within a bucket into one value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reduction {
+    Avg,
+    Max,
+}
+
+impl Reduction {
+    /// Reduces the given values into one, or [`None`] if there are no values.
+    pub fn apply<'a>(self, values: impl Iterator<Item = &'a f64>) -> Option<f64> {
+        match self {
+            Reduction::Avg => {
+                let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+                (count > 0).then(|| sum / count as f64)
+            }
+            Reduction::Max => values.copied().reduce(f64::max),
+        }
+    }
+}
+
+/// The time series data at one resolution, ready to be drawn.
+///
+/// Both share the same time values. At full resolution, they are the same data.
+#[derive(Clone, Copy, Debug)]
+pub struct TimeSeriesResolution<'a> {
+    /// The average of each bucket. This is what should be drawn.
+    pub avg: &'a TimeSeriesData,
+
+    /// The maximum of each bucket. Use this to determine graph bounds so that
+    /// short spikes are not lost.
+    pub max: &'a TimeSeriesData,
+}
+
+impl<'a> TimeSeriesResolution<'a> {
+    /// A resolution of just the given data.
+    pub fn raw(data: &'a TimeSeriesData) -> Self {
+        Self {
+            avg: data,
+            max: data,
+        }
+    }
+}
+
+/// One level of downsampled data.
+#[derive(Clone, Debug)]
+pub struct DownsampledTier {
+    /// How much time each bucket covers.
+    pub bucket_width: Duration,
+
+    /// How long to keep buckets for, at most.
+    pub max_age: Duration,
+
+    /// The average of each completed bucket, followed by the pending samples.
+    pub avg: TimeSeriesData,
+
+    /// The maximum of each completed bucket, followed by the pending samples.
+    pub max: TimeSeriesData,
+
+    /// The raw samples in the current, not yet completed, bucket.
+    pending: TimeSeriesData,
+}
+
+impl DownsampledTier {
+    fn new(bucket_width: Duration, max_age: Duration) -> Self {
+        Self {
+            bucket_width,
+            max_age,
+            avg: TimeSeriesData::default(),
+            max: TimeSeriesData::default(),
+            pending: TimeSeriesData::default(),
+        }
+    }
+
+    /// Add a new data point, completing the current bucket if it is full.
+    fn add(&mut self, data: &Data) {
+        if let Some(&start) = self.pending.time.first() {
+            if data.collection_time.duration_since(start) >= self.bucket_width {
+                self.complete_bucket();
+            }
+        }
+
+        self.pending.add(data);
+        self.avg.add(data);
+        self.max.add(data);
+    }
+
+    /// Rolls up the pending samples into a new bucket, replacing them.
+    fn complete_bucket(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        let Some(&time) = pending.time.last() else {
+            return;
+        };
+
+        let completed = self.avg.time.len().saturating_sub(pending.time.len());
+        self.avg.truncate(completed);
+        self.max.truncate(completed);
+
+        self.avg.add_reduced(time, &pending, Reduction::Avg);
+        self.max.add_reduced(time, &pending, Reduction::Max);
+    }
+
+    fn prune(&mut self, max_age: Duration) {
+        let max_age = max_age.min(self.max_age);
+
+        self.avg.prune(max_age);
+        self.max.prune(max_age);
+    }
+
+    fn resolution(&self) -> TimeSeriesResolution<'_> {
+        TimeSeriesResolution {
+            avg: &self.avg,
+            max: &self.max,
+        }
+    }
+}
+
+/// Time series data rolled up into progressively coarser tiers.
+#[derive(Clone, Debug)]
+pub struct DownsampledData {
+    tiers: Vec<DownsampledTier>,
+}
+
+impl Default for DownsampledData {
+    fn default() -> Self {
+        Self {
+            tiers: TIERS
+                .iter()
+                .map(|&(bucket_width, max_age)| DownsampledTier::new(bucket_width, max_age))
+                .collect(),
+        }
+    }
+}
+
+impl DownsampledData {
+    /// Add a new data point to every tier.
+    pub fn add(&mut self, data: &Data) {
+        for tier in &mut self.tiers {
+            tier.add(data);
+        }
+    }
+
+    /// Prune any buckets older than the given duration, or the tier's own
+    /// maximum age, whichever is shorter.
+    pub fn prune(&mut self, max_age: Duration) {
+        for tier in &mut self.tiers {
+            tier.prune(max_age);
+        }
+    }
+
+    /// Returns the finest tier that covers the last `display_time`, if any
+    /// tier has data. If none cover it, the coarsest tier with data is used.
+    pub fn resolution_for(&self, display_time: Duration) -> Option<TimeSeriesResolution<'_>> {
+        let mut tiers = self.tiers.iter().filter(|tier| !tier.avg.time.is_empty());
+
+        tiers
+            .clone()
+            .find(|tier| tier.max_age >= display_time)
+            .or_else(|| tiers.next_back())
+            .map(DownsampledTier::resolution)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{num::NonZeroU64, time::Instant};
+
+    use super::*;
+    use crate::collection::memory::MemData;
+
+    fn data_at(collection_time: Instant, used_bytes: u64) -> Data {
+        Data {
+            collection_time,
+            memory: Some(MemData {
+                used_bytes,
+                total_bytes: NonZeroU64::new(100).unwrap(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reductions() {
+        let values = [1.0, 4.0, 2.0];
+
+        assert_eq!(Reduction::Avg.apply(values.iter()), Some(7.0 / 3.0));
+        assert_eq!(Reduction::Max.apply(values.iter()), Some(4.0));
+        assert_eq!(Reduction::Avg.apply([].iter()), None);
+    }
+
+    #[test]
+    fn rolls_up_buckets() {
+        let start = Instant::now();
+        let mut downsampled = DownsampledData::default();
+
+        // Two full 5 second buckets of samples every second, then one more sample to
+        // complete the second bucket.
+        for (secs, used) in [(0, 10), (1, 20), (2, 30), (3, 40), (4, 50)]
+            .into_iter()
+            .chain([(5, 0), (6, 0), (7, 100), (8, 0), (9, 0), (10, 0)])
+        {
+            downsampled.add(&data_at(start + Duration::from_secs(secs), used));
+        }
+
+        // The two buckets, then the pending sample.
+        let tier = &downsampled.tiers[0];
+        assert_eq!(
+            tier.avg.time,
+            [4, 9, 10].map(|secs| start + Duration::from_secs(secs))
+        );
+
+        let avg = tier.avg.ram.iter().copied().collect::<Vec<_>>();
+        let max = tier.max.ram.iter().copied().collect::<Vec<_>>();
+        assert_eq!(avg, vec![30.0, 20.0, 0.0]);
+        assert_eq!(max, vec![50.0, 100.0, 0.0]);
+
+        // The coarser tier has not completed a bucket yet, so it only has the
+        // pending samples.
+        assert_eq!(downsampled.tiers[1].avg.time.len(), 11);
+    }
+
+    #[test]
+    fn rolls_up_pending_gaps() {
+        let start = Instant::now();
+        let mut downsampled = DownsampledData::default();
+
+        // A sample without memory data leaves a gap until its bucket completes.
+        downsampled.add(&data_at(start, 10));
+        downsampled.add(&Data {
+            collection_time: start + Duration::from_secs(1),
+            ..Default::default()
+        });
+        downsampled.add(&data_at(start + Duration::from_secs(2), 30));
+
+        let tier = &downsampled.tiers[0];
+        let ram = tier.avg.ram.iter_with_index().collect::<Vec<_>>();
+        assert_eq!(ram, vec![(0, &10.0), (2, &30.0)]);
+
+        downsampled.add(&data_at(start + Duration::from_secs(5), 50));
+
+        let tier = &downsampled.tiers[0];
+        let ram = tier.avg.ram.iter_with_index().collect::<Vec<_>>();
+        assert_eq!(ram, vec![(0, &20.0), (1, &50.0)]);
+    }
+
+    #[test]
+    fn picks_resolution() {
+        let start = Instant::now();
+        let mut downsampled = DownsampledData::default();
+        assert!(
+            downsampled
+                .resolution_for(Duration::from_secs(60))
+                .is_none()
+        );
+
+        for secs in 0..=120 {
+            downsampled.add(&data_at(start + Duration::from_secs(secs), 50));
+        }
+
+        let fine = downsampled
+            .resolution_for(Duration::from_secs(30 * 60))
+            .unwrap();
+        // 24 buckets and the newest sample.
+        assert_eq!(fine.avg.time.len(), 25);
+
+        let coarse = downsampled
+            .resolution_for(Duration::from_secs(2 * 60 * 60))
+            .unwrap();
+        assert_eq!(coarse.avg.time.len(), 3);
+        assert_eq!(
+            coarse.avg.time.last(),
+            Some(&(start + Duration::from_secs(120)))
+        );
+    }
+}
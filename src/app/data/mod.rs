@@ -3,8 +3,14 @@
 mod time_series;
 pub use time_series::{TimeSeriesData, Values};
 
+mod downsampled;
+pub use downsampled::{DownsampledData, RAW_RETENTION, TimeSeriesResolution};
+
 mod process;
-pub use process::ProcessData;
+pub use process::{ProcessData, ProcessHistory};
+
+mod process_log;
+pub use process_log::{ProcessEvent, ProcessLog};
 
 mod store;
 pub use store::*;
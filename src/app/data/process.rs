@@ -1,8 +1,13 @@
-use std::{collections::BTreeMap, vec::Vec};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+    vec::Vec,
+};
 
 use nohash::IntMap;
 
-use crate::collection::processes::{Pid, ProcessHarvest};
+use crate::collection::processes::{Bytes, Pid, ProcessHarvest};
 
 #[derive(Clone, Debug, Default)]
 pub struct ProcessData {
@@ -53,3 +58,203 @@ impl ProcessData {
             .collect();
     }
 }
+
+/// The values of a process that change from one collection to the next.
+#[derive(Clone, Copy, Debug)]
+struct ProcessUsage {
+    pid: Pid,
+    cpu_usage_percent: f32,
+    mem_usage_percent: f32,
+    mem_usage: Bytes,
+    virtual_mem: Bytes,
+    read_per_sec: Bytes,
+    write_per_sec: Bytes,
+}
+
+impl From<&ProcessHarvest> for ProcessUsage {
+    fn from(process: &ProcessHarvest) -> Self {
+        ProcessUsage {
+            pid: process.pid,
+            cpu_usage_percent: process.cpu_usage_percent,
+            mem_usage_percent: process.mem_usage_percent,
+            mem_usage: process.mem_usage,
+            virtual_mem: process.virtual_mem,
+            read_per_sec: process.read_per_sec,
+            write_per_sec: process.write_per_sec,
+        }
+    }
+}
+
+/// The usage of every process at one collection.
+#[derive(Debug)]
+struct ProcessSnapshot {
+    time: Instant,
+    usage: Vec<ProcessUsage>,
+}
+
+/// Compact snapshots of past process data, one per process collection, so that
+/// the process table can show what was running at an earlier point in time.
+///
+/// Only the usage values are stored per snapshot. Everything else about a
+/// process, such as its name and user, is stored once and taken from the last
+/// time it was seen.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessHistory {
+    snapshots: VecDeque<Arc<ProcessSnapshot>>,
+
+    /// The last harvested details of each process in the snapshots, along with
+    /// when it was last seen.
+    details: IntMap<Pid, (ProcessHarvest, Instant)>,
+}
+
+impl ProcessHistory {
+    /// Adds a snapshot of `processes`, collected at `time`.
+    pub(super) fn add(&mut self, time: Instant, processes: &[ProcessHarvest]) {
+        let usage = processes.iter().map(ProcessUsage::from).collect();
+        self.snapshots
+            .push_back(Arc::new(ProcessSnapshot { time, usage }));
+
+        for process in processes {
+            self.details.insert(process.pid, (process.clone(), time));
+        }
+    }
+
+    /// Removes snapshots older than `max_age`, along with the details of
+    /// processes that are no longer in any of them.
+    pub(super) fn prune(&mut self, max_age: Duration) {
+        let now = Instant::now();
+        let end = self
+            .snapshots
+            .partition_point(|snapshot| now.duration_since(snapshot.time) > max_age);
+        if end == 0 {
+            return;
+        }
+
+        self.snapshots.drain(..end);
+        self.snapshots.shrink_to_fit();
+
+        match self.snapshots.front() {
+            Some(oldest) => {
+                let oldest = oldest.time;
+                self.details
+                    .retain(|_, (_, last_seen)| *last_seen >= oldest);
+            }
+            None => self.details.clear(),
+        }
+    }
+
+    /// Returns the times of the snapshots, oldest first.
+    pub fn times(&self) -> impl DoubleEndedIterator<Item = Instant> + '_ {
+        self.snapshots.iter().map(|snapshot| snapshot.time)
+    }
+
+    /// Returns the time of the snapshot closest to `time`.
+    pub fn nearest(&self, time: Instant) -> Option<Instant> {
+        self.times().min_by_key(|snapshot_time| {
+            if *snapshot_time > time {
+                snapshot_time.duration_since(time)
+            } else {
+                time.duration_since(*snapshot_time)
+            }
+        })
+    }
+
+    /// Rebuilds the process data from the snapshot taken at `time`.
+    pub fn process_data_at(&self, time: Instant) -> Option<ProcessData> {
+        let index = self
+            .snapshots
+            .binary_search_by_key(&time, |snapshot| snapshot.time)
+            .ok()?;
+
+        let processes = self.snapshots[index]
+            .usage
+            .iter()
+            .filter_map(|usage| {
+                let (details, _) = self.details.get(&usage.pid)?;
+
+                Some(ProcessHarvest {
+                    cpu_usage_percent: usage.cpu_usage_percent,
+                    mem_usage_percent: usage.mem_usage_percent,
+                    mem_usage: usage.mem_usage,
+                    virtual_mem: usage.virtual_mem,
+                    read_per_sec: usage.read_per_sec,
+                    write_per_sec: usage.write_per_sec,
+                    ..details.clone()
+                })
+            })
+            .collect();
+
+        let mut process_data = ProcessData::default();
+        process_data.ingest(processes);
+
+        Some(process_data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn process(pid: Pid, name: &str, cpu_usage_percent: f32) -> ProcessHarvest {
+        ProcessHarvest {
+            pid,
+            parent_pid: None,
+            name: name.to_string(),
+            cpu_usage_percent,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rebuilds_past_processes() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(1);
+
+        let mut history = ProcessHistory::default();
+        history.add(start, &[process(1, "init", 1.0), process(2, "spike", 90.0)]);
+        history.add(later, &[process(1, "init", 2.0)]);
+
+        assert_eq!(history.times().collect::<Vec<_>>(), vec![start, later]);
+        assert_eq!(
+            history.nearest(start + Duration::from_millis(400)),
+            Some(start)
+        );
+        assert_eq!(history.nearest(later + Duration::from_secs(5)), Some(later));
+
+        let past = history.process_data_at(start).unwrap();
+        assert_eq!(past.process_harvest.len(), 2);
+        assert_eq!(past.process_harvest[&2].name, "spike");
+        assert_eq!(past.process_harvest[&2].cpu_usage_percent, 90.0);
+        assert_eq!(past.orphan_pids, vec![1, 2]);
+
+        let latest = history.process_data_at(later).unwrap();
+        assert_eq!(latest.process_harvest.len(), 1);
+        assert_eq!(latest.process_harvest[&1].cpu_usage_percent, 2.0);
+
+        assert!(
+            history
+                .process_data_at(start - Duration::from_secs(1))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn prunes_old_snapshots() {
+        let now = Instant::now();
+        let Some(old) = now.checked_sub(Duration::from_secs(60)) else {
+            return;
+        };
+
+        let mut history = ProcessHistory::default();
+        history.add(old, &[process(1, "init", 1.0), process(2, "gone", 5.0)]);
+        history.add(now, &[process(1, "init", 2.0)]);
+
+        history.prune(Duration::from_secs(30));
+        assert_eq!(history.times().collect::<Vec<_>>(), vec![now]);
+        assert!(history.details.contains_key(&1));
+        assert!(!history.details.contains_key(&2));
+
+        history.prune(Duration::ZERO);
+        assert!(history.details.is_empty());
+    }
+}
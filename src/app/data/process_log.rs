@@ -0,0 +1,292 @@
+//! A log of processes starting and exiting, found by comparing each process
+//! harvest with the one before it.
+
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use nohash::{IntMap, IntSet};
+use rustc_hash::FxHashMap as HashMap;
+
+use crate::collection::processes::{Pid, ProcessHarvest};
+
+/// The most events kept in the log.
+const MAX_EVENTS: usize = 1000;
+
+/// How far apart the start times of two processes with the same PID can be
+/// while still being treated as the same process. Process uptimes are only
+/// collected to the second, so the start times worked out from them jitter a
+/// little between harvests.
+const START_TIME_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// How long starts of the same command are counted for to find processes that
+/// restart repeatedly.
+const RESTART_WINDOW: Duration = Duration::from_secs(60);
+
+/// How many times a command has to start within [`RESTART_WINDOW`] to be
+/// treated as restarting repeatedly.
+pub const RESTART_THRESHOLD: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProcessEventKind {
+    Started,
+    Exited,
+}
+
+impl ProcessEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProcessEventKind::Started => "Start",
+            ProcessEventKind::Exited => "Exit",
+        }
+    }
+}
+
+/// A process starting or exiting.
+#[derive(Clone, Debug)]
+pub struct ProcessEvent {
+    /// When the event was noticed.
+    pub time: Instant,
+
+    pub kind: ProcessEventKind,
+
+    /// The process as it was last seen, with its CPU and memory usage replaced
+    /// by the highest seen. Its running time is how long it had been running
+    /// when last seen.
+    pub process: ProcessHarvest,
+
+    /// How many times the process's command started within [`RESTART_WINDOW`]
+    /// up to this event.
+    pub recent_starts: usize,
+}
+
+impl ProcessEvent {
+    /// Whether the process's command keeps restarting.
+    pub fn is_restarting(&self) -> bool {
+        self.recent_starts >= RESTART_THRESHOLD
+    }
+}
+
+/// A process that is still running.
+#[derive(Clone, Debug)]
+struct TrackedProcess {
+    /// The process as it was last seen, with the peak usage seen so far.
+    process: ProcessHarvest,
+
+    /// When the process started, worked out from its running time.
+    start: Instant,
+}
+
+impl TrackedProcess {
+    fn new(process: &ProcessHarvest, time: Instant) -> Self {
+        Self {
+            process: process.clone(),
+            start: time.checked_sub(process.time).unwrap_or(time),
+        }
+    }
+
+    /// Whether `other`, with the same PID, is this process rather than a new
+    /// one that reused the PID.
+    fn is_same(&self, other: &TrackedProcess) -> bool {
+        // Some platforms don't always report running times, so treat those as
+        // the same process rather than a new one every harvest.
+        if self.process.time.is_zero() || other.process.time.is_zero() {
+            return true;
+        }
+
+        other.start <= self.start + START_TIME_TOLERANCE
+    }
+
+    /// Updates the process with a newer harvest of it, keeping the peak usage.
+    fn update(&mut self, newer: TrackedProcess) {
+        let old = std::mem::replace(&mut self.process, newer.process);
+        let process = &mut self.process;
+
+        process.cpu_usage_percent = process.cpu_usage_percent.max(old.cpu_usage_percent);
+        if old.mem_usage > process.mem_usage {
+            process.mem_usage = old.mem_usage;
+            process.mem_usage_percent = old.mem_usage_percent;
+        }
+    }
+}
+
+/// Processes that started or exited, newest last.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessLog {
+    events: VecDeque<Arc<ProcessEvent>>,
+
+    /// The processes seen in the last harvest.
+    running: IntMap<Pid, TrackedProcess>,
+
+    /// When each command recently started, for finding restarting processes.
+    recent_starts: HashMap<String, VecDeque<Instant>>,
+
+    /// Whether a harvest has been seen yet. The processes in the first one were
+    /// already running, so their starts aren't logged.
+    has_first_harvest: bool,
+}
+
+impl ProcessLog {
+    /// Logs the processes that started or exited since the last harvest, given
+    /// `processes` harvested at `time`.
+    pub(crate) fn add(&mut self, time: Instant, processes: &[ProcessHarvest]) {
+        let is_first_harvest = !self.has_first_harvest;
+        self.has_first_harvest = true;
+
+        let mut seen = IntSet::default();
+        for process in processes {
+            #[cfg(target_os = "linux")]
+            if process.process_type.is_thread() {
+                continue;
+            }
+
+            seen.insert(process.pid);
+            let newer = TrackedProcess::new(process, time);
+            match self.running.get_mut(&process.pid) {
+                Some(tracked) if tracked.is_same(&newer) => tracked.update(newer),
+                _ => {
+                    if let Some(old) = self.running.remove(&process.pid) {
+                        self.push(time, ProcessEventKind::Exited, old.process);
+                    }
+                    if !is_first_harvest {
+                        self.push(time, ProcessEventKind::Started, newer.process.clone());
+                    }
+                    self.running.insert(process.pid, newer);
+                }
+            }
+        }
+
+        let exited = self
+            .running
+            .keys()
+            .filter(|pid| !seen.contains(*pid))
+            .copied()
+            .collect::<Vec<_>>();
+        for pid in exited {
+            if let Some(old) = self.running.remove(&pid) {
+                self.push(time, ProcessEventKind::Exited, old.process);
+            }
+        }
+
+        self.recent_starts.retain(|_, starts| {
+            starts
+                .back()
+                .is_some_and(|start| time.duration_since(*start) <= RESTART_WINDOW)
+        });
+    }
+
+    fn push(&mut self, time: Instant, kind: ProcessEventKind, process: ProcessHarvest) {
+        let starts = self
+            .recent_starts
+            .entry(process.command.clone())
+            .or_default();
+        if kind == ProcessEventKind::Started {
+            starts.push_back(time);
+        }
+        while starts
+            .front()
+            .is_some_and(|start| time.duration_since(*start) > RESTART_WINDOW)
+        {
+            starts.pop_front();
+        }
+        let recent_starts = starts.len();
+
+        self.events.push_back(Arc::new(ProcessEvent {
+            time,
+            kind,
+            process,
+            recent_starts,
+        }));
+        if self.events.len() > MAX_EVENTS {
+            self.events.pop_front();
+        }
+    }
+
+    /// Returns the events, oldest first.
+    pub fn events(&self) -> impl DoubleEndedIterator<Item = &Arc<ProcessEvent>> + '_ {
+        self.events.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn process(pid: Pid, command: &str, secs: u64, cpu_usage_percent: f32) -> ProcessHarvest {
+        ProcessHarvest {
+            pid,
+            name: command.to_string(),
+            command: command.to_string(),
+            time: Duration::from_secs(secs),
+            cpu_usage_percent,
+            ..Default::default()
+        }
+    }
+
+    fn kinds(log: &ProcessLog) -> Vec<(Pid, ProcessEventKind)> {
+        log.events()
+            .map(|event| (event.process.pid, event.kind))
+            .collect()
+    }
+
+    #[test]
+    fn logs_started_and_exited_processes() {
+        use ProcessEventKind::*;
+
+        let start = Instant::now() + Duration::from_secs(100);
+        let at = |secs| start + Duration::from_secs(secs);
+
+        let mut log = ProcessLog::default();
+        log.add(
+            at(0),
+            &[process(1, "init", 50, 1.0), process(2, "cc", 1, 80.0)],
+        );
+        assert!(kinds(&log).is_empty());
+
+        log.add(
+            at(1),
+            &[process(1, "init", 51, 1.0), process(2, "cc", 2, 20.0)],
+        );
+        log.add(
+            at(2),
+            &[process(1, "init", 52, 1.0), process(3, "cron", 1, 5.0)],
+        );
+        assert_eq!(kinds(&log), vec![(3, Started), (2, Exited)]);
+
+        let exited = log.events().next_back().unwrap();
+        assert_eq!(exited.process.cpu_usage_percent, 80.0);
+        assert_eq!(exited.process.time, Duration::from_secs(2));
+
+        // The PID was reused by a process that started after the first.
+        log.add(
+            at(10),
+            &[process(1, "init", 60, 1.0), process(3, "sh", 1, 0.0)],
+        );
+        assert_eq!(kinds(&log)[2..], [(3, Exited), (3, Started)]);
+        assert_eq!(log.events().next_back().unwrap().process.name, "sh");
+    }
+
+    #[test]
+    fn finds_restarting_processes() {
+        let start = Instant::now() + Duration::from_secs(100);
+        let at = |secs| start + Duration::from_secs(secs);
+
+        let mut log = ProcessLog::default();
+        log.add(at(0), &[]);
+        for (secs, pid) in [(1, 10), (2, 11), (3, 12)] {
+            log.add(at(secs), &[process(pid, "crashy", 0, 0.0)]);
+        }
+
+        let restarts = log
+            .events()
+            .filter(|event| event.kind == ProcessEventKind::Started)
+            .map(|event| event.is_restarting())
+            .collect::<Vec<_>>();
+        assert_eq!(restarts, vec![false, false, true]);
+
+        log.add(at(200), &[process(13, "crashy", 0, 0.0)]);
+        assert!(!log.events().next_back().unwrap().is_restarting());
+    }
+}
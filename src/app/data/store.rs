@@ -3,12 +3,18 @@ use std::{
     vec::Vec,
 };
 
-use super::{ProcessData, TimeSeriesData};
+use super::{
+    DownsampledData, ProcessData, ProcessHistory, ProcessLog, RAW_RETENTION, TemperatureType,
+    TimeSeriesData, TimeSeriesResolution,
+};
 #[cfg(feature = "battery")]
 use crate::collection::batteries;
 use crate::{
     app::AppConfigFields,
-    collection::{Data, cpu, disks, memory::MemData, network},
+    collection::{
+        Data, connections::ConnectionHarvest, cpu, custom::CustomMetricData,
+        diagnostics::CollectionDiagnostics, disks, memory::MemData, network, power::PowerData,
+    },
     utils::data_units::DataUnit,
     widgets::{DiskWidgetData, TempWidgetData},
 };
@@ -20,6 +26,9 @@ use crate::{
 pub struct StoredData {
     pub last_update_time: Instant, // FIXME: (points_rework_v1) we could be able to remove this with some more refactoring.
     pub timeseries_data: TimeSeriesData,
+    /// Older time series data at coarser resolutions. Only used if the retention
+    /// period is longer than [`RAW_RETENTION`].
+    pub downsampled_data: DownsampledData,
     pub network_harvest: network::NetworkHarvest,
     pub ram_harvest: Option<MemData>,
     pub swap_harvest: Option<MemData>,
@@ -31,15 +40,25 @@ pub struct StoredData {
     pub gpu_harvest: Vec<(String, MemData)>,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     pub gpu_data_harvest: Vec<crate::collection::gpu::GpuData>,
+    pub power_harvest: Vec<PowerData>,
+    pub custom_harvest: Vec<CustomMetricData>,
     pub cpu_harvest: cpu::CpuHarvest,
     pub load_avg_harvest: cpu::LoadAvgHarvest,
     pub process_data: ProcessData,
+    /// Past process data, kept for as long as [`StoredData::timeseries_data`].
+    pub process_history: ProcessHistory,
+    /// Processes that recently started or exited.
+    pub process_log: ProcessLog,
+    pub connection_harvest: Vec<ConnectionHarvest>,
     /// TODO: (points_rework_v1) Might be a better way to do this without having to store here?
     pub prev_io: Vec<(u64, u64)>,
+    /// When disk data was last harvested, as disks may be collected less often than other data.
+    pub last_disk_update_time: Instant,
     pub disk_harvest: Vec<DiskWidgetData>,
     pub temp_data: Vec<TempWidgetData>,
     #[cfg(feature = "battery")]
     pub battery_harvest: Vec<batteries::BatteryData>,
+    pub diagnostics: CollectionDiagnostics,
 }
 
 impl Default for StoredData {
@@ -47,6 +66,7 @@ impl Default for StoredData {
         StoredData {
             last_update_time: Instant::now(),
             timeseries_data: TimeSeriesData::default(),
+            downsampled_data: DownsampledData::default(),
             network_harvest: network::NetworkHarvest::default(),
             ram_harvest: None,
             #[cfg(not(target_os = "windows"))]
@@ -55,7 +75,11 @@ impl Default for StoredData {
             cpu_harvest: cpu::CpuHarvest::default(),
             load_avg_harvest: cpu::LoadAvgHarvest::default(),
             process_data: Default::default(),
+            process_history: ProcessHistory::default(),
+            process_log: ProcessLog::default(),
+            connection_harvest: Vec::default(),
             prev_io: Vec::default(),
+            last_disk_update_time: Instant::now(),
             disk_harvest: Vec::default(),
             temp_data: Vec::default(),
             #[cfg(feature = "battery")]
@@ -66,6 +90,9 @@ impl Default for StoredData {
             gpu_harvest: Vec::default(),
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
             gpu_data_harvest: Vec::default(),
+            power_harvest: Vec::default(),
+            custom_harvest: Vec::default(),
+            diagnostics: CollectionDiagnostics::default(),
         }
     }
 }
@@ -75,6 +102,28 @@ impl StoredData {
         *self = StoredData::default();
     }
 
+    /// Converts the stored temperatures to `temperature_type`.
+    fn set_temperature_type(&mut self, temperature_type: TemperatureType) {
+        for temp in &mut self.temp_data {
+            temp.temperature = temp.celsius.map(|c| temperature_type.convert_temp_unit(c));
+        }
+    }
+
+    /// Returns the time series data to use to draw the last `display_time_ms`
+    /// milliseconds. Anything longer than [`RAW_RETENTION`] uses downsampled
+    /// data if there is any.
+    pub fn timeseries(&self, display_time_ms: u64) -> TimeSeriesResolution<'_> {
+        let display_time = Duration::from_millis(display_time_ms);
+
+        if display_time > RAW_RETENTION {
+            if let Some(resolution) = self.downsampled_data.resolution_for(display_time) {
+                return resolution;
+            }
+        }
+
+        TimeSeriesResolution::raw(&self.timeseries_data)
+    }
+
     #[allow(
         clippy::boxed_local,
         reason = "This avoids warnings on certain platforms (e.g. 32-bit)."
@@ -90,25 +139,36 @@ impl StoredData {
             }
         }
 
-        if !settings.use_basic_mode {
+        // Inline mode draws a CPU sparkline, so it needs the time series too.
+        if !settings.use_basic_mode || settings.inline_height.is_some() {
             self.timeseries_data.add(&data);
+
+            if Duration::from_millis(settings.retention_ms) > RAW_RETENTION {
+                self.downsampled_data.add(&data);
+            }
         }
 
         if let Some(network) = data.network {
             self.network_harvest = network;
         }
 
-        self.ram_harvest = data.memory;
-        self.swap_harvest = data.swap;
+        self.diagnostics = data.diagnostics;
 
-        #[cfg(not(target_os = "windows"))]
-        {
-            self.cache_harvest = data.cache;
-        }
+        // Subsystems may be collected at different rates, so only replace memory data
+        // when memory was actually collected this time.
+        if data.memory.is_some() {
+            self.ram_harvest = data.memory;
+            self.swap_harvest = data.swap;
 
-        #[cfg(feature = "zfs")]
-        {
-            self.arc_harvest = data.arc;
+            #[cfg(not(target_os = "windows"))]
+            {
+                self.cache_harvest = data.cache;
+            }
+
+            #[cfg(feature = "zfs")]
+            {
+                self.arc_harvest = data.arc;
+            }
         }
 
         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -121,6 +181,14 @@ impl StoredData {
             self.gpu_data_harvest = gpu_data;
         }
 
+        if let Some(power) = data.power {
+            self.power_harvest = power;
+        }
+
+        if let Some(custom) = data.custom {
+            self.custom_harvest = custom;
+        }
+
         if let Some(cpu) = data.cpu {
             self.cpu_harvest = cpu;
         }
@@ -129,20 +197,18 @@ impl StoredData {
             self.load_avg_harvest = load_avg;
         }
 
-        self.temp_data = data
-            .temperature_sensors
-            .map(|sensors| {
-                sensors
-                    .into_iter()
-                    .map(|temp| TempWidgetData {
-                        sensor: temp.name,
-                        temperature: temp
-                            .temperature
-                            .map(|c| settings.temperature_type.convert_temp_unit(c)),
-                    })
-                    .collect()
-            })
-            .unwrap_or_default();
+        if let Some(sensors) = data.temperature_sensors {
+            self.temp_data = sensors
+                .into_iter()
+                .map(|temp| TempWidgetData {
+                    sensor: temp.name,
+                    celsius: temp.temperature,
+                    temperature: temp
+                        .temperature
+                        .map(|c| settings.temperature_type.convert_temp_unit(c)),
+                })
+                .collect();
+        }
 
         if let Some(disks) = data.disks {
             if let Some(io) = data.io {
@@ -151,9 +217,17 @@ impl StoredData {
         }
 
         if let Some(list_of_processes) = data.list_of_processes {
+            if !settings.use_basic_mode {
+                self.process_history.add(harvested_time, &list_of_processes);
+                self.process_log.add(harvested_time, &list_of_processes);
+            }
             self.process_data.ingest(list_of_processes);
         }
 
+        if let Some(connections) = data.connections {
+            self.connection_harvest = connections;
+        }
+
         #[cfg(feature = "battery")]
         {
             if let Some(list_of_batteries) = data.list_of_batteries {
@@ -169,8 +243,9 @@ impl StoredData {
         &mut self, disks: Vec<disks::DiskHarvest>, io: disks::IoHarvest, harvested_time: Instant,
     ) {
         let time_since_last_harvest = harvested_time
-            .duration_since(self.last_update_time)
+            .duration_since(self.last_disk_update_time)
             .as_secs_f64();
+        self.last_disk_update_time = harvested_time;
 
         self.disk_harvest.clear();
 
@@ -269,6 +344,7 @@ impl StoredData {
                 summed_total_bytes,
                 io_read_rate_bytes,
                 io_write_rate_bytes,
+                unresponsive: device.unresponsive,
             });
         }
     }
@@ -322,7 +398,24 @@ impl DataStore {
 
     /// Clean data.
     pub fn clean_data(&mut self, max_duration: Duration) {
-        self.main.timeseries_data.prune(max_duration);
+        if max_duration > RAW_RETENTION {
+            self.main.timeseries_data.prune(RAW_RETENTION);
+            self.main.process_history.prune(RAW_RETENTION);
+            self.main.downsampled_data.prune(max_duration);
+        } else {
+            self.main.timeseries_data.prune(max_duration);
+            self.main.process_history.prune(max_duration);
+        }
+    }
+
+    /// Converts the stored temperatures, including any frozen ones, to
+    /// `temperature_type`, so they aren't shown in the old unit until the
+    /// next update.
+    pub fn set_temperature_type(&mut self, temperature_type: TemperatureType) {
+        self.main.set_temperature_type(temperature_type);
+        if let FrozenState::Frozen(data) = &mut self.frozen_state {
+            data.set_temperature_type(temperature_type);
+        }
     }
 
     /// Reset data state.
@@ -26,6 +26,15 @@ impl FromStr for TemperatureType {
 }
 
 impl TemperatureType {
+    /// The name used for this unit in the config file.
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            TemperatureType::Celsius => "celsius",
+            TemperatureType::Kelvin => "kelvin",
+            TemperatureType::Fahrenheit => "fahrenheit",
+        }
+    }
+
     /// Given a temperature in Celsius, convert it if necessary for a different
     /// unit.
     pub fn convert_temp_unit(&self, celsius: f32) -> TypedTemperature {
@@ -6,10 +6,10 @@ use std::{
     vec::Vec,
 };
 
-#[cfg(any(feature = "gpu", feature = "apple-gpu"))]
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use timeless::data::ChunkedData;
 
+use super::downsampled::Reduction;
 use crate::collection::Data;
 
 /// Values corresponding to a time slice.
@@ -44,6 +44,13 @@ pub struct TimeSeriesData {
     /// Swap data.
     pub swap: Values,
 
+    /// Power draw data in watts, keyed by the power domain or device name.
+    pub power: HashMap<String, Values>,
+
+    /// Custom metric data, keyed by the metric name. Each metric has one entry
+    /// per extracted value.
+    pub custom: HashMap<String, Vec<Values>>,
+
     #[cfg(not(target_os = "windows"))]
     /// Cache data.
     pub cache_mem: Values,
@@ -66,12 +73,16 @@ impl TimeSeriesData {
     pub fn add(&mut self, data: &Data) {
         self.time.push(data.collection_time);
 
+        // Any series without new data gets an empty entry rather than being skipped, so
+        // that series collected at different rates still line up with the time values.
+        let num_times = self.time.len();
+
         if let Some(network) = &data.network {
             self.rx.push(network.rx as f64);
             self.tx.push(network.tx as f64);
         } else {
-            self.rx.insert_break();
-            self.tx.insert_break();
+            self.rx.try_push(None);
+            self.tx.try_push(None);
         }
 
         if let Some(cpu) = &data.cpu {
@@ -81,7 +92,7 @@ impl TimeSeriesData {
                     self.cpu.reserve_exact(diff);
 
                     for _ in 0..diff {
-                        self.cpu.push(Default::default());
+                        self.cpu.push(padded_values(num_times));
                     }
                 }
                 Ordering::Greater => {
@@ -89,7 +100,7 @@ impl TimeSeriesData {
                     let offset = self.cpu.len() - diff;
 
                     for curr in &mut self.cpu[offset..] {
-                        curr.insert_break();
+                        curr.try_push(None);
                     }
                 }
                 Ordering::Equal => {}
@@ -100,20 +111,80 @@ impl TimeSeriesData {
             }
         } else {
             for c in &mut self.cpu {
-                c.insert_break();
+                c.try_push(None);
             }
         }
 
         if let Some(memory) = &data.memory {
             self.ram.push(memory.percentage());
         } else {
-            self.ram.insert_break();
+            self.ram.try_push(None);
         }
 
         if let Some(swap) = &data.swap {
             self.swap.push(swap.percentage());
         } else {
-            self.swap.insert_break();
+            self.swap.try_push(None);
+        }
+
+        if let Some(power) = &data.power {
+            let mut not_visited = self
+                .power
+                .keys()
+                .map(String::to_owned)
+                .collect::<HashSet<_>>();
+
+            for entry in power {
+                not_visited.remove(&entry.name);
+
+                self.power
+                    .entry(entry.name.clone())
+                    .or_insert_with(|| padded_values(num_times))
+                    .push(entry.watts);
+            }
+
+            for nv in not_visited {
+                if let Some(entry) = self.power.get_mut(&nv) {
+                    entry.try_push(None);
+                }
+            }
+        } else {
+            for p in self.power.values_mut() {
+                p.try_push(None);
+            }
+        }
+
+        if let Some(custom) = &data.custom {
+            let mut not_visited = self
+                .custom
+                .keys()
+                .map(String::to_owned)
+                .collect::<HashSet<_>>();
+
+            for metric in custom {
+                not_visited.remove(&metric.name);
+
+                let series = self.custom.entry(metric.name.clone()).or_default();
+                if series.len() < metric.values.len() {
+                    series.resize_with(metric.values.len(), || padded_values(num_times));
+                }
+
+                for (index, values) in series.iter_mut().enumerate() {
+                    values.try_push(metric.values.get(index).copied().flatten());
+                }
+            }
+
+            for nv in not_visited {
+                if let Some(series) = self.custom.get_mut(&nv) {
+                    for values in series {
+                        values.try_push(None);
+                    }
+                }
+            }
+        } else {
+            for values in self.custom.values_mut().flatten() {
+                values.try_push(None);
+            }
         }
 
         #[cfg(not(target_os = "windows"))]
@@ -121,7 +192,7 @@ impl TimeSeriesData {
             if let Some(cache) = &data.cache {
                 self.cache_mem.push(cache.percentage());
             } else {
-                self.cache_mem.insert_break();
+                self.cache_mem.try_push(None);
             }
         }
 
@@ -130,7 +201,7 @@ impl TimeSeriesData {
             if let Some(arc) = &data.arc {
                 self.arc_mem.push(arc.percentage());
             } else {
-                self.arc_mem.insert_break();
+                self.arc_mem.try_push(None);
             }
         }
 
@@ -148,7 +219,7 @@ impl TimeSeriesData {
 
                     if !self.gpu_mem.contains_key(name) {
                         self.gpu_mem
-                            .insert(name.to_string(), ChunkedData::default());
+                            .insert(name.to_string(), padded_values(num_times));
                     }
 
                     let curr = self
@@ -160,12 +231,12 @@ impl TimeSeriesData {
 
                 for nv in not_visited {
                     if let Some(entry) = self.gpu_mem.get_mut(&nv) {
-                        entry.insert_break();
+                        entry.try_push(None);
                     }
                 }
             } else {
                 for g in self.gpu_mem.values_mut() {
-                    g.insert_break();
+                    g.try_push(None);
                 }
             }
 
@@ -182,7 +253,7 @@ impl TimeSeriesData {
 
                     if !self.gpu_data.contains_key(&gpu.name) {
                         self.gpu_data
-                            .insert(gpu.name.clone(), ChunkedData::default());
+                            .insert(gpu.name.clone(), padded_values(num_times));
                     }
 
                     let curr = self
@@ -196,17 +267,107 @@ impl TimeSeriesData {
 
                 for nv in not_visited {
                     if let Some(entry) = self.gpu_data.get_mut(&nv) {
-                        entry.insert_break();
+                        entry.try_push(None);
                     }
                 }
             } else {
                 for g in self.gpu_data.values_mut() {
-                    g.insert_break();
+                    g.try_push(None);
                 }
             }
         }
     }
 
+    /// Add a new data point at `time`, made by reducing each series in `source`
+    /// into one value. This is used to roll up samples into coarser buckets.
+    pub fn add_reduced(&mut self, time: Instant, source: &TimeSeriesData, reduction: Reduction) {
+        self.time.push(time);
+        let num_times = self.time.len();
+
+        self.rx.try_push(reduction.apply(source.rx.iter()));
+        self.tx.try_push(reduction.apply(source.tx.iter()));
+        add_reduced_list(&mut self.cpu, &source.cpu, num_times, reduction);
+        self.ram.try_push(reduction.apply(source.ram.iter()));
+        self.swap.try_push(reduction.apply(source.swap.iter()));
+        add_reduced_map(&mut self.power, &source.power, num_times, reduction);
+
+        for name in source.custom.keys() {
+            if !self.custom.contains_key(name) {
+                self.custom.insert(name.clone(), Vec::new());
+            }
+        }
+        for (name, series) in &mut self.custom {
+            let source_series = source.custom.get(name).map(Vec::as_slice);
+            add_reduced_list(
+                series,
+                source_series.unwrap_or_default(),
+                num_times,
+                reduction,
+            );
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        self.cache_mem
+            .try_push(reduction.apply(source.cache_mem.iter()));
+
+        #[cfg(feature = "zfs")]
+        self.arc_mem
+            .try_push(reduction.apply(source.arc_mem.iter()));
+
+        #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+        {
+            add_reduced_map(&mut self.gpu_mem, &source.gpu_mem, num_times, reduction);
+            add_reduced_map(&mut self.gpu_data, &source.gpu_data, num_times, reduction);
+        }
+    }
+
+    /// Keeps only the first `len` data points. This rebuilds every series, so
+    /// avoid calling it often on long series.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.time.len() {
+            return;
+        }
+
+        self.time.truncate(len);
+
+        truncate_values(&mut self.rx, len);
+        truncate_values(&mut self.tx, len);
+
+        for cpu in &mut self.cpu {
+            truncate_values(cpu, len);
+        }
+
+        truncate_values(&mut self.ram, len);
+        truncate_values(&mut self.swap, len);
+
+        for power in self.power.values_mut() {
+            truncate_values(power, len);
+        }
+
+        for series in self.custom.values_mut() {
+            for values in series {
+                truncate_values(values, len);
+            }
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        truncate_values(&mut self.cache_mem, len);
+
+        #[cfg(feature = "zfs")]
+        truncate_values(&mut self.arc_mem, len);
+
+        #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+        {
+            for gpu in self.gpu_mem.values_mut() {
+                truncate_values(gpu, len);
+            }
+
+            for gpu in self.gpu_data.values_mut() {
+                truncate_values(gpu, len);
+            }
+        }
+    }
+
     /// Prune any data older than the given duration.
     pub fn prune(&mut self, max_age: Duration) {
         if self.time.is_empty() {
@@ -245,6 +406,27 @@ impl TimeSeriesData {
         let _ = self.ram.prune_and_shrink_to_fit(end);
         let _ = self.swap.prune_and_shrink_to_fit(end);
 
+        self.power.retain(|_, power| {
+            let _ = power.prune(end);
+
+            // Remove the entry if it is empty. We can always add it again later.
+            if power.no_elements() {
+                false
+            } else {
+                power.shrink_to_fit();
+                true
+            }
+        });
+
+        self.custom.retain(|_, series| {
+            for values in series.iter_mut() {
+                let _ = values.prune_and_shrink_to_fit(end);
+            }
+
+            // Remove the entry if it is empty. We can always add it again later.
+            !series.iter().all(|values| values.no_elements())
+        });
+
         #[cfg(not(target_os = "windows"))]
         let _ = self.cache_mem.prune_and_shrink_to_fit(end);
 
@@ -278,3 +460,70 @@ impl TimeSeriesData {
         }
     }
 }
+
+/// Keeps only the first `len` entries of `values`, including skipped ones.
+fn truncate_values(values: &mut Values, len: usize) {
+    if values.length() <= len {
+        return;
+    }
+
+    let mut truncated = Values::default();
+    for (index, &value) in values.iter_with_index() {
+        if index >= len {
+            break;
+        }
+
+        while truncated.length() < index {
+            truncated.try_push(None);
+        }
+        truncated.push(value);
+    }
+
+    while truncated.length() < len {
+        truncated.try_push(None);
+    }
+
+    *values = truncated;
+}
+
+/// Returns [`Values`] padded so that the next pushed value lines up with the
+/// `num_times`-th time value.
+fn padded_values(num_times: usize) -> Values {
+    let mut values = Values::default();
+    for _ in 1..num_times {
+        values.try_push(None);
+    }
+
+    values
+}
+
+/// Pushes the reduction of each series in `source` onto the matching series in
+/// `dest`, adding new series as needed.
+fn add_reduced_list(
+    dest: &mut Vec<Values>, source: &[Values], num_times: usize, reduction: Reduction,
+) {
+    if dest.len() < source.len() {
+        dest.resize_with(source.len(), || padded_values(num_times));
+    }
+
+    for (index, values) in dest.iter_mut().enumerate() {
+        values.try_push(source.get(index).and_then(|v| reduction.apply(v.iter())));
+    }
+}
+
+/// Pushes the reduction of each series in `source` onto the series with the
+/// same key in `dest`, adding new series as needed.
+fn add_reduced_map(
+    dest: &mut HashMap<String, Values>, source: &HashMap<String, Values>, num_times: usize,
+    reduction: Reduction,
+) {
+    for name in source.keys() {
+        if !dest.contains_key(name) {
+            dest.insert(name.clone(), padded_values(num_times));
+        }
+    }
+
+    for (name, values) in dest.iter_mut() {
+        values.try_push(source.get(name).and_then(|v| reduction.apply(v.iter())));
+    }
+}
@@ -0,0 +1,472 @@
+//! Exporting what a table or graph shows to CSV or JSON.
+
+use std::{
+    borrow::Cow,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::Context;
+use serde::{Serialize, ser::SerializeMap};
+
+use super::{
+    App,
+    data::{TimeSeriesData, Values},
+    layout_manager::BottomWidgetType,
+};
+use crate::{collection::cpu::CpuDataType, utils::data_units::DataUnit};
+
+/// The file format to export to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            _ => Err(format!(
+                "'{s}' is an invalid export format, use one of: [csv, json]."
+            )),
+        }
+    }
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// What a widget shows, as rows of text under headers.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ExportData {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// A row serialized as a JSON object, keeping the columns in order.
+struct JsonRow<'a> {
+    headers: &'a [String],
+    cells: &'a [String],
+}
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.headers.len()))?;
+        for (header, cell) in self.headers.iter().zip(self.cells) {
+            map.serialize_entry(header, cell)?;
+        }
+        map.end()
+    }
+}
+
+/// Quotes `field` for CSV if needed.
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+impl ExportData {
+    pub fn to_csv(&self) -> String {
+        std::iter::once(&self.headers)
+            .chain(&self.rows)
+            .map(|row| {
+                let mut line = row
+                    .iter()
+                    .map(|field| csv_field(field))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                line.push('\n');
+                line
+            })
+            .collect()
+    }
+
+    /// Returns the rows as a JSON array of objects keyed by header.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        let rows = self
+            .rows
+            .iter()
+            .map(|cells| JsonRow {
+                headers: &self.headers,
+                cells,
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::to_string_pretty(&rows)
+    }
+
+    /// Writes the data to `path` as `format`.
+    pub fn write(&self, path: &Path, format: ExportFormat) -> anyhow::Result<()> {
+        let text = match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => self.to_json()?,
+        };
+
+        fs::write(path, text).with_context(|| format!("Could not write to {}.", path.display()))
+    }
+}
+
+/// Builds the rows for the time series `series` over the last `display_time`,
+/// with one row per collection time and one column per series. Missing values
+/// are left empty.
+fn export_series(
+    times: &[Instant], series: &[(String, &Values)], display_time: Duration, now: SystemTime,
+) -> ExportData {
+    let Some(&last_time) = times.last() else {
+        return ExportData::default();
+    };
+    let start = times.partition_point(|&time| last_time.duration_since(time) > display_time);
+
+    let instant_now = Instant::now();
+    let mut rows = times[start..]
+        .iter()
+        .map(|&time| {
+            let time = now - instant_now.saturating_duration_since(time);
+
+            let mut row = vec![String::new(); series.len() + 1];
+            row[0] = humantime::format_rfc3339_millis(time).to_string();
+            row
+        })
+        .collect::<Vec<_>>();
+
+    for (column, (_, values)) in series.iter().enumerate() {
+        for (index, value) in values.iter_with_index() {
+            if let Some(row) = index
+                .checked_sub(start)
+                .and_then(|index| rows.get_mut(index))
+            {
+                row[column + 1] = value.to_string();
+            }
+        }
+    }
+
+    ExportData {
+        headers: std::iter::once("time".to_string())
+            .chain(series.iter().map(|(name, _)| name.clone()))
+            .collect(),
+        rows,
+    }
+}
+
+/// Returns the series in `map` sorted by name, with `suffix` after each name.
+fn sorted_series<'a>(
+    map: impl IntoIterator<Item = (&'a String, &'a Values)>, suffix: &str,
+) -> Vec<(String, &'a Values)> {
+    let mut series = map
+        .into_iter()
+        .map(|(name, values)| (format!("{name}{suffix}"), values))
+        .collect::<Vec<_>>();
+    series.sort_by(|(a, _), (b, _)| a.cmp(b));
+    series
+}
+
+/// Expands a leading `~` in `path` to the home directory.
+pub(crate) fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Returns the current time for use in file names, such as `20240102T030405Z`.
+pub(crate) fn file_timestamp() -> String {
+    humantime::format_rfc3339_seconds(SystemTime::now())
+        .to_string()
+        .replace(['-', ':'], "")
+}
+
+impl App {
+    /// Returns the ID and type of the widget whose data would be exported,
+    /// which is the graph for a legend and the process table for its search or
+    /// sort widgets.
+    pub(super) fn export_widget(&self) -> (u64, BottomWidgetType) {
+        let widget = &self.current_widget;
+        let offset = widget
+            .parent_reflector
+            .as_ref()
+            .map_or(0, |(_, offset)| *offset);
+
+        match &widget.widget_type {
+            BottomWidgetType::CpuLegend => (widget.widget_id - offset, BottomWidgetType::Cpu),
+            #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+            BottomWidgetType::GpuLegend => (widget.widget_id - offset, BottomWidgetType::Gpu),
+            BottomWidgetType::ProcSearch => (widget.widget_id - 1, BottomWidgetType::Proc),
+            BottomWidgetType::ProcSort => (widget.widget_id - 2, BottomWidgetType::Proc),
+            widget_type => (widget.widget_id, widget_type.clone()),
+        }
+    }
+
+    /// Returns the rows shown by the selected table, or the visible window of
+    /// the selected graph, along with a name for it.
+    pub(crate) fn export_data(&self) -> Option<(&'static str, ExportData)> {
+        let (widget_id, widget_type) = self.export_widget();
+        let states = &self.states;
+        let data = self.data_store.get_data();
+
+        let table = |(headers, rows)| ExportData { headers, rows };
+        let graph =
+            |display_time: u64, series: &dyn Fn(&TimeSeriesData) -> Vec<(String, &Values)>| {
+                let timeseries = data.timeseries(display_time).avg;
+                export_series(
+                    &timeseries.time,
+                    &series(timeseries),
+                    Duration::from_millis(display_time),
+                    SystemTime::now(),
+                )
+            };
+
+        let export = match widget_type {
+            BottomWidgetType::Proc => {
+                let state = states.proc_state.get_widget_state(widget_id)?;
+                ("processes", table(state.table.export_rows()))
+            }
+            BottomWidgetType::ProcLog => {
+                let state = states.proc_log_state.get_widget_state(widget_id)?;
+                ("process-log", table(state.table.export_rows()))
+            }
+            BottomWidgetType::Conn => {
+                let state = states.conn_state.get_widget_state(widget_id)?;
+                ("connections", table(state.table.export_rows()))
+            }
+            BottomWidgetType::Disk => {
+                let state = states.disk_state.get_widget_state(widget_id)?;
+                ("disks", table(state.table.export_rows()))
+            }
+            BottomWidgetType::Temp => {
+                let state = states.temp_state.get_widget_state(widget_id)?;
+                ("temperatures", table(state.table.export_rows()))
+            }
+            BottomWidgetType::Cpu => {
+                let state = states.cpu_state.get_widget_state(widget_id)?;
+                let export = graph(state.current_display_time, &|timeseries| {
+                    timeseries
+                        .cpu
+                        .iter()
+                        .enumerate()
+                        .map(|(index, values)| {
+                            let name = match data.cpu_harvest.get(index).map(|cpu| cpu.data_type) {
+                                Some(CpuDataType::Avg) => "AVG%".to_string(),
+                                Some(CpuDataType::Cpu(cpu)) => format!("CPU{cpu}%"),
+                                None => format!("CPU{index}%"),
+                            };
+                            (name, values)
+                        })
+                        .collect()
+                });
+                ("cpu", export)
+            }
+            BottomWidgetType::Mem => {
+                let state = states.mem_state.widget_states.get(&widget_id)?;
+                let export = graph(state.current_display_time, &|timeseries| {
+                    #[allow(unused_mut, reason = "Some series are only on some platforms.")]
+                    let mut series = vec![
+                        ("RAM%".to_string(), &timeseries.ram),
+                        ("SWP%".to_string(), &timeseries.swap),
+                    ];
+                    #[cfg(not(target_os = "windows"))]
+                    series.push(("CACHE%".to_string(), &timeseries.cache_mem));
+                    #[cfg(feature = "zfs")]
+                    series.push(("ARC%".to_string(), &timeseries.arc_mem));
+                    #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+                    series.extend(sorted_series(&timeseries.gpu_mem, "%"));
+                    series
+                });
+                ("memory", export)
+            }
+            BottomWidgetType::Net => {
+                let state = states.net_state.widget_states.get(&widget_id)?;
+                let unit = match self.app_config_fields.network_unit_type {
+                    DataUnit::Byte => "B/s",
+                    DataUnit::Bit => "b/s",
+                };
+                let export = graph(state.current_display_time, &|timeseries| {
+                    vec![
+                        (format!("RX ({unit})"), &timeseries.rx),
+                        (format!("TX ({unit})"), &timeseries.tx),
+                    ]
+                });
+                ("network", export)
+            }
+            BottomWidgetType::Power => {
+                let state = states.power_state.widget_states.get(&widget_id)?;
+                let export = graph(state.current_display_time, &|timeseries| {
+                    sorted_series(&timeseries.power, " (W)")
+                });
+                ("power", export)
+            }
+            BottomWidgetType::Custom => {
+                let state = states.custom_state.widget_states.get(&widget_id)?;
+                let export = graph(state.current_display_time, &|timeseries| {
+                    timeseries
+                        .custom
+                        .get(&state.metric)
+                        .map(|series| {
+                            series
+                                .iter()
+                                .enumerate()
+                                .map(|(index, values)| {
+                                    let label = state.labels.get(index).cloned();
+                                    (label.unwrap_or_else(|| state.metric.clone()), values)
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                });
+                ("custom", export)
+            }
+            #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+            BottomWidgetType::Gpu => {
+                let state = states.gpu_state.widget_states.get(&widget_id)?;
+                let export = graph(state.current_display_time, &|timeseries| {
+                    sorted_series(&timeseries.gpu_data, "")
+                });
+                ("gpu", export)
+            }
+            _ => return None,
+        };
+
+        Some(export)
+    }
+
+    /// Returns a file name for exporting the selected widget as `format`, with
+    /// the current time in it.
+    pub(crate) fn default_export_path(&self, format: ExportFormat) -> Option<String> {
+        let (name, _) = self.export_data()?;
+
+        Some(format!(
+            "bottom-{name}-{}.{}",
+            file_timestamp(),
+            format.extension()
+        ))
+    }
+
+    /// Exports the selected widget to `path` as `format`, returning a message
+    /// saying where it went.
+    pub(crate) fn export_selected(
+        &self, format: ExportFormat, path: Option<&str>,
+    ) -> Result<String, String> {
+        const SELECT_WIDGET: &str = "Select a table or graph first.";
+
+        let (_, data) = self.export_data().ok_or(SELECT_WIDGET)?;
+        let path = match path {
+            Some(path) => path.to_string(),
+            None => self.default_export_path(format).ok_or(SELECT_WIDGET)?,
+        };
+        let path = expand_home(&path);
+
+        data.write(&path, format)
+            .map_err(|err| format!("{err:#}"))?;
+
+        let rows = data.rows.len();
+        Ok(format!(
+            "Exported {rows} {} to {}.",
+            if rows == 1 { "row" } else { "rows" },
+            path.display()
+        ))
+    }
+
+    /// Opens the command prompt with an export command for the selected widget,
+    /// so that the path can be changed before running it.
+    pub fn open_export_prompt(&mut self) {
+        if let Some(path) = self.default_export_path(ExportFormat::Csv) {
+            self.open_command_prompt();
+            self.edit_command_input(|input| input.set_query(format!("export csv {path}")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn data() -> ExportData {
+        ExportData {
+            headers: vec!["PID".into(), "Name".into()],
+            rows: vec![
+                vec!["1".into(), "init".into()],
+                vec!["2".into(), "say \"hi\", bye".into()],
+            ],
+        }
+    }
+
+    #[test]
+    fn writes_csv() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+
+        assert_eq!(
+            data().to_csv(),
+            "PID,Name\n1,init\n2,\"say \"\"hi\"\", bye\"\n"
+        );
+    }
+
+    #[test]
+    fn writes_json() {
+        let json = data().to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([
+                { "PID": "1", "Name": "init" },
+                { "PID": "2", "Name": "say \"hi\", bye" },
+            ])
+        );
+
+        // Columns keep their order rather than being sorted.
+        assert!(json.find("PID").unwrap() < json.find("Name").unwrap());
+    }
+
+    #[test]
+    fn exports_visible_series() {
+        let start = Instant::now();
+        let times = (0..4)
+            .map(|seconds| start + Duration::from_secs(seconds))
+            .collect::<Vec<_>>();
+
+        let mut ram = Values::default();
+        for value in [Some(1.0), Some(2.0), None, Some(4.5)] {
+            ram.try_push(value);
+        }
+        let mut swap = Values::default();
+        swap.try_push(None);
+        swap.push(0.5);
+
+        let export = export_series(
+            &times,
+            &[("RAM%".to_string(), &ram), ("SWP%".to_string(), &swap)],
+            Duration::from_secs(2),
+            SystemTime::now(),
+        );
+
+        assert_eq!(export.headers, vec!["time", "RAM%", "SWP%"]);
+        let values = export
+            .rows
+            .iter()
+            .map(|row| row[1..].to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                vec!["2".to_string(), "0.5".to_string()],
+                vec![String::new(), String::new()],
+                vec!["4.5".to_string(), String::new()],
+            ]
+        );
+        assert!(export.rows.iter().all(|row| row[0].ends_with('Z')));
+    }
+}
@@ -0,0 +1,297 @@
+//! A cursor for reading off the values of a time graph at one point in time.
+
+use std::time::Instant;
+
+use rustc_hash::FxHashMap as HashMap;
+use tui::layout::{Position, Rect};
+
+use super::{App, layout_manager::BottomWidgetType};
+
+/// A cursor on a time graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphCursor {
+    /// The graph the cursor is on.
+    pub widget_id: u64,
+
+    /// How far back from the latest data point the cursor is, in milliseconds.
+    /// As this is relative, the cursor moves along with new data.
+    pub offset: u64,
+
+    /// Whether the cursor follows the mouse, in which case it is hidden once the
+    /// mouse leaves the graph.
+    pub is_from_mouse: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct GraphCursorState {
+    /// The cursor, if one is shown.
+    pub cursor: Option<GraphCursor>,
+
+    /// Where each graph drew its data in the last frame, for placing the cursor
+    /// with the mouse.
+    pub plot_areas: HashMap<u64, Rect>,
+}
+
+impl GraphCursorState {
+    /// Returns the offset of the cursor if it is on the graph `widget_id`.
+    pub fn offset(&self, widget_id: u64) -> Option<u64> {
+        self.cursor
+            .filter(|cursor| cursor.widget_id == widget_id)
+            .map(|cursor| cursor.offset)
+    }
+}
+
+/// Returns how far back each of `times` is from the latest one in
+/// milliseconds, newest first, up to `display_time`.
+fn visible_offsets(times: &[Instant], display_time: u64) -> Vec<u64> {
+    let Some(latest) = times.last() else {
+        return Vec::new();
+    };
+
+    times
+        .iter()
+        .rev()
+        .map(|time| latest.duration_since(*time).as_millis() as u64)
+        .take_while(|offset| *offset <= display_time)
+        .collect()
+}
+
+/// Returns the offset of the data point next to `offset` in `offsets`, going
+/// back in time if `older` is set. Stays put if there is none.
+fn step_offset(offsets: &[u64], offset: u64, older: bool) -> u64 {
+    let next = if older {
+        offsets.iter().find(|o| **o > offset)
+    } else {
+        offsets.iter().rev().find(|o| **o < offset)
+    };
+
+    next.copied().unwrap_or(offset)
+}
+
+impl App {
+    /// Returns the ID of the graph that is selected, or whose legend is.
+    fn selected_graph(&self) -> Option<u64> {
+        let (widget_id, widget_type) = self.export_widget();
+        widget_type.is_widget_graph().then_some(widget_id)
+    }
+
+    /// Returns how many milliseconds the graph `widget_id` currently shows.
+    fn graph_display_time(&self, widget_id: u64) -> Option<u64> {
+        let states = &self.states;
+
+        match self.widget_map.get(&widget_id)?.widget_type {
+            BottomWidgetType::Cpu => states
+                .cpu_state
+                .widget_states
+                .get(&widget_id)
+                .map(|state| state.current_display_time),
+            BottomWidgetType::Mem => states
+                .mem_state
+                .widget_states
+                .get(&widget_id)
+                .map(|state| state.current_display_time),
+            BottomWidgetType::Net => states
+                .net_state
+                .widget_states
+                .get(&widget_id)
+                .map(|state| state.current_display_time),
+            BottomWidgetType::Power => states
+                .power_state
+                .widget_states
+                .get(&widget_id)
+                .map(|state| state.current_display_time),
+            BottomWidgetType::Custom => states
+                .custom_state
+                .widget_states
+                .get(&widget_id)
+                .map(|state| state.current_display_time),
+            #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+            BottomWidgetType::Gpu => states
+                .gpu_state
+                .widget_states
+                .get(&widget_id)
+                .map(|state| state.current_display_time),
+            _ => None,
+        }
+    }
+
+    /// Shows a cursor on the selected graph at the latest data point, or hides
+    /// it if one is already there.
+    pub fn toggle_graph_cursor(&mut self) {
+        let Some(widget_id) = self.selected_graph() else {
+            return;
+        };
+
+        self.graph_cursor.cursor = match self.graph_cursor.cursor {
+            Some(cursor) if cursor.widget_id == widget_id && !cursor.is_from_mouse => None,
+            _ => Some(GraphCursor {
+                widget_id,
+                offset: 0,
+                is_from_mouse: false,
+            }),
+        };
+    }
+
+    /// Hides the cursor if it is on the selected graph, returning whether it
+    /// was.
+    pub(crate) fn hide_graph_cursor(&mut self) -> bool {
+        let is_on_selected = self
+            .graph_cursor
+            .cursor
+            .is_some_and(|cursor| self.selected_graph() == Some(cursor.widget_id));
+        if is_on_selected {
+            self.graph_cursor.cursor = None;
+        }
+
+        is_on_selected
+    }
+
+    /// Moves the cursor on the selected graph to another of its visible data
+    /// points, picked by `pick` from the offsets of those points, newest first.
+    /// Returns whether there was a cursor to move.
+    fn move_graph_cursor(&mut self, pick: impl FnOnce(&[u64], u64) -> u64) -> bool {
+        let Some(cursor) = self.graph_cursor.cursor else {
+            return false;
+        };
+        if self.selected_graph() != Some(cursor.widget_id) {
+            return false;
+        }
+        let Some(display_time) = self.graph_display_time(cursor.widget_id) else {
+            return false;
+        };
+
+        let times = &self.data_store.get_data().timeseries(display_time).avg.time;
+        let offsets = visible_offsets(times, display_time);
+        let offset = pick(&offsets, cursor.offset.min(display_time));
+
+        self.graph_cursor.cursor = Some(GraphCursor {
+            offset,
+            is_from_mouse: false,
+            ..cursor
+        });
+        true
+    }
+
+    /// Moves the cursor on the selected graph one data point back or forward
+    /// in time, returning whether there was a cursor to move.
+    pub(crate) fn step_graph_cursor(&mut self, older: bool) -> bool {
+        self.move_graph_cursor(|offsets, offset| step_offset(offsets, offset, older))
+    }
+
+    /// Moves the cursor on the selected graph to its oldest or latest visible
+    /// data point, returning whether there was a cursor to move.
+    pub(crate) fn jump_graph_cursor(&mut self, oldest: bool) -> bool {
+        self.move_graph_cursor(|offsets, offset| {
+            let end = if oldest {
+                offsets.last()
+            } else {
+                offsets.first()
+            };
+            end.copied().unwrap_or(offset)
+        })
+    }
+
+    /// Shows a cursor at the mouse if it is over a graph, or hides the cursor
+    /// the mouse showed once it leaves.
+    pub fn on_mouse_move(&mut self, x: u16, y: u16) {
+        let hovered = self
+            .graph_cursor
+            .plot_areas
+            .iter()
+            .find(|(_, area)| area.contains(Position { x, y }))
+            .map(|(widget_id, area)| (*widget_id, *area));
+
+        if let Some((widget_id, area)) = hovered {
+            let Some(display_time) = self.graph_display_time(widget_id) else {
+                return;
+            };
+            let from_right = u64::from(area.right() - 1 - x);
+            let offset = display_time * from_right / u64::from(area.width.max(2) - 1);
+
+            self.graph_cursor.cursor = Some(GraphCursor {
+                widget_id,
+                offset,
+                is_from_mouse: true,
+            });
+        } else if self
+            .graph_cursor
+            .cursor
+            .is_some_and(|cursor| cursor.is_from_mouse)
+        {
+            self.graph_cursor.cursor = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use clap::Parser;
+
+    use super::*;
+    use crate::{args::BottomArgs, options::config::Config};
+
+    #[test]
+    fn finds_visible_offsets() {
+        let start = Instant::now();
+        let times = [0, 1000, 2000, 3000].map(|ms| start + Duration::from_millis(ms));
+
+        assert_eq!(visible_offsets(&times, 10_000), vec![0, 1000, 2000, 3000]);
+        assert_eq!(visible_offsets(&times, 1500), vec![0, 1000]);
+        assert!(visible_offsets(&[], 1500).is_empty());
+    }
+
+    #[test]
+    fn steps_offsets() {
+        let offsets = [0, 1000, 2000];
+
+        assert_eq!(step_offset(&offsets, 0, true), 1000);
+        assert_eq!(step_offset(&offsets, 1000, false), 0);
+        assert_eq!(step_offset(&offsets, 1500, true), 2000);
+        assert_eq!(step_offset(&offsets, 1500, false), 1000);
+        assert_eq!(step_offset(&offsets, 2000, true), 2000);
+        assert_eq!(step_offset(&offsets, 0, false), 0);
+    }
+
+    #[test]
+    fn moves_graph_cursor() {
+        let args = BottomArgs::parse_from(["btm"]);
+        let mut app = crate::options::init_app(args, Config::default()).unwrap().0;
+        let graph = app
+            .widget_map
+            .values()
+            .find(|widget| widget.widget_type == BottomWidgetType::Mem)
+            .cloned()
+            .unwrap();
+        let widget_id = graph.widget_id;
+
+        app.current_widget = graph;
+        app.toggle_graph_cursor();
+        assert_eq!(app.graph_cursor.offset(widget_id), Some(0));
+        assert!(app.step_graph_cursor(true));
+        assert!(app.jump_graph_cursor(true));
+
+        app.toggle_graph_cursor();
+        assert_eq!(app.graph_cursor.cursor, None);
+        assert!(!app.step_graph_cursor(true));
+
+        app.graph_cursor
+            .plot_areas
+            .insert(widget_id, Rect::new(10, 5, 11, 4));
+        app.on_mouse_move(15, 6);
+        let cursor = app.graph_cursor.cursor.unwrap();
+        assert!(cursor.is_from_mouse);
+        assert_eq!(
+            cursor.offset,
+            app.graph_display_time(widget_id).unwrap() / 2
+        );
+
+        app.on_mouse_move(0, 0);
+        assert_eq!(app.graph_cursor.cursor, None);
+
+        app.toggle_graph_cursor();
+        assert!(app.hide_graph_cursor());
+        assert!(!app.hide_graph_cursor());
+    }
+}
@@ -1,8 +1,10 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use tui::layout::Constraint;
 
-use crate::{constants::DEFAULT_WIDGET_ID, options::OptionError};
+use crate::{
+    collection::processes::ProcessFields, constants::DEFAULT_WIDGET_ID, options::OptionError,
+};
 
 // Represents a start and end coordinate in some dimension.
 type LineSegment = (u16, u16);
@@ -556,6 +558,48 @@ impl BottomLayout {
         }
     }
 
+    /// Creates a layout of just the given basic widgets side by side, for inline
+    /// mode. The widgets use the same IDs as in [`BottomLayout::init_basic_default`].
+    pub fn init_inline(widget_types: &[BottomWidgetType]) -> Self {
+        let ids = widget_types
+            .iter()
+            .map(|widget_type| match widget_type {
+                BottomWidgetType::Cpu => 1,
+                BottomWidgetType::Mem => 2,
+                _ => 3,
+            })
+            .collect::<Vec<_>>();
+
+        let widgets = widget_types
+            .iter()
+            .zip(&ids)
+            .enumerate()
+            .map(|(index, (widget_type, &id))| {
+                let basic_type = match widget_type {
+                    BottomWidgetType::Cpu => BottomWidgetType::BasicCpu,
+                    BottomWidgetType::Mem => BottomWidgetType::BasicMem,
+                    _ => BottomWidgetType::BasicNet,
+                };
+
+                BottomWidget::new(basic_type, id)
+                    .canvas_handled()
+                    .left_neighbour(index.checked_sub(1).map(|left| ids[left]))
+                    .right_neighbour(ids.get(index + 1).copied())
+            })
+            .collect();
+
+        BottomLayout {
+            total_row_height_ratio: 1,
+            rows: vec![
+                BottomRow::new(vec![
+                    BottomCol::new(vec![BottomColRow::new(widgets).canvas_handled()])
+                        .canvas_handled(),
+                ])
+                .canvas_handled(),
+            ],
+        }
+    }
+
     pub fn init_basic_default(use_battery: bool) -> Self {
         let table_widgets = if use_battery {
             let disk_widget = BottomWidget::new(BottomWidgetType::Disk, 4)
@@ -709,6 +753,128 @@ impl BottomLayout {
     }
 }
 
+impl BottomLayout {
+    /// Returns every widget in the layout.
+    pub fn widgets(&self) -> impl Iterator<Item = &BottomWidget> {
+        self.rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+    }
+
+    /// Returns the ID of the first widget that can be selected on its own,
+    /// rather than being part of another widget like a legend.
+    pub fn first_widget_id(&self) -> Option<u64> {
+        self.widgets()
+            .find(|widget| !widget.widget_type.get_pretty_name().is_empty())
+            .map(|widget| widget.widget_id)
+    }
+
+    /// Gives each widget the ID of the matching widget in `base`, so both
+    /// layouts share the same widget states. The nth widget of a type matches
+    /// the nth widget of that type in `base`, and widgets without a match keep
+    /// their own IDs.
+    pub fn share_widget_ids(&mut self, base: &BottomLayout) {
+        fn parents(layout: &BottomLayout) -> impl Iterator<Item = &BottomWidget> {
+            layout
+                .widgets()
+                .filter(|widget| widget.parent_reflector.is_none())
+        }
+
+        let mut base_parents = parents(base).map(Some).collect::<Vec<_>>();
+        let mut new_ids = HashMap::new();
+
+        for widget in parents(self) {
+            let matching = base_parents.iter_mut().find(|candidate| {
+                candidate.is_some_and(|candidate| {
+                    candidate.widget_type == widget.widget_type
+                        && candidate.custom_metric == widget.custom_metric
+                })
+            });
+
+            if let Some(matching) = matching.and_then(Option::take) {
+                new_ids.insert(widget.widget_id, matching.widget_id);
+            }
+        }
+
+        // Widgets like legends are placed at a fixed offset from their parent.
+        for widget in self
+            .rows
+            .iter_mut()
+            .flat_map(|row| &mut row.children)
+            .flat_map(|col| &mut col.children)
+            .flat_map(|col_row| &mut col_row.children)
+        {
+            let offset = widget
+                .parent_reflector
+                .as_ref()
+                .map_or(0, |(_, offset)| *offset);
+            if let Some(parent_id) = new_ids.get(&(widget.widget_id - offset)) {
+                widget.widget_id = parent_id + offset;
+            }
+        }
+    }
+}
+
+/// A named layout. If the config has more than one, they are shown as tabs.
+#[derive(Clone, Debug)]
+pub struct LayoutTab {
+    pub name: String,
+    pub layout: BottomLayout,
+
+    /// Alternative layouts for certain terminal sizes, in order of priority.
+    pub breakpoints: Vec<LayoutBreakpoint>,
+}
+
+impl LayoutTab {
+    /// Returns the index of the breakpoint to use for the given terminal
+    /// size, or [`None`] if the base layout should be used.
+    pub fn breakpoint_index(&self, width: u16, height: u16) -> Option<usize> {
+        self.breakpoints
+            .iter()
+            .position(|breakpoint| breakpoint.matches(width, height))
+    }
+
+    /// Returns the layout for a breakpoint index from
+    /// [`LayoutTab::breakpoint_index`].
+    pub fn layout_at(&self, breakpoint: Option<usize>) -> &BottomLayout {
+        breakpoint
+            .and_then(|index| self.breakpoints.get(index))
+            .map_or(&self.layout, |breakpoint| &breakpoint.layout)
+    }
+
+    /// Returns every widget in the tab, across all of its layouts. The base
+    /// layout's widgets come last.
+    pub fn widgets(&self) -> impl Iterator<Item = &BottomWidget> {
+        self.breakpoints
+            .iter()
+            .flat_map(|breakpoint| breakpoint.layout.widgets())
+            .chain(self.layout.widgets())
+    }
+}
+
+/// A layout used in place of a tab's base layout while the terminal size is
+/// within the bounds. All bounds are inclusive.
+#[derive(Clone, Debug)]
+pub struct LayoutBreakpoint {
+    pub min_width: Option<u16>,
+    pub max_width: Option<u16>,
+    pub min_height: Option<u16>,
+    pub max_height: Option<u16>,
+    pub layout: BottomLayout,
+}
+
+impl LayoutBreakpoint {
+    /// Whether the breakpoint applies to the given terminal size.
+    pub fn matches(&self, width: u16, height: u16) -> bool {
+        self.min_width.is_none_or(|min| width >= min)
+            && self.max_width.is_none_or(|max| width <= max)
+            && self.min_height.is_none_or(|min| height >= min)
+            && self.max_height.is_none_or(|max| height <= max)
+    }
+}
+
 /// Represents a single row in the layout.
 #[derive(Clone, Debug)]
 pub struct BottomRow {
@@ -857,6 +1023,10 @@ pub struct BottomWidget {
     /// Bottom right corner when drawn, for mouse click detection. (x, y)
     pub bottom_right_corner: Option<(u16, u16)>,
 
+    /// The name of the custom metric shown by this widget, if this is a
+    /// [`BottomWidgetType::Custom`] widget.
+    pub custom_metric: Option<String>,
+
     /// TODO: REMOVE THIS LATER. This is temporary code to bridge the
     /// old layout system with a newer system later.
     ratio_override: Option<u16>,
@@ -875,10 +1045,16 @@ impl BottomWidget {
             parent_reflector: None,
             top_left_corner: None,
             bottom_right_corner: None,
+            custom_metric: None,
             ratio_override: None,
         }
     }
 
+    pub(crate) fn custom_metric(mut self, custom_metric: Option<String>) -> Self {
+        self.custom_metric = custom_metric;
+        self
+    }
+
     pub(crate) fn left_neighbour(mut self, left_neighbour: Option<u64>) -> Self {
         self.left_neighbour = left_neighbour;
         self
@@ -940,6 +1116,8 @@ pub enum BottomWidgetType {
     Proc,
     ProcSearch,
     ProcSort,
+    ProcLog,
+    Conn,
     Temp,
     Disk,
     BasicCpu,
@@ -947,6 +1125,8 @@ pub enum BottomWidgetType {
     BasicNet,
     BasicTables,
     Battery,
+    Power,
+    Custom,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     Gpu,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -958,11 +1138,17 @@ impl BottomWidgetType {
         use BottomWidgetType::*;
         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
         {
-            matches!(self, Disk | Proc | ProcSort | Temp | CpuLegend | GpuLegend)
+            matches!(
+                self,
+                Disk | Proc | ProcSort | ProcLog | Conn | Temp | CpuLegend | GpuLegend
+            )
         }
         #[cfg(not(any(feature = "gpu", feature = "apple-gpu")))]
         {
-            matches!(self, Disk | Proc | ProcSort | Temp | CpuLegend)
+            matches!(
+                self,
+                Disk | Proc | ProcSort | ProcLog | Conn | Temp | CpuLegend
+            )
         }
     }
 
@@ -970,11 +1156,11 @@ impl BottomWidgetType {
         use BottomWidgetType::*;
         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
         {
-            matches!(self, Cpu | Net | Mem | Gpu)
+            matches!(self, Cpu | Net | Mem | Power | Custom | Gpu)
         }
         #[cfg(not(any(feature = "gpu", feature = "apple-gpu")))]
         {
-            matches!(self, Cpu | Net | Mem)
+            matches!(self, Cpu | Net | Mem | Power | Custom)
         }
     }
 
@@ -985,9 +1171,13 @@ impl BottomWidgetType {
             Mem => "Memory",
             Net => "Network",
             Proc => "Processes",
+            ProcLog => "Process Log",
+            Conn => "Connections",
             Temp => "Temperature",
             Disk => "Disks",
             Battery => "Battery",
+            Power => "Power",
+            Custom => "Custom",
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
             Gpu => "GPU",
             _ => "",
@@ -1005,9 +1195,13 @@ impl std::str::FromStr for BottomWidgetType {
             "mem" | "memory" => Ok(BottomWidgetType::Mem),
             "net" | "network" => Ok(BottomWidgetType::Net),
             "proc" | "process" | "processes" => Ok(BottomWidgetType::Proc),
+            "proc_log" | "process_log" => Ok(BottomWidgetType::ProcLog),
+            "conn" | "connections" => Ok(BottomWidgetType::Conn),
             "temp" | "temperature" => Ok(BottomWidgetType::Temp),
             "disk" => Ok(BottomWidgetType::Disk),
             "empty" => Ok(BottomWidgetType::Empty),
+            "power" => Ok(BottomWidgetType::Power),
+            "custom" => Ok(BottomWidgetType::Custom),
             #[cfg(feature = "battery")]
             "battery" | "batt" => Ok(BottomWidgetType::Battery),
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -1032,6 +1226,14 @@ Supported widget names:
 +--------------------------+
 |           disk           |
 +--------------------------+
+|           power          |
++--------------------------+
+|          custom          |
++--------------------------+
+|   proc_log, process_log  |
++--------------------------+
+|    conn, connections     |
++--------------------------+
 |       batt, battery      |
 +--------------------------+
 |           gpu            |
@@ -1060,6 +1262,14 @@ Supported widget names:
 +--------------------------+
 |           disk           |
 +--------------------------+
+|           power          |
++--------------------------+
+|          custom          |
++--------------------------+
+|   proc_log, process_log  |
++--------------------------+
+|    conn, connections     |
++--------------------------+
 |       batt, battery      |
 +--------------------------+
 |           empty          |
@@ -1086,6 +1296,14 @@ Supported widget names:
 +--------------------------+
 |           disk           |
 +--------------------------+
+|           power          |
++--------------------------+
+|          custom          |
++--------------------------+
+|   proc_log, process_log  |
++--------------------------+
+|    conn, connections     |
++--------------------------+
 |           gpu            |
 +--------------------------+
 |           empty          |
@@ -1115,6 +1333,14 @@ Supported widget names:
 +--------------------------+
 |           disk           |
 +--------------------------+
+|           power          |
++--------------------------+
+|          custom          |
++--------------------------+
+|   proc_log, process_log  |
++--------------------------+
+|    conn, connections     |
++--------------------------+
 |           empty          |
 +--------------------------+
                 ",
@@ -1136,4 +1362,9 @@ pub struct UsedWidgets {
     pub use_disk: bool,
     pub use_temp: bool,
     pub use_battery: bool,
+    pub use_power: bool,
+    pub use_custom: bool,
+    pub use_connections: bool,
+    /// The slower process details needed by process widgets.
+    pub process_fields: ProcessFields,
 }
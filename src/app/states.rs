@@ -1,4 +1,4 @@
-use std::ops::Range;
+use std::{ops::Range, time::Duration};
 
 use indexmap::IndexMap;
 use rustc_hash::FxHashMap as HashMap;
@@ -6,11 +6,15 @@ use unicode_ellipsis::grapheme_width;
 use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete, UnicodeSegmentation};
 
 use crate::{
-    app::layout_manager::BottomWidgetType,
+    app::{
+        commands::CommandCompletion,
+        layout_manager::{BottomWidgetType, LayoutTab},
+    },
     constants,
     widgets::{
-        BatteryWidgetState, CpuWidgetState, DiskTableWidget, MemWidgetState, NetWidgetState,
-        ProcWidgetState, TempWidgetState, query::ProcessQuery,
+        BatteryWidgetState, ConnectionTableWidgetState, CpuWidgetState, CustomWidgetState,
+        DiskTableWidget, GraphLegend, MemWidgetState, NetWidgetState, PowerWidgetState,
+        ProcWidgetState, ProcessLogWidgetState, TempWidgetState, query::ProcessQuery,
     },
 };
 
@@ -22,14 +26,143 @@ pub struct AppWidgetStates {
     pub mem_state: MemState,
     pub net_state: NetState,
     pub proc_state: ProcState,
+    pub proc_log_state: ProcLogState,
+    pub conn_state: ConnState,
     pub temp_state: TempState,
     pub disk_state: DiskState,
     pub battery_state: AppBatteryState,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     pub gpu_state: GpuState,
+    pub power_state: PowerState,
+    pub custom_state: CustomState,
     pub basic_table_widget_state: Option<BasicTableWidgetState>,
 }
 
+impl AppWidgetStates {
+    /// Keeps the state changed while running, such as searches, sorting, and
+    /// scroll positions, of each widget in `old` that is still in the layout
+    /// after the config was reloaded.
+    pub fn keep_runtime_state(&mut self, old: AppWidgetStates) {
+        fn keep_each<S>(
+            states: &mut HashMap<u64, S>, old: HashMap<u64, S>, keep: impl Fn(&mut S, S),
+        ) {
+            for (widget_id, old_state) in old {
+                if let Some(state) = states.get_mut(&widget_id) {
+                    keep(state, old_state);
+                }
+            }
+        }
+
+        keep_each(
+            &mut self.cpu_state.widget_states,
+            old.cpu_state.widget_states,
+            CpuWidgetState::keep_runtime_state,
+        );
+        keep_each(
+            &mut self.mem_state.widget_states,
+            old.mem_state.widget_states,
+            MemWidgetState::keep_runtime_state,
+        );
+        keep_each(
+            &mut self.net_state.widget_states,
+            old.net_state.widget_states,
+            NetWidgetState::keep_runtime_state,
+        );
+        keep_each(
+            &mut self.proc_state.widget_states,
+            old.proc_state.widget_states,
+            ProcWidgetState::keep_runtime_state,
+        );
+        keep_each(
+            &mut self.proc_log_state.widget_states,
+            old.proc_log_state.widget_states,
+            ProcessLogWidgetState::keep_runtime_state,
+        );
+        keep_each(
+            &mut self.conn_state.widget_states,
+            old.conn_state.widget_states,
+            ConnectionTableWidgetState::keep_runtime_state,
+        );
+        keep_each(
+            &mut self.temp_state.widget_states,
+            old.temp_state.widget_states,
+            TempWidgetState::keep_runtime_state,
+        );
+        keep_each(
+            &mut self.disk_state.widget_states,
+            old.disk_state.widget_states,
+            DiskTableWidget::keep_runtime_state,
+        );
+        keep_each(
+            &mut self.battery_state.widget_states,
+            old.battery_state.widget_states,
+            |state, old| {
+                state.currently_selected_battery_index = old.currently_selected_battery_index
+            },
+        );
+        #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+        keep_each(
+            &mut self.gpu_state.widget_states,
+            old.gpu_state.widget_states,
+            GpuWidgetState::keep_runtime_state,
+        );
+        keep_each(
+            &mut self.power_state.widget_states,
+            old.power_state.widget_states,
+            PowerWidgetState::keep_runtime_state,
+        );
+        keep_each(
+            &mut self.custom_state.widget_states,
+            old.custom_state.widget_states,
+            CustomWidgetState::keep_runtime_state,
+        );
+    }
+
+    /// Returns the legend of the graph widget with `widget_id`, if it is a
+    /// graph with a legend drawn inside it.
+    pub fn graph_legend_mut(&mut self, widget_id: u64) -> Option<&mut GraphLegend> {
+        if let Some(state) = self.mem_state.widget_states.get_mut(&widget_id) {
+            Some(&mut state.legend)
+        } else if let Some(state) = self.net_state.widget_states.get_mut(&widget_id) {
+            Some(&mut state.legend)
+        } else if let Some(state) = self.power_state.widget_states.get_mut(&widget_id) {
+            Some(&mut state.legend)
+        } else {
+            self.custom_state
+                .widget_states
+                .get_mut(&widget_id)
+                .map(|state| &mut state.legend)
+        }
+    }
+
+    /// Returns the legend of each graph widget with a legend drawn inside it,
+    /// by widget ID.
+    pub fn graph_legends(&self) -> impl Iterator<Item = (u64, GraphLegend)> + '_ {
+        let mem = self
+            .mem_state
+            .widget_states
+            .iter()
+            .map(|(id, s)| (*id, s.legend));
+        let net = self
+            .net_state
+            .widget_states
+            .iter()
+            .map(|(id, s)| (*id, s.legend));
+        let power = self
+            .power_state
+            .widget_states
+            .iter()
+            .map(|(id, s)| (*id, s.legend));
+        let custom = self
+            .custom_state
+            .widget_states
+            .iter()
+            .map(|(id, s)| (*id, s.legend));
+
+        mem.chain(net).chain(power).chain(custom)
+    }
+}
+
 #[derive(Debug)]
 pub enum CursorDirection {
     Left,
@@ -54,6 +187,115 @@ impl Default for AppHelpDialogState {
     }
 }
 
+/// The state of the diagnostics dialog.
+#[derive(Default)]
+pub struct AppDiagnosticsDialogState {
+    pub is_showing: bool,
+
+    /// How long the last draw took.
+    pub draw_duration: Duration,
+}
+
+/// The state of the column picker dialog.
+#[derive(Default)]
+pub struct AppColumnDialogState {
+    pub is_showing: bool,
+
+    /// The ID of the table widget whose columns are being picked.
+    pub widget_id: u64,
+
+    /// The selected row in the dialog.
+    pub selected: usize,
+}
+
+/// The state of the `:` command prompt.
+#[derive(Default)]
+pub struct AppCommandState {
+    pub is_showing: bool,
+    pub input: AppSearchState,
+
+    /// Previously run commands, oldest first.
+    pub history: Vec<String>,
+
+    /// The shown command from the history, if browsing it.
+    pub history_index: Option<usize>,
+
+    /// Suggestions for the command being typed, best first.
+    pub completions: Vec<CommandCompletion>,
+
+    /// The suggestion filled in with tab, if any.
+    pub selected_completion: Option<usize>,
+
+    /// Why the last command failed.
+    pub error: Option<String>,
+
+    /// What the last command did, for commands that say so.
+    pub message: Option<String>,
+
+    /// Whether a command asked to quit.
+    pub is_quit_requested: bool,
+}
+
+/// The state of the layout tabs. There is only one tab unless the config sets
+/// more than one layout.
+#[derive(Default)]
+pub struct AppTabState {
+    /// The index of the shown tab.
+    pub current: usize,
+
+    /// The names of the tabs.
+    pub names: Vec<String>,
+
+    /// The selected widget of each tab, which is selected again when switching
+    /// back to it.
+    pub selected_widgets: Vec<u64>,
+
+    /// The tab that each widget is in.
+    pub widget_tabs: HashMap<u64, usize>,
+
+    /// Where each tab was drawn in the tab bar, for mouse click detection.
+    pub click_locs: Vec<((u16, u16), (u16, u16))>,
+}
+
+impl AppTabState {
+    /// Creates the state for `tabs`, starting on the tab with the widget
+    /// `initial_widget_id`.
+    pub fn new(tabs: &[LayoutTab], initial_widget_id: u64) -> Self {
+        let mut widget_tabs = HashMap::default();
+        for (index, tab) in tabs.iter().enumerate() {
+            for widget in tab.widgets() {
+                widget_tabs.insert(widget.widget_id, index);
+            }
+        }
+
+        let current = widget_tabs.get(&initial_widget_id).copied().unwrap_or(0);
+        let selected_widgets = tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| {
+                if index == current {
+                    initial_widget_id
+                } else {
+                    tab.layout.first_widget_id().unwrap_or(initial_widget_id)
+                }
+            })
+            .collect();
+
+        Self {
+            current,
+            names: tabs.iter().map(|tab| tab.name.clone()).collect(),
+            selected_widgets,
+            widget_tabs,
+            click_locs: Vec::new(),
+        }
+    }
+
+    /// Whether there is more than one tab, and so a tab bar is shown.
+    pub fn has_tabs(&self) -> bool {
+        self.names.len() > 1
+    }
+}
+
 /// AppSearchState deals with generic searching (I might do this in the future).
 pub struct AppSearchState {
     pub is_enabled: bool,
@@ -227,6 +469,95 @@ impl AppSearchState {
         }
     }
 
+    /// Returns the byte index of the cursor in the query.
+    pub(crate) fn cursor_index(&self) -> usize {
+        self.grapheme_cursor.cur_cursor()
+    }
+
+    /// Replaces the query, moving the cursor to the end.
+    pub(crate) fn set_query(&mut self, query: String) {
+        let len = query.len();
+        self.current_search_query = query;
+        self.grapheme_cursor = GraphemeCursor::new(len, len, true);
+        self.cursor_direction = CursorDirection::Right;
+        self.is_blank_search = self.current_search_query.is_empty();
+        self.update_sizes();
+    }
+
+    /// Inserts `text` at the cursor, moving the cursor past it.
+    pub(crate) fn insert_str(&mut self, text: &str) {
+        let index = self.cursor_index();
+        self.current_search_query.insert_str(index, text);
+        self.grapheme_cursor = GraphemeCursor::new(index, self.current_search_query.len(), true);
+        for _ in UnicodeSegmentation::graphemes(text, true) {
+            self.walk_forward();
+        }
+
+        self.cursor_direction = CursorDirection::Right;
+        self.is_blank_search = self.current_search_query.is_empty();
+        self.update_sizes();
+    }
+
+    /// Removes the grapheme before the cursor.
+    pub(crate) fn delete_backward(&mut self) {
+        let end = self.cursor_index();
+        if end == 0 {
+            return;
+        }
+
+        self.walk_backward();
+        let start = self.cursor_index();
+        self.current_search_query.drain(start..end);
+        self.grapheme_cursor = GraphemeCursor::new(start, self.current_search_query.len(), true);
+        self.cursor_direction = CursorDirection::Left;
+        self.is_blank_search = self.current_search_query.is_empty();
+        self.update_sizes();
+    }
+
+    /// Removes the grapheme after the cursor.
+    pub(crate) fn delete_forward(&mut self) {
+        let start = self.cursor_index();
+        if start >= self.current_search_query.len() {
+            return;
+        }
+
+        self.walk_forward();
+        let end = self.cursor_index();
+        self.current_search_query.drain(start..end);
+        self.grapheme_cursor = GraphemeCursor::new(start, self.current_search_query.len(), true);
+        self.is_blank_search = self.current_search_query.is_empty();
+        self.update_sizes();
+    }
+
+    /// Moves the cursor one grapheme to the left.
+    pub(crate) fn move_cursor_left(&mut self) {
+        if self.cursor_index() > 0 {
+            self.walk_backward();
+        }
+        self.cursor_direction = CursorDirection::Left;
+    }
+
+    /// Moves the cursor one grapheme to the right.
+    pub(crate) fn move_cursor_right(&mut self) {
+        if self.cursor_index() < self.current_search_query.len() {
+            self.walk_forward();
+        }
+        self.cursor_direction = CursorDirection::Right;
+    }
+
+    /// Moves the cursor to the start or the end of the query.
+    pub(crate) fn move_cursor_to_end(&mut self, is_start: bool) {
+        let len = self.current_search_query.len();
+        let (index, direction) = if is_start {
+            (0, CursorDirection::Left)
+        } else {
+            (len, CursorDirection::Right)
+        };
+
+        self.grapheme_cursor = GraphemeCursor::new(index, len, true);
+        self.cursor_direction = direction;
+    }
+
     pub(crate) fn update_sizes(&mut self) {
         self.size_mappings.clear();
         let mut curr_offset = 0;
@@ -301,6 +632,44 @@ impl MemState {
     }
 }
 
+pub struct ProcLogState {
+    pub widget_states: HashMap<u64, ProcessLogWidgetState>,
+}
+
+impl ProcLogState {
+    pub fn init(widget_states: HashMap<u64, ProcessLogWidgetState>) -> Self {
+        ProcLogState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut ProcessLogWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&ProcessLogWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct ConnState {
+    pub widget_states: HashMap<u64, ConnectionTableWidgetState>,
+}
+
+impl ConnState {
+    pub fn init(widget_states: HashMap<u64, ConnectionTableWidgetState>) -> Self {
+        ConnState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(
+        &mut self, widget_id: u64,
+    ) -> Option<&mut ConnectionTableWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&ConnectionTableWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
 pub struct TempState {
     pub widget_states: HashMap<u64, TempWidgetState>,
 }
@@ -382,6 +751,42 @@ impl GpuState {
     }
 }
 
+pub struct PowerState {
+    pub widget_states: HashMap<u64, PowerWidgetState>,
+}
+
+impl PowerState {
+    pub fn init(widget_states: HashMap<u64, PowerWidgetState>) -> Self {
+        PowerState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut PowerWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&PowerWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
+pub struct CustomState {
+    pub widget_states: HashMap<u64, CustomWidgetState>,
+}
+
+impl CustomState {
+    pub fn init(widget_states: HashMap<u64, CustomWidgetState>) -> Self {
+        CustomState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut CustomWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&CustomWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
 #[derive(Default)]
 pub struct ParagraphScrollState {
     pub current_scroll_index: u16,
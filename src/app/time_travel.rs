@@ -0,0 +1,168 @@
+//! Showing the processes from an earlier point in time in the process tables.
+
+use std::time::{Duration, Instant};
+
+use super::{App, layout_manager::BottomWidgetType};
+use crate::canvas::components::time_graph::time_label;
+
+impl App {
+    /// Returns the time of the process snapshot that the process tables should
+    /// show, or `None` to show the latest data.
+    ///
+    /// This follows a cursor on a CPU or memory graph if there is one, and is
+    /// otherwise the time picked by stepping back while frozen.
+    pub(crate) fn process_snapshot_time(&self) -> Option<Instant> {
+        let data = self.data_store.get_data();
+        let history = &data.process_history;
+
+        let time = match self.graph_cursor.cursor {
+            Some(cursor)
+                if matches!(
+                    self.widget_map
+                        .get(&cursor.widget_id)
+                        .map(|widget| &widget.widget_type),
+                    Some(BottomWidgetType::Cpu | BottomWidgetType::Mem)
+                ) =>
+            {
+                let latest = data.timeseries_data.time.last()?;
+                history.nearest(latest.checked_sub(Duration::from_millis(cursor.offset))?)?
+            }
+            _ => self.process_time?,
+        };
+
+        // The latest snapshot is the same as the latest data.
+        (history.times().next_back() != Some(time)).then_some(time)
+    }
+
+    /// Moves the time shown by the process tables one process collection back
+    /// or forward while the data is frozen. Moving forward past the last
+    /// collection goes back to showing the latest data.
+    pub fn step_process_time(&mut self, older: bool) {
+        if !self.data_store.is_frozen() {
+            return;
+        }
+
+        let times = self
+            .data_store
+            .get_data()
+            .process_history
+            .times()
+            .collect::<Vec<_>>();
+        let Some(latest) = times.len().checked_sub(1) else {
+            return;
+        };
+
+        let current = self
+            .process_time
+            .and_then(|time| times.iter().position(|t| *t == time))
+            .unwrap_or(latest);
+        let next = if older {
+            current.saturating_sub(1)
+        } else {
+            (current + 1).min(latest)
+        };
+
+        self.process_time = (next < latest).then(|| times[next]);
+    }
+
+    /// Freezes or unfreezes the data. Unfreezing goes back to showing the
+    /// latest processes.
+    pub fn toggle_frozen(&mut self) {
+        self.data_store.toggle_frozen();
+        if !self.data_store.is_frozen() {
+            self.process_time = None;
+        }
+    }
+
+    /// Describes the time shown by the process tables, relative to the latest
+    /// process collection.
+    pub(crate) fn process_time_label(&self, time: Instant) -> String {
+        let latest = self
+            .data_store
+            .get_data()
+            .process_history
+            .times()
+            .next_back()
+            .unwrap_or(time);
+
+        time_label(latest.duration_since(time).as_millis() as u64, time)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::*;
+    use crate::{
+        app::graph_cursor::GraphCursor,
+        args::BottomArgs,
+        collection::{Data, processes::ProcessHarvest},
+        options::config::Config,
+    };
+
+    fn collect(app: &mut App, time: Instant, cpu_usage_percent: f32) {
+        let data = Data {
+            collection_time: time,
+            list_of_processes: Some(vec![ProcessHarvest {
+                pid: 1,
+                name: "spike".to_string(),
+                cpu_usage_percent,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        app.data_store
+            .eat_data(Box::new(data), &app.app_config_fields);
+    }
+
+    #[test]
+    fn steps_through_past_processes() {
+        let args = BottomArgs::parse_from(["btm"]);
+        let mut app = crate::options::init_app(args, Config::default()).unwrap().0;
+        let start = Instant::now();
+        for (second, cpu) in [0, 1, 2].into_iter().zip([10.0, 90.0, 20.0]) {
+            collect(&mut app, start + Duration::from_secs(second), cpu);
+        }
+
+        app.step_process_time(true);
+        assert_eq!(app.process_snapshot_time(), None);
+
+        app.toggle_frozen();
+        app.step_process_time(true);
+        let time = app.process_snapshot_time().unwrap();
+        assert_eq!(time, start + Duration::from_secs(1));
+        assert!(app.process_time_label(time).starts_with("-1.0s ("));
+
+        app.force_widget_data_update();
+        app.update_data();
+        let state = app.states.proc_state.widget_states.values().next().unwrap();
+        let (_, rows) = state.table.shown_rows();
+        assert!(rows[0].iter().any(|cell| cell == "90.0%"));
+
+        app.step_process_time(true);
+        app.step_process_time(true);
+        assert_eq!(app.process_snapshot_time(), Some(start));
+
+        app.step_process_time(false);
+        app.step_process_time(false);
+        assert_eq!(app.process_snapshot_time(), None);
+
+        app.step_process_time(true);
+        app.toggle_frozen();
+        assert_eq!(app.process_snapshot_time(), None);
+
+        let cpu = app
+            .widget_map
+            .values()
+            .find(|widget| widget.widget_type == BottomWidgetType::Cpu)
+            .unwrap()
+            .widget_id;
+        app.graph_cursor.cursor = Some(GraphCursor {
+            widget_id: cpu,
+            offset: 1900,
+            is_from_mouse: false,
+        });
+        assert_eq!(app.process_snapshot_time(), Some(start));
+    }
+}
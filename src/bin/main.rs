@@ -3,11 +3,11 @@
 use bottom::{reset_stdout, start_bottom};
 
 fn main() -> anyhow::Result<()> {
-    let mut run_error_hook = false;
+    let mut terminal_mode = None;
 
-    start_bottom(&mut run_error_hook).inspect_err(|_| {
-        if run_error_hook {
-            reset_stdout();
+    start_bottom(&mut terminal_mode).inspect_err(|_| {
+        if let Some(mode) = terminal_mode {
+            reset_stdout(mode);
         }
     })
 }
@@ -3,24 +3,27 @@
 //! Note that eventually this should not contain any widget-specific draw code, but rather just generic code
 //! or components.
 
+pub mod capture;
 pub mod components;
 pub mod dialogs;
 mod drawing_utils;
 mod widgets;
 
 use tui::{
-    Frame, Terminal,
+    CompletedFrame, Frame, Terminal,
     backend::Backend,
     layout::{Constraint, Direction, Flex, Layout, Rect},
-    text::Span,
-    widgets::Paragraph,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Tabs, Wrap},
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app::{
         App,
-        layout_manager::{BottomColRow, BottomLayout, BottomWidgetType},
+        layout_manager::{BottomColRow, BottomWidgetType, LayoutTab},
     },
+    canvas::drawing_utils::dialog_block,
     constants::*,
     options::config::style::Styles,
 };
@@ -35,17 +38,21 @@ pub struct Painter {
     /// Used to know whether to invalidate things.
     previous_width: u16,
 
-    /// The layout.
-    layout: BottomLayout,
+    /// The layouts of each tab.
+    tabs: Vec<LayoutTab>,
+
+    /// The tab and breakpoint of the layout that was last drawn.
+    active_layout: Option<(usize, Option<usize>)>,
 }
 
 impl Painter {
-    pub fn init(layout: BottomLayout, styling: Styles) -> anyhow::Result<Self> {
+    pub fn init(tabs: Vec<LayoutTab>, styling: Styles) -> anyhow::Result<Self> {
         let painter = Painter {
             styles: styling,
             previous_height: 0,
             previous_width: 0,
-            layout,
+            tabs,
+            active_layout: None,
         };
 
         Ok(painter)
@@ -74,12 +81,109 @@ impl Painter {
         )
     }
 
-    pub fn draw_data<B: Backend>(
-        &mut self, terminal: &mut Terminal<B>, app_state: &mut App,
-    ) -> Result<(), B::Error> {
+    /// Draws the bar of layout tabs, recording where each tab is for mouse
+    /// clicks. The first nine tabs are numbered, matching the keys that select
+    /// them.
+    fn draw_tab_bar(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
+        let tab_state = &mut app_state.tab_state;
+        let titles = tab_state
+            .names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                if index < 9 {
+                    format!("{} {name}", index + 1)
+                } else {
+                    name.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Each tab is padded by a space on either side, and followed by a divider.
+        let mut current_x = draw_loc.x;
+        tab_state.click_locs = titles
+            .iter()
+            .map(|title| {
+                let width = UnicodeWidthStr::width(title.as_str()) as u16 + 2;
+                let loc = ((current_x, draw_loc.y), (current_x + width, draw_loc.y));
+                current_x += width + 1;
+                loc
+            })
+            .collect();
+
+        f.render_widget(
+            Tabs::new(titles)
+                .divider(tui::symbols::line::VERTICAL)
+                .style(self.styles.text_style)
+                .highlight_style(self.styles.selected_text_style)
+                .select(tab_state.current),
+            draw_loc,
+        );
+    }
+
+    /// Draws an error from reloading the config or saving settings as a banner
+    /// over the top of the screen, so bottom can keep running.
+    fn draw_config_error_banner(&self, f: &mut Frame<'_>, config_error: &str, draw_loc: Rect) {
+        let lines: Vec<Line<'_>> = config_error
+            .lines()
+            .map(|line| Line::styled(line, self.styles.text_style))
+            .collect();
+        let height = (lines.len() as u16 + 2).min(draw_loc.height / 2);
+
+        let [banner] = Layout::default()
+            .constraints([Constraint::Length(height)])
+            .areas(draw_loc);
+
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.invalid_query_style)
+            .title_top(Line::styled(
+                " Config error ",
+                self.styles.invalid_query_style,
+            ))
+            .title_top(
+                Line::styled(" Esc to dismiss ", self.styles.widget_title_style).right_aligned(),
+            );
+
+        f.render_widget(Clear, banner);
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            banner,
+        );
+    }
+
+    /// Draws the basic widgets chosen for inline mode side by side.
+    fn draw_inline(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
+        let widgets = app_state.app_config_fields.inline_widgets.clone();
+        let locs = Layout::horizontal(widgets.iter().map(|_| Constraint::Fill(1)))
+            .spacing(1)
+            .split(draw_loc);
+
+        for (widget_type, &loc) in widgets.iter().zip(locs.iter()) {
+            if loc.width < 2 {
+                continue;
+            }
+
+            // These use the IDs of the basic mode widgets.
+            match widget_type {
+                BottomWidgetType::Cpu => self.draw_inline_cpu(f, app_state, loc, 1),
+                BottomWidgetType::Mem => self.draw_basic_memory(f, app_state, loc, 2),
+                BottomWidgetType::Net => self.draw_basic_network(f, app_state, loc, 3),
+                _ => {}
+            }
+        }
+    }
+
+    /// Draws a frame, returning it so that it can be captured.
+    pub fn draw_data<'a, B: Backend>(
+        &mut self, terminal: &'a mut Terminal<B>, app_state: &mut App,
+    ) -> Result<CompletedFrame<'a>, B::Error> {
         use BottomWidgetType::*;
 
-        terminal.draw(|f| {
+        let frame = terminal.draw(|f| {
+            app_state.graph_cursor.plot_areas.clear();
+
             let (terminal_size, frozen_draw_loc) = if app_state.data_store.is_frozen() {
                 // TODO: Remove built-in cache?
                 let split_loc = Layout::default()
@@ -101,6 +205,20 @@ impl Painter {
                 self.previous_width = terminal_width;
             }
 
+            // Switch layouts if the terminal was resized past a breakpoint.
+            let tab_index = app_state.tab_state.current.min(self.tabs.len() - 1);
+            let tab = &self.tabs[tab_index];
+            let active_layout = (
+                tab_index,
+                tab.breakpoint_index(f.area().width, f.area().height),
+            );
+            if self.active_layout != Some(active_layout) {
+                self.active_layout = Some(active_layout);
+                if !tab.breakpoints.is_empty() {
+                    app_state.use_layout(tab.layout_at(active_layout.1));
+                }
+            }
+
             // TODO: We should probably remove this or make it done elsewhere, not the responsibility of the app.
             if app_state.should_get_widget_bounds() {
                 // If we're force drawing, reset ALL mouse boundaries.
@@ -112,6 +230,8 @@ impl Painter {
                 // Reset process kill dialog button locations...
                 app_state.process_kill_dialog.handle_redraw();
 
+                app_state.tab_state.click_locs.clear();
+
                 // Reset battery dialog button locations...
                 for battery_widget in app_state.states.battery_state.widget_states.values_mut() {
                     battery_widget.tab_click_locs = None;
@@ -203,6 +323,48 @@ impl Painter {
                 app_state
                     .process_kill_dialog
                     .draw(f, dialog_draw_area, &self.styles);
+            } else if app_state.diagnostics_dialog_state.is_showing {
+                const DIALOG_WIDTH: u16 = 120;
+
+                let dialog_height = dialogs::diagnostics_dialog::diagnostics_dialog_height(
+                    &app_state.data_store.get_data().diagnostics,
+                );
+
+                let [vertical_dialog_chunk] = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(dialog_height)])
+                    .flex(Flex::Center)
+                    .areas(terminal_size);
+
+                let [dialog_draw_area] = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Length(DIALOG_WIDTH)])
+                    .flex(Flex::Center)
+                    .areas(vertical_dialog_chunk);
+
+                self.draw_diagnostics_dialog(f, app_state, dialog_draw_area);
+            } else if app_state.column_dialog_state.is_showing {
+                let count = app_state
+                    .column_picker(app_state.column_dialog_state.widget_id)
+                    .map(|picker| picker.picker_columns().len())
+                    .unwrap_or(0);
+                let dialog_height = dialogs::column_dialog::column_dialog_height(count);
+
+                let [vertical_dialog_chunk] = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(dialog_height)])
+                    .flex(Flex::Center)
+                    .areas(terminal_size);
+
+                let [dialog_draw_area] = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Length(
+                        dialogs::column_dialog::COLUMN_DIALOG_WIDTH,
+                    )])
+                    .flex(Flex::Center)
+                    .areas(vertical_dialog_chunk);
+
+                self.draw_column_dialog(f, app_state, dialog_draw_area);
             } else if app_state.is_expanded {
                 if let Some(frozen_draw_loc) = frozen_draw_loc {
                     self.draw_frozen_indicator(f, frozen_draw_loc);
@@ -238,6 +400,18 @@ impl Painter {
                         rect[0],
                         app_state.current_widget.widget_id,
                     ),
+                    ProcLog => self.draw_process_log(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Conn => self.draw_connection_table(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     Net => self.draw_network_graph(
                         f,
                         app_state,
@@ -260,6 +434,18 @@ impl Painter {
                         #[cfg(feature = "battery")]
                         self.draw_battery(f, app_state, rect[0], app_state.current_widget.widget_id)
                     }
+                    Power => self.draw_power_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Custom => self.draw_custom_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
                     Gpu => self.draw_gpu(f, app_state, rect[0], app_state.current_widget.widget_id),
                     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -271,6 +457,12 @@ impl Painter {
                     ),
                     _ => {}
                 }
+            } else if app_state.app_config_fields.inline_height.is_some() {
+                if let Some(frozen_draw_loc) = frozen_draw_loc {
+                    self.draw_frozen_indicator(f, frozen_draw_loc);
+                }
+
+                self.draw_inline(f, app_state, terminal_size);
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode. This basically removes all graphs but otherwise
                 // the same info.
@@ -395,10 +587,25 @@ impl Painter {
                 // then pass each layout to the corresponding widget (second pass).
                 // Note that layouts are already cached in ratatui, so we don't need
                 // to do it manually!
-                let base = Layout::vertical(self.layout.rows.iter().map(|r| r.constraint))
-                    .split(terminal_size);
+                let layout_loc = if app_state.tab_state.has_tabs() {
+                    let [tab_bar_loc, layout_loc] =
+                        Layout::vertical([Constraint::Length(1), Constraint::Min(0)])
+                            .areas(terminal_size);
+                    self.draw_tab_bar(f, app_state, tab_bar_loc);
+
+                    layout_loc
+                } else {
+                    terminal_size
+                };
+
+                let layout = match self.active_layout {
+                    Some((tab_index, breakpoint)) => self.tabs[tab_index].layout_at(breakpoint),
+                    None => &self.tabs[0].layout,
+                };
+                let base =
+                    Layout::vertical(layout.rows.iter().map(|r| r.constraint)).split(layout_loc);
 
-                for (br, base) in self.layout.rows.iter().zip(base.iter()) {
+                for (br, base) in layout.rows.iter().zip(base.iter()) {
                     let base =
                         Layout::horizontal(br.children.iter().map(|bc| bc.constraint)).split(*base);
 
@@ -421,6 +628,14 @@ impl Painter {
                     }
                 }
             }
+
+            if let Some(config_error) = &app_state.config_error {
+                self.draw_config_error_banner(f, config_error, f.area());
+            }
+
+            if app_state.command_state.is_showing {
+                self.draw_command_palette(f, app_state, f.area());
+            }
         })?;
 
         if let Some(updated_current_widget) = app_state
@@ -433,7 +648,7 @@ impl Painter {
         app_state.is_force_redraw = false;
         app_state.is_determining_widget_boundary = false;
 
-        Ok(())
+        Ok(frame)
     }
 
     fn draw_widgets_with_constraints(
@@ -450,11 +665,15 @@ impl Painter {
                     Temp => self.draw_temp_table(f, app_state, *draw_loc, widget.widget_id),
                     Disk => self.draw_disk_table(f, app_state, *draw_loc, widget.widget_id),
                     Proc => self.draw_process(f, app_state, *draw_loc, widget.widget_id),
+                    ProcLog => self.draw_process_log(f, app_state, *draw_loc, widget.widget_id),
+                    Conn => self.draw_connection_table(f, app_state, *draw_loc, widget.widget_id),
                     Battery =>
                     {
                         #[cfg(feature = "battery")]
                         self.draw_battery(f, app_state, *draw_loc, widget.widget_id)
                     }
+                    Power => self.draw_power_graph(f, app_state, *draw_loc, widget.widget_id),
+                    Custom => self.draw_custom_graph(f, app_state, *draw_loc, widget.widget_id),
                     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
                     Gpu => self.draw_gpu(f, app_state, *draw_loc, widget.widget_id),
                     _ => {}
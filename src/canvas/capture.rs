@@ -0,0 +1,500 @@
+//! Turning a drawn frame into text, text with colour escape codes, SVG, or
+//! HTML, so that it can be shared without a screenshot.
+
+use std::{fmt::Write, fs, path::Path, str::FromStr};
+
+use anyhow::Context;
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::options::config::style::Styles;
+
+/// The width of a cell in an SVG capture, in pixels.
+const CELL_WIDTH: f32 = 8.4;
+
+/// The height of a cell in an SVG capture, in pixels.
+const CELL_HEIGHT: u16 = 17;
+
+/// The font size of an SVG capture, in pixels.
+const FONT_SIZE: u16 = 14;
+
+/// How a captured frame is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaptureFormat {
+    /// Plain text, without any colours.
+    Text,
+
+    /// Text with ANSI escape codes for colours, for viewing in a terminal.
+    Ansi,
+    Svg,
+    Html,
+}
+
+impl FromStr for CaptureFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" | "txt" => Ok(CaptureFormat::Text),
+            "ansi" => Ok(CaptureFormat::Ansi),
+            "svg" => Ok(CaptureFormat::Svg),
+            "html" => Ok(CaptureFormat::Html),
+            _ => Err(format!(
+                "'{s}' is an invalid capture format, use one of: [text, ansi, svg, html]."
+            )),
+        }
+    }
+}
+
+impl CaptureFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CaptureFormat::Text => "txt",
+            CaptureFormat::Ansi => "ansi",
+            CaptureFormat::Svg => "svg",
+            CaptureFormat::Html => "html",
+        }
+    }
+
+    /// Picks the format from the extension of `path`, using plain text if it
+    /// isn't one of the others.
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| extension.to_lowercase().parse().ok())
+            .unwrap_or(CaptureFormat::Text)
+    }
+}
+
+/// A run of cells on one line with the same style.
+#[derive(Debug, PartialEq)]
+struct Run {
+    /// The column that the run starts at.
+    column: u16,
+    text: String,
+    fg: Color,
+    bg: Color,
+    modifier: Modifier,
+}
+
+/// Splits each line of `buffer` into runs of cells with the same style. The
+/// cells covered by wide characters are skipped.
+fn runs(buffer: &Buffer) -> Vec<Vec<Run>> {
+    let area = buffer.area;
+
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut runs: Vec<Run> = Vec::new();
+            let mut covered = 0;
+
+            for x in area.left()..area.right() {
+                if covered > 0 {
+                    covered -= 1;
+                    continue;
+                }
+
+                let Some(cell) = buffer.cell((x, y)) else {
+                    continue;
+                };
+                let symbol = cell.symbol();
+                covered = symbol.width().saturating_sub(1);
+
+                match runs.last_mut() {
+                    Some(run)
+                        if run.fg == cell.fg
+                            && run.bg == cell.bg
+                            && run.modifier == cell.modifier =>
+                    {
+                        run.text.push_str(symbol);
+                    }
+                    _ => runs.push(Run {
+                        column: x - area.left(),
+                        text: symbol.to_string(),
+                        fg: cell.fg,
+                        bg: cell.bg,
+                        modifier: cell.modifier,
+                    }),
+                }
+            }
+
+            runs
+        })
+        .collect()
+}
+
+/// Returns the frame in `buffer` as `format`, using `styles` for the default
+/// colours where needed.
+pub fn capture_buffer(buffer: &Buffer, format: CaptureFormat, styles: &Styles) -> String {
+    let lines = runs(buffer);
+
+    match format {
+        CaptureFormat::Text => capture_text(&lines),
+        CaptureFormat::Ansi => capture_ansi(&lines),
+        CaptureFormat::Svg => capture_svg(&lines, buffer.area, styles),
+        CaptureFormat::Html => capture_html(&lines, styles),
+    }
+}
+
+/// Writes the frame in `buffer` to `path` as `format`.
+pub fn write_capture(
+    buffer: &Buffer, format: CaptureFormat, path: &Path, styles: &Styles,
+) -> anyhow::Result<()> {
+    fs::write(path, capture_buffer(buffer, format, styles))
+        .with_context(|| format!("Could not write the capture to {}.", path.display()))
+}
+
+fn capture_text(lines: &[Vec<Run>]) -> String {
+    lines
+        .iter()
+        .map(|runs| {
+            let line = runs.iter().map(|run| run.text.as_str()).collect::<String>();
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+/// Returns the ANSI escape code number for `color`, or [`None`] for the
+/// terminal's default.
+fn ansi_colour(color: Color, is_background: bool) -> Option<String> {
+    let offset = if is_background { 10 } else { 0 };
+    let basic = |code: u8| Some((code + offset).to_string());
+
+    match color {
+        Color::Reset => None,
+        Color::Black => basic(30),
+        Color::Red => basic(31),
+        Color::Green => basic(32),
+        Color::Yellow => basic(33),
+        Color::Blue => basic(34),
+        Color::Magenta => basic(35),
+        Color::Cyan => basic(36),
+        Color::Gray => basic(37),
+        Color::DarkGray => basic(90),
+        Color::LightRed => basic(91),
+        Color::LightGreen => basic(92),
+        Color::LightYellow => basic(93),
+        Color::LightBlue => basic(94),
+        Color::LightMagenta => basic(95),
+        Color::LightCyan => basic(96),
+        Color::White => basic(97),
+        Color::Indexed(index) => Some(format!("{};5;{index}", 38 + offset)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{r};{g};{b}", 38 + offset)),
+    }
+}
+
+const MODIFIER_CODES: [(Modifier, &str); 6] = [
+    (Modifier::BOLD, "1"),
+    (Modifier::DIM, "2"),
+    (Modifier::ITALIC, "3"),
+    (Modifier::UNDERLINED, "4"),
+    (Modifier::REVERSED, "7"),
+    (Modifier::CROSSED_OUT, "9"),
+];
+
+fn capture_ansi(lines: &[Vec<Run>]) -> String {
+    let mut capture = String::new();
+
+    for runs in lines {
+        for run in runs {
+            let codes = MODIFIER_CODES
+                .iter()
+                .filter(|(modifier, _)| run.modifier.contains(*modifier))
+                .map(|(_, code)| code.to_string())
+                .chain(ansi_colour(run.fg, false))
+                .chain(ansi_colour(run.bg, true))
+                .collect::<Vec<_>>();
+
+            if codes.is_empty() {
+                capture.push_str(&run.text);
+            } else {
+                let _ = write!(capture, "\x1b[{}m{}\x1b[0m", codes.join(";"), run.text);
+            }
+        }
+        capture.push('\n');
+    }
+
+    capture
+}
+
+/// Returns the RGB value of `color` in the xterm palette, or [`None`] for the
+/// terminal's default.
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Indexed(index @ 16..=231) => {
+            let index = usize::from(index - 16);
+            return Some((
+                CUBE_LEVELS[index / 36],
+                CUBE_LEVELS[index / 6 % 6],
+                CUBE_LEVELS[index % 6],
+            ));
+        }
+        Color::Indexed(index @ 232..=255) => {
+            let level = 8 + (index - 232) * 10;
+            return Some((level, level, level));
+        }
+        Color::Indexed(index) => index,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+    };
+
+    Some(BASIC[usize::from(index)])
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Returns the default foreground and background colours for `styles`. The
+/// background is light if the text colour is dark, as in the light themes.
+fn default_colours(styles: &Styles) -> ((u8, u8, u8), (u8, u8, u8)) {
+    const DARK: (u8, u8, u8) = (28, 28, 28);
+    const LIGHT: (u8, u8, u8) = (250, 250, 250);
+
+    let fg = styles
+        .text_style
+        .fg
+        .and_then(rgb)
+        .unwrap_or((208, 208, 208));
+    let is_dark_text =
+        u32::from(fg.0) * 299 + u32::from(fg.1) * 587 + u32::from(fg.2) * 114 < 128_000;
+    let bg = styles
+        .text_style
+        .bg
+        .and_then(rgb)
+        .unwrap_or(if is_dark_text { LIGHT } else { DARK });
+
+    (fg, bg)
+}
+
+/// Returns the foreground and background colours of `run` as hex, swapping
+/// them if it is reversed.
+fn run_colours(run: &Run, defaults: ((u8, u8, u8), (u8, u8, u8))) -> (String, String) {
+    let fg = hex(rgb(run.fg).unwrap_or(defaults.0));
+    let bg = hex(rgb(run.bg).unwrap_or(defaults.1));
+
+    if run.modifier.contains(Modifier::REVERSED) {
+        (bg, fg)
+    } else {
+        (fg, bg)
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn capture_svg(lines: &[Vec<Run>], area: Rect, styles: &Styles) -> String {
+    let defaults = default_colours(styles);
+    let default_bg = hex(defaults.1);
+    let width = f32::from(area.width) * CELL_WIDTH;
+    let height = area.height * CELL_HEIGHT;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.1}\" height=\"{height}\" \
+        viewBox=\"0 0 {width:.1} {height}\" font-family=\"monospace\" font-size=\"{FONT_SIZE}\" \
+        xml:space=\"preserve\">\n<rect width=\"100%\" height=\"100%\" fill=\"{default_bg}\"/>\n"
+    );
+
+    for (row, runs) in lines.iter().enumerate() {
+        let y = row as u16 * CELL_HEIGHT;
+
+        for run in runs {
+            let (fg, bg) = run_colours(run, defaults);
+            let x = f32::from(run.column) * CELL_WIDTH;
+            let run_width = f32::from(run.text.width() as u16) * CELL_WIDTH;
+
+            if bg != default_bg {
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{x:.1}\" y=\"{y}\" width=\"{run_width:.1}\" height=\"{CELL_HEIGHT}\" fill=\"{bg}\"/>"
+                );
+            }
+
+            if run.text.trim().is_empty() {
+                continue;
+            }
+
+            let mut attributes = String::new();
+            if run.modifier.contains(Modifier::BOLD) {
+                attributes.push_str(" font-weight=\"bold\"");
+            }
+            if run.modifier.contains(Modifier::ITALIC) {
+                attributes.push_str(" font-style=\"italic\"");
+            }
+            if run.modifier.contains(Modifier::UNDERLINED) {
+                attributes.push_str(" text-decoration=\"underline\"");
+            }
+            if run.modifier.contains(Modifier::DIM) {
+                attributes.push_str(" opacity=\"0.6\"");
+            }
+
+            // Text is placed by its baseline, which is a little above the
+            // bottom of the cell.
+            let _ = writeln!(
+                svg,
+                "<text x=\"{x:.1}\" y=\"{}\" fill=\"{fg}\" textLength=\"{run_width:.1}\"{attributes}>{}</text>",
+                y + CELL_HEIGHT - 4,
+                escape_xml(&run.text),
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn capture_html(lines: &[Vec<Run>], styles: &Styles) -> String {
+    let defaults = default_colours(styles);
+    let (default_fg, default_bg) = (hex(defaults.0), hex(defaults.1));
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>bottom</title></head>\n\
+        <body style=\"margin: 0\">\n<pre style=\"margin: 0; padding: 0.5em; font-family: monospace; \
+        line-height: 1.2; color: {default_fg}; background: {default_bg}\">"
+    );
+
+    for runs in lines {
+        for run in runs {
+            let (fg, bg) = run_colours(run, defaults);
+
+            let mut style = String::new();
+            if fg != default_fg {
+                let _ = write!(style, "color: {fg}; ");
+            }
+            if bg != default_bg {
+                let _ = write!(style, "background: {bg}; ");
+            }
+            if run.modifier.contains(Modifier::BOLD) {
+                style.push_str("font-weight: bold; ");
+            }
+            if run.modifier.contains(Modifier::ITALIC) {
+                style.push_str("font-style: italic; ");
+            }
+            if run.modifier.contains(Modifier::UNDERLINED) {
+                style.push_str("text-decoration: underline; ");
+            }
+            if run.modifier.contains(Modifier::DIM) {
+                style.push_str("opacity: 0.6; ");
+            }
+
+            let text = escape_xml(&run.text);
+            if style.is_empty() {
+                html.push_str(&text);
+            } else {
+                let style = style.trim_end().trim_end_matches(';');
+                let _ = write!(html, "<span style=\"{style}\">{text}</span>");
+            }
+        }
+        html.push('\n');
+    }
+
+    html.push_str("</pre>\n</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod test {
+    use tui::style::Style;
+
+    use super::*;
+
+    fn buffer() -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 2));
+        buffer.set_string(0, 0, "CPU", Style::default().fg(Color::Red));
+        buffer.set_string(4, 0, "<50%", Style::default());
+        buffer.set_string(0, 1, "名前 x", Style::default().bg(Color::Indexed(16)));
+        buffer
+    }
+
+    #[test]
+    fn parses_formats_and_sizes() {
+        assert_eq!(
+            CaptureFormat::from_path(Path::new("a/frame.SVG")),
+            CaptureFormat::Svg
+        );
+        assert_eq!(
+            CaptureFormat::from_path(Path::new("frame")),
+            CaptureFormat::Text
+        );
+    }
+
+    #[test]
+    fn captures_text_and_ansi() {
+        let lines = runs(&buffer());
+
+        assert_eq!(capture_text(&lines), "CPU <50%\n名前 x\n");
+        assert_eq!(
+            capture_ansi(&lines),
+            "\x1b[31mCPU\x1b[0m <50%\n\x1b[48;5;16m名前 x\x1b[0m  \n"
+        );
+    }
+
+    #[test]
+    fn captures_svg_and_html() {
+        let styles = Styles::default();
+        let buffer = buffer();
+
+        let svg = capture_buffer(&buffer, CaptureFormat::Svg, &styles);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("fill=\"#cd0000\""));
+        assert!(svg.contains("&lt;50%"));
+        assert!(svg.contains("fill=\"#000000\""));
+
+        let html = capture_buffer(&buffer, CaptureFormat::Html, &styles);
+        assert!(html.contains("<span style=\"color: #cd0000\">CPU</span>"));
+        assert!(html.contains("&lt;50%"));
+    }
+
+    #[test]
+    fn picks_default_colours() {
+        let (fg, bg) = default_colours(&Styles::default());
+        assert_ne!(fg, bg);
+        assert_eq!(rgb(Color::Indexed(231)), Some((255, 255, 255)));
+        assert_eq!(rgb(Color::Indexed(232)), Some((8, 8, 8)));
+        assert_eq!(rgb(Color::Reset), None);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod column;
+pub mod column_picker;
 pub mod data_type;
 pub mod draw;
 pub mod props;
@@ -6,9 +7,10 @@ pub mod sortable;
 pub mod state;
 pub mod styling;
 
-use std::{convert::TryInto, marker::PhantomData};
+use std::{borrow::Cow, convert::TryInto, marker::PhantomData, num::NonZeroU16};
 
 pub use column::*;
+pub use column_picker::*;
 pub use data_type::*;
 pub use draw::*;
 pub use props::DataTableProps;
@@ -138,6 +140,15 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
         self.state.current_index = new_index;
     }
 
+    /// Scrolls to where `old` was scrolled to, such as when the table is rebuilt
+    /// after the config is reloaded.
+    pub fn keep_position_from(&mut self, old: &Self) {
+        self.state.display_start_index = old.state.display_start_index;
+        self.state.current_index = old.state.current_index;
+        self.state.scroll_direction = old.state.scroll_direction;
+        self.first_draw = old.first_draw;
+    }
+
     /// Returns the current scroll index.
     pub fn current_index(&self) -> usize {
         self.state.current_index
@@ -148,10 +159,189 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
         self.data.get(self.state.current_index)
     }
 
+    /// Returns the full text of the selected row's cell in the column at
+    /// `index`, for copying.
+    pub fn current_cell_text(&self, index: usize) -> Option<Cow<'static, str>> {
+        let column = self.columns.get(index)?;
+        self.current_item()?.to_copy_text(column.inner())
+    }
+
+    /// Returns the full text of the selected row's shown cells, separated by
+    /// tabs, for copying.
+    pub fn current_row_text(&self) -> Option<String> {
+        let item = self.current_item()?;
+        let cells = self
+            .columns
+            .iter()
+            .filter(|column| !column.is_hidden())
+            .filter_map(|column| item.to_copy_text(column.inner()))
+            .collect::<Vec<_>>();
+
+        Some(cells.join("\t"))
+    }
+
+    /// Returns the headers of the shown columns and the full text of every row
+    /// in the order shown, for exporting.
+    pub fn export_rows(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        self.rows_text(|item, column| item.to_copy_text(column))
+    }
+
+    /// Returns the headers of the shown columns and every row in the order
+    /// shown, as it would be drawn if the columns were as wide as needed.
+    pub fn shown_rows(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        self.rows_text(|item, column| item.to_cell_text(column, NonZeroU16::MAX))
+    }
+
+    fn rows_text(
+        &self, cell_text: impl Fn(&DataType, &H) -> Option<Cow<'static, str>>,
+    ) -> (Vec<String>, Vec<Vec<String>>) {
+        let columns = self
+            .columns
+            .iter()
+            .filter(|column| !column.is_hidden())
+            .collect::<Vec<_>>();
+
+        let headers = columns
+            .iter()
+            .map(|column| column.inner().text().into_owned())
+            .collect();
+        let rows = self
+            .data
+            .iter()
+            .map(|item| {
+                columns
+                    .iter()
+                    .map(|column| {
+                        cell_text(item, column.inner())
+                            .map(Cow::into_owned)
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        (headers, rows)
+    }
+
+    /// Returns the rows that were on screen as of the last draw.
+    pub fn visible_data(&self) -> &[DataType] {
+        let start = self.state.display_start_index.min(self.data.len());
+        let end = (start + self.state.num_shown_rows).min(self.data.len());
+
+        &self.data[start..end]
+    }
+
     /// Returns ratatui's internal selection.
     pub fn ratatui_selected(&self) -> Option<usize> {
         self.state.table_state.selected()
     }
+
+    /// Returns the index of the `shown_index`-th column that isn't hidden.
+    pub fn shown_column_index(&self, shown_index: usize) -> Option<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.is_hidden())
+            .nth(shown_index)
+            .map(|(index, _)| index)
+    }
+
+    /// The opposite of [`DataTable::shown_column_index`], returning where the
+    /// column at `index` is among the columns that aren't hidden.
+    pub fn shown_index(&self, index: usize) -> Option<usize> {
+        if self.columns.get(index)?.is_hidden() {
+            return None;
+        }
+
+        Some(
+            self.columns[..index]
+                .iter()
+                .filter(|column| !column.is_hidden())
+                .count(),
+        )
+    }
+
+    /// Returns the starting x position and width of the column at `index` when
+    /// it was last drawn, if it was drawn.
+    fn column_span(&self, index: usize) -> Option<(u16, u16)> {
+        let shown_index = self.shown_index(index)?;
+
+        let mut start = self.state.inner_rect.x;
+        for (current, width) in self.state.calculated_widths.iter().enumerate() {
+            if current == shown_index {
+                return Some((start, width.get()));
+            }
+            start += width.get() + 1; // +1 for the gap b/w cols.
+        }
+
+        None
+    }
+
+    /// Returns the width set for the column at `index`, or otherwise its width
+    /// when it was last drawn, if it was drawn.
+    pub fn column_width(&self, index: usize) -> Option<u16> {
+        match self.columns.get(index)?.bounds() {
+            ColumnWidthBounds::Fixed(width) => Some(width),
+            _ => self.column_span(index).map(|(_, width)| width),
+        }
+    }
+
+    /// Sets the width of the column at `index`, which is kept until it is
+    /// changed again. A column can't be narrower than its header.
+    pub fn set_column_width(&mut self, index: usize, width: u16) {
+        if let Some(column) = self.columns.get_mut(index) {
+            let width = width.max(column.header_len() as u16).max(1);
+            *column.bounds_mut() = ColumnWidthBounds::Fixed(width);
+        }
+    }
+
+    /// Widens or narrows the column at `index` by `delta`.
+    pub fn resize_column(&mut self, index: usize, delta: i16) {
+        if let Some(current) = self.column_width(index) {
+            self.set_column_width(index, current.saturating_add_signed(delta));
+        }
+    }
+
+    /// Starts resizing a column if `x` and `y` are on the header, in the gap
+    /// right after the column. Returns whether a column is now being resized.
+    pub fn try_start_column_resize(&mut self, x: u16, y: u16) -> bool {
+        if self.state.inner_rect.height <= 1 || self.state.inner_rect.y != y {
+            return false;
+        }
+
+        let mut end = self.state.inner_rect.x;
+        for (shown_index, width) in self.state.calculated_widths.iter().enumerate() {
+            end += width.get();
+            if x == end {
+                self.state.resizing_column = self.shown_column_index(shown_index);
+                return self.state.resizing_column.is_some();
+            }
+            end += 1;
+        }
+
+        false
+    }
+
+    /// Resizes the column being dragged so that it ends at `x`. Returns whether
+    /// a column was resized.
+    pub fn drag_column_resize(&mut self, x: u16) -> bool {
+        let Some(index) = self.state.resizing_column else {
+            return false;
+        };
+
+        match self.column_span(index) {
+            Some((start, _)) => {
+                self.set_column_width(index, x.saturating_sub(start));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stops resizing a column by dragging.
+    pub fn stop_column_resize(&mut self) {
+        self.state.resizing_column = None;
+    }
 }
 
 #[cfg(test)]
@@ -294,4 +484,31 @@ mod test {
         assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 2 }));
     }
+
+    #[test]
+    fn test_column_resize() {
+        let mut table = create_test_table();
+        table.state.inner_rect = tui::layout::Rect::new(2, 5, 40, 10);
+        table.state.calculated_widths = vec![NonZeroU16::new(10).unwrap(); 2];
+
+        assert_eq!(table.column_width(1), Some(10));
+
+        // Only the gap after a column on the header row starts a resize.
+        assert!(!table.try_start_column_resize(12, 6));
+        assert!(!table.try_start_column_resize(11, 5));
+        assert!(table.try_start_column_resize(12, 5));
+
+        assert!(table.drag_column_resize(20));
+        assert_eq!(table.column_width(0), Some(18));
+
+        // Columns can't be narrower than their header.
+        assert!(table.drag_column_resize(0));
+        assert_eq!(table.column_width(0), Some(1));
+
+        table.stop_column_resize();
+        assert!(!table.drag_column_resize(20));
+
+        table.resize_column(1, -3);
+        assert_eq!(table.column_width(1), Some(7));
+    }
 }
@@ -25,6 +25,11 @@ pub enum ColumnWidthBounds {
 
     /// A width of this type always resizes to the column header's text width.
     FollowHeader,
+
+    /// A width of this type was set by the user, and is always exactly as long
+    /// as specified. Unlike [`ColumnWidthBounds::Hard`], it is not given any
+    /// leftover space.
+    Fixed(u16),
 }
 
 pub trait ColumnHeader {
@@ -166,6 +171,7 @@ where
 
         let mut total_width_left = total_width;
         let mut calculated_widths = vec![];
+        let mut is_fixed = vec![];
         let columns = if left_to_right {
             Either::Left(self.iter())
         } else {
@@ -210,7 +216,7 @@ where
                         }
                     }
                 }
-                ColumnWidthBounds::Hard(width) => {
+                ColumnWidthBounds::Hard(width) | ColumnWidthBounds::Fixed(width) => {
                     let min_width = *width;
                     if stop_allocating_space(min_width, total_width_left) {
                         break;
@@ -241,19 +247,27 @@ where
                     }
                 }
             }
+
+            // Only reached if a width was pushed, as the loop otherwise stops.
+            is_fixed.push(matches!(column.bounds(), ColumnWidthBounds::Fixed(_)));
         }
 
         if !calculated_widths.is_empty() {
             if !left_to_right {
                 calculated_widths.reverse();
+                is_fixed.reverse();
             }
 
-            // Redistribute remaining space.
-            let mut num_dist = calculated_widths.len() as u16;
-            let amount_per_slot = total_width_left / num_dist; // Safe from DBZ by above empty check.
-            total_width_left %= num_dist;
+            // Redistribute remaining space to any columns that don't have a fixed width.
+            let mut num_dist = is_fixed.iter().filter(|fixed| !**fixed).count() as u16;
+            let amount_per_slot = total_width_left.checked_div(num_dist).unwrap_or(0);
+            total_width_left = total_width_left.checked_rem(num_dist).unwrap_or(0);
 
-            for width in calculated_widths.iter_mut() {
+            for (width, _) in calculated_widths
+                .iter_mut()
+                .zip(&is_fixed)
+                .filter(|(_, fixed)| !**fixed)
+            {
                 if num_dist == 0 {
                     break;
                 }
@@ -0,0 +1,248 @@
+//! Picking which columns a table shows, in what order, and how wide they are.
+
+use std::borrow::Cow;
+
+/// A column as listed in the column picker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PickerColumn {
+    pub name: Cow<'static, str>,
+    pub is_shown: bool,
+
+    /// The width set for the column, or the width it was last drawn with.
+    pub width: Option<u16>,
+}
+
+/// A table whose columns can be changed while running.
+///
+/// Implement this, and [`ColumnPicker`] is provided for it.
+pub trait PickableColumns {
+    type Column: Copy + PartialEq;
+
+    /// Every column the table can show.
+    fn available_columns(&self) -> Vec<Self::Column>;
+
+    /// The columns currently shown, in order.
+    fn shown_columns(&self) -> Vec<Self::Column>;
+
+    /// Replaces the shown columns.
+    fn set_shown_columns(&mut self, columns: Vec<Self::Column>);
+
+    fn column_name(&self, column: Self::Column) -> Cow<'static, str>;
+
+    /// The width set for `column`, or the width it was last drawn with.
+    fn column_width(&self, column: Self::Column) -> Option<u16>;
+
+    /// Widens or narrows `column` by `delta`.
+    fn resize_column(&mut self, column: Self::Column, delta: i16);
+
+    /// Sorts by `column` if it is shown, or flips the sort order if the table
+    /// is already sorted by it.
+    fn sort_by_column(&mut self, column: Self::Column);
+
+    /// Every column, with the shown ones first and in order.
+    fn picker_order(&self) -> Vec<(Self::Column, bool)> {
+        let shown = self.shown_columns();
+        let hidden = self
+            .available_columns()
+            .into_iter()
+            .filter(|column| !shown.contains(column))
+            .map(|column| (column, false))
+            .collect::<Vec<_>>();
+
+        shown
+            .into_iter()
+            .map(|column| (column, true))
+            .chain(hidden)
+            .collect()
+    }
+
+    /// Returns where `column` is in [`PickableColumns::picker_order`], or
+    /// `fallback` if it isn't there.
+    fn picker_position(&self, column: Self::Column, fallback: usize) -> usize {
+        self.picker_order()
+            .iter()
+            .position(|(other, _)| *other == column)
+            .unwrap_or(fallback)
+    }
+}
+
+/// The column picker's view of a table, where columns are referred to by their
+/// position in the picker. Shown columns are listed first in order, followed by
+/// the hidden ones.
+pub trait ColumnPicker {
+    fn picker_columns(&self) -> Vec<PickerColumn>;
+
+    /// Shows or hides the column at `index`, returning its new position. The
+    /// last shown column can't be hidden.
+    fn toggle_picked_column(&mut self, index: usize) -> usize;
+
+    /// Moves the shown column at `index` one place earlier or later, returning
+    /// its new position.
+    fn move_picked_column(&mut self, index: usize, earlier: bool) -> usize;
+
+    /// Widens or narrows the shown column at `index` by `delta`.
+    fn resize_picked_column(&mut self, index: usize, delta: i16);
+
+    /// Sorts by the shown column at `index`.
+    fn sort_by_picked_column(&mut self, index: usize);
+}
+
+impl<T: PickableColumns> ColumnPicker for T {
+    fn picker_columns(&self) -> Vec<PickerColumn> {
+        self.picker_order()
+            .into_iter()
+            .map(|(column, is_shown)| PickerColumn {
+                name: self.column_name(column),
+                is_shown,
+                width: if is_shown {
+                    self.column_width(column)
+                } else {
+                    None
+                },
+            })
+            .collect()
+    }
+
+    fn toggle_picked_column(&mut self, index: usize) -> usize {
+        let Some(&(column, is_shown)) = self.picker_order().get(index) else {
+            return index;
+        };
+
+        let mut shown = self.shown_columns();
+        if is_shown {
+            if shown.len() <= 1 {
+                return index;
+            }
+            shown.retain(|other| *other != column);
+        } else {
+            shown.push(column);
+        }
+
+        self.set_shown_columns(shown);
+        self.picker_position(column, index)
+    }
+
+    fn move_picked_column(&mut self, index: usize, earlier: bool) -> usize {
+        let mut shown = self.shown_columns();
+        let other = if earlier {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        };
+
+        match other {
+            Some(other) if index < shown.len() && other < shown.len() => {
+                shown.swap(index, other);
+                self.set_shown_columns(shown);
+                other
+            }
+            _ => index,
+        }
+    }
+
+    fn resize_picked_column(&mut self, index: usize, delta: i16) {
+        if let Some(&column) = self.shown_columns().get(index) {
+            self.resize_column(column, delta);
+        }
+    }
+
+    fn sort_by_picked_column(&mut self, index: usize) {
+        if let Some(&column) = self.shown_columns().get(index) {
+            self.sort_by_column(column);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct TestTable {
+        shown: Vec<u8>,
+    }
+
+    impl PickableColumns for TestTable {
+        type Column = u8;
+
+        fn available_columns(&self) -> Vec<u8> {
+            vec![0, 1, 2, 3]
+        }
+
+        fn shown_columns(&self) -> Vec<u8> {
+            self.shown.clone()
+        }
+
+        fn set_shown_columns(&mut self, columns: Vec<u8>) {
+            self.shown = columns;
+        }
+
+        fn column_name(&self, column: u8) -> Cow<'static, str> {
+            column.to_string().into()
+        }
+
+        fn column_width(&self, _column: u8) -> Option<u16> {
+            Some(5)
+        }
+
+        fn resize_column(&mut self, _column: u8, _delta: i16) {}
+
+        fn sort_by_column(&mut self, _column: u8) {}
+    }
+
+    fn names(table: &TestTable) -> Vec<(String, bool)> {
+        table
+            .picker_columns()
+            .into_iter()
+            .map(|column| (column.name.into_owned(), column.is_shown))
+            .collect()
+    }
+
+    #[test]
+    fn lists_shown_columns_first() {
+        let table = TestTable { shown: vec![2, 0] };
+
+        assert_eq!(
+            names(&table),
+            vec![
+                ("2".to_string(), true),
+                ("0".to_string(), true),
+                ("1".to_string(), false),
+                ("3".to_string(), false),
+            ]
+        );
+        assert_eq!(table.picker_columns()[2].width, None);
+    }
+
+    #[test]
+    fn toggles_columns() {
+        let mut table = TestTable { shown: vec![2, 0] };
+
+        assert_eq!(table.toggle_picked_column(3), 2);
+        assert_eq!(table.shown, vec![2, 0, 3]);
+
+        assert_eq!(table.toggle_picked_column(0), 3);
+        assert_eq!(table.shown, vec![0, 3]);
+
+        table.toggle_picked_column(0);
+        assert_eq!(table.shown, vec![3]);
+
+        // The last column stays.
+        assert_eq!(table.toggle_picked_column(0), 0);
+        assert_eq!(table.shown, vec![3]);
+    }
+
+    #[test]
+    fn moves_columns() {
+        let mut table = TestTable {
+            shown: vec![0, 1, 2],
+        };
+
+        assert_eq!(table.move_picked_column(0, false), 1);
+        assert_eq!(table.shown, vec![1, 0, 2]);
+
+        assert_eq!(table.move_picked_column(0, true), 0);
+        assert_eq!(table.move_picked_column(2, false), 2);
+        assert_eq!(table.move_picked_column(3, true), 3);
+        assert_eq!(table.shown, vec![1, 0, 2]);
+    }
+}
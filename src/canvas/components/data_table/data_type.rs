@@ -13,6 +13,12 @@ where
     /// the cell that will be displayed in the [`super::DataTable`].
     fn to_cell_text(&self, column: &H, calculated_width: NonZeroU16) -> Option<Cow<'static, str>>;
 
+    /// Given a column, return the full text of the cell for copying. By default
+    /// this is the displayed text as if the column were as wide as needed.
+    fn to_copy_text(&self, column: &H) -> Option<Cow<'static, str>> {
+        self.to_cell_text(column, NonZeroU16::MAX)
+    }
+
     /// Given a column, how to style a cell if one needs to override the default styling.
     ///
     /// By default this just returns [`None`], deferring to the row or table styling.
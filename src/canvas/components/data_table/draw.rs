@@ -203,6 +203,7 @@ where
                 let rows = {
                     let num_rows =
                         usize::from(inner_height.saturating_sub(table_gap + header_height));
+                    self.state.num_shown_rows = num_rows;
                     self.state
                         .get_start_position(num_rows, draw_info.force_redraw);
                     let start = self.state.display_start_index;
@@ -215,6 +216,7 @@ where
                         let row = Row::new(
                             columns
                                 .iter()
+                                .filter(|column| !column.is_hidden())
                                 .zip(&self.state.calculated_widths)
                                 .filter_map(|(column, &width)| {
                                     data_row.to_cell_text(column.inner(), width).map(|content| {
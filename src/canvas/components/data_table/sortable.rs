@@ -66,6 +66,7 @@ pub trait SortType: private::Sealed {
         Row::new(
             columns
                 .iter()
+                .filter(|c| !c.is_hidden())
                 .zip(widths)
                 .map(|(c, &width)| truncate_to_text(&c.header(), width.get())),
         )
@@ -95,9 +96,10 @@ impl SortType for Sortable {
         Row::new(
             columns
                 .iter()
-                .zip(widths)
                 .enumerate()
-                .map(|(index, (c, &width))| {
+                .filter(|(_, c)| !c.is_hidden())
+                .zip(widths)
+                .map(|((index, c), &width)| {
                     if index == self.sort_index {
                         let arrow = match self.order {
                             SortOrder::Ascending => UP_ARROW,
@@ -290,7 +292,10 @@ where
     /// returned. If nothing happens, [`None`] is returned.
     pub fn try_select_location(&mut self, x: u16, y: u16) -> Option<usize> {
         if self.state.inner_rect.height > 1 && self.state.inner_rect.y == y {
-            if let Some(index) = self.get_range(x) {
+            if let Some(index) = self
+                .get_range(x)
+                .and_then(|index| self.shown_column_index(index))
+            {
                 self.set_sort_index(index);
                 Some(self.sort_type.sort_index)
             } else {
@@ -322,6 +327,80 @@ where
         self.sort_type.sort_index
     }
 
+    /// Sorts by `column` if it is in the table, or toggles the sort order if it
+    /// is already sorted by.
+    pub fn select_column(&mut self, column: &H)
+    where
+        H: PartialEq,
+    {
+        if let Some(index) = self.columns.iter().position(|c| c.inner == *column) {
+            self.set_sort_index(index);
+        }
+    }
+
+    /// Keeps the scroll position, sorting, and any widths set by the user of
+    /// `old`, such as when the table is rebuilt after the config is reloaded.
+    /// The sorting is only kept if `old` is sorted by a shown column.
+    pub fn keep_state_from(&mut self, old: &Self)
+    where
+        H: PartialEq,
+    {
+        for column in &mut self.columns {
+            if let Some(old) = old.columns.iter().find(|old| old.inner == column.inner) {
+                if let ColumnWidthBounds::Fixed(_) = old.bounds {
+                    column.bounds = old.bounds;
+                }
+            }
+        }
+
+        if let Some(index) = old
+            .columns
+            .get(old.sort_type.sort_index)
+            .and_then(|sorted| {
+                self.columns
+                    .iter()
+                    .position(|c| c.inner == sorted.inner && !c.is_hidden)
+            })
+        {
+            self.sort_type.sort_index = index;
+            self.sort_type.order = old.sort_type.order;
+        }
+
+        self.keep_position_from(old);
+    }
+
+    /// Replaces the columns, such as after picking which ones to show. The sort
+    /// column and any widths set by the user are kept for columns that are still
+    /// in the table, and otherwise the first column is sorted by.
+    pub fn set_columns(&mut self, mut columns: Vec<SortColumn<H>>)
+    where
+        H: PartialEq,
+    {
+        for column in &mut columns {
+            if let Some(old) = self.columns.iter().find(|old| old.inner == column.inner) {
+                if let ColumnWidthBounds::Fixed(_) = old.bounds {
+                    column.bounds = old.bounds;
+                }
+            }
+        }
+
+        let sort_index = self
+            .columns
+            .get(self.sort_type.sort_index)
+            .and_then(|sorted| columns.iter().position(|c| c.inner == sorted.inner));
+
+        match sort_index {
+            Some(index) => self.sort_type.sort_index = index,
+            None => {
+                self.sort_type.sort_index = 0;
+                self.sort_type.order = columns.first().map(|c| c.default_order).unwrap_or_default();
+            }
+        }
+
+        self.columns = columns;
+        self.state.resizing_column = None;
+    }
+
     /// Given a `needle` coordinate, select the corresponding index and value.
     fn get_range(&self, needle: u16) -> Option<usize> {
         let mut start = self.state.inner_rect.x;
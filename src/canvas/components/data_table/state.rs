@@ -31,6 +31,12 @@ pub struct DataTableState {
 
     /// The current inner [`Rect`].
     pub inner_rect: Rect,
+
+    /// The index of the column being resized by dragging its header, if any.
+    pub resizing_column: Option<usize>,
+
+    /// How many rows fit in the table as of the last draw.
+    pub num_shown_rows: usize,
 }
 
 impl Default for DataTableState {
@@ -42,6 +48,8 @@ impl Default for DataTableState {
             calculated_widths: vec![],
             table_state: TableState::default(),
             inner_rect: Rect::default(),
+            resizing_column: None,
+            num_shown_rows: 0,
         }
     }
 }
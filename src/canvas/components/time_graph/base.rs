@@ -24,6 +24,7 @@ pub(crate) struct GraphData<'a> {
     values: Option<&'a Values>,
     style: Style,
     name: Option<Cow<'a, str>>,
+    label: Option<Cow<'a, str>>,
 }
 
 impl<'a> GraphData<'a> {
@@ -46,6 +47,13 @@ impl<'a> GraphData<'a> {
         self.name = Some(name);
         self
     }
+
+    /// Sets a short name for the series, shown with its value while a
+    /// [`TimeCursor`] is on the graph.
+    pub fn label(mut self, label: Cow<'a, str>) -> Self {
+        self.label = Some(label);
+        self
+    }
 }
 
 pub struct TimeGraph<'a> {
@@ -94,6 +102,9 @@ pub struct TimeGraph<'a> {
 
     /// The chart scaling.
     pub scaling: ChartScaling,
+
+    /// A cursor to draw on the graph, if any.
+    pub cursor: Option<TimeCursor<'a>>,
 }
 
 impl TimeGraph<'_> {
@@ -142,7 +153,9 @@ impl TimeGraph<'_> {
     ///   graph.
     /// - Expects `graph_data`, which represents *what* data to draw, and
     ///   various details like style and optional legends.
-    pub fn draw(&self, f: &mut Frame<'_>, draw_loc: Rect, graph_data: Vec<GraphData<'_>>) {
+    ///
+    /// Returns the area the data was drawn in.
+    pub fn draw(&self, f: &mut Frame<'_>, draw_loc: Rect, graph_data: Vec<GraphData<'_>>) -> Rect {
         // TODO: (points_rework_v1) can we reduce allocations in the underlying graph by saving some sort of state?
 
         let x_axis = self.generate_x_axis();
@@ -161,21 +174,24 @@ impl TimeGraph<'_> {
             b
         };
 
-        f.render_widget(
-            TimeChart::new(data)
-                .block(block)
-                .x_axis(x_axis)
-                .y_axis(y_axis)
-                .marker(self.marker)
-                .legend_style(self.graph_style)
-                .legend_position(self.legend_position)
-                .hidden_legend_constraints(
-                    self.legend_constraints
-                        .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS),
-                )
-                .scaling(self.scaling),
-            draw_loc,
-        )
+        let chart = TimeChart::new(data)
+            .block(block)
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .marker(self.marker)
+            .legend_style(self.graph_style)
+            .legend_position(self.legend_position)
+            .hidden_legend_constraints(
+                self.legend_constraints
+                    .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS),
+            )
+            .scaling(self.scaling)
+            .cursor(self.cursor);
+        let graph_area = chart.graph_area(draw_loc);
+
+        f.render_widget(chart, draw_loc);
+
+        graph_area
     }
 }
 
@@ -186,6 +202,7 @@ fn create_dataset(data: GraphData<'_>) -> Dataset<'_> {
         values,
         style,
         name,
+        label,
     } = data;
 
     let Some(values) = values else {
@@ -197,6 +214,12 @@ fn create_dataset(data: GraphData<'_>) -> Dataset<'_> {
         .data(time, values)
         .graph_type(GraphType::Line);
 
+    let dataset = if let Some(label) = label {
+        dataset.label(label)
+    } else {
+        dataset
+    };
+
     if let Some(name) = name {
         dataset.name(name)
     } else {
@@ -241,6 +264,7 @@ mod test {
             legend_constraints: None,
             marker: Marker::Braille,
             scaling: ChartScaling::Linear,
+            cursor: None,
         }
     }
 
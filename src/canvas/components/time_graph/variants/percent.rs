@@ -7,7 +7,7 @@ use tui::{layout::Constraint, symbols::Marker};
 use crate::{
     app::AppConfigFields,
     canvas::components::time_graph::{
-        AxisBound, ChartScaling, LegendPosition, TimeGraph, variants::get_border_style,
+        AxisBound, ChartScaling, LegendPosition, TimeCursor, TimeGraph, variants::get_border_style,
     },
     options::config::style::Styles,
 };
@@ -54,6 +54,9 @@ pub(crate) struct PercentTimeGraph<'a> {
 
     /// The constraints for the legend.
     pub(crate) legend_constraints: Option<(Constraint, Constraint)>,
+
+    /// The cursor to draw, if any.
+    pub(crate) cursor: Option<TimeCursor<'a>>,
 }
 
 impl<'a> PercentTimeGraph<'a> {
@@ -91,6 +94,7 @@ impl<'a> PercentTimeGraph<'a> {
             legend_constraints: self.legend_constraints,
             marker,
             scaling: ChartScaling::Linear,
+            cursor: self.cursor,
         }
     }
 }
@@ -5,12 +5,15 @@
 //! the specializations are factored out to `time_graph/points.rs`.
 
 mod canvas;
+mod cursor;
 mod grid;
 mod points;
 
-use std::{cmp::max, str::FromStr, time::Instant};
+use std::{borrow::Cow, cmp::max, str::FromStr, time::Instant};
 
 use canvas::*;
+pub use cursor::TimeCursor;
+pub(crate) use cursor::time_label;
 use tui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Flex, Layout, Rect},
@@ -150,6 +153,32 @@ pub enum LegendPosition {
 }
 
 impl LegendPosition {
+    /// Every position, in the order they are suggested.
+    pub const ALL: [LegendPosition; 8] = [
+        Self::TopLeft,
+        Self::Top,
+        Self::TopRight,
+        Self::Left,
+        Self::Right,
+        Self::BottomLeft,
+        Self::Bottom,
+        Self::BottomRight,
+    ];
+
+    /// The name of the position, as used in the config file.
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            Self::Top => "top",
+            Self::TopLeft => "top-left",
+            Self::TopRight => "top-right",
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::BottomLeft => "bottom-left",
+            Self::Bottom => "bottom",
+            Self::BottomRight => "bottom-right",
+        }
+    }
+
     fn layout(
         &self, area: Rect, legend_width: u16, legend_height: u16, x_title_width: u16,
         y_title_width: u16,
@@ -280,6 +309,8 @@ enum Data<'a> {
 pub struct Dataset<'a> {
     /// Name of the dataset (used in the legend if shown)
     name: Option<Line<'a>>,
+    /// A short name for the dataset, used in the legend while a cursor is shown
+    label: Option<Cow<'a, str>>,
     /// A reference to data.
     data: Data<'a>,
     /// Symbol used for each points of this dataset
@@ -301,6 +332,14 @@ impl<'a> Dataset<'a> {
         self
     }
 
+    /// Sets a short name for the dataset, used instead of its name in the
+    /// legend while a [`TimeCursor`] is shown.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn label(mut self, label: Cow<'a, str>) -> Dataset<'a> {
+        self.label = Some(label);
+        self
+    }
+
     /// Sets the data points of this dataset
     ///
     /// Points will then either be rendered as scattered points or with lines
@@ -433,6 +472,8 @@ pub(super) struct TimeChart<'a> {
     marker: Marker,
     /// Whether to scale the values differently.
     scaling: ChartScaling,
+    /// A cursor to draw, if any.
+    cursor: Option<TimeCursor<'a>>,
 }
 
 impl<'a> TimeChart<'a> {
@@ -449,6 +490,7 @@ impl<'a> TimeChart<'a> {
             legend_position: Some(LegendPosition::default()),
             marker: Marker::Braille,
             scaling: ChartScaling::default(),
+            cursor: None,
         }
     }
 
@@ -547,9 +589,24 @@ impl<'a> TimeChart<'a> {
         self
     }
 
+    /// Set a cursor to draw.
+    #[must_use = "method moves the value of self and returns the modified value"]
+    pub fn cursor(mut self, cursor: Option<TimeCursor<'a>>) -> TimeChart<'a> {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Returns the area the data is drawn in, within `area`.
+    pub fn graph_area(&self, area: Rect) -> Rect {
+        self.layout(self.block.inner_if_some(area), None).graph_area
+    }
+
     /// Compute the internal layout of the chart given the area. If the area is
-    /// too small some elements may be automatically hidden
-    fn layout(&self, area: Rect) -> ChartLayout {
+    /// too small some elements may be automatically hidden.
+    ///
+    /// If `cursor_legend` is set, the legend is sized to fit it instead of the
+    /// dataset names, and is always shown, cut down to fit if needed.
+    fn layout(&self, area: Rect, cursor_legend: Option<&[Line<'_>]>) -> ChartLayout {
         let mut layout = ChartLayout::default();
         if area.height == 0 || area.width == 0 {
             return layout;
@@ -593,21 +650,37 @@ impl<'a> TimeChart<'a> {
             }
         }
 
-        if let Some(legend_position) = self.legend_position {
-            let legends = self
-                .datasets
-                .iter()
-                .filter_map(|d| Some(d.name.as_ref()?.width() as u16));
+        let legend_position = match cursor_legend {
+            Some(_) => Some(self.legend_position.unwrap_or_default()),
+            None => self.legend_position,
+        };
+
+        if let Some(legend_position) = legend_position {
+            let legends = match cursor_legend {
+                Some(lines) => lines.iter().map(|line| line.width() as u16).collect(),
+                None => self
+                    .datasets
+                    .iter()
+                    .filter_map(|d| Some(d.name.as_ref()?.width() as u16))
+                    .collect::<Vec<_>>(),
+            };
 
-            if let Some(inner_width) = legends.clone().max() {
-                let legend_width = inner_width + 2;
-                let legend_height = legends.count() as u16 + 2;
+            if let Some(&inner_width) = legends.iter().max() {
+                let mut legend_width = inner_width + 2;
+                let mut legend_height = legends.len() as u16 + 2;
+                let constraints = if cursor_legend.is_some() {
+                    legend_width = legend_width.min(layout.graph_area.width);
+                    legend_height = legend_height.min(layout.graph_area.height);
+                    (Constraint::Ratio(1, 1), Constraint::Ratio(1, 1))
+                } else {
+                    self.hidden_legend_constraints
+                };
 
-                let [max_legend_width] = Layout::horizontal([self.hidden_legend_constraints.0])
+                let [max_legend_width] = Layout::horizontal([constraints.0])
                     .flex(Flex::Start)
                     .areas(layout.graph_area);
 
-                let [max_legend_height] = Layout::vertical([self.hidden_legend_constraints.1])
+                let [max_legend_height] = Layout::vertical([constraints.1])
                     .flex(Flex::Start)
                     .areas(layout.graph_area);
 
@@ -792,7 +865,11 @@ impl Widget for TimeChart<'_> {
             return;
         };
 
-        let layout = self.layout(chart_area);
+        let cursor_legend = self.cursor_legend();
+        let layout = self.layout(
+            chart_area,
+            cursor_legend.as_ref().map(|(_, lines)| lines.as_slice()),
+        );
         let graph_area = layout.graph_area;
         if graph_area.width < 1 || graph_area.height < 1 {
             return;
@@ -841,6 +918,10 @@ impl Widget for TimeChart<'_> {
             })
             .render(graph_area, buf);
 
+        if let Some((x, _)) = &cursor_legend {
+            self.render_cursor_line(*x, graph_area, buf);
+        }
+
         if let Some((x, y)) = layout.title_x {
             if let Some(title) = self.x_axis.title.as_ref() {
                 let width = graph_area
@@ -891,18 +972,25 @@ impl Widget for TimeChart<'_> {
             }
             block.render(legend_area, buf);
 
-            for (i, (dataset_name, dataset_style)) in self
-                .datasets
-                .iter()
-                .filter_map(|ds| Some((ds.name.as_ref()?, ds.style())))
+            let lines = match cursor_legend {
+                Some((_, lines)) => lines,
+                None => self
+                    .datasets
+                    .iter()
+                    .filter_map(|ds| Some(ds.name.as_ref()?.clone().patch_style(ds.style())))
+                    .collect(),
+            };
+
+            for (i, line) in lines
+                .into_iter()
+                .take(legend_area.height.saturating_sub(2).into())
                 .enumerate()
             {
-                let name = dataset_name.clone().patch_style(dataset_style);
-                name.render(
+                line.render(
                     Rect {
                         x: legend_area.x + 1,
                         y: legend_area.y + 1 + i as u16,
-                        width: legend_area.width - 2,
+                        width: legend_area.width.saturating_sub(2),
                         height: 1,
                     },
                     buf,
@@ -1049,7 +1137,7 @@ mod tests {
                 .x_axis(Axis::default().title("X axis"))
                 .y_axis(Axis::default().title("Y axis"))
                 .hidden_legend_constraints(case.hidden_legend_constraints);
-            let layout = chart.layout(case.chart_area);
+            let layout = chart.layout(case.chart_area, None);
             assert_eq!(layout.legend_area, case.legend_area);
         }
     }
@@ -1126,7 +1214,7 @@ mod tests {
         let data_unnamed = Dataset::default(); // must not occupy a row in legend
         let widget = TimeChart::new(vec![data_named_1, data_unnamed, data_named_2]);
         let buffer = Buffer::empty(Rect::new(0, 0, 50, 25));
-        let layout = widget.layout(buffer.area);
+        let layout = widget.layout(buffer.area, None);
 
         assert!(layout.legend_area.is_some());
         assert_eq!(layout.legend_area.unwrap().height, 4); // 2 for borders, 2
@@ -1138,7 +1226,7 @@ mod tests {
         let dataset = Dataset::default();
         let widget = TimeChart::new(vec![dataset; 3]);
         let buffer = Buffer::empty(Rect::new(0, 0, 50, 25));
-        let layout = widget.layout(buffer.area);
+        let layout = widget.layout(buffer.area, None);
 
         assert!(layout.legend_area.is_none());
     }
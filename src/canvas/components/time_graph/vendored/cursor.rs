@@ -0,0 +1,200 @@
+use std::{fmt, time::Instant};
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    symbols,
+    text::{Line, Span},
+};
+
+use super::{Data, TimeChart};
+use crate::utils::general::utc_time_of_day;
+
+/// A vertical line drawn over a [`TimeChart`] at one point in time. While it is
+/// shown, the legend lists the value of each dataset at that point instead of
+/// the dataset names.
+#[derive(Clone, Copy)]
+pub struct TimeCursor<'a> {
+    /// How far back from the latest data point the cursor is, in milliseconds.
+    pub offset: u64,
+
+    /// Formats a value for the legend.
+    pub format: &'a dyn Fn(f64) -> String,
+}
+
+impl fmt::Debug for TimeCursor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeCursor")
+            .field("offset", &self.offset)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TimeCursor<'_> {
+    /// A cursor for data in a percentage format.
+    pub fn percent(offset: u64) -> TimeCursor<'static> {
+        fn format_percent(value: f64) -> String {
+            format!("{value:.1}%")
+        }
+
+        TimeCursor {
+            offset,
+            format: &format_percent,
+        }
+    }
+}
+
+/// Returns the index of the time in `times` closest to `offset` milliseconds
+/// before the latest one.
+pub(crate) fn nearest_time_index(times: &[Instant], offset: u64) -> Option<usize> {
+    let latest = times.last()?;
+
+    times
+        .iter()
+        .enumerate()
+        .rev()
+        .min_by_key(|(_, time)| (latest.duration_since(**time).as_millis() as u64).abs_diff(offset))
+        .map(|(index, _)| index)
+}
+
+/// Describes how long before the latest data point `offset` milliseconds is,
+/// and the time of day it was in UTC.
+pub(crate) fn time_label(offset: u64, time: Instant) -> String {
+    let relative = if offset == 0 {
+        "now".to_string()
+    } else {
+        format!("-{:.1}s", offset as f64 / 1000.0)
+    };
+
+    format!("{relative} ({} UTC)", utc_time_of_day(time))
+}
+
+impl<'a> TimeChart<'a> {
+    /// Returns where the cursor is on the x-axis, and the legend lines to show
+    /// for it, if a cursor is set and there is data under it.
+    pub(super) fn cursor_legend(&self) -> Option<(f64, Vec<Line<'a>>)> {
+        let cursor = self.cursor?;
+        let times = self
+            .datasets
+            .iter()
+            .find_map(|dataset| match dataset.data {
+                Data::Some { times, .. } if !times.is_empty() => Some(times),
+                _ => None,
+            })?;
+
+        let left_edge = self.x_axis.bounds.get_bounds()[0];
+        let offset = cursor.offset.min(-left_edge as u64);
+        let latest = times.last()?;
+        let time = times[nearest_time_index(times, offset)?];
+        let offset = latest.duration_since(time).as_millis() as u64;
+
+        let mut lines = vec![Line::styled(time_label(offset, time), self.legend_style)];
+        lines.extend(self.datasets.iter().filter_map(|dataset| {
+            let Data::Some { times, values } = dataset.data else {
+                return None;
+            };
+            let label = match (&dataset.label, &dataset.name) {
+                (Some(label), _) => label.to_string(),
+                (None, Some(name)) => name.to_string(),
+                (None, None) => return None,
+            };
+            let value = values
+                .iter_along_base(times)
+                .rev()
+                .find(|(t, _)| **t == time)
+                .map_or_else(|| "N/A".to_string(), |(_, value)| (cursor.format)(*value));
+
+            Some(Line::from(Span::styled(
+                format!("{label}: {value}"),
+                dataset.style,
+            )))
+        }));
+
+        Some((-(offset as f64), lines))
+    }
+
+    /// Draws the cursor's line at `x` over the empty cells of `graph_area`.
+    pub(super) fn render_cursor_line(&self, x: f64, graph_area: Rect, buf: &mut Buffer) {
+        let [left, right] = self.x_axis.bounds.get_bounds();
+        if right <= left {
+            return;
+        }
+
+        let ratio = ((x - left) / (right - left)).clamp(0.0, 1.0);
+        let column = graph_area.left()
+            + (ratio * f64::from(graph_area.width.saturating_sub(1))).round() as u16;
+
+        for y in graph_area.top()..graph_area.bottom() {
+            if let Some(cell) = buf.cell_mut((column, y)) {
+                if cell.symbol() == " " {
+                    cell.set_symbol(symbols::line::VERTICAL)
+                        .set_style(self.x_axis.style);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn finds_nearest_time() {
+        let start = Instant::now();
+        let times = [0, 1000, 2000, 3000].map(|ms| start + Duration::from_millis(ms));
+
+        assert_eq!(nearest_time_index(&times, 0), Some(3));
+        assert_eq!(nearest_time_index(&times, 400), Some(3));
+        assert_eq!(nearest_time_index(&times, 600), Some(2));
+        assert_eq!(nearest_time_index(&times, 10_000), Some(0));
+        assert_eq!(nearest_time_index(&[], 0), None);
+    }
+
+    #[test]
+    fn renders_cursor_legend() {
+        use tui::widgets::Widget;
+
+        use super::super::{Axis, AxisBound, Dataset};
+        use crate::app::data::Values;
+
+        let start = Instant::now();
+        let times = [0, 1000, 2000].map(|ms| start + Duration::from_millis(ms));
+        let mut values = Values::default();
+        for value in [10.0, 20.0, 30.0] {
+            values.push(value);
+        }
+
+        let chart = TimeChart::new(vec![
+            Dataset::default()
+                .name("RAM: 30%")
+                .label("RAM".into())
+                .data(&times, &values),
+        ])
+        .x_axis(Axis::default().bounds(AxisBound::Min(-2000.0)))
+        .y_axis(Axis::default().bounds(AxisBound::Max(100.0)))
+        .cursor(Some(TimeCursor::percent(1100)));
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 30, 6));
+        chart.render(buffer.area, &mut buffer);
+
+        let text = buffer
+            .content()
+            .chunks(30)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>();
+        assert!(text[1].contains("-1.0s ("));
+        assert!(text[2].contains("RAM: 20.0%"));
+        assert_eq!(text[5].chars().nth(15), Some('│'));
+    }
+
+    #[test]
+    fn labels_time() {
+        let now = Instant::now();
+
+        assert!(time_label(0, now).starts_with("now ("));
+        assert!(time_label(40_000, now).starts_with("-40.0s ("));
+        assert!(time_label(1_500, now).ends_with(" UTC)"));
+    }
+}
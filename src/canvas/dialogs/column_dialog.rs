@@ -0,0 +1,92 @@
+use tui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::App,
+    canvas::{Painter, components::data_table::PickerColumn, drawing_utils::dialog_block},
+};
+
+const HELP_TEXT: &str = "Space: show/hide  J/K: move  ←/→: width";
+
+/// How wide the column picker dialog is, including borders.
+pub const COLUMN_DIALOG_WIDTH: u16 = 46;
+
+/// How many lines the column picker dialog needs for `count` columns,
+/// including borders.
+pub fn column_dialog_height(count: usize) -> u16 {
+    count as u16 + 4
+}
+
+/// Returns the text of a row in the column picker.
+fn column_row(column: &PickerColumn) -> String {
+    let check = if column.is_shown { "[x]" } else { "[ ]" };
+    let width = column
+        .width
+        .map(|width| width.to_string())
+        .unwrap_or_default();
+
+    format!("{check} {:24}{width:>5}", column.name)
+}
+
+impl Painter {
+    pub fn draw_column_dialog(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let dialog_state = &app_state.column_dialog_state;
+        let columns = app_state
+            .column_picker(dialog_state.widget_id)
+            .map(|picker| picker.picker_columns())
+            .unwrap_or_default();
+
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.border_style)
+            .title_top(Line::styled(" Columns ", self.styles.widget_title_style))
+            .title_top(
+                Line::styled(" Esc to close ", self.styles.widget_title_style).right_aligned(),
+            );
+
+        let mut lines = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let style = if index == dialog_state.selected {
+                    self.styles.selected_text_style
+                } else {
+                    self.styles.text_style
+                };
+
+                Line::from(Span::styled(column_row(column), style))
+            })
+            .collect::<Vec<_>>();
+
+        lines.push(Line::default());
+        lines.push(Line::styled(HELP_TEXT, self.styles.table_header_style));
+
+        f.render_widget(Paragraph::new(lines).block(block), draw_loc);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn formats_rows() {
+        let shown = PickerColumn {
+            name: "CPU%".into(),
+            is_shown: true,
+            width: Some(8),
+        };
+        let hidden = PickerColumn {
+            name: "Nice".into(),
+            is_shown: false,
+            width: None,
+        };
+
+        assert_eq!(column_row(&shown), "[x] CPU%                        8");
+        assert_eq!(column_row(&hidden).trim_end(), "[ ] Nice");
+        assert_eq!(column_dialog_height(2), 6);
+    }
+}
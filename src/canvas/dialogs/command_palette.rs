@@ -0,0 +1,126 @@
+use tui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+};
+
+use crate::{
+    app::App,
+    canvas::{
+        Painter,
+        drawing_utils::{build_query_span, dialog_block},
+    },
+};
+
+/// The most suggestions shown at once.
+const MAX_COMPLETIONS: usize = 6;
+
+/// Returns the range of suggestions to show so that `selected` is visible.
+fn visible_completions(count: usize, selected: Option<usize>) -> std::ops::Range<usize> {
+    let start = selected
+        .map(|selected| (selected + 1).saturating_sub(MAX_COMPLETIONS))
+        .unwrap_or(0);
+
+    start..count.min(start + MAX_COMPLETIONS)
+}
+
+impl Painter {
+    /// Draws the command prompt along the bottom of `draw_loc`, with
+    /// suggestions and any error above the input.
+    pub fn draw_command_palette(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect) {
+        let is_force_redraw = app_state.is_force_redraw;
+        let state = &mut app_state.command_state;
+
+        let visible = visible_completions(state.completions.len(), state.selected_completion);
+        let text_width = state.completions[visible.clone()]
+            .iter()
+            .map(|completion| completion.text.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut lines = state.completions[visible.clone()]
+            .iter()
+            .zip(visible)
+            .map(|(completion, index)| {
+                let style = if state.selected_completion == Some(index) {
+                    self.styles.selected_text_style
+                } else {
+                    self.styles.text_style
+                };
+
+                Line::from(vec![
+                    Span::styled(format!("{:text_width$}", completion.text), style),
+                    Span::styled(
+                        format!("  {}", completion.hint),
+                        self.styles.table_header_style,
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(error) = &state.error {
+            lines.push(Line::styled(
+                error.as_str(),
+                self.styles.invalid_query_style,
+            ));
+        } else if let Some(message) = &state.message {
+            lines.push(Line::styled(message.as_str(), self.styles.text_style));
+        }
+
+        // Leave room for the borders and the ": " before the input.
+        let available_width = usize::from(draw_loc.width.saturating_sub(4));
+        state
+            .input
+            .get_start_position(available_width, is_force_redraw);
+
+        let mut input = vec![Span::styled(": ", self.styles.widget_title_style)];
+        input.extend(build_query_span(
+            &state.input,
+            available_width,
+            true,
+            self.styles.selected_text_style,
+            self.styles.text_style,
+        ));
+        lines.push(Line::from(input));
+
+        let height = (lines.len() as u16 + 2).min(draw_loc.height);
+        let [_, palette] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(height)]).areas(draw_loc);
+
+        // Keep the input visible if there isn't room for everything.
+        let skipped = lines
+            .len()
+            .saturating_sub(usize::from(height.saturating_sub(2)));
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.highlighted_border_style)
+            .title_top(Line::styled(" Command ", self.styles.widget_title_style))
+            .title_top(
+                Line::styled(
+                    " Tab to complete, Esc to close ",
+                    self.styles.widget_title_style,
+                )
+                .right_aligned(),
+            );
+
+        f.render_widget(Clear, palette);
+        f.render_widget(
+            Paragraph::new(lines.into_iter().skip(skipped).collect::<Vec<_>>()).block(block),
+            palette,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scrolls_to_selected_completion() {
+        assert_eq!(visible_completions(3, None), 0..3);
+        assert_eq!(visible_completions(10, None), 0..6);
+        assert_eq!(visible_completions(10, Some(5)), 0..6);
+        assert_eq!(visible_completions(10, Some(6)), 1..7);
+        assert_eq!(visible_completions(10, Some(9)), 4..10);
+    }
+}
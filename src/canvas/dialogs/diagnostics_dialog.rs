@@ -0,0 +1,166 @@
+use std::time::{Duration, Instant};
+
+use tui::{
+    Frame,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    app::App,
+    canvas::{Painter, drawing_utils::dialog_block},
+    collection::diagnostics::CollectionDiagnostics,
+    utils::data_units::get_binary_bytes,
+};
+
+const SOURCE_WIDTH: usize = 16;
+const COLUMN_WIDTH: usize = 10;
+
+/// How many lines the diagnostics dialog needs, including borders.
+pub fn diagnostics_dialog_height(diagnostics: &CollectionDiagnostics) -> u16 {
+    diagnostics.iter().count() as u16 + 6
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+fn format_ago(now: Instant, then: Instant) -> String {
+    format!(
+        "{} ago",
+        format_duration(now.saturating_duration_since(then))
+    )
+}
+
+/// Returns the summary line about bottom itself.
+fn summary_text(diagnostics: &CollectionDiagnostics, draw_duration: Duration) -> String {
+    let usage = match diagnostics.self_usage {
+        Some(usage) => {
+            let (rss, unit) = get_binary_bytes(usage.rss_bytes);
+            format!("CPU: {:.1}%  RSS: {rss:.1}{unit}", usage.cpu_percent)
+        }
+        None => "CPU: N/A  RSS: N/A".to_string(),
+    };
+
+    format!(
+        "bottom  {usage}  Draw: {}  Collection: {}",
+        format_duration(draw_duration),
+        format_duration(diagnostics.last_update_duration),
+    )
+}
+
+/// Returns the header and a row for each source, along with each row's last
+/// error if there is one.
+fn source_rows(diagnostics: &CollectionDiagnostics, now: Instant) -> Vec<(String, Option<String>)> {
+    let header = format!(
+        "{:SOURCE_WIDTH$}{:COLUMN_WIDTH$}{:COLUMN_WIDTH$}{:COLUMN_WIDTH$}{:COLUMN_WIDTH$}Last error",
+        "Source", "Last run", "Took", "Entries", "Errors",
+    );
+
+    std::iter::once((header, None))
+        .chain(diagnostics.iter().map(|(source, diagnostics)| {
+            let last_run = diagnostics
+                .last_collected
+                .map(|last| format_ago(now, last))
+                .unwrap_or_else(|| "never".to_string());
+
+            let row = format!(
+                "{:SOURCE_WIDTH$}{last_run:COLUMN_WIDTH$}{:COLUMN_WIDTH$}{:<COLUMN_WIDTH$}{:<COLUMN_WIDTH$}",
+                source.name(),
+                format_duration(diagnostics.last_duration),
+                diagnostics.entries,
+                diagnostics.error_count,
+            );
+
+            let last_error = diagnostics
+                .last_error
+                .as_ref()
+                .map(|(when, err)| format!("{err} ({})", format_ago(now, *when)));
+
+            (row, last_error)
+        }))
+        .collect()
+}
+
+impl Painter {
+    pub fn draw_diagnostics_dialog(&self, f: &mut Frame<'_>, app_state: &App, draw_loc: Rect) {
+        let diagnostics = &app_state.data_store.get_data().diagnostics;
+        let draw_duration = app_state.diagnostics_dialog_state.draw_duration;
+
+        let block = dialog_block(self.styles.border_type)
+            .border_style(self.styles.border_style)
+            .title_top(Line::styled(
+                " Diagnostics ",
+                self.styles.widget_title_style,
+            ))
+            .title_top(
+                Line::styled(" Esc to close ", self.styles.widget_title_style).right_aligned(),
+            );
+
+        let mut lines = vec![
+            Line::styled(
+                summary_text(diagnostics, draw_duration),
+                self.styles.text_style,
+            ),
+            Line::default(),
+        ];
+
+        for (index, (row, last_error)) in source_rows(diagnostics, Instant::now())
+            .into_iter()
+            .enumerate()
+        {
+            if index == 0 {
+                lines.push(Line::styled(row, self.styles.table_header_style));
+            } else {
+                let mut spans = vec![Span::styled(row, self.styles.text_style)];
+
+                if let Some(last_error) = last_error {
+                    spans.push(Span::styled(last_error, self.styles.invalid_query_style));
+                }
+
+                lines.push(Line::from(spans));
+            }
+        }
+
+        f.render_widget(Paragraph::new(lines).block(block), draw_loc);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::collection::diagnostics::Source;
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.5ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.5s");
+    }
+
+    #[test]
+    fn lists_collected_sources() {
+        let mut diagnostics = CollectionDiagnostics::default();
+        let started = Instant::now();
+        diagnostics.record_ok(Source::Cpu, started, 8);
+        diagnostics.record_error(Source::Disks, "permission denied");
+
+        let rows = source_rows(&diagnostics, Instant::now());
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].0.starts_with("Source"));
+        assert!(rows[1].0.starts_with("CPU"));
+        assert!(rows[1].1.is_none());
+        assert!(rows[2].0.starts_with("Disks"));
+        assert!(rows[2].0.contains("never"));
+        assert!(
+            rows[2]
+                .1
+                .as_ref()
+                .is_some_and(|err| err.starts_with("permission denied"))
+        );
+    }
+}
@@ -1,2 +1,5 @@
+pub mod column_dialog;
+pub mod command_palette;
+pub mod diagnostics_dialog;
 pub mod help_dialog;
 pub mod process_kill_dialog;
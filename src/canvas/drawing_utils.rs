@@ -2,8 +2,13 @@ use std::time::Instant;
 
 use tui::{
     layout::Rect,
+    style::Style,
+    text::Span,
     widgets::{Block, BorderType, Borders},
 };
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::app::AppSearchState;
 
 pub const SIDE_BORDERS: Borders = Borders::LEFT.union(Borders::RIGHT);
 pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
@@ -52,6 +57,51 @@ pub fn dialog_block(border_type: BorderType) -> Block<'static> {
         .borders(Borders::all())
 }
 
+/// Builds the spans of a text input's query, highlighting the cursor if
+/// `is_on_widget` is set.
+pub fn build_query_span(
+    search_state: &AppSearchState, available_width: usize, is_on_widget: bool,
+    currently_selected_text_style: Style, text_style: Style,
+) -> Vec<Span<'_>> {
+    let start_index = search_state.display_start_char_index;
+    let cursor_index = search_state.grapheme_cursor.cur_cursor();
+    let mut current_width = 0;
+    let query = search_state.current_search_query.as_str();
+
+    if is_on_widget {
+        let mut res = Vec::with_capacity(available_width);
+        for ((index, grapheme), lengths) in UnicodeSegmentation::grapheme_indices(query, true)
+            .zip(search_state.size_mappings.values())
+        {
+            if index < start_index {
+                continue;
+            } else if current_width > available_width {
+                break;
+            } else {
+                let styled = if index == cursor_index {
+                    Span::styled(grapheme, currently_selected_text_style)
+                } else {
+                    Span::styled(grapheme, text_style)
+                };
+
+                res.push(styled);
+                current_width += lengths.end - lengths.start;
+            }
+        }
+
+        if cursor_index == query.len() {
+            res.push(Span::styled(" ", currently_selected_text_style))
+        }
+
+        res
+    } else {
+        // This is easier - we just need to get a range of graphemes, rather than
+        // dealing with possibly inserting a cursor (as none is shown!)
+
+        vec![Span::styled(query.to_string(), text_style)]
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -4,6 +4,8 @@ use itertools::Itertools;
 use tui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
+    text::Span,
+    widgets::Sparkline,
 };
 
 use crate::{
@@ -152,6 +154,81 @@ impl Painter {
         }
     }
 
+    /// Draws the average CPU usage and a sparkline of its recent history, for
+    /// inline mode.
+    pub fn draw_inline_cpu(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if app_state.current_widget.widget_id == widget_id {
+            f.render_widget(
+                widget_block(true, true, self.styles.border_type)
+                    .border_style(self.styles.highlighted_border_style),
+                draw_loc,
+            );
+        }
+
+        let data = app_state.data_store.get_data();
+        let cpu_data = &data.cpu_harvest;
+        let timeseries = &data.timeseries_data;
+
+        // Use the average if it is collected, otherwise average all the cores.
+        let avg_index = cpu_data
+            .iter()
+            .position(|datum| matches!(datum.data_type, CpuDataType::Avg));
+        let usage = match avg_index {
+            Some(index) => Some(cpu_data[index].usage),
+            None if !cpu_data.is_empty() => {
+                Some(cpu_data.iter().map(|datum| datum.usage).sum::<f32>() / cpu_data.len() as f32)
+            }
+            None => None,
+        };
+
+        let [label_loc, sparkline_loc] =
+            Layout::horizontal([Constraint::Length(9), Constraint::Min(0)])
+                .horizontal_margin(1)
+                .areas(draw_loc);
+
+        let label = match usage {
+            Some(usage) => format!("CPU {:>3.0}%", usage.round()),
+            None => "CPU   N/A".to_string(),
+        };
+        f.render_widget(
+            Span::styled(label, self.styles.avg_cpu_colour),
+            label_loc.rows().next_back().unwrap_or(label_loc),
+        );
+
+        // One bar per sample, with the newest on the right.
+        let num_times = timeseries.time.len();
+        let start = num_times.saturating_sub(usize::from(sparkline_loc.width));
+        let mut sums = vec![(0.0, 0); num_times - start];
+        for (index, values) in timeseries.cpu.iter().enumerate() {
+            if avg_index.is_some_and(|avg_index| avg_index != index) {
+                continue;
+            }
+
+            for (time_index, value) in values.iter_with_index() {
+                if let Some((sum, count)) = time_index
+                    .checked_sub(start)
+                    .and_then(|offset| sums.get_mut(offset))
+                {
+                    *sum += value;
+                    *count += 1;
+                }
+            }
+        }
+
+        let bars = sums
+            .into_iter()
+            .map(|(sum, count)| (count > 0).then(|| (sum / f64::from(count)).round() as u64));
+        f.render_widget(
+            Sparkline::default()
+                .data(bars)
+                .max(100)
+                .style(self.styles.avg_cpu_colour),
+            sparkline_loc,
+        );
+    }
+
     #[inline]
     fn cpu_info(&self, data: &CpuData) -> (String, String, f32, tui::style::Style) {
         let (outer, style) = match data.data_type {
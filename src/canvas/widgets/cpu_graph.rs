@@ -9,17 +9,26 @@ use crate::{
         Painter,
         components::{
             data_table::{DrawInfo, SelectionState},
-            time_graph::{GraphData, PercentTimeGraph},
+            time_graph::{GraphData, PercentTimeGraph, TimeCursor},
         },
         drawing_utils::should_hide_x_label,
     },
-    collection::cpu::CpuData,
+    collection::cpu::{CpuData, CpuDataType},
     widgets::CpuWidgetState,
 };
 
 const AVG_POSITION: usize = 1;
 const ALL_POSITION: usize = 0;
 
+/// Returns a short name for the CPU entry at `index`.
+fn cpu_label(data: &StoredData, index: usize) -> String {
+    match data.cpu_harvest.get(index).map(|cpu| cpu.data_type) {
+        Some(CpuDataType::Avg) => "AVG".to_string(),
+        Some(CpuDataType::Cpu(cpu)) => format!("CPU{cpu}"),
+        None => format!("CPU{index}"),
+    }
+}
+
 impl Painter {
     pub fn draw_cpu(&self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64) {
         let legend_width = (draw_loc.width as f64 * 0.15) as u16;
@@ -122,8 +131,9 @@ impl Painter {
         let show_avg_offset = if show_avg_cpu { AVG_POSITION } else { 0 };
         let current_scroll_position = cpu_widget_state.table.state.current_index;
         let cpu_entries = &data.cpu_harvest;
-        let cpu_points = &data.timeseries_data.cpu;
-        let time = &data.timeseries_data.time;
+        let timeseries = data.timeseries(cpu_widget_state.current_display_time).avg;
+        let cpu_points = &timeseries.cpu;
+        let time = &timeseries.time;
 
         if current_scroll_position == ALL_POSITION {
             // This case ensures the other cases cannot have the position be equal to 0.
@@ -139,7 +149,11 @@ impl Painter {
                             [(itx - show_avg_offset) % self.styles.cpu_colour_styles.len()]
                     };
 
-                    GraphData::default().style(style).time(time).values(values)
+                    GraphData::default()
+                        .style(style)
+                        .time(time)
+                        .values(values)
+                        .label(cpu_label(data, itx).into())
                 })
                 .rev()
                 .collect()
@@ -159,7 +173,8 @@ impl Painter {
                 GraphData::default()
                     .style(style)
                     .time(time)
-                    .values(&cpu_points[current_scroll_position - 1]),
+                    .values(&cpu_points[current_scroll_position - 1])
+                    .label(cpu_label(data, current_scroll_position - 1).into()),
             ]
         } else {
             vec![]
@@ -204,7 +219,7 @@ impl Painter {
                 }
             };
 
-            PercentTimeGraph {
+            let plot_area = PercentTimeGraph {
                 display_range: cpu_widget_state.current_display_time,
                 hide_x_labels,
                 app_config_fields: &app_state.app_config_fields,
@@ -215,9 +230,17 @@ impl Painter {
                 widget_id,
                 legend_position: None,
                 legend_constraints: None,
+                cursor: app_state
+                    .graph_cursor
+                    .offset(widget_id)
+                    .map(TimeCursor::percent),
             }
             .build()
             .draw(f, draw_loc, graph_data);
+            app_state
+                .graph_cursor
+                .plot_areas
+                .insert(widget_id, plot_area);
         }
     }
 
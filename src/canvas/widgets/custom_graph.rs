@@ -0,0 +1,221 @@
+use std::{borrow::Cow, time::Duration};
+
+use tui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::Style,
+    symbols::Marker,
+};
+
+use crate::{
+    app::App,
+    canvas::{
+        Painter,
+        components::time_graph::{AxisBound, ChartScaling, GraphData, TimeCursor, TimeGraph},
+        drawing_utils::should_hide_x_label,
+    },
+};
+
+/// Formats a custom metric value, with an optional unit.
+fn format_custom_value(value: f64, unit: Option<&str>) -> String {
+    let value = if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{value:.0}")
+    } else if value.abs() >= 100.0 {
+        format!("{value:.1}")
+    } else {
+        format!("{value:.2}")
+    };
+
+    match unit {
+        Some(unit) => format!("{value} {unit}"),
+        None => value,
+    }
+}
+
+/// Returns a "nice" upper bound for the y-axis given the largest visible value,
+/// along with the labels to use.
+fn adjust_custom_data_point(max_entry: f64, unit: Option<&str>) -> (f64, Vec<Cow<'static, str>>) {
+    // Leave some headroom above the highest point, and round up to 1, 2, or 5
+    // times a power of ten.
+    let headroom = max_entry * 1.1;
+    let max = if headroom > 0.0 {
+        let magnitude = 10_f64.powf(headroom.log10().floor());
+        let normalized = headroom / magnitude;
+        let step = if normalized <= 1.0 {
+            1.0
+        } else if normalized <= 2.0 {
+            2.0
+        } else if normalized <= 5.0 {
+            5.0
+        } else {
+            10.0
+        };
+
+        step * magnitude
+    } else {
+        1.0
+    };
+
+    (
+        max,
+        vec![
+            format_custom_value(0.0, unit).into(),
+            format_custom_value(max / 2.0, unit).into(),
+            format_custom_value(max, unit).into(),
+        ],
+    )
+}
+
+impl Painter {
+    pub fn draw_custom_graph(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if let Some(custom_state) = app_state
+            .states
+            .custom_state
+            .widget_states
+            .get_mut(&widget_id)
+        {
+            let data = app_state.data_store.get_data();
+            let resolution = data.timeseries(custom_state.current_display_time);
+            let times = &resolution.avg.time;
+            let time_start = -(custom_state.current_display_time as f64);
+            let series = resolution.avg.custom.get(&custom_state.metric);
+            let max_series = resolution.max.custom.get(&custom_state.metric);
+            let unit = custom_state.unit.as_deref();
+
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let hide_x_labels = should_hide_x_label(
+                app_state.app_config_fields.hide_time,
+                app_state.app_config_fields.autohide_time,
+                &mut custom_state.autohide_timer,
+                draw_loc,
+            );
+
+            let y_max = match (max_series, times.last()) {
+                (Some(series), Some(last_time)) => {
+                    let visible_duration = Duration::from_millis(custom_state.current_display_time);
+                    let left_bound = last_time.checked_sub(visible_duration);
+
+                    series
+                        .iter()
+                        .flat_map(|values| {
+                            values
+                                .iter_along_base(times)
+                                .rev()
+                                .take_while(|&(time, _)| left_bound.is_none_or(|lb| *time >= lb))
+                                .map(|(_, &v)| v)
+                        })
+                        .fold(0.0, f64::max)
+                }
+                _ => 0.0,
+            };
+            let (y_max, y_labels) = adjust_custom_data_point(y_max, unit);
+
+            let current_values = data
+                .custom_harvest
+                .iter()
+                .find(|metric| metric.name == custom_state.metric)
+                .map(|metric| metric.values.as_slice())
+                .unwrap_or_default();
+
+            let colours = &self.styles.cpu_colour_styles;
+            let graph_data = series
+                .into_iter()
+                .flatten()
+                .zip(custom_state.labels.iter())
+                .enumerate()
+                .map(|(index, (values, label))| {
+                    let style = if colours.is_empty() {
+                        Style::default()
+                    } else {
+                        colours[index % colours.len()]
+                    };
+                    let current = match current_values.get(index).copied().flatten() {
+                        Some(value) => format_custom_value(value, unit),
+                        None => "N/A".to_string(),
+                    };
+
+                    GraphData::default()
+                        .name(format!("{label}: {current}").into())
+                        .label(label.as_str().into())
+                        .time(times)
+                        .values(values)
+                        .style(style)
+                })
+                .collect::<Vec<_>>();
+
+            let marker = if app_state.app_config_fields.use_dot {
+                Marker::Dot
+            } else {
+                Marker::Braille
+            };
+
+            let format_value = |value: f64| format_custom_value(value, unit);
+            let cursor = app_state
+                .graph_cursor
+                .offset(widget_id)
+                .map(|offset| TimeCursor {
+                    offset,
+                    format: &format_value,
+                });
+
+            let plot_area = TimeGraph {
+                x_min: time_start,
+                hide_x_labels,
+                y_bounds: AxisBound::Max(y_max),
+                y_labels: &y_labels,
+                graph_style: self.styles.graph_style,
+                border_style,
+                border_type: self.styles.border_type,
+                title: format!(" {} ", custom_state.metric).into(),
+                is_selected: app_state.current_widget.widget_id == widget_id,
+                is_expanded: app_state.is_expanded,
+                title_style: self.styles.widget_title_style,
+                legend_position: custom_state.legend.shown_position(),
+                legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                marker,
+                scaling: ChartScaling::Linear,
+                cursor,
+            }
+            .draw(f, draw_loc, graph_data);
+            app_state
+                .graph_cursor
+                .plot_areas
+                .insert(widget_id, plot_area);
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn custom_axis_bounds() {
+        assert_eq!(adjust_custom_data_point(0.0, None).0, 1.0);
+        assert_eq!(adjust_custom_data_point(0.5, None).0, 1.0);
+        assert_eq!(adjust_custom_data_point(42.0, None).0, 50.0);
+        assert_eq!(adjust_custom_data_point(150.0, None).0, 200.0);
+        assert_eq!(
+            adjust_custom_data_point(1200.0, Some("jobs")).1[2],
+            "2000 jobs"
+        );
+    }
+
+    #[test]
+    fn custom_value_format() {
+        assert_eq!(format_custom_value(3.0, None), "3");
+        assert_eq!(format_custom_value(1.23456, Some("V")), "1.23 V");
+        assert_eq!(format_custom_value(1234.56, None), "1234.6");
+    }
+}
@@ -18,7 +18,7 @@ use crate::{
         Painter,
         components::{
             data_table::{DrawInfo, SelectionState},
-            time_graph::{GraphData, PercentTimeGraph},
+            time_graph::{GraphData, PercentTimeGraph, TimeCursor},
         },
         drawing_utils::should_hide_x_label,
     },
@@ -129,19 +129,24 @@ impl Painter {
     ) -> Vec<GraphData<'a>> {
         let current_scroll_position = gpu_widget_state.table.state.current_index;
         let gpu_data = &data.gpu_data_harvest;
-        let gpu_timeseries = &data.timeseries_data.gpu_data;
-        let time = &data.timeseries_data.time;
+        let timeseries = data.timeseries(gpu_widget_state.current_display_time).avg;
+        let gpu_timeseries = &timeseries.gpu_data;
+        let time = &timeseries.time;
 
         if current_scroll_position == ALL_POSITION {
             // Show all GPUs. Collect into Vec first to allow reversing.
             let mut points: Vec<GraphData<'a>> = gpu_timeseries
                 .iter()
                 .enumerate()
-                .map(|(itx, (_name, values))| {
+                .map(|(itx, (name, values))| {
                     let style =
                         self.styles.cpu_colour_styles[itx % self.styles.cpu_colour_styles.len()];
 
-                    GraphData::default().style(style).time(time).values(values)
+                    GraphData::default()
+                        .style(style)
+                        .time(time)
+                        .values(values)
+                        .label(name.into())
                 })
                 .collect();
             points.reverse();
@@ -152,7 +157,13 @@ impl Painter {
                 let style = self.styles.cpu_colour_styles
                     [(current_scroll_position - 1) % self.styles.cpu_colour_styles.len()];
 
-                vec![GraphData::default().style(style).time(time).values(values)]
+                vec![
+                    GraphData::default()
+                        .style(style)
+                        .time(time)
+                        .values(values)
+                        .label(gpu.name.as_str().into()),
+                ]
             } else {
                 vec![]
             }
@@ -189,7 +200,7 @@ impl Painter {
                 " GPU Usage ".into()
             };
 
-            PercentTimeGraph {
+            let plot_area = PercentTimeGraph {
                 display_range: gpu_widget_state.current_display_time,
                 hide_x_labels,
                 app_config_fields: &app_state.app_config_fields,
@@ -200,9 +211,17 @@ impl Painter {
                 widget_id,
                 legend_position: None,
                 legend_constraints: None,
+                cursor: app_state
+                    .graph_cursor
+                    .offset(widget_id)
+                    .map(TimeCursor::percent),
             }
             .build()
             .draw(f, draw_loc, graph_data);
+            app_state
+                .graph_cursor
+                .plot_areas
+                .insert(widget_id, plot_area);
         }
     }
 
@@ -10,7 +10,7 @@ use crate::{
     app::{App, data::Values},
     canvas::{
         Painter,
-        components::time_graph::{GraphData, PercentTimeGraph},
+        components::time_graph::{GraphData, PercentTimeGraph, TimeCursor},
         drawing_utils::should_hide_x_label,
     },
     collection::memory::MemData,
@@ -45,6 +45,7 @@ fn graph_data<'a>(
         out.push(
             GraphData::default()
                 .name(label)
+                .label(name.to_string().into())
                 .time(time)
                 .values(values)
                 .style(style),
@@ -85,7 +86,7 @@ impl Painter {
                 }
 
                 let mut points = Vec::with_capacity(size);
-                let timeseries = &data.timeseries_data;
+                let timeseries = data.timeseries(mem_state.current_display_time).avg;
                 let time = &timeseries.time;
 
                 // TODO: Add a "no data" option here/to time graph if there is no entries
@@ -137,7 +138,7 @@ impl Painter {
                     let gpu_styles = &self.styles.gpu_colours;
 
                     for (name, harvest) in &data.gpu_harvest {
-                        if let Some(gpu_data) = data.timeseries_data.gpu_mem.get(name) {
+                        if let Some(gpu_data) = timeseries.gpu_mem.get(name) {
                             let style = {
                                 if gpu_styles.is_empty() {
                                     Style::default()
@@ -164,7 +165,7 @@ impl Painter {
                 points
             };
 
-            PercentTimeGraph {
+            let plot_area = PercentTimeGraph {
                 display_range: mem_state.current_display_time,
                 hide_x_labels,
                 app_config_fields: &app_state.app_config_fields,
@@ -173,11 +174,19 @@ impl Painter {
                 title: " Memory ".into(),
                 styles: &self.styles,
                 widget_id,
-                legend_position: app_state.app_config_fields.memory_legend_position,
+                legend_position: mem_state.legend.shown_position(),
                 legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                cursor: app_state
+                    .graph_cursor
+                    .offset(widget_id)
+                    .map(TimeCursor::percent),
             }
             .build()
             .draw(f, draw_loc, graph_data);
+            app_state
+                .graph_cursor
+                .plot_areas
+                .insert(widget_id, plot_area);
         }
 
         if app_state.should_get_widget_bounds() {
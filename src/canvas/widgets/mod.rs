@@ -1,5 +1,7 @@
+pub mod connection_table;
 pub mod cpu_basic;
 pub mod cpu_graph;
+pub mod custom_graph;
 pub mod disk_table;
 #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
 pub mod gpu_graph;
@@ -7,6 +9,8 @@ pub mod mem_basic;
 pub mod mem_graph;
 pub mod network_basic;
 pub mod network_graph;
+pub mod power_graph;
+pub mod process_log;
 pub mod process_table;
 pub mod temperature_table;
 
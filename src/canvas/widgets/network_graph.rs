@@ -12,7 +12,7 @@ use crate::{
     app::{App, AppConfigFields, AxisScaling},
     canvas::{
         Painter,
-        components::time_graph::{AxisBound, ChartScaling, GraphData, TimeGraph},
+        components::time_graph::{AxisBound, ChartScaling, GraphData, TimeCursor, TimeGraph},
         drawing_utils::should_hide_x_label,
     },
     utils::{
@@ -64,9 +64,10 @@ impl Painter {
         {
             let shared_data = app_state.data_store.get_data();
             let network_latest_data = &(shared_data.network_harvest);
-            let rx_points = &(shared_data.timeseries_data.rx);
-            let tx_points = &(shared_data.timeseries_data.tx);
-            let times = &(shared_data.timeseries_data.time);
+            let resolution = shared_data.timeseries(network_widget_state.current_display_time);
+            let rx_points = &(resolution.avg.rx);
+            let tx_points = &(resolution.avg.tx);
+            let times = &(resolution.avg.time);
             let time_start = -(network_widget_state.current_display_time as f64);
 
             let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
@@ -110,7 +111,10 @@ impl Painter {
                             (0.0, visible_left_bound, visible_left_bound)
                         });
 
-                    for (&time, &v) in rx_points
+                    // Use the bucket maximums so that short spikes are not cut off.
+                    for (&time, &v) in resolution
+                        .max
+                        .rx
                         .iter_along_base(times)
                         .rev()
                         .take_while(|&(&time, _)| time >= oldest_to_check)
@@ -121,7 +125,9 @@ impl Painter {
                         }
                     }
 
-                    for (&time, &v) in tx_points
+                    for (&time, &v) in resolution
+                        .max
+                        .tx
                         .iter_along_base(times)
                         .rev()
                         .take_while(|&(&time, _)| time >= oldest_to_check)
@@ -178,11 +184,13 @@ impl Painter {
                 vec![
                     GraphData::default()
                         .name(rx_label.into())
+                        .label("RX".into())
                         .time(times)
                         .values(rx_points)
                         .style(self.styles.rx_style),
                     GraphData::default()
                         .name(tx_label.into())
+                        .label("TX".into())
                         .time(times)
                         .values(tx_points)
                         .style(self.styles.tx_style),
@@ -202,11 +210,13 @@ impl Painter {
                 vec![
                     GraphData::default()
                         .name(format!("RX: {rx_label:<10}  All: {total_rx_label}").into())
+                        .label("RX".into())
                         .time(times)
                         .values(rx_points)
                         .style(self.styles.rx_style),
                     GraphData::default()
                         .name(format!("TX: {tx_label:<10}  All: {total_tx_label}").into())
+                        .label("TX".into())
                         .time(times)
                         .values(tx_points)
                         .style(self.styles.tx_style),
@@ -231,7 +241,19 @@ impl Painter {
                 AxisScaling::Linear => ChartScaling::Linear,
             };
 
-            TimeGraph {
+            let format_rate = |value: f64| {
+                let (value, prefix) = get_unit_prefix(value as u64, use_binary_prefix);
+                format!("{value:.1}{prefix}{unit}")
+            };
+            let cursor = app_state
+                .graph_cursor
+                .offset(widget_id)
+                .map(|offset| TimeCursor {
+                    offset,
+                    format: &format_rate,
+                });
+
+            let plot_area = TimeGraph {
                 x_min: time_start,
                 hide_x_labels,
                 y_bounds,
@@ -243,12 +265,17 @@ impl Painter {
                 is_selected: app_state.current_widget.widget_id == widget_id,
                 is_expanded: app_state.is_expanded,
                 title_style: self.styles.widget_title_style,
-                legend_position: app_state.app_config_fields.network_legend_position,
+                legend_position: network_widget_state.legend.shown_position(),
                 legend_constraints: Some(legend_constraints),
                 marker,
                 scaling,
+                cursor,
             }
             .draw(f, draw_loc, graph_data);
+            app_state
+                .graph_cursor
+                .plot_areas
+                .insert(widget_id, plot_area);
         }
     }
 
@@ -0,0 +1,168 @@
+use std::{borrow::Cow, time::Duration};
+
+use tui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::Style,
+    symbols::Marker,
+};
+
+use crate::{
+    app::App,
+    canvas::{
+        Painter,
+        components::time_graph::{AxisBound, ChartScaling, GraphData, TimeCursor, TimeGraph},
+        drawing_utils::should_hide_x_label,
+    },
+};
+
+/// Returns a "nice" upper bound for the y-axis given the largest visible value in watts,
+/// along with the labels to use.
+fn adjust_power_data_point(max_entry: f64) -> (f64, Vec<Cow<'static, str>>) {
+    // Leave some headroom above the highest point, and round up to a multiple of 5W
+    // (or 1W for very small values).
+    let headroom = max_entry * 1.1;
+    let step = if headroom < 5.0 { 1.0 } else { 5.0 };
+    let max = ((headroom / step).ceil() * step).max(step);
+
+    (
+        max,
+        vec![
+            "0W".into(),
+            format!("{}W", max / 2.0).into(),
+            format!("{max}W").into(),
+        ],
+    )
+}
+
+impl Painter {
+    pub fn draw_power_graph(
+        &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if let Some(power_state) = app_state
+            .states
+            .power_state
+            .widget_states
+            .get_mut(&widget_id)
+        {
+            let data = app_state.data_store.get_data();
+            let resolution = data.timeseries(power_state.current_display_time);
+            let times = &resolution.avg.time;
+            let time_start = -(power_state.current_display_time as f64);
+
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let hide_x_labels = should_hide_x_label(
+                app_state.app_config_fields.hide_time,
+                app_state.app_config_fields.autohide_time,
+                &mut power_state.autohide_timer,
+                draw_loc,
+            );
+
+            let y_max = if let Some(last_time) = times.last() {
+                let visible_duration = Duration::from_millis(power_state.current_display_time);
+                let left_bound = last_time.checked_sub(visible_duration);
+
+                resolution
+                    .max
+                    .power
+                    .values()
+                    .flat_map(|values| {
+                        values
+                            .iter_along_base(times)
+                            .rev()
+                            .take_while(|&(time, _)| left_bound.is_none_or(|lb| *time >= lb))
+                            .map(|(_, &v)| v)
+                    })
+                    .fold(0.0, f64::max)
+            } else {
+                0.0
+            };
+            let (y_max, y_labels) = adjust_power_data_point(y_max);
+
+            let colours = &self.styles.cpu_colour_styles;
+            let graph_data = data
+                .power_harvest
+                .iter()
+                .enumerate()
+                .filter_map(|(index, entry)| {
+                    let values = resolution.avg.power.get(&entry.name)?;
+                    let style = if colours.is_empty() {
+                        Style::default()
+                    } else {
+                        colours[index % colours.len()]
+                    };
+
+                    Some(
+                        GraphData::default()
+                            .name(format!("{}: {:.1}W", entry.name, entry.watts).into())
+                            .label(entry.name.as_str().into())
+                            .time(times)
+                            .values(values)
+                            .style(style),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let marker = if app_state.app_config_fields.use_dot {
+                Marker::Dot
+            } else {
+                Marker::Braille
+            };
+
+            let format_watts = |value: f64| format!("{value:.1}W");
+            let cursor = app_state
+                .graph_cursor
+                .offset(widget_id)
+                .map(|offset| TimeCursor {
+                    offset,
+                    format: &format_watts,
+                });
+
+            let plot_area = TimeGraph {
+                x_min: time_start,
+                hide_x_labels,
+                y_bounds: AxisBound::Max(y_max),
+                y_labels: &y_labels,
+                graph_style: self.styles.graph_style,
+                border_style,
+                border_type: self.styles.border_type,
+                title: " Power ".into(),
+                is_selected: app_state.current_widget.widget_id == widget_id,
+                is_expanded: app_state.is_expanded,
+                title_style: self.styles.widget_title_style,
+                legend_position: power_state.legend.shown_position(),
+                legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
+                marker,
+                scaling: ChartScaling::Linear,
+                cursor,
+            }
+            .draw(f, draw_loc, graph_data);
+            app_state
+                .graph_cursor
+                .plot_areas
+                .insert(widget_id, plot_area);
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn power_axis_bounds() {
+        assert_eq!(adjust_power_data_point(0.0).0, 1.0);
+        assert_eq!(adjust_power_data_point(2.5).0, 3.0);
+        assert_eq!(adjust_power_data_point(42.0).0, 50.0);
+        assert_eq!(adjust_power_data_point(90.0).1[2], "100W");
+    }
+}
@@ -1,18 +1,16 @@
 use tui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::Style,
     text::{Line, Span},
     widgets::Paragraph,
 };
-use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    app::{App, AppSearchState},
+    app::App,
     canvas::{
         Painter,
         components::data_table::{DrawInfo, SelectionState},
-        drawing_utils::widget_block,
+        drawing_utils::{build_query_span, widget_block},
     },
 };
 
@@ -106,50 +104,6 @@ impl Painter {
     fn draw_search_field(
         &self, f: &mut Frame<'_>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
-        fn build_query_span(
-            search_state: &AppSearchState, available_width: usize, is_on_widget: bool,
-            currently_selected_text_style: Style, text_style: Style,
-        ) -> Vec<Span<'_>> {
-            let start_index = search_state.display_start_char_index;
-            let cursor_index = search_state.grapheme_cursor.cur_cursor();
-            let mut current_width = 0;
-            let query = search_state.current_search_query.as_str();
-
-            if is_on_widget {
-                let mut res = Vec::with_capacity(available_width);
-                for ((index, grapheme), lengths) in
-                    UnicodeSegmentation::grapheme_indices(query, true)
-                        .zip(search_state.size_mappings.values())
-                {
-                    if index < start_index {
-                        continue;
-                    } else if current_width > available_width {
-                        break;
-                    } else {
-                        let styled = if index == cursor_index {
-                            Span::styled(grapheme, currently_selected_text_style)
-                        } else {
-                            Span::styled(grapheme, text_style)
-                        };
-
-                        res.push(styled);
-                        current_width += lengths.end - lengths.start;
-                    }
-                }
-
-                if cursor_index == query.len() {
-                    res.push(Span::styled(" ", currently_selected_text_style))
-                }
-
-                res
-            } else {
-                // This is easier - we just need to get a range of graphemes, rather than
-                // dealing with possibly inserting a cursor (as none is shown!)
-
-                vec![Span::styled(query.to_string(), text_style)]
-            }
-        }
-
         let is_basic = app_state.app_config_fields.use_basic_mode;
 
         if let Some(proc_widget_state) = app_state
@@ -17,18 +17,25 @@ pub mod apple;
 
 #[cfg(target_os = "linux")]
 mod linux {
+    pub mod roots;
     pub mod utils;
 }
 
 #[cfg(feature = "battery")]
 pub mod batteries;
+pub mod connections;
 pub mod cpu;
+pub mod custom;
+pub mod diagnostics;
 pub mod disks;
 pub mod error;
 pub mod memory;
 pub mod network;
+pub mod power;
 pub mod processes;
+pub mod schedule;
 pub mod temperature;
+pub(crate) mod worker;
 
 use std::time::{Duration, Instant};
 
@@ -39,6 +46,12 @@ use processes::Pid;
 #[cfg(feature = "battery")]
 use starship_battery::{Battery, Manager};
 
+#[cfg(target_os = "linux")]
+pub use self::linux::roots::SystemRoots;
+use self::{
+    diagnostics::{CollectionDiagnostics, SelfCpuUsage, SelfUsage, Source},
+    schedule::{CollectionRates, CollectionSchedule},
+};
 use super::DataFilters;
 use crate::app::layout_manager::UsedWidgets;
 
@@ -54,7 +67,10 @@ pub struct Data {
     pub swap: Option<memory::MemData>,
     pub temperature_sensors: Option<Vec<temperature::TempSensorData>>,
     pub network: Option<network::NetworkHarvest>,
+    pub power: Option<Vec<power::PowerData>>,
+    pub custom: Option<Vec<custom::CustomMetricData>>,
     pub list_of_processes: Option<Vec<processes::ProcessHarvest>>,
+    pub connections: Option<Vec<connections::ConnectionHarvest>>,
     pub disks: Option<Vec<disks::DiskHarvest>>,
     pub io: Option<disks::IoHarvest>,
     #[cfg(feature = "battery")]
@@ -65,6 +81,7 @@ pub struct Data {
     pub gpu: Option<Vec<(String, memory::MemData)>>,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     pub gpu_data: Option<Vec<gpu::GpuData>>,
+    pub diagnostics: CollectionDiagnostics,
 }
 
 impl Default for Data {
@@ -79,9 +96,12 @@ impl Default for Data {
             swap: None,
             temperature_sensors: None,
             list_of_processes: None,
+            connections: None,
             disks: None,
             io: None,
             network: None,
+            power: None,
+            custom: None,
             #[cfg(feature = "battery")]
             list_of_batteries: None,
             #[cfg(feature = "zfs")]
@@ -90,6 +110,7 @@ impl Default for Data {
             gpu: None,
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
             gpu_data: None,
+            diagnostics: CollectionDiagnostics::default(),
         }
     }
 }
@@ -99,11 +120,13 @@ impl Data {
         self.io = None;
         self.temperature_sensors = None;
         self.list_of_processes = None;
+        self.connections = None;
         self.disks = None;
         self.memory = None;
         self.swap = None;
         self.cpu = None;
         self.load_avg = None;
+        self.power = None;
 
         if let Some(network) = &mut self.network {
             network.first_run_cleanup();
@@ -121,9 +144,9 @@ impl Data {
 
 /// A wrapper around the sysinfo data source. We use sysinfo for the following
 /// data:
-/// - CPU usage
-/// - Memory usage
-/// - Network usage
+/// - CPU usage (non-Linux)
+/// - Memory usage (non-Linux)
+/// - Network usage (non-Linux)
 /// - Processes (non-Linux)
 /// - Disk (anything outside of Linux, macOS, and FreeBSD)
 /// - Temperatures (non-Linux)
@@ -131,6 +154,7 @@ impl Data {
 pub struct SysinfoSource {
     /// Handles CPU, memory, and processes.
     pub(crate) system: sysinfo::System,
+    #[cfg(not(target_os = "linux"))]
     pub(crate) network: sysinfo::Networks,
     #[cfg(not(target_os = "linux"))]
     pub(crate) temps: sysinfo::Components,
@@ -146,6 +170,7 @@ impl Default for SysinfoSource {
 
         Self {
             system: System::new(),
+            #[cfg(not(target_os = "linux"))]
             network: Networks::new(),
             #[cfg(not(target_os = "linux"))]
             temps: Components::new(),
@@ -176,12 +201,36 @@ pub struct DataCollector {
     last_list_collection_time: Instant,
     should_run_less_routine_tasks: bool,
 
+    custom_metrics: Vec<custom::CustomMetricState>,
+    schedule: CollectionSchedule,
+    diagnostics: CollectionDiagnostics,
+    self_cpu_usage: SelfCpuUsage,
+
+    /// Sources that have timed out, such as dead network mounts.
+    stale_sources: worker::StaleSources,
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    disk_usage_workers: disks::UsageWorkers,
+    #[cfg(feature = "nvidia")]
+    nvml_worker: Option<NvmlWorker>,
+
     #[cfg(target_os = "linux")]
     prev_process_details: IntMap<Pid, processes::PrevProcDetails>,
     #[cfg(target_os = "linux")]
     prev_idle: f64,
     #[cfg(target_os = "linux")]
     prev_non_idle: f64,
+    #[cfg(target_os = "linux")]
+    rapl_state: power::linux::RaplState,
+    #[cfg(target_os = "linux")]
+    tcp_traffic: connections::sock_diag::TcpTraffic,
+    #[cfg(target_os = "linux")]
+    memory_details: processes::MemoryDetailsCache,
+    #[cfg(target_os = "linux")]
+    visible_pids: nohash::IntSet<Pid>,
+    #[cfg(target_os = "linux")]
+    cpu_state: cpu::CpuState,
+    #[cfg(target_os = "linux")]
+    roots: SystemRoots,
 
     #[cfg(feature = "battery")]
     battery_manager: Option<Manager>,
@@ -201,6 +250,21 @@ pub struct DataCollector {
 
 const LESS_ROUTINE_TASK_TIME: Duration = Duration::from_secs(60);
 
+/// The most processes whose detailed memory is all read. With more than
+/// this, only the shown processes are read, unless it is sorted or searched by.
+#[cfg(target_os = "linux")]
+const MAX_ALL_PROCESS_MEMORY_DETAILS: usize = 250;
+
+/// How long to wait for NVML before treating it as unresponsive.
+#[cfg(feature = "nvidia")]
+const NVML_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The worker that reads NVIDIA GPU data through NVML, given the temperature
+/// filter and the widgets in use.
+#[cfg(feature = "nvidia")]
+type NvmlWorker =
+    worker::Worker<(Option<crate::app::filter::Filter>, UsedWidgets), Option<nvidia::GpusData>>;
+
 impl DataCollector {
     pub fn new(filters: DataFilters) -> Self {
         // Initialize it to the past to force it to load on initialization.
@@ -216,6 +280,18 @@ impl DataCollector {
             prev_idle: 0_f64,
             #[cfg(target_os = "linux")]
             prev_non_idle: 0_f64,
+            #[cfg(target_os = "linux")]
+            rapl_state: power::linux::RaplState::default(),
+            #[cfg(target_os = "linux")]
+            tcp_traffic: connections::sock_diag::TcpTraffic::default(),
+            #[cfg(target_os = "linux")]
+            memory_details: processes::MemoryDetailsCache::default(),
+            #[cfg(target_os = "linux")]
+            visible_pids: nohash::IntSet::default(),
+            #[cfg(target_os = "linux")]
+            cpu_state: cpu::CpuState::default(),
+            #[cfg(target_os = "linux")]
+            roots: SystemRoots::default(),
             use_current_cpu_total: false,
             unnormalized_cpu: false,
             get_process_threads: false,
@@ -239,6 +315,15 @@ impl DataCollector {
             free_arc_mem: false,
             last_list_collection_time: last_collection_time,
             should_run_less_routine_tasks: true,
+            custom_metrics: Vec::new(),
+            schedule: CollectionSchedule::new(&CollectionRates::default(), last_collection_time),
+            diagnostics: CollectionDiagnostics::default(),
+            self_cpu_usage: SelfCpuUsage::new(),
+            stale_sources: worker::StaleSources::default(),
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            disk_usage_workers: disks::UsageWorkers::default(),
+            #[cfg(feature = "nvidia")]
+            nvml_worker: None,
         }
     }
 
@@ -262,10 +347,33 @@ impl DataCollector {
         }
     }
 
+    pub fn set_filters(&mut self, filters: DataFilters) {
+        self.filters = filters;
+    }
+
     pub fn set_collection(&mut self, used_widgets: UsedWidgets) {
+        // Don't compute rates from old counters if TCP traffic is shown again later.
+        #[cfg(target_os = "linux")]
+        if !used_widgets.process_fields.tcp_traffic {
+            self.tcp_traffic = connections::sock_diag::TcpTraffic::default();
+        }
+
+        // Likewise, don't show stale memory details if they're shown again later.
+        #[cfg(target_os = "linux")]
+        if !used_widgets.process_fields.detailed_memory {
+            self.memory_details = processes::MemoryDetailsCache::default();
+        }
+
         self.widgets_to_harvest = used_widgets;
     }
 
+    /// Sets the processes that are currently shown, which are the only ones
+    /// whose detailed memory is read if there are many processes.
+    #[cfg(target_os = "linux")]
+    pub fn set_visible_pids(&mut self, pids: Vec<Pid>) {
+        self.visible_pids = pids.into_iter().collect();
+    }
+
     pub fn set_use_current_cpu_total(&mut self, use_current_cpu_total: bool) {
         self.use_current_cpu_total = use_current_cpu_total;
     }
@@ -282,6 +390,27 @@ impl DataCollector {
         self.get_process_threads = get_process_threads;
     }
 
+    pub fn set_custom_metrics(&mut self, custom_metrics: Vec<custom::CustomMetric>) {
+        self.custom_metrics = custom_metrics
+            .into_iter()
+            .map(custom::CustomMetricState::new)
+            .collect();
+    }
+
+    pub fn set_collection_rates(&mut self, rates: &CollectionRates) {
+        self.schedule = CollectionSchedule::new(rates, self.last_collection_time);
+    }
+
+    /// Collect every subsystem on the next update, regardless of their rates.
+    pub fn reset_schedule(&mut self) {
+        self.schedule.reset();
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn set_system_roots(&mut self, roots: SystemRoots) {
+        self.roots = roots;
+    }
+
     #[cfg(feature = "zfs")]
     pub fn set_free_arc_mem(&mut self, free_mem: bool) {
         self.free_arc_mem = free_mem;
@@ -298,25 +427,28 @@ impl DataCollector {
         // Refresh the list of objects once every minute. If it's too frequent it can
         // cause segfaults.
 
-        if self.widgets_to_harvest.use_cpu || self.widgets_to_harvest.use_proc {
-            self.sys.system.refresh_cpu_all();
-        }
-
-        if self.widgets_to_harvest.use_mem || self.widgets_to_harvest.use_proc {
-            self.sys.system.refresh_memory();
-        }
-
-        if self.widgets_to_harvest.use_net {
-            self.sys.network.refresh(true);
-        }
-
         // sysinfo is used on non-Linux systems for the following:
+        // - CPU, memory, and network usage
         // - Processes (users list as well for Windows)
         // - Disks (Windows only)
         // - Temperatures and temperature components list.
         #[cfg(not(target_os = "linux"))]
         {
-            if self.widgets_to_harvest.use_proc {
+            let collect_proc = self.widgets_to_harvest.use_proc && self.schedule.processes.is_due();
+
+            if (self.widgets_to_harvest.use_cpu && self.schedule.cpu.is_due()) || collect_proc {
+                self.sys.system.refresh_cpu_all();
+            }
+
+            if (self.widgets_to_harvest.use_mem && self.schedule.memory.is_due()) || collect_proc {
+                self.sys.system.refresh_memory();
+            }
+
+            if self.widgets_to_harvest.use_net && self.schedule.network.is_due() {
+                self.sys.network.refresh(true);
+            }
+
+            if collect_proc {
                 self.sys.system.refresh_processes_specifics(
                     sysinfo::ProcessesToUpdate::All,
                     true,
@@ -333,7 +465,7 @@ impl DataCollector {
                 }
             }
 
-            if self.widgets_to_harvest.use_temp {
+            if self.widgets_to_harvest.use_temp && self.schedule.temperatures.is_due() {
                 if self.should_run_less_routine_tasks {
                     self.sys.temps.refresh(true);
                 }
@@ -344,7 +476,7 @@ impl DataCollector {
             }
 
             #[cfg(target_os = "windows")]
-            if self.widgets_to_harvest.use_disk {
+            if self.widgets_to_harvest.use_disk && self.schedule.disks.is_due() {
                 if self.should_run_less_routine_tasks {
                     self.sys.disks.refresh(true);
                 }
@@ -364,6 +496,19 @@ impl DataCollector {
 
         self.run_less_routine_tasks();
 
+        self.schedule.start(self.data.collection_time);
+
+        // Routine tasks like refreshing device lists are tied to collection, so
+        // collect everything when they run.
+        if self.should_run_less_routine_tasks {
+            self.schedule.force_all();
+        }
+
+        // GPU data is also used for temperatures and power, so collect it if either is due.
+        if self.schedule.temperatures.is_due() || self.schedule.power.is_due() {
+            self.schedule.gpus.force();
+        }
+
         self.refresh_sysinfo_data();
 
         self.update_cpu_usage();
@@ -376,14 +521,24 @@ impl DataCollector {
         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
         self.update_gpus();
 
+        // This must run after the GPU update, as it reuses any GPU power readings.
+        self.update_power();
+
         self.update_processes();
+        #[cfg(target_os = "linux")]
+        self.update_connections();
+        #[cfg(target_os = "linux")]
+        self.update_process_memory();
         self.update_network_usage();
         self.update_disks();
+        self.update_custom_metrics();
+        self.update_diagnostics();
 
         // Make sure to run this to refresh the setting.
         self.should_run_less_routine_tasks = false;
 
         // Update times for future reference.
+        self.schedule.finish(self.data.collection_time);
         self.last_collection_time = self.data.collection_time;
     }
 
@@ -392,17 +547,19 @@ impl DataCollector {
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     #[inline]
     fn update_gpus(&mut self) {
-        if self.widgets_to_harvest.use_gpu {
+        if self.widgets_to_harvest.use_gpu && self.schedule.gpus.is_due() {
+            let started = Instant::now();
             let mut local_gpu: Vec<(String, memory::MemData)> = Vec::new();
             let mut local_gpu_pids: Vec<IntMap<Pid, (u64, u32)>> = Vec::new();
             let mut local_gpu_total_mem: u64 = 0;
             let mut local_gpu_data: Vec<gpu::GpuData> = Vec::new();
 
             #[cfg(feature = "nvidia")]
-            if let Some(data) =
-                nvidia::get_nvidia_vecs(&self.filters.temp_filter, &self.widgets_to_harvest)
-            {
-                if let Some(mut temp) = data.temperature {
+            if let Some(data) = self.get_nvidia_data() {
+                if let Some(mut temp) = data
+                    .temperature
+                    .filter(|_| self.schedule.temperatures.is_due())
+                {
                     if let Some(sensors) = &mut self.data.temperature_sensors {
                         sensors.append(&mut temp);
                     } else {
@@ -422,9 +579,11 @@ impl DataCollector {
             }
 
             #[cfg(all(target_os = "linux", feature = "gpu"))]
-            if let Some(data) =
-                amd::get_amd_vecs(&self.widgets_to_harvest, self.last_collection_time)
-            {
+            if let Some(data) = amd::get_amd_vecs(
+                &self.roots,
+                &self.widgets_to_harvest,
+                self.schedule.gpus.last_run(),
+            ) {
                 if let Some(mut mem) = data.memory {
                     local_gpu.append(&mut mem);
                 }
@@ -442,6 +601,11 @@ impl DataCollector {
                 }
             }
 
+            self.diagnostics.record_ok(
+                Source::Gpus,
+                started,
+                local_gpu.len() + local_gpu_data.len(),
+            );
             self.data.gpu = (!local_gpu.is_empty()).then_some(local_gpu);
             self.data.gpu_data = (!local_gpu_data.is_empty()).then_some(local_gpu_data);
             self.gpu_pids = (!local_gpu_pids.is_empty()).then_some(local_gpu_pids);
@@ -449,12 +613,116 @@ impl DataCollector {
         }
     }
 
+    /// Gets NVIDIA GPU data through NVML on a worker thread, as NVML calls can
+    /// hang. If NVML times out, it is skipped for a while.
+    #[cfg(feature = "nvidia")]
+    fn get_nvidia_data(&mut self) -> Option<nvidia::GpusData> {
+        const KEY: &str = "nvml";
+
+        let now = Instant::now();
+        let worker = self.nvml_worker.get_or_insert_with(|| {
+            worker::Worker::spawn("btm-nvml", |(filter, widgets_to_harvest)| {
+                nvidia::get_nvidia_vecs(&filter, &widgets_to_harvest)
+            })
+        });
+        if self.stale_sources.is_stale(KEY, now, worker.is_busy()) {
+            return None;
+        }
+
+        worker.send((self.filters.temp_filter.clone(), self.widgets_to_harvest));
+
+        match worker.wait_until(now + NVML_TIMEOUT) {
+            worker::WorkerResult::Done(data) => {
+                self.stale_sources.mark_responsive(KEY);
+                data
+            }
+            worker::WorkerResult::Failed => {
+                self.diagnostics
+                    .record_error(Source::Gpus, "the NVML worker failed");
+                None
+            }
+            worker::WorkerResult::TimedOut => {
+                self.stale_sources.mark_stale(KEY, now);
+                self.diagnostics
+                    .record_error(Source::Gpus, "NVML timed out, skipping it for a while");
+                None
+            }
+        }
+    }
+
+    /// Gets power draw data, from RAPL on Linux and from any GPUs that report power.
+    #[inline]
+    fn update_power(&mut self) {
+        if self.widgets_to_harvest.use_power && self.schedule.power.is_due() {
+            let started = Instant::now();
+            let mut local_power: Vec<power::PowerData> = Vec::new();
+
+            #[cfg(target_os = "linux")]
+            if let Some(mut rapl) = power::linux::get_rapl_power(
+                &self.roots,
+                &mut self.rapl_state,
+                self.data
+                    .collection_time
+                    .duration_since(self.schedule.power.last_run()),
+                self.should_run_less_routine_tasks,
+            ) {
+                local_power.append(&mut rapl);
+            }
+
+            #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+            if let Some(gpu_data) = &self.data.gpu_data {
+                for gpu in gpu_data {
+                    if let gpu::GpuMetric::Power { draw_mw, .. } = gpu.metric {
+                        local_power.push(power::PowerData {
+                            name: gpu.name.clone(),
+                            watts: draw_mw as f64 / 1000.0,
+                        });
+                    }
+                }
+            }
+
+            self.diagnostics
+                .record_ok(Source::Power, started, local_power.len());
+            self.data.power = (!local_power.is_empty()).then_some(local_power);
+        }
+    }
+
+    /// Gets data for any user-defined custom metrics.
+    #[inline]
+    fn update_custom_metrics(&mut self) {
+        if self.widgets_to_harvest.use_custom
+            && self.schedule.custom.is_due()
+            && !self.custom_metrics.is_empty()
+        {
+            let started = Instant::now();
+            let custom = custom::get_custom_metrics(
+                &mut self.custom_metrics,
+                &mut self.stale_sources,
+                &mut self.diagnostics,
+            );
+            self.diagnostics
+                .record_ok(Source::Custom, started, custom.len());
+            self.data.custom = Some(custom);
+        }
+    }
+
     #[inline]
     fn update_cpu_usage(&mut self) {
-        if self.widgets_to_harvest.use_cpu {
-            self.data.cpu = cpu::get_cpu_data_list(&self.sys.system, self.show_average_cpu).ok();
+        if self.widgets_to_harvest.use_cpu && self.schedule.cpu.is_due() {
+            let started = Instant::now();
+            #[cfg(target_os = "linux")]
+            let cpu = self
+                .cpu_state
+                .get_cpu_data_list(&self.roots, self.show_average_cpu);
+            #[cfg(not(target_os = "linux"))]
+            let cpu = cpu::get_cpu_data_list(&self.sys.system, self.show_average_cpu);
+            self.data.cpu = self.diagnostics.record(Source::Cpu, started, cpu, Vec::len);
 
-            #[cfg(unix)]
+            #[cfg(target_os = "linux")]
+            {
+                self.data.load_avg = cpu::get_load_avg(&self.roots);
+            }
+            #[cfg(all(unix, not(target_os = "linux")))]
             {
                 self.data.load_avg = Some(cpu::get_load_avg());
             }
@@ -463,8 +731,14 @@ impl DataCollector {
 
     #[inline]
     fn update_processes(&mut self) {
-        if self.widgets_to_harvest.use_proc {
-            if let Ok(mut process_list) = self.get_processes() {
+        if self.widgets_to_harvest.use_proc && self.schedule.processes.is_due() {
+            let started = Instant::now();
+            let processes = self.get_processes();
+
+            if let Some(mut process_list) =
+                self.diagnostics
+                    .record(Source::Processes, started, processes, Vec::len)
+            {
                 // NB: To avoid duplicate sorts on rerenders/events, we sort the processes by
                 // PID here. We also want to avoid re-sorting *again* later on
                 // if we're sorting by PID, since we already did it here!
@@ -474,18 +748,123 @@ impl DataCollector {
         }
     }
 
+    /// Gets network connections and the TCP traffic of processes, which are
+    /// collected alongside processes. Any listening ports are also added to
+    /// the processes that own them.
+    #[cfg(target_os = "linux")]
     #[inline]
-    fn update_temps(&mut self) {
-        if self.widgets_to_harvest.use_temp {
-            #[cfg(not(target_os = "linux"))]
-            if let Ok(data) =
-                temperature::get_temperature_data(&self.sys.temps, &self.filters.temp_filter)
+    fn update_connections(&mut self) {
+        let widgets = &self.widgets_to_harvest;
+        let use_connections = widgets.use_connections || widgets.process_fields.listening_ports;
+        let use_tcp_traffic = widgets.process_fields.tcp_traffic;
+
+        if !(use_connections || use_tcp_traffic) || !self.schedule.processes.is_due() {
+            return;
+        }
+
+        // Both need the process owning each socket, and finding them means
+        // reading every process's file descriptors, so it's only done once.
+        let started = Instant::now();
+        let owners = connections::linux::socket_owners(&self.roots);
+
+        if use_connections {
+            let connections = connections::linux::get_connections(&self.roots, &owners);
+
+            if let Some(mut connections) =
+                self.diagnostics
+                    .record(Source::Connections, started, connections, Vec::len)
             {
-                self.data.temperature_sensors = data;
+                for connection in &mut connections {
+                    connection.user = connection
+                        .uid
+                        .and_then(|uid| self.user_table.uid_to_username(uid).ok());
+                }
+
+                if let Some(processes) = &mut self.data.list_of_processes {
+                    connections::linux::add_listening_ports(processes, &connections);
+                }
+
+                self.data.connections = Some(connections);
             }
+        }
 
+        if use_tcp_traffic {
+            self.update_tcp_traffic(&owners);
+        }
+    }
+
+    /// Estimates each process's TCP traffic from the byte counters of the
+    /// sockets it has open, owned by the processes in `owners`.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    fn update_tcp_traffic(&mut self, owners: &IntMap<u64, connections::linux::SocketOwner>) {
+        let Some(processes) = &mut self.data.list_of_processes else {
+            return;
+        };
+
+        let started = Instant::now();
+        let sockets = connections::sock_diag::tcp_socket_traffic();
+
+        if let Some(sockets) =
+            self.diagnostics
+                .record(Source::TcpTraffic, started, sockets, IntMap::len)
+        {
+            self.tcp_traffic
+                .update(processes, sockets, owners, self.data.collection_time);
+        }
+    }
+
+    /// Reads the detailed memory of processes. This is read at its own slower
+    /// rate, and only for the shown processes if there are many of them and it
+    /// isn't sorted or searched by.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    fn update_process_memory(&mut self) {
+        if !self.widgets_to_harvest.process_fields.detailed_memory
+            || !self.schedule.processes.is_due()
+        {
+            return;
+        }
+
+        let Some(processes) = &mut self.data.list_of_processes else {
+            return;
+        };
+
+        let started = Instant::now();
+        let fields = &self.widgets_to_harvest.process_fields;
+        let targets = (processes.len() > MAX_ALL_PROCESS_MEMORY_DETAILS
+            && !fields.all_detailed_memory)
+            .then_some(&self.visible_pids);
+        let num_read = self.memory_details.update(
+            processes,
+            &self.roots,
+            targets,
+            self.schedule.process_memory.is_due(),
+        );
+
+        if num_read > 0 {
+            self.diagnostics
+                .record_ok(Source::ProcessMemory, started, num_read);
+        }
+    }
+
+    #[inline]
+    fn update_temps(&mut self) {
+        if self.widgets_to_harvest.use_temp && self.schedule.temperatures.is_due() {
+            let started = Instant::now();
+
+            #[cfg(not(target_os = "linux"))]
+            let temps =
+                temperature::get_temperature_data(&self.sys.temps, &self.filters.temp_filter);
             #[cfg(target_os = "linux")]
-            if let Ok(data) = temperature::get_temperature_data(&self.filters.temp_filter) {
+            let temps = temperature::get_temperature_data(&self.roots, &self.filters.temp_filter);
+
+            if let Some(data) =
+                self.diagnostics
+                    .record(Source::Temperatures, started, temps, |data| {
+                        data.as_ref().map_or(0, Vec::len)
+                    })
+            {
                 self.data.temperature_sensors = data;
             }
         }
@@ -493,13 +872,36 @@ impl DataCollector {
 
     #[inline]
     fn update_memory_usage(&mut self) {
-        if self.widgets_to_harvest.use_mem {
-            self.data.memory = memory::get_ram_usage(&self.sys.system);
+        if self.widgets_to_harvest.use_mem && self.schedule.memory.is_due() {
+            let started = Instant::now();
+            #[cfg(target_os = "linux")]
+            let meminfo = {
+                let meminfo = memory::linux::MemInfo::read(&self.roots);
+                let meminfo = self
+                    .diagnostics
+                    .record(Source::Memory, started, meminfo, |_| 1);
+                self.data.memory = meminfo.and_then(|info| info.ram());
+
+                meminfo
+            };
+            #[cfg(not(target_os = "linux"))]
+            {
+                let memory = memory::get_ram_usage(&self.sys.system)
+                    .ok_or("the total amount of memory could not be read");
+                self.data.memory = self
+                    .diagnostics
+                    .record(Source::Memory, started, memory, |_| 1);
+            }
 
             #[cfg(feature = "zfs")]
             {
+                #[cfg(target_os = "linux")]
+                let arc = memory::arc::get_arc_usage(&self.roots);
+                #[cfg(target_os = "freebsd")]
+                let arc = memory::arc::get_arc_usage();
+
                 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
-                if let Some(arc) = memory::arc::get_arc_usage() {
+                if let Some(arc) = arc {
                     if let Some(mem) = &mut self.data.memory {
                         if self.free_arc_mem {
                             if arc.0.used_bytes > arc.1 {
@@ -529,30 +931,59 @@ impl DataCollector {
                 }
             }
 
-            #[cfg(not(target_os = "windows"))]
-            if self.widgets_to_harvest.use_cache {
-                self.data.cache = memory::get_cache_usage(&self.sys.system);
+            #[cfg(target_os = "linux")]
+            {
+                if self.widgets_to_harvest.use_cache {
+                    self.data.cache = meminfo.and_then(|info| info.cache());
+                }
+
+                self.data.swap = meminfo.and_then(|info| info.swap());
             }
+            #[cfg(not(target_os = "linux"))]
+            {
+                #[cfg(not(target_os = "windows"))]
+                if self.widgets_to_harvest.use_cache {
+                    self.data.cache = memory::get_cache_usage(&self.sys.system);
+                }
 
-            self.data.swap = memory::get_swap_usage(&self.sys.system);
+                self.data.swap = memory::get_swap_usage(&self.sys.system);
+            }
         }
     }
 
     #[inline]
     fn update_network_usage(&mut self) {
-        if self.widgets_to_harvest.use_net {
+        if self.widgets_to_harvest.use_net && self.schedule.network.is_due() {
+            let started = Instant::now();
+
+            #[cfg(target_os = "linux")]
             let net_data = network::get_network_data(
-                &self.sys.network,
-                self.last_collection_time,
+                &self.roots,
+                self.schedule.network.last_run(),
                 &mut self.total_rx,
                 &mut self.total_tx,
                 self.data.collection_time,
                 &self.filters.net_filter,
             );
-
-            self.total_rx = net_data.total_rx;
-            self.total_tx = net_data.total_tx;
-            self.data.network = Some(net_data);
+            #[cfg(not(target_os = "linux"))]
+            let net_data = Ok::<_, anyhow::Error>((
+                self.sys.network.list().len(),
+                network::get_network_data(
+                    &self.sys.network,
+                    self.schedule.network.last_run(),
+                    &mut self.total_rx,
+                    &mut self.total_tx,
+                    self.data.collection_time,
+                    &self.filters.net_filter,
+                ),
+            ));
+
+            if let Some((_, net_data)) =
+                self.diagnostics
+                    .record(Source::Network, started, net_data, |(len, _)| *len)
+            {
+                self.data.network = Some(net_data);
+            }
         }
     }
 
@@ -564,6 +995,23 @@ impl DataCollector {
     #[inline]
     #[cfg(feature = "battery")]
     fn update_batteries(&mut self) {
+        if !self.schedule.batteries.is_due() {
+            return;
+        }
+
+        let started = Instant::now();
+        let batteries = self.get_batteries();
+        self.data.list_of_batteries = self
+            .diagnostics
+            .record(Source::Batteries, started, batteries, |batteries| {
+                batteries.as_ref().map_or(0, Vec::len)
+            })
+            .flatten();
+    }
+
+    /// Refreshes and returns battery data, initializing the battery manager if needed.
+    #[cfg(feature = "battery")]
+    fn get_batteries(&mut self) -> error::CollectionResult<Option<Vec<batteries::BatteryData>>> {
         let battery_manager = match &self.battery_manager {
             Some(manager) => {
                 // Also check if we need to refresh the list of batteries.
@@ -586,46 +1034,98 @@ impl DataCollector {
                 manager
             }
             None => {
-                if let Ok(manager) = Manager::new() {
-                    let Ok(batteries) = manager.batteries() else {
-                        return;
-                    };
-
-                    let battery_list = batteries.filter_map(Result::ok).collect::<Vec<_>>();
+                let manager = Manager::new().map_err(anyhow::Error::from)?;
+                let batteries = manager.batteries().map_err(anyhow::Error::from)?;
+                let battery_list = batteries.filter_map(Result::ok).collect::<Vec<_>>();
 
-                    if battery_list.is_empty() {
-                        return;
-                    }
-
-                    self.battery_list = Some(battery_list);
-                    self.battery_manager.insert(manager)
-                } else {
-                    return;
+                if battery_list.is_empty() {
+                    return Ok(None);
                 }
+
+                self.battery_list = Some(battery_list);
+                self.battery_manager.insert(manager)
             }
         };
 
-        self.data.list_of_batteries = self
+        Ok(self
             .battery_list
             .as_mut()
-            .map(|battery_list| batteries::refresh_batteries(battery_manager, battery_list));
+            .map(|battery_list| batteries::refresh_batteries(battery_manager, battery_list)))
     }
 
     #[inline]
     fn update_disks(&mut self) {
-        if self.widgets_to_harvest.use_disk {
-            self.data.disks = disks::get_disk_usage(self).ok();
-            self.data.io = disks::get_io_usage().ok();
+        if self.widgets_to_harvest.use_disk && self.schedule.disks.is_due() {
+            let started = Instant::now();
+            let disks = disks::get_disk_usage(self);
+            let io = disks::get_io_usage(self);
+
+            self.data.disks = self
+                .diagnostics
+                .record(Source::Disks, started, disks, Vec::len);
+            self.data.io = io
+                .map_err(|err| self.diagnostics.record_error(Source::Disks, err))
+                .ok();
+
+            // Disks that time out are otherwise only shown as unresponsive, so also
+            // report them as an error.
+            if let Some(disks) = &self.data.disks {
+                let unresponsive = disks
+                    .iter()
+                    .filter(|disk| disk.unresponsive)
+                    .map(|disk| disk.mount_point.as_str())
+                    .collect::<Vec<_>>();
+
+                if !unresponsive.is_empty() {
+                    self.diagnostics.record_error(
+                        Source::Disks,
+                        anyhow::anyhow!("timed out reading usage of {}", unresponsive.join(", ")),
+                    );
+                }
+            }
         }
     }
 
+    /// Updates bottom's own resource usage and attaches the diagnostics to the
+    /// current data.
+    fn update_diagnostics(&mut self) {
+        // sysinfo's CPU usage for a single process isn't accurate on every
+        // platform, so only its memory is used.
+        let cpu_percent = self.self_cpu_usage.update();
+        self.diagnostics.self_usage = cpu_percent.and_then(|cpu_percent| {
+            let pid = sysinfo::get_current_pid().ok()?;
+            self.sys.system.refresh_processes_specifics(
+                sysinfo::ProcessesToUpdate::Some(&[pid]),
+                false,
+                sysinfo::ProcessRefreshKind::nothing().with_memory(),
+            );
+
+            self.sys.system.process(pid).map(|process| SelfUsage {
+                cpu_percent,
+                rss_bytes: process.memory(),
+            })
+        });
+
+        self.diagnostics.last_update_duration = self.data.collection_time.elapsed();
+        self.data.diagnostics = self.diagnostics.clone();
+    }
+
     /// Returns the total memory of the system.
     #[inline]
     fn total_memory(&self) -> u64 {
         if let Some(memory) = &self.data.memory {
             memory.total_bytes.get()
         } else {
-            self.sys.system.total_memory()
+            #[cfg(target_os = "linux")]
+            {
+                memory::linux::MemInfo::read(&self.roots)
+                    .map(|info| info.total)
+                    .unwrap_or_default()
+            }
+            #[cfg(not(target_os = "linux"))]
+            {
+                self.sys.system.total_memory()
+            }
         }
     }
 }
@@ -14,7 +14,7 @@ use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use super::linux::utils::is_device_awake;
 use crate::{
     app::layout_manager::UsedWidgets,
-    collection::{memory::MemData, processes::Pid},
+    collection::{SystemRoots, memory::MemData, processes::Pid},
 };
 
 // TODO: May be able to clean up some of these, Option<Vec> for example is a bit redundant.
@@ -45,11 +45,11 @@ pub struct AmdGpuProc {
 static PROC_DATA: LazyLock<Mutex<HashMap<PathBuf, IntMap<Pid, AmdGpuProc>>>> =
     LazyLock::new(|| Mutex::new(HashMap::default()));
 
-fn get_amd_devs() -> Option<Vec<PathBuf>> {
+fn get_amd_devs(roots: &SystemRoots) -> Option<Vec<PathBuf>> {
     let mut devices = Vec::new();
 
     // read all PCI devices controlled by the AMDGPU module
-    let Ok(paths) = fs::read_dir("/sys/module/amdgpu/drivers/pci:amdgpu") else {
+    let Ok(paths) = fs::read_dir(roots.sys_path("module/amdgpu/drivers/pci:amdgpu")) else {
         return None;
     };
 
@@ -166,8 +166,10 @@ fn diff_usage(pre: u64, cur: u64, interval: &Duration) -> u64 {
 }
 
 // from amdgpu_top: https://github.com/Umio-Yasuno/amdgpu_top/blob/c961cf6625c4b6d63fda7f03348323048563c584/crates/libamdgpu_top/src/stat/fdinfo/proc_info.rs#L13-L27
-fn get_amdgpu_pid_fds(pid: Pid, device_path: Vec<PathBuf>) -> Option<Vec<u32>> {
-    let Ok(fd_list) = fs::read_dir(format!("/proc/{pid}/fd/")) else {
+fn get_amdgpu_pid_fds(
+    roots: &SystemRoots, pid: Pid, device_path: Vec<PathBuf>,
+) -> Option<Vec<u32>> {
+    let Ok(fd_list) = fs::read_dir(roots.proc_path(format!("{pid}/fd/"))) else {
         return None;
     };
 
@@ -226,12 +228,12 @@ fn get_amdgpu_drm(device_path: &Path) -> Option<Vec<PathBuf>> {
     }
 }
 
-fn get_amd_fdinfo(device_path: &Path) -> Option<IntMap<Pid, AmdGpuProc>> {
+fn get_amd_fdinfo(roots: &SystemRoots, device_path: &Path) -> Option<IntMap<Pid, AmdGpuProc>> {
     let mut fdinfo = IntMap::default();
 
     let drm_paths = get_amdgpu_drm(device_path)?;
 
-    let Ok(proc_dir) = fs::read_dir("/proc") else {
+    let Ok(proc_dir) = fs::read_dir(roots.procfs()) else {
         return None;
     };
 
@@ -258,7 +260,7 @@ fn get_amd_fdinfo(device_path: &Path) -> Option<IntMap<Pid, AmdGpuProc>> {
 
     for pid in pids {
         // collect file descriptors that point to our device renderers
-        let Some(fds) = get_amdgpu_pid_fds(pid, drm_paths.clone()) else {
+        let Some(fds) = get_amdgpu_pid_fds(roots, pid, drm_paths.clone()) else {
             continue;
         };
 
@@ -267,7 +269,7 @@ fn get_amd_fdinfo(device_path: &Path) -> Option<IntMap<Pid, AmdGpuProc>> {
         let mut observed_ids: HashSet<usize> = HashSet::default();
 
         for fd in fds {
-            let fdinfo_path = format!("/proc/{pid}/fdinfo/{fd}");
+            let fdinfo_path = roots.proc_path(format!("{pid}/fdinfo/{fd}"));
             let Ok(fdinfo_data) = read_to_string(fdinfo_path) else {
                 continue;
             };
@@ -327,8 +329,10 @@ fn get_amd_fdinfo(device_path: &Path) -> Option<IntMap<Pid, AmdGpuProc>> {
     Some(fdinfo)
 }
 
-pub fn get_amd_vecs(widgets_to_harvest: &UsedWidgets, prev_time: Instant) -> Option<AmdGpuData> {
-    let device_path_list = get_amd_devs()?;
+pub fn get_amd_vecs(
+    roots: &SystemRoots, widgets_to_harvest: &UsedWidgets, prev_time: Instant,
+) -> Option<AmdGpuData> {
+    let device_path_list = get_amd_devs(roots)?;
     let interval = Instant::now().duration_since(prev_time);
     let num_gpu = device_path_list.len();
     let mut mem_vec = Vec::with_capacity(num_gpu);
@@ -356,7 +360,7 @@ pub fn get_amd_vecs(widgets_to_harvest: &UsedWidgets, prev_time: Instant) -> Opt
         }
 
         if widgets_to_harvest.use_proc {
-            if let Some(procs) = get_amd_fdinfo(&device_path) {
+            if let Some(procs) = get_amd_fdinfo(roots, &device_path) {
                 let mut proc_info = PROC_DATA.lock().expect("mutex is poisoned");
                 let prev_fdinfo = proc_info.entry(device_path).or_default();
 
@@ -412,3 +416,35 @@ pub fn get_amd_vecs(widgets_to_harvest: &UsedWidgets, prev_time: Instant) -> Opt
         procs: (!proc_vec.is_empty()).then_some((total_mem, proc_vec)),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amd_devices_from_sysfs_root() {
+        // Colons aren't valid in file names on every platform, so build this tree
+        // on the fly rather than checking it into the fixtures directory.
+        let dir = tempfile::tempdir().unwrap();
+        let roots = SystemRoots::new(None, Some(dir.path().to_path_buf()));
+        let device = roots.sys_path("module/amdgpu/drivers/pci:amdgpu/0000:03:00.0");
+
+        fs::create_dir_all(device.join("drm/card0")).unwrap();
+        fs::write(device.join("mem_info_vram_total"), "17163091968\n").unwrap();
+        fs::write(device.join("mem_info_vram_used"), "1073741824\n").unwrap();
+
+        assert_eq!(get_amd_devs(&roots), Some(vec![device]));
+
+        let widgets = UsedWidgets {
+            use_mem: true,
+            ..Default::default()
+        };
+        let data = get_amd_vecs(&roots, &widgets, Instant::now()).unwrap();
+        let memory = data.memory.unwrap();
+
+        assert_eq!(memory.len(), 1);
+        assert_eq!(memory[0].0, amd_gpu_marketing::AMDGPU_DEFAULT_NAME);
+        assert_eq!(memory[0].1.total_bytes.get(), 17163091968);
+        assert_eq!(memory[0].1.used_bytes, 1073741824);
+    }
+}
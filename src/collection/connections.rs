@@ -0,0 +1,69 @@
+//! Data collection for network connections and the processes that own them.
+//!
+//! For Linux, connections are read from the `/proc/net` socket tables, and
+//! matched to processes through the socket inodes in each process's open file
+//! descriptors.
+
+#[cfg(target_os = "linux")]
+pub mod linux;
+#[cfg(target_os = "linux")]
+pub mod sock_diag;
+
+use std::sync::Arc;
+
+use crate::collection::processes::Pid;
+
+/// The protocol of a connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Protocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl Protocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Tcp6 => "tcp6",
+            Protocol::Udp => "udp",
+            Protocol::Udp6 => "udp6",
+            Protocol::Unix => "unix",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConnectionHarvest {
+    pub protocol: Protocol,
+
+    /// The local address, e.g. `127.0.0.1:22`, or the path of a Unix socket.
+    pub local_address: String,
+
+    /// The remote address, with `*` for any unset part.
+    pub remote_address: String,
+
+    /// The connection state, named like `ss` does (e.g. `ESTAB`, `LISTEN`).
+    pub state: &'static str,
+
+    /// The port being listened on, if this is a listening TCP socket or an
+    /// unconnected UDP socket bound to a port.
+    pub listening_port: Option<u16>,
+
+    /// The socket's inode, used to find the process that owns it.
+    pub inode: u64,
+
+    /// The UID of the socket's owner.
+    pub uid: Option<u32>,
+
+    /// The user of the socket's owner.
+    pub user: Option<Arc<str>>,
+
+    /// The PID of a process with the socket open, if it could be found.
+    pub pid: Option<Pid>,
+
+    /// The name of the process with the socket open.
+    pub process_name: Option<String>,
+}
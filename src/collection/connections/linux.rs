@@ -0,0 +1,394 @@
+//! Gets connections for Linux platforms from the `/proc/net` socket tables.
+//!
+//! Each table lists a socket per line, with hex-encoded addresses and the
+//! socket's inode. The inode is matched against the `socket:[inode]` links in
+//! `/proc/<pid>/fd` to find a process that has the socket open.
+
+use std::{
+    fs,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    os::unix::fs::MetadataExt,
+};
+
+use nohash::IntMap;
+
+use super::{ConnectionHarvest, Protocol};
+use crate::collection::{
+    SystemRoots,
+    error::CollectionResult,
+    processes::{Pid, ProcessHarvest},
+};
+
+/// The socket tables to read, and the protocol of each.
+const TABLES: [(&str, Protocol); 5] = [
+    ("net/tcp", Protocol::Tcp),
+    ("net/tcp6", Protocol::Tcp6),
+    ("net/udp", Protocol::Udp),
+    ("net/udp6", Protocol::Udp6),
+    ("net/unix", Protocol::Unix),
+];
+
+/// The flag set on Unix sockets that are listening for connections.
+const UNIX_ACCEPTING_FLAG: u32 = 1 << 16;
+
+/// A process that has a socket open.
+#[derive(Debug)]
+pub(crate) struct SocketOwner {
+    pub(crate) pid: Pid,
+    pub(crate) name: Option<String>,
+    pub(crate) uid: Option<u32>,
+}
+
+/// Returns the connection state for a TCP or UDP state code.
+fn inet_state(code: u8) -> &'static str {
+    match code {
+        0x01 => "ESTAB",
+        0x02 => "SYN-SENT",
+        0x03 | 0x0C => "SYN-RECV",
+        0x04 => "FIN-WAIT-1",
+        0x05 => "FIN-WAIT-2",
+        0x06 => "TIME-WAIT",
+        0x07 => "UNCONN",
+        0x08 => "CLOSE-WAIT",
+        0x09 => "LAST-ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Returns the connection state for a Unix socket's flags and state code.
+fn unix_state(flags: u32, code: u8) -> &'static str {
+    if flags & UNIX_ACCEPTING_FLAG != 0 {
+        return "LISTEN";
+    }
+
+    match code {
+        0x01 => "UNCONN",
+        0x02 => "CONNECTING",
+        0x03 => "ESTAB",
+        0x04 => "DISCONNECTING",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Parses a hex-encoded address and port like `0100007F:0016`. The address is
+/// made of 32-bit words in host byte order.
+fn parse_address(text: &str) -> Option<(IpAddr, u16)> {
+    let (address, port) = text.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let mut bytes = Vec::with_capacity(16);
+    for index in (0..address.len()).step_by(8) {
+        let word = u32::from_str_radix(address.get(index..index + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+
+    let address = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(bytes).ok()?)),
+        16 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(bytes).ok()?)),
+        _ => return None,
+    };
+
+    Some((address, port))
+}
+
+/// Formats an address like `ss` does, with `*` for an unset port.
+fn format_address(address: IpAddr, port: u16) -> String {
+    let port = if port == 0 {
+        "*".to_string()
+    } else {
+        port.to_string()
+    };
+
+    match address {
+        IpAddr::V4(address) => format!("{address}:{port}"),
+        IpAddr::V6(address) => format!("[{address}]:{port}"),
+    }
+}
+
+/// Parses a TCP or UDP socket table, like `/proc/net/tcp`.
+fn parse_inet_table(text: &str, protocol: Protocol) -> Vec<ConnectionHarvest> {
+    let is_udp = matches!(protocol, Protocol::Udp | Protocol::Udp6);
+
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let (local, local_port) = parse_address(fields.get(1)?)?;
+            let (remote, remote_port) = parse_address(fields.get(2)?)?;
+            let code = u8::from_str_radix(fields.get(3)?, 16).ok()?;
+            let uid = fields.get(7)?.parse().ok();
+            let inode = fields.get(9)?.parse().ok()?;
+
+            let state = inet_state(code);
+            let is_listening = state == "LISTEN" || (is_udp && state == "UNCONN");
+
+            Some(ConnectionHarvest {
+                protocol,
+                local_address: format_address(local, local_port),
+                remote_address: format_address(remote, remote_port),
+                state,
+                listening_port: (is_listening && local_port != 0).then_some(local_port),
+                inode,
+                uid,
+                user: None,
+                pid: None,
+                process_name: None,
+            })
+        })
+        .collect()
+}
+
+/// Parses the Unix socket table, `/proc/net/unix`.
+fn parse_unix_table(text: &str) -> Vec<ConnectionHarvest> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let flags = u32::from_str_radix(fields.get(3)?, 16).ok()?;
+            let code = u8::from_str_radix(fields.get(5)?, 16).ok()?;
+            let inode = fields.get(6)?.parse().ok()?;
+            let path = fields.get(7).copied().unwrap_or("*");
+
+            Some(ConnectionHarvest {
+                protocol: Protocol::Unix,
+                local_address: path.to_string(),
+                remote_address: "*".to_string(),
+                state: unix_state(flags, code),
+                listening_port: None,
+                inode,
+                uid: None,
+                user: None,
+                pid: None,
+                process_name: None,
+            })
+        })
+        .collect()
+}
+
+/// Finds a process with each socket open, keyed by the socket's inode. Only
+/// the file descriptors of processes we are allowed to read are checked.
+///
+/// This reads every file descriptor of every process, so it should only be
+/// done once per collection.
+pub(crate) fn socket_owners(roots: &SystemRoots) -> IntMap<u64, SocketOwner> {
+    let mut owners = IntMap::default();
+
+    let Ok(entries) = fs::read_dir(roots.procfs()) else {
+        return owners;
+    };
+
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<Pid>().ok())
+        else {
+            continue;
+        };
+
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+
+        let mut name = None;
+        let mut uid = None;
+        let mut is_first_socket = true;
+
+        for fd in fds.flatten() {
+            let Some(inode) = fs::read_link(fd.path()).ok().and_then(|target| {
+                target
+                    .to_str()?
+                    .strip_prefix("socket:[")?
+                    .strip_suffix(']')?
+                    .parse::<u64>()
+                    .ok()
+            }) else {
+                continue;
+            };
+
+            // Only look up the process's details if it has any sockets.
+            if is_first_socket {
+                is_first_socket = false;
+                name = fs::read_to_string(entry.path().join("comm"))
+                    .ok()
+                    .map(|comm| comm.trim_end().to_string());
+                uid = entry.metadata().ok().map(|metadata| metadata.uid());
+            }
+
+            owners.entry(inode).or_insert_with(|| SocketOwner {
+                pid,
+                name: name.clone(),
+                uid,
+            });
+        }
+    }
+
+    owners
+}
+
+/// Returns the connections in every socket table, along with the processes
+/// in `owners` that own them.
+pub(crate) fn get_connections(
+    roots: &SystemRoots, owners: &IntMap<u64, SocketOwner>,
+) -> CollectionResult<Vec<ConnectionHarvest>> {
+    let mut connections = Vec::new();
+    let mut has_table = false;
+
+    for (table, protocol) in TABLES {
+        let Ok(text) = fs::read_to_string(roots.proc_path(table)) else {
+            continue;
+        };
+
+        has_table = true;
+        connections.extend(match protocol {
+            Protocol::Unix => parse_unix_table(&text),
+            _ => parse_inet_table(&text, protocol),
+        });
+    }
+
+    if !has_table {
+        return Err("no socket tables could be read".into());
+    }
+
+    for connection in &mut connections {
+        if let Some(owner) = owners.get(&connection.inode) {
+            connection.pid = Some(owner.pid);
+            connection.process_name.clone_from(&owner.name);
+            connection.uid = connection.uid.or(owner.uid);
+        }
+    }
+
+    Ok(connections)
+}
+
+/// Sets the ports each process listens on from `connections`. `processes`
+/// must be sorted by PID.
+pub(crate) fn add_listening_ports(
+    processes: &mut [ProcessHarvest], connections: &[ConnectionHarvest],
+) {
+    for connection in connections {
+        let (Some(pid), Some(port)) = (connection.pid, connection.listening_port) else {
+            continue;
+        };
+
+        if let Ok(index) = processes.binary_search_by_key(&pid, |process| process.pid) {
+            let ports = &mut processes[index].listening_ports;
+            if let Err(position) = ports.binary_search(&port) {
+                ports.insert(position, port);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_addresses() {
+        assert_eq!(
+            parse_address("0100007F:0016"),
+            Some((IpAddr::V4(Ipv4Addr::LOCALHOST), 22))
+        );
+        assert_eq!(
+            parse_address("00000000000000000000000001000000:1F90"),
+            Some((IpAddr::V6(Ipv6Addr::LOCALHOST), 8080))
+        );
+        assert_eq!(parse_address("0100007F"), None);
+
+        let (address, port) = parse_address("00000000:0000").unwrap();
+        assert_eq!(format_address(address, port), "0.0.0.0:*");
+        assert_eq!(
+            format_address(IpAddr::V6(Ipv6Addr::LOCALHOST), 443),
+            "[::1]:443"
+        );
+    }
+
+    #[test]
+    fn test_fixture_connections() {
+        let roots = SystemRoots::fixture();
+        let mut connections = get_connections(&roots, &socket_owners(&roots)).unwrap();
+        connections.sort_by_key(|connection| connection.inode);
+
+        let summary = connections
+            .iter()
+            .map(|connection| {
+                (
+                    connection.protocol,
+                    connection.local_address.as_str(),
+                    connection.remote_address.as_str(),
+                    connection.state,
+                    connection.listening_port,
+                    connection.pid,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    Protocol::Tcp,
+                    "0.0.0.0:22",
+                    "0.0.0.0:*",
+                    "LISTEN",
+                    Some(22),
+                    Some(1)
+                ),
+                (
+                    Protocol::Tcp,
+                    "192.168.1.20:22",
+                    "192.168.1.5:51234",
+                    "ESTAB",
+                    None,
+                    None
+                ),
+                (
+                    Protocol::Tcp6,
+                    "[::1]:8080",
+                    "[::]:*",
+                    "LISTEN",
+                    Some(8080),
+                    Some(1)
+                ),
+                (
+                    Protocol::Udp,
+                    "127.0.0.53:53",
+                    "0.0.0.0:*",
+                    "UNCONN",
+                    Some(53),
+                    None
+                ),
+                (
+                    Protocol::Unix,
+                    "/run/systemd/notify",
+                    "*",
+                    "UNCONN",
+                    None,
+                    Some(1)
+                ),
+                (
+                    Protocol::Unix,
+                    "/run/dbus/system_bus_socket",
+                    "*",
+                    "LISTEN",
+                    None,
+                    None
+                ),
+            ]
+        );
+
+        assert_eq!(connections[0].process_name.as_deref(), Some("systemd"));
+        assert_eq!(connections[1].uid, Some(1000));
+
+        let mut processes = [1, 42].map(|pid| ProcessHarvest {
+            pid,
+            ..Default::default()
+        });
+        add_listening_ports(&mut processes, &connections);
+        assert_eq!(processes[0].listening_ports, vec![22, 8080]);
+        assert!(processes[1].listening_ports.is_empty());
+    }
+}
@@ -0,0 +1,442 @@
+//! Reads the byte counters of TCP sockets through the `sock_diag` netlink
+//! interface, which is what `ss -ti` uses.
+//!
+//! Only TCP keeps per-socket byte counters (`tcpi_bytes_received` and
+//! `tcpi_bytes_acked` in `struct tcp_info`), so UDP and other traffic is not
+//! seen.
+
+use std::{
+    io, mem,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    time::Instant,
+};
+
+use nohash::IntMap;
+
+use crate::collection::{
+    connections::linux::SocketOwner,
+    error::CollectionResult,
+    processes::{Pid, ProcessHarvest},
+};
+
+/// The message type for `sock_diag` requests and replies for a socket family.
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+
+/// The attribute holding a `struct tcp_info`.
+const INET_DIAG_INFO: u16 = 2;
+
+const NLMSG_HEADER_LEN: usize = 16;
+
+/// The length of `struct inet_diag_msg`, which starts each reply.
+const INET_DIAG_MSG_LEN: usize = 72;
+
+/// The offset of `idiag_inode` in `struct inet_diag_msg`.
+const INODE_OFFSET: usize = 68;
+
+/// The offsets of `tcpi_bytes_acked` and `tcpi_bytes_received` in
+/// `struct tcp_info`. These were added in Linux 4.1, and older kernels send a
+/// shorter struct without them.
+const BYTES_ACKED_OFFSET: usize = 120;
+const BYTES_RECEIVED_OFFSET: usize = 128;
+
+/// The byte counters of a TCP socket.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SocketTraffic {
+    /// Bytes received, including any not yet read by the process.
+    pub received: u64,
+
+    /// Bytes sent and acknowledged by the other side.
+    pub sent: u64,
+}
+
+/// Rounds `len` up to the 4-byte alignment netlink uses.
+fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_ne_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_ne_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_ne_bytes(
+        bytes.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
+
+/// Builds a request to dump every TCP socket of `family` along with its
+/// `struct tcp_info`.
+fn dump_request(family: u8) -> Vec<u8> {
+    // An `inet_diag_req_v2` is 8 bytes followed by a 48 byte socket ID, which
+    // is left zeroed as every socket is wanted.
+    let len = NLMSG_HEADER_LEN + 56;
+    let mut request = Vec::with_capacity(len);
+
+    request.extend_from_slice(&(len as u32).to_ne_bytes());
+    request.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    request.extend_from_slice(&((libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16).to_ne_bytes());
+    request.extend_from_slice(&1_u32.to_ne_bytes());
+    request.extend_from_slice(&0_u32.to_ne_bytes());
+
+    request.push(family);
+    request.push(libc::IPPROTO_TCP as u8);
+    request.push(1 << (INET_DIAG_INFO - 1));
+    request.push(0);
+    request.extend_from_slice(&u32::MAX.to_ne_bytes());
+    request.resize(len, 0);
+
+    request
+}
+
+/// Parses the `struct tcp_info` attribute of a reply, if it has one with the
+/// byte counters.
+fn parse_reply(payload: &[u8]) -> Option<(u64, SocketTraffic)> {
+    let inode = read_u32(payload, INODE_OFFSET)?;
+
+    let mut offset = INET_DIAG_MSG_LEN;
+    while let (Some(len), Some(kind)) = (read_u16(payload, offset), read_u16(payload, offset + 2)) {
+        let len = usize::from(len);
+        if len < 4 {
+            break;
+        }
+
+        if kind == INET_DIAG_INFO {
+            let info = payload.get(offset + 4..offset + len)?;
+            return Some((
+                u64::from(inode),
+                SocketTraffic {
+                    received: read_u64(info, BYTES_RECEIVED_OFFSET)?,
+                    sent: read_u64(info, BYTES_ACKED_OFFSET)?,
+                },
+            ));
+        }
+
+        offset += align(len);
+    }
+
+    None
+}
+
+/// Adds the sockets in a buffer of netlink messages to `sockets`. Returns
+/// whether the end of the dump was reached.
+fn parse_messages(
+    buffer: &[u8], sockets: &mut IntMap<u64, SocketTraffic>,
+) -> CollectionResult<bool> {
+    let mut offset = 0;
+
+    while let (Some(len), Some(kind)) = (read_u32(buffer, offset), read_u16(buffer, offset + 4)) {
+        let len = len as usize;
+        let Some(payload) = buffer.get(offset + NLMSG_HEADER_LEN..offset + len) else {
+            return Err("a sock_diag message was cut off".into());
+        };
+
+        match i32::from(kind) {
+            libc::NLMSG_DONE => return Ok(true),
+            libc::NLMSG_ERROR => {
+                let error = payload.get(..4).map_or(0, |error| {
+                    i32::from_ne_bytes(error.try_into().expect("slice is 4 bytes"))
+                });
+                if error != 0 {
+                    return Err(io::Error::from_raw_os_error(-error).into());
+                }
+            }
+            _ if kind == SOCK_DIAG_BY_FAMILY => {
+                if let Some((inode, traffic)) = parse_reply(payload) {
+                    sockets.insert(inode, traffic);
+                }
+            }
+            _ => {}
+        }
+
+        offset += align(len.max(NLMSG_HEADER_LEN));
+    }
+
+    Ok(false)
+}
+
+/// Adds the byte counters of every TCP socket of `family` to `sockets`.
+///
+/// Each family is dumped on its own netlink socket, so that replies left over
+/// from a failed dump can't be mistaken for another family's.
+fn dump_family(family: i32, sockets: &mut IntMap<u64, SocketTraffic>) -> CollectionResult<()> {
+    // SAFETY: libc call with constant arguments. The result is checked before
+    // being used as a file descriptor.
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            libc::NETLINK_SOCK_DIAG,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+
+    // SAFETY: `fd` is a valid file descriptor that nothing else owns.
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    let mut buffer = vec![0_u8; 32 * 1024];
+    let request = dump_request(family as u8);
+
+    // SAFETY: A zeroed `sockaddr_nl` is valid, and addresses the kernel.
+    let mut address: libc::sockaddr_nl = unsafe { mem::zeroed() };
+    address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+
+    // SAFETY: libc call, `request` and `address` are valid for the lengths
+    // passed.
+    let sent = unsafe {
+        libc::sendto(
+            socket.as_raw_fd(),
+            request.as_ptr().cast(),
+            request.len(),
+            0,
+            (&raw const address).cast(),
+            mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        )
+    };
+    if sent < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+
+    loop {
+        // SAFETY: libc call, `buffer` is valid to write to for its length.
+        let received = unsafe {
+            libc::recv(
+                socket.as_raw_fd(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len(),
+                0,
+            )
+        };
+        if received < 0 {
+            return Err(io::Error::last_os_error().into());
+        } else if received == 0 {
+            return Ok(());
+        }
+
+        if parse_messages(&buffer[..received as usize], sockets)? {
+            return Ok(());
+        }
+    }
+}
+
+/// Returns the byte counters of every TCP socket, keyed by the socket's inode.
+///
+/// A family that can't be dumped, such as IPv6 when it is disabled, is
+/// skipped. This only fails if no family could be dumped.
+pub(crate) fn tcp_socket_traffic() -> CollectionResult<IntMap<u64, SocketTraffic>> {
+    let mut sockets = IntMap::default();
+    let mut error = None;
+
+    for family in [libc::AF_INET, libc::AF_INET6] {
+        let mut family_sockets = IntMap::default();
+
+        match dump_family(family, &mut family_sockets) {
+            Ok(()) => sockets.extend(family_sockets),
+            Err(err) => error = Some(err),
+        }
+    }
+
+    match error {
+        Some(err) if sockets.is_empty() => Err(err),
+        _ => Ok(sockets),
+    }
+}
+
+/// Tracks the TCP traffic of each process between collections.
+#[derive(Debug, Default)]
+pub(crate) struct TcpTraffic {
+    /// Each socket's counters from the last collection, by inode.
+    prev_sockets: IntMap<u64, SocketTraffic>,
+
+    /// The bytes each process has received and sent so far.
+    totals: IntMap<Pid, SocketTraffic>,
+
+    /// When the counters were last read.
+    last_time: Option<Instant>,
+}
+
+impl TcpTraffic {
+    /// Sets the TCP traffic of `processes` from the counters in `sockets` and
+    /// the process owning each socket. `processes` must be sorted by PID.
+    ///
+    /// A socket's traffic is only counted from the first time it is seen, as
+    /// its counters include everything since it was opened. Rates are left at
+    /// zero the first time, as they need two readings.
+    pub(crate) fn update(
+        &mut self, processes: &mut [ProcessHarvest], sockets: IntMap<u64, SocketTraffic>,
+        owners: &IntMap<u64, SocketOwner>, now: Instant,
+    ) {
+        let mut deltas: IntMap<Pid, SocketTraffic> = IntMap::default();
+        for (inode, traffic) in &sockets {
+            let Some(owner) = owners.get(inode) else {
+                continue;
+            };
+
+            // A new socket's counters are its starting point.
+            let prev = self.prev_sockets.get(inode).unwrap_or(traffic);
+            let delta = deltas.entry(owner.pid).or_default();
+            delta.received += traffic.received.saturating_sub(prev.received);
+            delta.sent += traffic.sent.saturating_sub(prev.sent);
+        }
+
+        // Forget processes that have exited.
+        self.totals.retain(|pid, _| {
+            processes
+                .binary_search_by_key(pid, |process| process.pid)
+                .is_ok()
+        });
+
+        let elapsed = self
+            .last_time
+            .map(|last_time| now.duration_since(last_time).as_secs_f64())
+            .filter(|elapsed| *elapsed > 0.0);
+
+        for process in processes {
+            let delta = deltas.get(&process.pid).copied().unwrap_or_default();
+            let total = self.totals.entry(process.pid).or_default();
+            total.received += delta.received;
+            total.sent += delta.sent;
+
+            process.total_tcp_rx = total.received;
+            process.total_tcp_tx = total.sent;
+            if let Some(elapsed) = elapsed {
+                process.tcp_rx_per_sec = (delta.received as f64 / elapsed) as u64;
+                process.tcp_tx_per_sec = (delta.sent as f64 / elapsed) as u64;
+            }
+        }
+
+        self.prev_sockets = sockets;
+        self.last_time = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    /// Builds a netlink message with `payload`.
+    fn message(kind: u16, payload: &[u8]) -> Vec<u8> {
+        let len = NLMSG_HEADER_LEN + payload.len();
+        let mut message = Vec::new();
+        message.extend_from_slice(&(len as u32).to_ne_bytes());
+        message.extend_from_slice(&kind.to_ne_bytes());
+        message.extend_from_slice(&[0; 10]);
+        message.extend_from_slice(payload);
+        message.resize(align(len), 0);
+        message
+    }
+
+    /// Builds a `sock_diag` reply for a socket, with a `tcp_info` of
+    /// `info_len` bytes.
+    fn reply(inode: u32, received: u64, sent: u64, info_len: usize) -> Vec<u8> {
+        let mut info = vec![0; info_len];
+        if info_len >= BYTES_RECEIVED_OFFSET + 8 {
+            info[BYTES_ACKED_OFFSET..][..8].copy_from_slice(&sent.to_ne_bytes());
+            info[BYTES_RECEIVED_OFFSET..][..8].copy_from_slice(&received.to_ne_bytes());
+        }
+
+        let mut payload = vec![0; INET_DIAG_MSG_LEN];
+        payload[INODE_OFFSET..][..4].copy_from_slice(&inode.to_ne_bytes());
+        // An attribute before the one wanted, to check that it is skipped.
+        payload.extend_from_slice(&6_u16.to_ne_bytes());
+        payload.extend_from_slice(&1_u16.to_ne_bytes());
+        payload.extend_from_slice(&[0; 4]);
+        payload.extend_from_slice(&((info_len + 4) as u16).to_ne_bytes());
+        payload.extend_from_slice(&INET_DIAG_INFO.to_ne_bytes());
+        payload.extend_from_slice(&info);
+
+        message(SOCK_DIAG_BY_FAMILY, &payload)
+    }
+
+    #[test]
+    fn parses_replies() {
+        let mut buffer = reply(1001, 500, 200, 232);
+        buffer.extend(reply(1002, 1, 1, 104));
+
+        let mut sockets = IntMap::default();
+        assert!(!parse_messages(&buffer, &mut sockets).unwrap());
+        assert_eq!(
+            sockets.get(&1001),
+            Some(&SocketTraffic {
+                received: 500,
+                sent: 200
+            })
+        );
+        // Older kernels don't have the byte counters.
+        assert!(!sockets.contains_key(&1002));
+
+        assert!(parse_messages(&message(libc::NLMSG_DONE as u16, &[0; 4]), &mut sockets).unwrap());
+
+        let error = message(libc::NLMSG_ERROR as u16, &(-libc::EPERM).to_ne_bytes());
+        assert!(parse_messages(&error, &mut sockets).is_err());
+    }
+
+    #[test]
+    fn tracks_process_traffic() {
+        let mut traffic = TcpTraffic::default();
+        let mut processes = [1, 2].map(|pid| ProcessHarvest {
+            pid,
+            ..Default::default()
+        });
+        let owners = IntMap::from_iter([(10, 1), (11, 1), (20, 2)].map(|(inode, pid)| {
+            (
+                inode,
+                SocketOwner {
+                    pid,
+                    name: None,
+                    uid: None,
+                },
+            )
+        }));
+        let counters = |counts: &[(u64, u64, u64)]| {
+            counts
+                .iter()
+                .map(|&(inode, received, sent)| (inode, SocketTraffic { received, sent }))
+                .collect::<IntMap<_, _>>()
+        };
+
+        let start = Instant::now();
+        traffic.update(
+            &mut processes,
+            counters(&[(10, 1000, 100), (20, 50, 50)]),
+            &owners,
+            start,
+        );
+        // Traffic from before the sockets were first seen isn't counted.
+        assert_eq!(processes[0].total_tcp_rx, 0);
+        assert_eq!(processes[0].tcp_rx_per_sec, 0);
+
+        // Socket 20 closed, and socket 11 opened.
+        traffic.update(
+            &mut processes,
+            counters(&[(10, 3000, 100), (11, 1000, 400)]),
+            &owners,
+            start + Duration::from_secs(2),
+        );
+        assert_eq!(processes[0].total_tcp_rx, 2000);
+        assert_eq!(processes[0].tcp_rx_per_sec, 1000);
+        assert_eq!(processes[0].tcp_tx_per_sec, 0);
+        assert_eq!(processes[1].total_tcp_tx, 0);
+
+        traffic.update(
+            &mut processes,
+            counters(&[(10, 3000, 100), (11, 1500, 600)]),
+            &owners,
+            start + Duration::from_secs(3),
+        );
+        assert_eq!(processes[0].total_tcp_rx, 2500);
+        assert_eq!(processes[0].tcp_rx_per_sec, 500);
+        assert_eq!(processes[0].tcp_tx_per_sec, 200);
+    }
+}
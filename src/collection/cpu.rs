@@ -1,7 +1,14 @@
 //! Data collection for CPU usage and load average.
 
-pub mod sysinfo;
-pub use self::sysinfo::*;
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub(crate) use self::linux::*;
+    } else {
+        pub mod sysinfo;
+        pub use self::sysinfo::*;
+    }
+}
 
 pub type LoadAvgHarvest = [f32; 3];
 
@@ -0,0 +1,168 @@
+//! CPU usage and load averages for Linux, read from procfs.
+
+use std::fs;
+
+use super::{CpuData, CpuDataType, CpuHarvest, LoadAvgHarvest};
+use crate::collection::{SystemRoots, error::CollectionResult};
+
+/// The time a CPU has spent busy and in total, in clock ticks.
+#[derive(Clone, Copy, Debug, Default)]
+struct CpuTimes {
+    busy: u64,
+    total: u64,
+}
+
+impl CpuTimes {
+    /// Parses a `cpu` line of `/proc/stat`, returning its label and times.
+    ///
+    /// Guest time is already counted in user time, so it isn't added again.
+    fn parse(line: &str) -> Option<(&str, CpuTimes)> {
+        let mut parts = line.split_whitespace();
+        let label = parts.next().filter(|label| label.starts_with("cpu"))?;
+
+        let mut values = parts.map(|value| value.parse::<u64>().unwrap_or(0));
+        let mut next = || values.next().unwrap_or(0);
+        let (user, nice, system, idle, iowait, irq, softirq, steal) = (
+            next(),
+            next(),
+            next(),
+            next(),
+            next(),
+            next(),
+            next(),
+            next(),
+        );
+
+        let busy = user + nice + system + irq + softirq + steal;
+
+        Some((
+            label,
+            CpuTimes {
+                busy,
+                total: busy + idle + iowait,
+            },
+        ))
+    }
+
+    /// The usage as a percentage since `prev`.
+    fn usage_since(&self, prev: CpuTimes) -> f32 {
+        let total = self.total.saturating_sub(prev.total);
+
+        if total == 0 {
+            0.0
+        } else {
+            (self.busy.saturating_sub(prev.busy) as f64 / total as f64 * 100.0) as f32
+        }
+    }
+}
+
+/// The CPU times from the last read, as usage is measured between reads.
+#[derive(Debug, Default)]
+pub(crate) struct CpuState {
+    prev_average: CpuTimes,
+    prev_cpus: Vec<CpuTimes>,
+}
+
+impl CpuState {
+    /// Reads the usage of each CPU, and of all of them if `show_average_cpu`
+    /// is set, since the last read. The first read is the usage since boot.
+    pub(crate) fn get_cpu_data_list(
+        &mut self, roots: &SystemRoots, show_average_cpu: bool,
+    ) -> CollectionResult<CpuHarvest> {
+        let stat = fs::read_to_string(roots.proc_path("stat"))?;
+
+        let mut average = None;
+        let mut cpus = Vec::new();
+        for (label, times) in stat.lines().filter_map(CpuTimes::parse) {
+            if label == "cpu" {
+                average = Some(times);
+            } else {
+                cpus.push(times);
+            }
+        }
+
+        let mut harvest = Vec::with_capacity(cpus.len() + 1);
+        if let Some(average) = average {
+            if show_average_cpu {
+                harvest.push(CpuData {
+                    data_type: CpuDataType::Avg,
+                    usage: average.usage_since(self.prev_average),
+                });
+            }
+            self.prev_average = average;
+        }
+
+        harvest.extend(cpus.iter().enumerate().map(|(index, times)| CpuData {
+            data_type: CpuDataType::Cpu(index),
+            usage: times.usage_since(self.prev_cpus.get(index).copied().unwrap_or_default()),
+        }));
+        self.prev_cpus = cpus;
+
+        Ok(harvest)
+    }
+}
+
+/// Returns the number of CPUs listed in `/proc/stat`.
+pub(crate) fn num_cpus(roots: &SystemRoots) -> usize {
+    fs::read_to_string(roots.proc_path("stat"))
+        .map(|stat| {
+            stat.lines()
+                .filter_map(CpuTimes::parse)
+                .filter(|(label, _)| *label != "cpu")
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+/// Reads the load averages from `/proc/loadavg`.
+pub(crate) fn get_load_avg(roots: &SystemRoots) -> Option<LoadAvgHarvest> {
+    let loadavg = fs::read_to_string(roots.proc_path("loadavg")).ok()?;
+    let mut values = loadavg
+        .split_whitespace()
+        .map(|value| value.parse::<f32>().ok());
+
+    Some([values.next()??, values.next()??, values.next()??])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fixture_cpu_usage() {
+        let roots = SystemRoots::fixture();
+        let mut state = CpuState::default();
+
+        let cpus = state.get_cpu_data_list(&roots, true).unwrap();
+        assert_eq!(cpus.len(), 3);
+        assert!(matches!(cpus[0].data_type, CpuDataType::Avg));
+        assert!(matches!(cpus[2].data_type, CpuDataType::Cpu(1)));
+
+        // 86378 busy ticks out of 194391 since boot.
+        assert!((cpus[0].usage - 44.435).abs() < 0.01);
+
+        // Nothing has changed since the last read.
+        let cpus = state.get_cpu_data_list(&roots, false).unwrap();
+        assert_eq!(cpus.len(), 2);
+        assert_eq!(cpus[0].usage, 0.0);
+
+        assert_eq!(num_cpus(&roots), 2);
+    }
+
+    #[test]
+    fn test_cpu_usage_since() {
+        let (_, prev) = CpuTimes::parse("cpu0 100 0 100 800 0 0 0 0 0 0").unwrap();
+        let (_, curr) = CpuTimes::parse("cpu0 150 0 150 850 50 0 0 0 0 0").unwrap();
+
+        assert_eq!(curr.usage_since(prev), 50.0);
+        assert!(CpuTimes::parse("intr 0").is_none());
+    }
+
+    #[test]
+    fn test_fixture_load_avg() {
+        assert_eq!(
+            get_load_avg(&SystemRoots::fixture()),
+            Some([0.52, 0.58, 0.59])
+        );
+    }
+}
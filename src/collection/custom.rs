@@ -0,0 +1,487 @@
+//! Data collection for user-defined custom metrics.
+//!
+//! Each custom metric reads the output of a command or the contents of a file
+//! every few collections, and extracts one or more numbers from it using either
+//! a regex or a list of JSON paths.
+//!
+//! Sources are read on worker threads, as a command can hang or a file can be
+//! something like a FIFO that never ends. Sources that time out are skipped for
+//! a while.
+
+use std::{
+    fs,
+    io::Read,
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use regex::Regex;
+
+use super::{
+    diagnostics::{CollectionDiagnostics, Source},
+    worker::{StaleSources, Worker, WorkerResult},
+};
+
+/// How long a custom metric command may run before it is killed.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long to wait between checks on whether a command has exited.
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Matches the first number in some text. Used if no regex or JSON path is given.
+pub const DEFAULT_NUMBER_REGEX: &str = r"[-+]?(?:\d+\.?\d*|\.\d+)(?:[eE][-+]?\d+)?";
+
+/// Where to read a custom metric from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomMetricSource {
+    /// A program and its arguments; the standard output is used.
+    Command(Vec<String>),
+
+    /// A file to read, such as something in sysfs.
+    File(PathBuf),
+}
+
+/// How to extract numbers from the read text.
+#[derive(Debug, Clone)]
+pub enum CustomMetricExtractor {
+    /// Uses each capture group of the first match, or the whole match if the
+    /// regex has no capture groups.
+    Regex(Regex),
+
+    /// Uses each dotted path (e.g. `stats.queues.0.depth`) into a JSON document.
+    JsonPath(Vec<String>),
+}
+
+impl PartialEq for CustomMetricExtractor {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Regex(a), Self::Regex(b)) => a.as_str() == b.as_str(),
+            (Self::JsonPath(a), Self::JsonPath(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for CustomMetricExtractor {}
+
+impl CustomMetricExtractor {
+    /// The number of values this extractor produces.
+    pub fn num_values(&self) -> usize {
+        match self {
+            CustomMetricExtractor::Regex(regex) => (regex.captures_len() - 1).max(1),
+            CustomMetricExtractor::JsonPath(paths) => paths.len(),
+        }
+    }
+
+    /// Returns the default name for each value, given the metric name.
+    pub fn default_labels(&self, metric_name: &str) -> Vec<String> {
+        match self {
+            CustomMetricExtractor::Regex(regex) => {
+                if regex.captures_len() <= 2 {
+                    vec![metric_name.to_string()]
+                } else {
+                    regex
+                        .capture_names()
+                        .skip(1)
+                        .enumerate()
+                        .map(|(index, name)| match name {
+                            Some(name) => name.to_string(),
+                            None => format!("{metric_name} {}", index + 1),
+                        })
+                        .collect()
+                }
+            }
+            CustomMetricExtractor::JsonPath(paths) => paths.clone(),
+        }
+    }
+
+    /// Extracts values from the given text. Any value that could not be found
+    /// or parsed is returned as [`None`].
+    pub fn extract(&self, text: &str) -> Vec<Option<f64>> {
+        match self {
+            CustomMetricExtractor::Regex(regex) => match regex.captures(text) {
+                Some(captures) if captures.len() == 1 => {
+                    vec![captures.get(0).and_then(|m| parse_number(m.as_str()))]
+                }
+                Some(captures) => captures
+                    .iter()
+                    .skip(1)
+                    .map(|m| m.and_then(|m| parse_number(m.as_str())))
+                    .collect(),
+                None => vec![None; self.num_values()],
+            },
+            CustomMetricExtractor::JsonPath(paths) => {
+                let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+                    return vec![None; paths.len()];
+                };
+
+                paths
+                    .iter()
+                    .map(|path| value.pointer(&json_pointer(path)).and_then(json_number))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A custom metric, as parsed from the config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomMetric {
+    /// The name of the metric, used to refer to it in the layout.
+    pub name: String,
+
+    /// Where to read the metric from.
+    pub source: CustomMetricSource,
+
+    /// How many collections to wait between reads. Must be at least 1.
+    pub interval: u64,
+
+    /// How to extract values from what was read.
+    pub extractor: CustomMetricExtractor,
+
+    /// The unit to display alongside the values.
+    pub unit: Option<String>,
+
+    /// The name of each extracted value.
+    pub labels: Vec<String>,
+}
+
+/// The most recent values of a custom metric.
+#[derive(Debug, Clone)]
+pub struct CustomMetricData {
+    /// The name of the metric.
+    pub name: String,
+
+    /// The values, in the same order as the metric's labels. Values that could
+    /// not be read are [`None`].
+    pub values: Vec<Option<f64>>,
+}
+
+/// Tracks when a custom metric should next be read, and its last values.
+#[derive(Debug)]
+pub struct CustomMetricState {
+    metric: CustomMetric,
+    collections_until_read: u64,
+    last_values: Vec<Option<f64>>,
+
+    /// Reads the source, started on the first read.
+    worker: Option<Worker<CustomMetricSource, Option<String>>>,
+}
+
+impl CustomMetricState {
+    pub fn new(metric: CustomMetric) -> Self {
+        let last_values = vec![None; metric.extractor.num_values()];
+
+        Self {
+            metric,
+            collections_until_read: 0,
+            last_values,
+            worker: None,
+        }
+    }
+
+    /// The key of this metric's source in [`StaleSources`].
+    fn stale_key(&self) -> String {
+        format!("custom:{}", self.metric.name)
+    }
+
+    /// Asks the worker to read the metric if enough collections have passed
+    /// and its source isn't stale. Returns whether it was asked.
+    fn request(&mut self, now: Instant, stale_sources: &mut StaleSources) -> bool {
+        let is_due = self.collections_until_read == 0;
+        if is_due {
+            self.collections_until_read = self.metric.interval.max(1);
+        }
+        self.collections_until_read -= 1;
+
+        if !is_due {
+            return false;
+        }
+
+        let key = self.stale_key();
+        let worker = self.worker.get_or_insert_with(|| {
+            Worker::spawn("btm-custom-metric", |source: CustomMetricSource| {
+                read_source(&source)
+            })
+        });
+
+        if stale_sources.is_stale(&key, now, worker.is_busy()) {
+            self.last_values = vec![None; self.metric.extractor.num_values()];
+            return false;
+        }
+
+        worker.send(self.metric.source.clone());
+        true
+    }
+
+    /// Waits until `deadline` for the metric if it was requested, and returns
+    /// the latest values. Values from the last read are reused between reads.
+    fn finish(
+        &mut self, is_requested: bool, now: Instant, deadline: Instant,
+        stale_sources: &mut StaleSources, diagnostics: &mut CollectionDiagnostics,
+    ) -> CustomMetricData {
+        let key = self.stale_key();
+        if let Some(worker) = self.worker.as_mut().filter(|_| is_requested) {
+            let text = match worker.wait_until(deadline) {
+                WorkerResult::Done(text) => {
+                    stale_sources.mark_responsive(&key);
+                    text
+                }
+                WorkerResult::Failed => None,
+                WorkerResult::TimedOut => {
+                    stale_sources.mark_stale(&key, now);
+                    diagnostics.record_error(
+                        Source::Custom,
+                        anyhow!("'{}' timed out, skipping it for a while", self.metric.name),
+                    );
+                    None
+                }
+            };
+
+            self.last_values = match text {
+                Some(text) => self.metric.extractor.extract(&text),
+                None => vec![None; self.metric.extractor.num_values()],
+            };
+        }
+
+        CustomMetricData {
+            name: self.metric.name.clone(),
+            values: self.last_values.clone(),
+        }
+    }
+}
+
+/// Returns the latest values for each custom metric. The metrics that are due
+/// are read in parallel, and any that take too long are skipped for a while.
+pub(crate) fn get_custom_metrics(
+    states: &mut [CustomMetricState], stale_sources: &mut StaleSources,
+    diagnostics: &mut CollectionDiagnostics,
+) -> Vec<CustomMetricData> {
+    let now = Instant::now();
+
+    // Every request is sent before waiting on any, so they are read in parallel.
+    let requested = states
+        .iter_mut()
+        .map(|state| state.request(now, stale_sources))
+        .collect::<Vec<_>>();
+
+    let deadline = now + COMMAND_TIMEOUT;
+    states
+        .iter_mut()
+        .zip(requested)
+        .map(|(state, is_requested)| {
+            state.finish(is_requested, now, deadline, stale_sources, diagnostics)
+        })
+        .collect()
+}
+
+fn read_source(source: &CustomMetricSource) -> Option<String> {
+    match source {
+        CustomMetricSource::Command(command) => run_command(command, COMMAND_TIMEOUT),
+        CustomMetricSource::File(path) => fs::read_to_string(path).ok(),
+    }
+}
+
+/// Runs a command and returns its standard output if it exits successfully
+/// before the timeout. Commands that take too long are killed.
+fn run_command(command: &[String], timeout: Duration) -> Option<String> {
+    let (program, args) = command.split_first()?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // The output is read while the command runs, so it doesn't block once it
+    // fills the pipe.
+    let mut stdout = child.stdout.take()?;
+    let (output_sender, output) = mpsc::channel();
+    thread::spawn(move || {
+        let mut text = String::new();
+        let _ = output_sender.send(stdout.read_to_string(&mut text).ok().map(|_| text));
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(COMMAND_POLL_INTERVAL),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+
+    if !status.success() {
+        return None;
+    }
+
+    // Anything the command started in the background may still hold the pipe
+    // open, so this is also bounded by the deadline.
+    output
+        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .ok()
+        .flatten()
+}
+
+fn parse_number(text: &str) -> Option<f64> {
+    text.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+fn json_number(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(num) => num.as_f64(),
+        serde_json::Value::String(text) => parse_number(text),
+        serde_json::Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        _ => None,
+    }
+}
+
+/// Converts a dotted path like `$.stats.queues.0` into a JSON pointer like
+/// `/stats/queues/0`.
+fn json_pointer(path: &str) -> String {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let path = path.strip_prefix('.').unwrap_or(path);
+
+    if path.is_empty() {
+        return String::new();
+    }
+
+    path.split('.')
+        .map(|part| format!("/{}", part.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn regex(re: &str) -> CustomMetricExtractor {
+        CustomMetricExtractor::Regex(Regex::new(re).unwrap())
+    }
+
+    #[test]
+    fn regex_extraction() {
+        let default = regex(DEFAULT_NUMBER_REGEX);
+        assert_eq!(default.extract("queue depth: 42\n"), vec![Some(42.0)]);
+        assert_eq!(default.extract("temp=-3.5e1C"), vec![Some(-35.0)]);
+        assert_eq!(default.extract("nothing here"), vec![None]);
+        assert_eq!(default.default_labels("queue"), vec!["queue"]);
+
+        let groups = regex(r"rx=(?<rx>\d+) tx=(\d+)");
+        assert_eq!(groups.num_values(), 2);
+        assert_eq!(groups.extract("rx=10 tx=20"), vec![Some(10.0), Some(20.0)]);
+        assert_eq!(groups.extract("rx=10"), vec![None, None]);
+        assert_eq!(groups.default_labels("link"), vec!["rx", "link 2"]);
+    }
+
+    #[test]
+    fn json_extraction() {
+        let json = CustomMetricExtractor::JsonPath(vec![
+            "$.queues.0.depth".to_string(),
+            "workers".to_string(),
+            "healthy".to_string(),
+            "missing.key".to_string(),
+        ]);
+        let text = r#"{"queues": [{"depth": 7}], "workers": "3", "healthy": true}"#;
+
+        assert_eq!(
+            json.extract(text),
+            vec![Some(7.0), Some(3.0), Some(1.0), None]
+        );
+        assert_eq!(json.extract("not json"), vec![None; 4]);
+    }
+
+    #[test]
+    fn json_pointer_paths() {
+        assert_eq!(json_pointer("a.b.0"), "/a/b/0");
+        assert_eq!(json_pointer("$.a"), "/a");
+        assert_eq!(json_pointer("a/b~c"), "/a~1b~0c");
+        assert_eq!(json_pointer("$"), "");
+    }
+
+    #[test]
+    fn reads_on_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("metric");
+        fs::write(&path, "1").unwrap();
+
+        let mut states = [CustomMetricState::new(CustomMetric {
+            name: "test".to_string(),
+            source: CustomMetricSource::File(path.clone()),
+            interval: 2,
+            extractor: regex(DEFAULT_NUMBER_REGEX),
+            unit: None,
+            labels: vec!["test".to_string()],
+        })];
+        let mut stale_sources = StaleSources::default();
+        let mut diagnostics = CollectionDiagnostics::default();
+        let mut update = || {
+            get_custom_metrics(&mut states, &mut stale_sources, &mut diagnostics)
+                .remove(0)
+                .values
+        };
+
+        assert_eq!(update(), vec![Some(1.0)]);
+        fs::write(&path, "2").unwrap();
+        assert_eq!(update(), vec![Some(1.0)]);
+        assert_eq!(update(), vec![Some(2.0)]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn skips_hanging_sources() {
+        let mut states = [CustomMetricState::new(CustomMetric {
+            name: "hang".to_string(),
+            source: CustomMetricSource::Command(vec!["sleep".to_string(), "5".to_string()]),
+            interval: 1,
+            extractor: regex(DEFAULT_NUMBER_REGEX),
+            unit: None,
+            labels: vec!["hang".to_string()],
+        })];
+        let mut stale_sources = StaleSources::default();
+        let mut diagnostics = CollectionDiagnostics::default();
+
+        let data = get_custom_metrics(&mut states, &mut stale_sources, &mut diagnostics);
+        assert_eq!(data[0].values, vec![None]);
+        assert!(diagnostics.source(Source::Custom).last_error.is_some());
+
+        // It is skipped rather than waited on again.
+        let started = Instant::now();
+        get_custom_metrics(&mut states, &mut stale_sources, &mut diagnostics);
+        assert!(started.elapsed() < COMMAND_TIMEOUT);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_source() {
+        let echo = vec!["echo".to_string(), "5".to_string()];
+        assert_eq!(
+            run_command(&echo, COMMAND_TIMEOUT)
+                .as_deref()
+                .map(str::trim),
+            Some("5")
+        );
+
+        let sleep = vec!["sleep".to_string(), "5".to_string()];
+        assert_eq!(run_command(&sleep, Duration::from_millis(50)), None);
+
+        let fail = vec!["false".to_string()];
+        assert_eq!(run_command(&fail, COMMAND_TIMEOUT), None);
+
+        // More output than fits in a pipe buffer.
+        let seq = vec!["seq".to_string(), "100000".to_string()];
+        assert_eq!(
+            run_command(&seq, COMMAND_TIMEOUT).map(|output| output.lines().count()),
+            Some(100000)
+        );
+    }
+}
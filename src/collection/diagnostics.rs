@@ -0,0 +1,317 @@
+//! Diagnostics about data collection itself, such as how long each source took
+//! and the last error it hit. These are shown in the diagnostics dialog to help
+//! figure out why some data is missing.
+
+use std::time::{Duration, Instant};
+
+use super::error::CollectionError;
+
+/// A source of data that is tracked in diagnostics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Cpu,
+    Memory,
+    Network,
+    Processes,
+    Disks,
+    Temperatures,
+    Batteries,
+    Gpus,
+    Power,
+    Custom,
+    Connections,
+    TcpTraffic,
+    ProcessMemory,
+}
+
+impl Source {
+    /// All sources, in the order they are shown.
+    pub const ALL: [Source; 13] = [
+        Source::Cpu,
+        Source::Memory,
+        Source::Network,
+        Source::Processes,
+        Source::Disks,
+        Source::Temperatures,
+        Source::Batteries,
+        Source::Gpus,
+        Source::Power,
+        Source::Custom,
+        Source::Connections,
+        Source::TcpTraffic,
+        Source::ProcessMemory,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Source::Cpu => "CPU",
+            Source::Memory => "Memory",
+            Source::Network => "Network",
+            Source::Processes => "Processes",
+            Source::Disks => "Disks",
+            Source::Temperatures => "Temperatures",
+            Source::Batteries => "Batteries",
+            Source::Gpus => "GPUs",
+            Source::Power => "Power",
+            Source::Custom => "Custom metrics",
+            Source::Connections => "Connections",
+            Source::TcpTraffic => "TCP traffic",
+            Source::ProcessMemory => "Process memory",
+        }
+    }
+}
+
+/// Diagnostics for a single source.
+#[derive(Clone, Debug, Default)]
+pub struct SourceDiagnostics {
+    /// When the source was last collected.
+    pub last_collected: Option<Instant>,
+
+    /// How long the source took the last time it was collected.
+    pub last_duration: Duration,
+
+    /// How many entries (e.g. sensors or processes) were collected last time.
+    pub entries: usize,
+
+    /// The last error, and when it happened. This is kept even if later
+    /// collections succeed.
+    pub last_error: Option<(Instant, String)>,
+
+    /// How many times collecting this source has failed.
+    pub error_count: u64,
+}
+
+/// bottom's own resource usage.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SelfUsage {
+    /// CPU usage, as a percentage of one core.
+    pub cpu_percent: f32,
+
+    /// Resident memory, in bytes.
+    pub rss_bytes: u64,
+}
+
+/// Measures bottom's own CPU usage from how much CPU time it used between
+/// updates.
+#[derive(Debug)]
+pub(crate) struct SelfCpuUsage {
+    /// When the CPU time was last read, and what it was.
+    last: Option<(Instant, Duration)>,
+}
+
+impl SelfCpuUsage {
+    pub(crate) fn new() -> Self {
+        SelfCpuUsage {
+            last: own_cpu_time().map(|cpu_time| (Instant::now(), cpu_time)),
+        }
+    }
+
+    /// Returns the CPU usage since the last update, as a percentage of one
+    /// core, if the CPU time could be read both times.
+    pub(crate) fn update(&mut self) -> Option<f32> {
+        let now = Instant::now();
+        let current = own_cpu_time().map(|cpu_time| (now, cpu_time));
+        let (last_time, last_cpu_time) = std::mem::replace(&mut self.last, current)?;
+        let (_, cpu_time) = current?;
+
+        let elapsed = now.saturating_duration_since(last_time).as_secs_f32();
+        (elapsed > 0.0)
+            .then(|| cpu_time.saturating_sub(last_cpu_time).as_secs_f32() / elapsed * 100.0)
+    }
+}
+
+/// Returns the user and system CPU time bottom has used so far.
+#[cfg(unix)]
+fn own_cpu_time() -> Option<Duration> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+
+    // SAFETY: getrusage only writes to `usage`, and we only read it on success.
+    let usage = unsafe {
+        if libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+            return None;
+        }
+        usage.assume_init()
+    };
+
+    let to_duration =
+        |time: libc::timeval| Duration::new(time.tv_sec as u64, (time.tv_usec as u32) * 1000);
+
+    Some(to_duration(usage.ru_utime) + to_duration(usage.ru_stime))
+}
+
+/// Returns the user and kernel CPU time bottom has used so far.
+#[cfg(target_os = "windows")]
+fn own_cpu_time() -> Option<Duration> {
+    use windows::Win32::{
+        Foundation::FILETIME,
+        System::Threading::{GetCurrentProcess, GetProcessTimes},
+    };
+
+    let mut creation = FILETIME::default();
+    let mut exit = FILETIME::default();
+    let mut kernel = FILETIME::default();
+    let mut user = FILETIME::default();
+
+    // SAFETY: The current process handle is always valid, and each time is
+    // only read on success.
+    unsafe {
+        GetProcessTimes(
+            GetCurrentProcess(),
+            &mut creation,
+            &mut exit,
+            &mut kernel,
+            &mut user,
+        )
+        .ok()?;
+    }
+
+    // Each time is in 100 nanosecond intervals.
+    let to_duration = |time: FILETIME| {
+        Duration::from_nanos(
+            ((u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime)) * 100,
+        )
+    };
+
+    Some(to_duration(kernel) + to_duration(user))
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+fn own_cpu_time() -> Option<Duration> {
+    None
+}
+
+/// Diagnostics for all sources.
+#[derive(Clone, Debug, Default)]
+pub struct CollectionDiagnostics {
+    sources: [SourceDiagnostics; Source::ALL.len()],
+
+    /// How long the last whole collection took.
+    pub last_update_duration: Duration,
+
+    /// bottom's own resource usage, if it could be read.
+    pub self_usage: Option<SelfUsage>,
+}
+
+impl CollectionDiagnostics {
+    /// Returns the diagnostics for a source.
+    pub fn source(&self, source: Source) -> &SourceDiagnostics {
+        &self.sources[source as usize]
+    }
+
+    /// Returns the diagnostics for every source that has been collected or has
+    /// failed at least once.
+    pub fn iter(&self) -> impl Iterator<Item = (Source, &SourceDiagnostics)> {
+        Source::ALL
+            .into_iter()
+            .map(|source| (source, self.source(source)))
+            .filter(|(_, diagnostics)| {
+                diagnostics.last_collected.is_some() || diagnostics.last_error.is_some()
+            })
+    }
+
+    /// Records the result of collecting a source that started at `started`,
+    /// using `entries` to count how many entries were collected. Returns the
+    /// collected data, if any.
+    pub(crate) fn record<T, E: Into<CollectionError>>(
+        &mut self, source: Source, started: Instant, result: Result<T, E>,
+        entries: impl FnOnce(&T) -> usize,
+    ) -> Option<T> {
+        match result {
+            Ok(data) => {
+                self.record_ok(source, started, entries(&data));
+                Some(data)
+            }
+            Err(err) => {
+                self.record_ok(source, started, 0);
+                self.record_error(source, err);
+                None
+            }
+        }
+    }
+
+    /// Records that collecting a source that started at `started` succeeded
+    /// with `entries` entries.
+    pub(crate) fn record_ok(&mut self, source: Source, started: Instant, entries: usize) {
+        let now = Instant::now();
+        let diagnostics = &mut self.sources[source as usize];
+
+        diagnostics.last_collected = Some(now);
+        diagnostics.last_duration = now.duration_since(started);
+        diagnostics.entries = entries;
+    }
+
+    /// Records an error for a source without counting it as a collection, such
+    /// as when only part of a source failed.
+    pub(crate) fn record_error(&mut self, source: Source, err: impl Into<CollectionError>) {
+        let diagnostics = &mut self.sources[source as usize];
+
+        diagnostics.last_error = Some((Instant::now(), err.into().to_string()));
+        diagnostics.error_count += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn measures_own_cpu_usage() {
+        let mut usage = SelfCpuUsage::new();
+
+        // Spin for a bit so there is some CPU time to measure.
+        let started = Instant::now();
+        let mut n = 0_u64;
+        while started.elapsed() < Duration::from_millis(50) {
+            n = std::hint::black_box(n.wrapping_add(1));
+        }
+
+        let percent = usage.update().unwrap();
+        assert!(percent > 0.0, "{percent}");
+    }
+
+    #[test]
+    fn records_sources() {
+        let mut diagnostics = CollectionDiagnostics::default();
+        assert_eq!(diagnostics.iter().count(), 0);
+
+        let started = Instant::now();
+        let temps = diagnostics.record(
+            Source::Temperatures,
+            started,
+            Ok::<_, CollectionError>(vec![1, 2, 3]),
+            Vec::len,
+        );
+        assert_eq!(temps, Some(vec![1, 2, 3]));
+
+        let disks: Option<Vec<i32>> =
+            diagnostics.record(Source::Disks, started, Err("permission denied"), Vec::len);
+        assert_eq!(disks, None);
+
+        diagnostics.record(
+            Source::Disks,
+            started,
+            Ok::<_, CollectionError>(vec![1, 2]),
+            Vec::len,
+        );
+
+        let sources = diagnostics.iter().map(|(s, _)| s).collect::<Vec<_>>();
+        assert_eq!(sources, vec![Source::Disks, Source::Temperatures]);
+
+        assert_eq!(diagnostics.source(Source::Temperatures).entries, 3);
+        assert!(
+            diagnostics
+                .source(Source::Temperatures)
+                .last_error
+                .is_none()
+        );
+
+        // Errors are kept around after a later success.
+        let disks = diagnostics.source(Source::Disks);
+        assert_eq!(disks.entries, 2);
+        assert_eq!(disks.error_count, 1);
+        assert_eq!(
+            disks.last_error.as_ref().map(|(_, err)| err.as_str()),
+            Some("permission denied")
+        );
+    }
+}
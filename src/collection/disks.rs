@@ -30,7 +30,7 @@ cfg_if! {
 use cfg_if::cfg_if;
 use rustc_hash::FxHashMap as HashMap;
 
-use crate::app::filter::Filter;
+use crate::{app::filter::Filter, collection::DataCollector};
 
 #[derive(Clone, Debug, Default)]
 pub struct DiskHarvest {
@@ -45,6 +45,10 @@ pub struct DiskHarvest {
     pub free_space: Option<u64>,
     pub used_space: Option<u64>,
     pub total_space: Option<u64>,
+
+    /// Whether reading the usage of this disk timed out, such as with a dead
+    /// network mount.
+    pub unresponsive: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -61,11 +65,18 @@ cfg_if! {
         pub use io_counters::IoCounters;
 
         /// Returns the I/O usage of certain mount points.
-        pub fn get_io_usage() -> anyhow::Result<IoHarvest> {
+        pub fn get_io_usage(
+            #[cfg_attr(not(target_os = "linux"), expect(unused_variables))] collector: &DataCollector,
+        ) -> anyhow::Result<IoHarvest> {
             let mut io_hash: HashMap<String, Option<IoData>> = HashMap::default();
 
+            #[cfg(target_os = "linux")]
+            let io_stats = io_stats(&collector.roots)?;
+            #[cfg(not(target_os = "linux"))]
+            let io_stats = io_stats()?;
+
             // TODO: Maybe rewrite this to not do a result of vec of result...
-            for io in io_stats()?.into_iter() {
+            for io in io_stats.into_iter() {
                 let mount_point = io.device_name().to_string_lossy();
 
                 io_hash.insert(
@@ -80,7 +91,7 @@ cfg_if! {
             Ok(io_hash)
         }
     } else if #[cfg(not(target_os = "freebsd"))] {
-        pub fn get_io_usage() -> anyhow::Result<IoHarvest> {
+        pub fn get_io_usage(_collector: &DataCollector) -> anyhow::Result<IoHarvest> {
             anyhow::bail!("Unsupported OS");
         }
     }
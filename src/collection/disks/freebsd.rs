@@ -24,7 +24,7 @@ struct FileSystem {
     mounted_on: String,
 }
 
-pub fn get_io_usage() -> CollectionResult<IoHarvest> {
+pub fn get_io_usage(_collector: &DataCollector) -> CollectionResult<IoHarvest> {
     // TODO: Should this (and other I/O collectors) fail fast? In general, should
     // collection ever fail fast?
     #[cfg_attr(not(feature = "zfs"), expect(unused_mut))]
@@ -69,6 +69,7 @@ pub fn get_disk_usage(collector: &DataCollector) -> CollectionResult<Vec<DiskHar
                         free_space: Some(disk.available_blocks * 1024),
                         used_space: Some(disk.used_blocks * 1024),
                         total_space: Some(disk.total_blocks * 1024),
+                        unresponsive: false,
                         mount_point: disk.mounted_on,
                         name: disk.name,
                     })
@@ -41,6 +41,7 @@ pub(crate) fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<Di
                     free_space: Some(free_space),
                     used_space: Some(used_space),
                     total_space: Some(total_space),
+                    unresponsive: false,
                 })
             } else {
                 None
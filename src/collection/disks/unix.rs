@@ -24,16 +24,42 @@ cfg_if::cfg_if! {
 use file_systems::*;
 use usage::*;
 
+use std::time::{Duration, Instant};
+
+use rustc_hash::FxHashMap as HashMap;
+
 use super::{DiskHarvest, keep_disk_entry};
-use crate::collection::DataCollector;
+use crate::collection::{
+    DataCollector,
+    worker::{Worker, WorkerResult},
+};
+
+/// How long to wait for the usage of all disks before treating any remaining
+/// ones as unresponsive.
+const USAGE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The worker reading the usage of each mount point, kept between collections.
+pub(crate) type UsageWorkers = HashMap<String, Worker<Partition, Option<Usage>>>;
 
 /// Returns the disk usage of the mounted (and for now, physical) disks.
-pub fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<DiskHarvest>> {
+///
+/// Usage is read on a worker thread per mount point, as `statvfs` can hang on
+/// things like dead network mounts. Mounts that time out are reported as
+/// unresponsive, and skipped for a while.
+pub fn get_disk_usage(collector: &mut DataCollector) -> anyhow::Result<Vec<DiskHarvest>> {
     let disk_filter = &collector.filters.disk_filter;
     let mount_filter = &collector.filters.mount_filter;
-    let mut vec_disks: Vec<DiskHarvest> = Vec::new();
+    let stale_mounts = &mut collector.stale_sources;
+    let workers = &mut collector.disk_usage_workers;
+    let now = Instant::now();
+    let mut pending = Vec::new();
+
+    #[cfg(target_os = "linux")]
+    let partitions = physical_partitions(&collector.roots)?;
+    #[cfg(not(target_os = "linux"))]
+    let partitions = physical_partitions()?;
 
-    for partition in physical_partitions()? {
+    for partition in partitions {
         let name = partition.get_device_name();
         let mount_point = partition.mount_point().to_string_lossy().to_string();
 
@@ -50,29 +76,66 @@ pub fn get_disk_usage(collector: &DataCollector) -> anyhow::Result<Vec<DiskHarve
         // 3. Anything else is allowed.
 
         if keep_disk_entry(&name, &mount_point, disk_filter, mount_filter) {
-            // The usage line can fail in some cases (for example, if you use Void Linux +
-            // LUKS, see https://github.com/ClementTsang/bottom/issues/419 for details).
-            if let Ok(usage) = partition.usage() {
-                let total = usage.total();
-
-                vec_disks.push(DiskHarvest {
-                    free_space: Some(usage.free()),
-                    used_space: Some(total - usage.available()),
-                    total_space: Some(total),
-                    mount_point,
-                    name,
-                });
-            } else {
-                vec_disks.push(DiskHarvest {
-                    free_space: None,
-                    used_space: None,
-                    total_space: None,
-                    mount_point,
-                    name,
-                });
+            let worker = workers.entry(mount_point.clone()).or_insert_with(|| {
+                Worker::spawn("btm-disk-usage", |partition: Partition| {
+                    partition.usage().ok()
+                })
+            });
+
+            // Requests are sent to every worker before waiting on any, so they
+            // are read in parallel.
+            let is_requested = !stale_mounts.is_stale(&mount_point, now, worker.is_busy());
+            if is_requested {
+                worker.send(partition);
             }
+
+            pending.push((name, mount_point, is_requested));
         }
     }
 
+    // Stop the workers of mount points that are gone. A worker that is stuck
+    // exits once its request returns.
+    workers.retain(|mount_point, _| pending.iter().any(|(_, mount, _)| mount == mount_point));
+
+    let deadline = now + USAGE_TIMEOUT;
+    let vec_disks = pending
+        .into_iter()
+        .map(|(name, mount_point, is_requested)| {
+            let result = is_requested
+                .then(|| workers.get_mut(&mount_point))
+                .flatten()
+                .map(|worker| worker.wait_until(deadline));
+
+            // The usage line can fail in some cases (for example, if you use Void Linux +
+            // LUKS, see https://github.com/ClementTsang/bottom/issues/419 for details).
+            let (usage, unresponsive) = match result {
+                Some(WorkerResult::Done(usage)) => {
+                    stale_mounts.mark_responsive(&mount_point);
+                    (usage, false)
+                }
+                Some(WorkerResult::Failed) => (None, false),
+                Some(WorkerResult::TimedOut) => {
+                    stale_mounts.mark_stale(&mount_point, now);
+                    (None, true)
+                }
+                None => (None, true),
+            };
+
+            let total = usage.as_ref().map(Usage::total);
+
+            DiskHarvest {
+                free_space: usage.as_ref().map(Usage::free),
+                used_space: usage
+                    .as_ref()
+                    .zip(total)
+                    .map(|(usage, total)| total - usage.available()),
+                total_space: total,
+                unresponsive,
+                mount_point,
+                name,
+            }
+        })
+        .collect();
+
     Ok(vec_disks)
 }
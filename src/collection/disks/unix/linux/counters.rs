@@ -7,7 +7,7 @@ use std::{
     str::FromStr,
 };
 
-use crate::collection::disks::IoCounters;
+use crate::collection::{SystemRoots, disks::IoCounters};
 
 /// Copied from the `psutil` sources:
 ///
@@ -65,11 +65,9 @@ impl FromStr for IoCounters {
 }
 
 /// Returns an iterator of disk I/O stats. Pulls data from `/proc/diskstats`.
-pub fn io_stats() -> anyhow::Result<Vec<IoCounters>> {
-    const PROC_DISKSTATS: &str = "/proc/diskstats";
-
+pub fn io_stats(roots: &SystemRoots) -> anyhow::Result<Vec<IoCounters>> {
     let mut results = vec![];
-    let mut reader = BufReader::new(File::open(PROC_DISKSTATS)?);
+    let mut reader = BufReader::new(File::open(roots.proc_path("diskstats"))?);
     let mut line = String::new();
 
     // This saves us from doing a string allocation on each iteration compared to
@@ -88,10 +86,26 @@ pub fn io_stats() -> anyhow::Result<Vec<IoCounters>> {
     #[cfg(feature = "zfs")]
     {
         use crate::collection::disks::zfs_io_counters;
-        if let Ok(mut zfs_io) = zfs_io_counters::zfs_io_stats() {
+        if let Ok(mut zfs_io) = zfs_io_counters::zfs_io_stats(roots) {
             results.append(&mut zfs_io);
         }
     }
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_io_stats() {
+        let stats = io_stats(&SystemRoots::fixture()).unwrap();
+
+        let names: Vec<_> = stats.iter().map(|io| io.device_name()).collect();
+        assert_eq!(names, ["nvme0n1", "nvme0n1p1", "nvme0n1p2", "sda"]);
+
+        assert_eq!(stats[0].read_bytes(), 4139690 * DISK_SECTOR_SIZE);
+        assert_eq!(stats[0].write_bytes(), 5212184 * DISK_SECTOR_SIZE);
+    }
+}
@@ -12,7 +12,10 @@ use std::{
 
 use anyhow::bail;
 
-use crate::collection::disks::unix::{FileSystem, Usage};
+use crate::collection::{
+    SystemRoots,
+    disks::unix::{FileSystem, Usage},
+};
 
 /// Representation of partition details. Based on [`heim`](https://github.com/heim-rs/heim/tree/master).
 pub(crate) struct Partition {
@@ -154,11 +157,9 @@ impl FromStr for Partition {
 
 #[expect(dead_code)]
 /// Returns a [`Vec`] containing all partitions.
-pub(crate) fn partitions() -> anyhow::Result<Vec<Partition>> {
-    const PROC_MOUNTS: &str = "/proc/mounts";
-
+pub(crate) fn partitions(roots: &SystemRoots) -> anyhow::Result<Vec<Partition>> {
     let mut results = vec![];
-    let mut reader = BufReader::new(File::open(PROC_MOUNTS)?);
+    let mut reader = BufReader::new(File::open(roots.proc_path("mounts"))?);
     let mut line = String::new();
 
     // This saves us from doing a string allocation on each iteration compared to
@@ -180,11 +181,9 @@ pub(crate) fn partitions() -> anyhow::Result<Vec<Partition>> {
 
 /// Returns a [`Vec`] containing all *physical* partitions. This is defined by
 /// [`FileSystem::is_physical()`].
-pub(crate) fn physical_partitions() -> anyhow::Result<Vec<Partition>> {
-    const PROC_MOUNTS: &str = "/proc/mounts";
-
+pub(crate) fn physical_partitions(roots: &SystemRoots) -> anyhow::Result<Vec<Partition>> {
     let mut results = vec![];
-    let mut reader = BufReader::new(File::open(PROC_MOUNTS)?);
+    let mut reader = BufReader::new(File::open(roots.proc_path("mounts"))?);
     let mut line = String::new();
 
     // This saves us from doing a string allocation on each iteration compared to
@@ -216,4 +215,20 @@ mod tests {
 
         assert_eq!(fix_mount_point(line), "/run/media/test/Samsung 980");
     }
+
+    #[test]
+    fn test_fixture_physical_partitions() {
+        let partitions = physical_partitions(&SystemRoots::fixture()).unwrap();
+
+        let mount_points: Vec<_> = partitions.iter().map(|p| p.mount_point()).collect();
+        assert_eq!(
+            mount_points,
+            [
+                Path::new("/"),
+                Path::new("/boot/efi"),
+                Path::new("/run/media/user/Samsung 980"),
+            ]
+        );
+        assert_eq!(partitions[0].device(), Some("/dev/nvme0n1p2"));
+    }
 }
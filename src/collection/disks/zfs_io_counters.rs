@@ -59,8 +59,8 @@ pub fn zfs_io_stats() -> anyhow::Result<Vec<IoCounters>> {
 
 /// Returns zpool I/O stats. Pulls data from `/proc/spl/kstat/zfs/*/objset-*`.
 #[cfg(target_os = "linux")]
-pub fn zfs_io_stats() -> anyhow::Result<Vec<IoCounters>> {
-    if let Ok(zpools) = std::fs::read_dir("/proc/spl/kstat/zfs") {
+pub fn zfs_io_stats(roots: &crate::collection::SystemRoots) -> anyhow::Result<Vec<IoCounters>> {
+    if let Ok(zpools) = std::fs::read_dir(roots.proc_path("spl/kstat/zfs")) {
         let zpools_vec: Vec<std::path::PathBuf> = zpools
             .filter_map(|e| {
                 e.ok().and_then(|d| {
@@ -33,6 +33,12 @@ impl std::error::Error for CollectionError {}
 /// A [`Result`] with the error type being a [`DataCollectionError`].
 pub(crate) type CollectionResult<T> = Result<T, CollectionError>;
 
+impl From<anyhow::Error> for CollectionError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::General(err)
+    }
+}
+
 impl From<std::io::Error> for CollectionError {
     fn from(err: std::io::Error) -> Self {
         Self::General(err.into())
@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+const DEFAULT_PROCFS_ROOT: &str = "/proc";
+const DEFAULT_SYSFS_ROOT: &str = "/sys";
+
+/// Where the procfs and sysfs filesystems that Linux collectors read from are
+/// mounted. These default to `/proc` and `/sys`, but can be pointed elsewhere
+/// to monitor a host's filesystems bind-mounted into a container, or to read
+/// from recorded fixture trees in tests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemRoots {
+    procfs: PathBuf,
+    sysfs: PathBuf,
+}
+
+impl Default for SystemRoots {
+    fn default() -> Self {
+        Self {
+            procfs: PathBuf::from(DEFAULT_PROCFS_ROOT),
+            sysfs: PathBuf::from(DEFAULT_SYSFS_ROOT),
+        }
+    }
+}
+
+impl SystemRoots {
+    /// Create a new [`SystemRoots`], using the defaults for any unset root.
+    pub fn new(procfs: Option<PathBuf>, sysfs: Option<PathBuf>) -> Self {
+        Self {
+            procfs: procfs.unwrap_or_else(|| PathBuf::from(DEFAULT_PROCFS_ROOT)),
+            sysfs: sysfs.unwrap_or_else(|| PathBuf::from(DEFAULT_SYSFS_ROOT)),
+        }
+    }
+
+    /// Returns the procfs root.
+    pub fn procfs(&self) -> &Path {
+        &self.procfs
+    }
+
+    /// Returns the sysfs root.
+    pub fn sysfs(&self) -> &Path {
+        &self.sysfs
+    }
+
+    /// Returns `path` relative to the procfs root, e.g. `proc_path("stat")`
+    /// for `/proc/stat`.
+    pub fn proc_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.procfs.join(path)
+    }
+
+    /// Returns `path` relative to the sysfs root, e.g. `sys_path("class/hwmon")`
+    /// for `/sys/class/hwmon`.
+    pub fn sys_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.sysfs.join(path)
+    }
+}
+
+#[cfg(test)]
+impl SystemRoots {
+    /// Returns roots pointing to the recorded fixture trees in `tests/fixtures/linux`.
+    pub fn fixture() -> Self {
+        let base = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/linux");
+
+        Self::new(Some(base.join("proc")), Some(base.join("sys")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_roots() {
+        let roots = SystemRoots::default();
+
+        assert_eq!(roots, SystemRoots::new(None, None));
+        assert_eq!(roots.proc_path("stat"), Path::new("/proc/stat"));
+        assert_eq!(roots.sys_path("class/hwmon"), Path::new("/sys/class/hwmon"));
+    }
+
+    #[test]
+    fn custom_roots() {
+        let roots = SystemRoots::new(Some("/host/proc".into()), Some("/host/sys".into()));
+
+        assert_eq!(roots.procfs(), Path::new("/host/proc"));
+        assert_eq!(roots.proc_path("1/stat"), Path::new("/host/proc/1/stat"));
+        assert_eq!(roots.sys_path("block"), Path::new("/host/sys/block"));
+    }
+}
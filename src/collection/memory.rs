@@ -2,16 +2,17 @@
 
 use std::num::NonZeroU64;
 
-pub(crate) use self::sysinfo::get_ram_usage;
-
-pub mod sysinfo;
-
 cfg_if::cfg_if! {
-    if #[cfg(target_os = "windows")] {
+    if #[cfg(target_os = "linux")] {
+        pub(crate) mod linux;
+    } else if #[cfg(target_os = "windows")] {
+        pub mod sysinfo;
         mod windows;
+        pub(crate) use self::sysinfo::get_ram_usage;
         pub(crate) use self::windows::get_swap_usage;
     } else {
-        pub(crate) use self::sysinfo::{get_cache_usage, get_swap_usage};
+        pub mod sysinfo;
+        pub(crate) use self::sysinfo::{get_cache_usage, get_ram_usage, get_swap_usage};
     }
 }
 
@@ -34,3 +35,11 @@ impl MemData {
         used / total * 100.0
     }
 }
+
+#[inline]
+pub(crate) fn get_usage(used: u64, total: u64) -> Option<MemData> {
+    NonZeroU64::new(total).map(|total_bytes| MemData {
+        total_bytes,
+        used_bytes: used,
+    })
+}
@@ -2,7 +2,9 @@
 use super::MemData;
 #[cfg(all(feature = "zfs", any(target_os = "linux", target_os = "freebsd")))]
 /// Return ARC usage.
-pub(crate) fn get_arc_usage() -> Option<(MemData, u64)> {
+pub(crate) fn get_arc_usage(
+    #[cfg(target_os = "linux")] roots: &crate::collection::SystemRoots,
+) -> Option<(MemData, u64)> {
     use std::num::NonZeroU64;
 
     let (mem_total, mem_used, mem_min) = {
@@ -10,7 +12,7 @@ pub(crate) fn get_arc_usage() -> Option<(MemData, u64)> {
             if #[cfg(target_os = "linux")] {
                 // TODO: [OPT] is this efficient?
                 use std::fs::read_to_string;
-                if let Ok(arc_stats) = read_to_string("/proc/spl/kstat/zfs/arcstats") {
+                if let Ok(arc_stats) = read_to_string(roots.proc_path("spl/kstat/zfs/arcstats")) {
                     let mut mem_arc = 0;
                     let mut mem_total = 0;
                     let mut mem_min = 0;
@@ -78,3 +80,18 @@ pub(crate) fn get_arc_usage() -> Option<(MemData, u64)> {
         )
     })
 }
+
+#[cfg(all(test, feature = "zfs", target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::collection::SystemRoots;
+
+    #[test]
+    fn test_fixture_arc_usage() {
+        let (arc, arc_min) = get_arc_usage(&SystemRoots::fixture()).unwrap();
+
+        assert_eq!(arc.total_bytes.get(), 8589934592);
+        assert_eq!(arc.used_bytes, 1073741824);
+        assert_eq!(arc_min, 536870912);
+    }
+}
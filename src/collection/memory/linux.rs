@@ -0,0 +1,109 @@
+//! Memory and swap usage for Linux, read from `/proc/meminfo`.
+
+use std::fs;
+
+use super::{MemData, get_usage};
+use crate::collection::SystemRoots;
+
+/// The fields of `/proc/meminfo` that bottom uses, in bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct MemInfo {
+    pub(crate) total: u64,
+    free: u64,
+    available: u64,
+    swap_total: u64,
+    swap_free: u64,
+}
+
+impl MemInfo {
+    /// Reads `/proc/meminfo` under `roots`.
+    pub(crate) fn read(roots: &SystemRoots) -> anyhow::Result<MemInfo> {
+        let meminfo = fs::read_to_string(roots.proc_path("meminfo"))?;
+
+        Self::parse(&meminfo)
+            .ok_or_else(|| anyhow::anyhow!("the total amount of memory could not be read"))
+    }
+
+    /// Parses the contents of `/proc/meminfo`, where each value is in kB.
+    /// Returns `None` if the total amount of memory is missing.
+    fn parse(meminfo: &str) -> Option<MemInfo> {
+        let mut info = MemInfo::default();
+        let mut has_available = false;
+
+        for line in meminfo.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(field), Some(value)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let Ok(kib) = value.parse::<u64>() else {
+                continue;
+            };
+            let bytes = kib * 1024;
+
+            match field {
+                "MemTotal:" => info.total = bytes,
+                "MemFree:" => info.free = bytes,
+                "MemAvailable:" => {
+                    info.available = bytes;
+                    has_available = true;
+                }
+                "SwapTotal:" => info.swap_total = bytes,
+                "SwapFree:" => info.swap_free = bytes,
+                _ => {}
+            }
+        }
+
+        // Kernels older than 3.14 don't report available memory.
+        if !has_available {
+            info.available = info.free;
+        }
+
+        (info.total > 0).then_some(info)
+    }
+
+    /// Returns RAM usage, where memory that can be made available counts as
+    /// unused.
+    pub(crate) fn ram(&self) -> Option<MemData> {
+        get_usage(self.total.saturating_sub(self.available), self.total)
+    }
+
+    /// Returns cache usage, the memory that is in use but can be made
+    /// available.
+    pub(crate) fn cache(&self) -> Option<MemData> {
+        get_usage(self.available.saturating_sub(self.free), self.total)
+    }
+
+    /// Returns swap usage.
+    pub(crate) fn swap(&self) -> Option<MemData> {
+        get_usage(
+            self.swap_total.saturating_sub(self.swap_free),
+            self.swap_total,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fixture_meminfo() {
+        let info = MemInfo::read(&SystemRoots::fixture()).unwrap();
+        const KIB: u64 = 1024;
+
+        assert_eq!(info.total, 16_000_000 * KIB);
+        assert_eq!(info.ram().unwrap().used_bytes, 6_000_000 * KIB);
+        assert_eq!(info.cache().unwrap().used_bytes, 6_000_000 * KIB);
+        assert_eq!(info.swap().unwrap().used_bytes, 1_000_000 * KIB);
+    }
+
+    #[test]
+    fn test_meminfo_without_swap_or_available() {
+        let info = MemInfo::parse("MemTotal: 1000 kB\nMemFree: 250 kB\nSwapTotal: 0 kB\n").unwrap();
+
+        assert_eq!(info.ram().unwrap().used_bytes, 750 * 1024);
+        assert!(info.swap().is_none());
+
+        assert_eq!(MemInfo::parse("MemFree: 250 kB\n"), None);
+    }
+}
@@ -1,18 +1,8 @@
 //! Collecting memory data using sysinfo.
 
-use std::num::NonZeroU64;
-
 use sysinfo::System;
 
-use crate::collection::memory::MemData;
-
-#[inline]
-fn get_usage(used: u64, total: u64) -> Option<MemData> {
-    NonZeroU64::new(total).map(|total_bytes| MemData {
-        total_bytes,
-        used_bytes: used,
-    })
-}
+use super::{MemData, get_usage};
 
 /// Returns RAM usage.
 pub(crate) fn get_ram_usage(sys: &System) -> Option<MemData> {
@@ -1,7 +1,18 @@
 //! Data collection for network usage/IO.
 
-pub mod sysinfo;
-pub use self::sysinfo::*;
+use std::time::Instant;
+
+use crate::app::filter::Filter;
+
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        pub mod linux;
+        pub use self::linux::*;
+    } else {
+        pub mod sysinfo;
+        pub use self::sysinfo::*;
+    }
+}
 
 #[derive(Default, Clone, Debug)]
 /// All units in bits.
@@ -18,3 +29,48 @@ impl NetworkHarvest {
         self.tx = 0;
     }
 }
+
+/// Sums the received and transmitted bytes of each interface kept by
+/// `filter`, and computes the rates since the previous totals.
+// TODO: Eventually make it so that this thing also takes individual usage into
+// account, so we can show per-interface!
+fn harvest<'a>(
+    interfaces: impl Iterator<Item = (&'a str, u64, u64)>, prev_net_access_time: Instant,
+    prev_net_rx: &mut u64, prev_net_tx: &mut u64, curr_time: Instant, filter: &Option<Filter>,
+) -> NetworkHarvest {
+    let mut total_rx: u64 = 0;
+    let mut total_tx: u64 = 0;
+
+    for (name, received, transmitted) in interfaces {
+        let to_keep = if let Some(filter) = filter {
+            filter.should_keep(name)
+        } else {
+            true
+        };
+
+        if to_keep {
+            total_rx += received * 8;
+            total_tx += transmitted * 8;
+        }
+    }
+
+    let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
+
+    let (rx, tx) = if elapsed_time == 0.0 {
+        (0, 0)
+    } else {
+        (
+            ((total_rx.saturating_sub(*prev_net_rx)) as f64 / elapsed_time) as u64,
+            ((total_tx.saturating_sub(*prev_net_tx)) as f64 / elapsed_time) as u64,
+        )
+    };
+
+    *prev_net_rx = total_rx;
+    *prev_net_tx = total_tx;
+    NetworkHarvest {
+        rx,
+        tx,
+        total_rx,
+        total_tx,
+    }
+}
@@ -0,0 +1,66 @@
+//! Gets network data on Linux from `/proc/net/dev`.
+
+use std::{fs, time::Instant};
+
+use super::NetworkHarvest;
+use crate::{app::filter::Filter, collection::SystemRoots};
+
+/// Parses the contents of `/proc/net/dev` into each interface's name and its
+/// total received and transmitted bytes.
+fn parse_net_dev(net_dev: &str) -> impl Iterator<Item = (&str, u64, u64)> {
+    // The first two lines are headers.
+    net_dev.lines().skip(2).filter_map(|line| {
+        let (name, counters) = line.split_once(':')?;
+        let mut counters = counters.split_whitespace();
+
+        // Bytes received is the first receive column, and bytes transmitted is
+        // the first of the eight transmit columns that follow.
+        let received = counters.next()?.parse().ok()?;
+        let transmitted = counters.nth(7)?.parse().ok()?;
+
+        Some((name.trim(), received, transmitted))
+    })
+}
+
+/// Returns the number of interfaces and the network usage since the previous
+/// totals.
+pub fn get_network_data(
+    roots: &SystemRoots, prev_net_access_time: Instant, prev_net_rx: &mut u64,
+    prev_net_tx: &mut u64, curr_time: Instant, filter: &Option<Filter>,
+) -> anyhow::Result<(usize, NetworkHarvest)> {
+    let net_dev = fs::read_to_string(roots.proc_path("net/dev"))?;
+    let num_interfaces = parse_net_dev(&net_dev).count();
+
+    let harvest = super::harvest(
+        parse_net_dev(&net_dev),
+        prev_net_access_time,
+        prev_net_rx,
+        prev_net_tx,
+        curr_time,
+        filter,
+    );
+
+    Ok((num_interfaces, harvest))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fixture_net_dev() {
+        let net_dev = fs::read_to_string(SystemRoots::fixture().proc_path("net/dev")).unwrap();
+        let interfaces: Vec<_> = parse_net_dev(&net_dev).collect();
+
+        assert_eq!(interfaces, [("lo", 1000, 1000), ("eth0", 5000000, 250000)]);
+
+        let now = Instant::now();
+        let (mut rx, mut tx) = (0, 0);
+        let (num_interfaces, harvest) =
+            get_network_data(&SystemRoots::fixture(), now, &mut rx, &mut tx, now, &None).unwrap();
+
+        assert_eq!(num_interfaces, 2);
+        assert_eq!(harvest.total_rx, 5001000 * 8);
+        assert_eq!(tx, 251000 * 8);
+    }
+}
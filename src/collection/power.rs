@@ -0,0 +1,17 @@
+//! Data collection for power draw.
+//!
+//! For Linux, CPU package/core/uncore/DRAM power is derived from the RAPL
+//! energy counters exposed through the powercap interface. GPU power draw is
+//! merged in from any GPU backends that report it.
+
+#[cfg(target_os = "linux")]
+pub mod linux;
+
+#[derive(Default, Debug, Clone)]
+pub struct PowerData {
+    /// The name of the power domain or device.
+    pub name: String,
+
+    /// The power draw in watts.
+    pub watts: f64,
+}
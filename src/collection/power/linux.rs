@@ -0,0 +1,259 @@
+//! Gets RAPL power data for Linux platforms via the powercap interface.
+//!
+//! Each zone under `/sys/class/powercap/intel-rapl*` exposes a cumulative
+//! `energy_uj` counter, which wraps back to zero once it hits
+//! `max_energy_range_uj`. Power draw is the difference between two readings
+//! divided by the time between them. Note that AMD processors also expose
+//! their counters under the `intel-rapl` name.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use rustc_hash::FxHashMap as HashMap;
+
+use super::PowerData;
+use crate::collection::SystemRoots;
+
+/// A single RAPL zone (e.g. `package-0`) or subzone (e.g. `core`).
+#[derive(Debug)]
+struct RaplZone {
+    path: PathBuf,
+    name: String,
+    max_energy_range_uj: u64,
+}
+
+/// State required to turn RAPL energy counters into power readings.
+#[derive(Debug, Default)]
+pub struct RaplState {
+    zones: Vec<RaplZone>,
+    prev_energy_uj: HashMap<PathBuf, u64>,
+}
+
+/// Returns a more readable name for a RAPL zone name.
+fn humanize_zone_name(name: &str) -> String {
+    if let Some(index) = name.strip_prefix("package-") {
+        format!("Package {index}")
+    } else {
+        match name {
+            "core" => "Core".to_string(),
+            "uncore" => "Uncore".to_string(),
+            "dram" => "DRAM".to_string(),
+            "psys" => "Platform".to_string(),
+            _ => name.to_string(),
+        }
+    }
+}
+
+#[inline]
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Find all RAPL zones under `base`. Subzones are named after their parent zone,
+/// and MMIO zones that duplicate an MSR-based zone are skipped.
+fn get_zones(base: &Path) -> Vec<RaplZone> {
+    let Ok(read_dir) = base.read_dir() else {
+        return vec![];
+    };
+
+    // Zone directories look like `intel-rapl:0` or `intel-rapl:0:1`; the bare
+    // `intel-rapl` entry is the control type and has no counters.
+    let mut entries: Vec<(bool, String)> = read_dir
+        .flatten()
+        .filter_map(|entry| {
+            let dir_name = entry.file_name().to_string_lossy().to_string();
+
+            if dir_name.starts_with("intel-rapl") && dir_name.contains(':') {
+                Some((dir_name.starts_with("intel-rapl-mmio"), dir_name))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Parents sort before their children, and MSR zones before MMIO ones.
+    entries.sort();
+
+    let mut zones: Vec<RaplZone> = Vec::with_capacity(entries.len());
+    let mut labels: HashMap<String, String> = HashMap::default();
+
+    for (_, dir_name) in entries {
+        let path = base.join(&dir_name);
+
+        let Ok(raw_name) = fs::read_to_string(path.join("name")) else {
+            continue;
+        };
+        let mut name = humanize_zone_name(raw_name.trim());
+
+        if let Some((parent, _)) = dir_name.rsplit_once(':') {
+            if let Some(parent_name) = labels.get(parent) {
+                name = format!("{parent_name} {name}");
+            }
+        }
+
+        if zones.iter().any(|zone| zone.name == name) {
+            continue;
+        }
+
+        let Some(max_energy_range_uj) = read_u64(&path.join("max_energy_range_uj")) else {
+            continue;
+        };
+
+        labels.insert(dir_name, name.clone());
+        zones.push(RaplZone {
+            path,
+            name,
+            max_energy_range_uj,
+        });
+    }
+
+    zones
+}
+
+/// Returns the energy used between two counter readings in microjoules,
+/// accounting for the counter wrapping around at `max_energy_range_uj`.
+fn diff_energy(prev_uj: u64, curr_uj: u64, max_energy_range_uj: u64) -> u64 {
+    if curr_uj >= prev_uj {
+        curr_uj - prev_uj
+    } else {
+        max_energy_range_uj.saturating_sub(prev_uj) + curr_uj
+    }
+}
+
+/// Converts an energy difference in microjoules over `interval` into watts.
+fn energy_to_watts(energy_uj: u64, interval: &Duration) -> f64 {
+    let secs = interval.as_secs_f64();
+
+    if secs > 0.0 {
+        energy_uj as f64 / 1_000_000.0 / secs
+    } else {
+        0.0
+    }
+}
+
+fn get_power_from_base(
+    base: &Path, state: &mut RaplState, interval: Duration, refresh_zones: bool,
+) -> Option<Vec<PowerData>> {
+    if refresh_zones || state.zones.is_empty() {
+        state.zones = get_zones(base);
+        state
+            .prev_energy_uj
+            .retain(|path, _| state.zones.iter().any(|zone| zone.path == *path));
+    }
+
+    let mut power = Vec::with_capacity(state.zones.len());
+
+    for zone in &state.zones {
+        // Note that reading this usually requires elevated permissions.
+        let Some(curr_uj) = read_u64(&zone.path.join("energy_uj")) else {
+            continue;
+        };
+
+        // We need at least two readings to get a power value.
+        if let Some(prev_uj) = state.prev_energy_uj.insert(zone.path.clone(), curr_uj) {
+            let used_uj = diff_energy(prev_uj, curr_uj, zone.max_energy_range_uj);
+
+            power.push(PowerData {
+                name: zone.name.clone(),
+                watts: energy_to_watts(used_uj, &interval),
+            });
+        }
+    }
+
+    if power.is_empty() { None } else { Some(power) }
+}
+
+/// Gets RAPL power readings, where `interval` is the time since the last call.
+///
+/// If `refresh_zones` is set, the list of zones will be re-read.
+pub fn get_rapl_power(
+    roots: &SystemRoots, state: &mut RaplState, interval: Duration, refresh_zones: bool,
+) -> Option<Vec<PowerData>> {
+    get_power_from_base(
+        &roots.sys_path("class/powercap"),
+        state,
+        interval,
+        refresh_zones,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_zone(base: &Path, dir: &str, name: &str, energy_uj: u64) {
+        let path = base.join(dir);
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("name"), format!("{name}\n")).unwrap();
+        fs::write(path.join("max_energy_range_uj"), "1000000\n").unwrap();
+        fs::write(path.join("energy_uj"), format!("{energy_uj}\n")).unwrap();
+    }
+
+    #[test]
+    fn energy_diff_wraps() {
+        assert_eq!(diff_energy(100, 300, 1000), 200);
+        assert_eq!(diff_energy(900, 100, 1000), 200);
+        assert_eq!(diff_energy(500, 500, 1000), 0);
+    }
+
+    #[test]
+    fn energy_to_watts_conversion() {
+        assert_eq!(energy_to_watts(2_000_000, &Duration::from_secs(2)), 1.0);
+        assert_eq!(energy_to_watts(500_000, &Duration::from_millis(250)), 2.0);
+        assert_eq!(energy_to_watts(500_000, &Duration::ZERO), 0.0);
+    }
+
+    #[test]
+    fn zone_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+
+        fs::create_dir_all(base.join("intel-rapl")).unwrap();
+        write_zone(base, "intel-rapl:0", "package-0", 0);
+        write_zone(base, "intel-rapl:0:0", "core", 0);
+        write_zone(base, "intel-rapl:0:1", "dram", 0);
+        write_zone(base, "intel-rapl-mmio:0", "package-0", 0);
+
+        let names: Vec<String> = get_zones(base).into_iter().map(|zone| zone.name).collect();
+
+        assert_eq!(names, ["Package 0", "Package 0 Core", "Package 0 DRAM"]);
+    }
+
+    #[test]
+    fn power_from_counters() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path();
+        let mut state = RaplState::default();
+
+        write_zone(base, "intel-rapl:0", "package-0", 999_000);
+        assert!(get_power_from_base(base, &mut state, Duration::from_secs(1), true).is_none());
+
+        // Wrap around past max_energy_range_uj.
+        write_zone(base, "intel-rapl:0", "package-0", 9_000);
+        let power = get_power_from_base(base, &mut state, Duration::from_secs(1), false).unwrap();
+
+        assert_eq!(power.len(), 1);
+        assert_eq!(power[0].name, "Package 0");
+        assert!((power[0].watts - 0.01).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn power_from_sysfs_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let roots = SystemRoots::new(None, Some(dir.path().to_path_buf()));
+        let base = roots.sys_path("class/powercap");
+        let mut state = RaplState::default();
+
+        write_zone(&base, "intel-rapl:0", "package-0", 100_000);
+        assert!(get_rapl_power(&roots, &mut state, Duration::from_secs(1), true).is_none());
+
+        write_zone(&base, "intel-rapl:0", "package-0", 2_100_000);
+        let power = get_rapl_power(&roots, &mut state, Duration::from_secs(2), false).unwrap();
+
+        assert_eq!(power[0].name, "Package 0");
+        assert_eq!(power[0].watts, 1.0);
+    }
+}
@@ -76,6 +76,52 @@ impl ProcessType {
     }
 }
 
+/// Process details that are slower to collect than the rest, so are only
+/// collected if a column or search needs them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProcessFields {
+    /// The ports each process listens on.
+    pub listening_ports: bool,
+
+    /// Each process's TCP traffic.
+    pub tcp_traffic: bool,
+
+    /// The number of file descriptors each process has open.
+    pub fd_count: bool,
+
+    /// Each process's context switch counts.
+    pub context_switches: bool,
+
+    /// The path of each process's executable.
+    pub exe: bool,
+
+    /// Each process's OOM score.
+    pub oom_score: bool,
+
+    /// Each process's PSS, USS, shared, and swapped memory.
+    pub detailed_memory: bool,
+
+    /// Whether the detailed memory of every process is needed, such as to sort
+    /// or search by it, rather than only that of the shown processes.
+    pub all_detailed_memory: bool,
+}
+
+impl ProcessFields {
+    /// Returns the fields needed by either `self` or `other`.
+    pub fn union(self, other: ProcessFields) -> ProcessFields {
+        ProcessFields {
+            listening_ports: self.listening_ports || other.listening_ports,
+            tcp_traffic: self.tcp_traffic || other.tcp_traffic,
+            fd_count: self.fd_count || other.fd_count,
+            context_switches: self.context_switches || other.context_switches,
+            exe: self.exe || other.exe,
+            oom_score: self.oom_score || other.oom_score,
+            detailed_memory: self.detailed_memory || other.detailed_memory,
+            all_detailed_memory: self.all_detailed_memory || other.all_detailed_memory,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ProcessHarvest {
     /// The pid of the process.
@@ -154,6 +200,84 @@ pub struct ProcessHarvest {
 
     /// The kernel scheduling priority.
     pub priority: i32,
+
+    /// The ports the process listens on, sorted. This is only filled in if
+    /// connections are collected.
+    #[cfg(target_os = "linux")]
+    pub listening_ports: Vec<u16>,
+
+    /// TCP bytes received per second, estimated from the process's sockets.
+    /// This is only filled in if [`ProcessFields::tcp_traffic`] is set.
+    #[cfg(target_os = "linux")]
+    pub tcp_rx_per_sec: Bytes,
+
+    /// TCP bytes sent per second, estimated from the process's sockets.
+    #[cfg(target_os = "linux")]
+    pub tcp_tx_per_sec: Bytes,
+
+    /// The total TCP bytes received on sockets seen while bottom has run.
+    #[cfg(target_os = "linux")]
+    pub total_tcp_rx: Bytes,
+
+    /// The total TCP bytes sent on sockets seen while bottom has run.
+    #[cfg(target_os = "linux")]
+    pub total_tcp_tx: Bytes,
+
+    /// The number of threads in the process.
+    #[cfg(target_os = "linux")]
+    pub num_threads: u64,
+
+    /// The number of open file descriptors. This is only filled in if
+    /// [`ProcessFields::fd_count`] is set and the process's `fd` directory
+    /// can be read.
+    #[cfg(target_os = "linux")]
+    pub fd_count: Option<u64>,
+
+    /// The number of voluntary context switches. This is only filled in if
+    /// [`ProcessFields::context_switches`] is set.
+    #[cfg(target_os = "linux")]
+    pub voluntary_ctx_switches: Option<u64>,
+
+    /// The number of involuntary context switches. This is only filled in if
+    /// [`ProcessFields::context_switches`] is set.
+    #[cfg(target_os = "linux")]
+    pub involuntary_ctx_switches: Option<u64>,
+
+    /// Minor page faults per second.
+    #[cfg(target_os = "linux")]
+    pub minor_faults_per_sec: u64,
+
+    /// Major page faults per second.
+    #[cfg(target_os = "linux")]
+    pub major_faults_per_sec: u64,
+
+    /// When the process started, in seconds since the Unix epoch.
+    #[cfg(target_os = "linux")]
+    pub start_time: u64,
+
+    /// The name of the process's controlling terminal, if it has one.
+    #[cfg(target_os = "linux")]
+    pub tty: Option<String>,
+
+    /// The path of the process's executable. This is only filled in if
+    /// [`ProcessFields::exe`] is set and the link can be read.
+    #[cfg(target_os = "linux")]
+    pub exe: Option<String>,
+
+    /// The OOM killer's score for the process. This is only filled in if
+    /// [`ProcessFields::oom_score`] is set.
+    #[cfg(target_os = "linux")]
+    pub oom_score: Option<u32>,
+
+    /// The CPU the process last ran on.
+    #[cfg(target_os = "linux")]
+    pub last_cpu: u32,
+
+    /// The process's memory split by how it is shared. This is only filled in
+    /// if [`ProcessFields::detailed_memory`] is set, the process's memory maps
+    /// can be read, and, for long lists, it has been shown.
+    #[cfg(target_os = "linux")]
+    pub memory_details: Option<MemoryDetails>,
     // TODO: Additional fields
     // pub rss_kb: u64,
     // pub virt_kb: u64,
@@ -164,7 +288,7 @@ impl DataCollector {
         cfg_if! {
             if #[cfg(target_os = "linux")] {
                 let time_diff = self.data.collection_time
-                    .duration_since(self.last_collection_time)
+                    .duration_since(self.schedule.processes.last_run())
                     .as_secs();
 
                 linux_process_data(
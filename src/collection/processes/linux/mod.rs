@@ -1,10 +1,12 @@
 //! Process data collection for Linux.
 
 mod process;
+mod smaps;
 
 use std::{
     fs::{self, File},
     io::{BufRead, BufReader},
+    path::Path,
     time::Duration,
 };
 
@@ -12,10 +14,14 @@ use concat_string::concat_string;
 use itertools::Itertools;
 use process::*;
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+pub use smaps::MemoryDetails;
+pub(crate) use smaps::MemoryDetailsCache;
 use sysinfo::ProcessStatus;
 
-use super::{Pid, ProcessHarvest, UserTable, process_status_str};
-use crate::collection::{DataCollector, error::CollectionResult, processes::ProcessType};
+use super::{Pid, ProcessFields, ProcessHarvest, UserTable, process_status_str};
+use crate::collection::{
+    DataCollector, SystemRoots, error::CollectionResult, processes::ProcessType,
+};
 
 /// Maximum character length of a `/proc/<PID>/stat` process name (the length is 16,
 /// but this includes a null terminator).
@@ -28,6 +34,8 @@ pub struct PrevProcDetails {
     total_read_bytes: u64,
     total_write_bytes: u64,
     cpu_time: u64,
+    minor_faults: u64,
+    major_faults: u64,
 }
 
 /// Given `/proc/stat` file contents, determine the idle and non-idle values of
@@ -66,13 +74,13 @@ struct CpuUsage {
 }
 
 fn cpu_usage_calculation(
-    prev_idle: &mut f64, prev_non_idle: &mut f64,
+    roots: &SystemRoots, prev_idle: &mut f64, prev_non_idle: &mut f64,
 ) -> CollectionResult<CpuUsage> {
     let (idle, non_idle) = {
         // From SO answer: https://stackoverflow.com/a/23376195
         let first_line = {
             // We just need a single line from this file. Read it and return it.
-            let mut reader = BufReader::new(File::open("/proc/stat")?);
+            let mut reader = BufReader::new(File::open(roots.proc_path("stat"))?);
             let mut buffer = String::new();
             reader.read_line(&mut buffer)?;
 
@@ -133,16 +141,50 @@ fn get_linux_cpu_usage(
     }
 }
 
+/// Returns the name of a terminal given its device number from `/proc/<PID>/stat`,
+/// the same way `ps` names them. Returns `None` if there is no terminal.
+fn tty_name(tty_nr: i32) -> Option<String> {
+    if tty_nr == 0 {
+        return None;
+    }
+
+    let tty_nr = tty_nr as u32;
+    let major = (tty_nr >> 8) & 0xfff;
+    let minor = (tty_nr & 0xff) | ((tty_nr >> 12) & 0xfff00);
+
+    Some(match major {
+        4 if minor < 64 => format!("tty{minor}"),
+        4 => format!("ttyS{}", minor - 64),
+        136..=143 => format!("pts/{}", (major - 136) * 256 + minor),
+        _ => format!("{major}:{minor}"),
+    })
+}
+
+/// Returns the change in a counter per second since the last reading.
+#[inline]
+fn per_second(current: u64, prev: u64, time_difference_in_secs: u64) -> u64 {
+    current
+        .saturating_sub(prev)
+        .checked_div(time_difference_in_secs)
+        .unwrap_or(0)
+}
+
+/// Reads a process's data, returning it along with the details to compare
+/// against the next time it is read.
 fn read_proc(
     prev_proc: &PrevProcDetails, process: Process, args: ReadProcArgs, user_table: &mut UserTable,
     thread_parent: Option<Pid>,
-) -> CollectionResult<(ProcessHarvest, u64)> {
+) -> CollectionResult<(ProcessHarvest, PrevProcDetails)> {
     let Process {
         pid: _pid,
         uid,
         stat,
         io,
         cmdline,
+        status,
+        fd_count,
+        exe,
+        oom_score,
     } = process;
 
     let ReadProcArgs {
@@ -152,7 +194,9 @@ fn read_proc(
         total_memory,
         time_difference_in_secs,
         system_uptime,
+        boot_time,
         get_process_threads: _,
+        process_fields: _,
     } = args;
 
     let process_state_char = stat.state;
@@ -187,15 +231,8 @@ fn read_proc(
         let prev_total_read = prev_proc.total_read_bytes;
         let prev_total_write = prev_proc.total_write_bytes;
 
-        let read_per_sec = total_read
-            .saturating_sub(prev_total_read)
-            .checked_div(time_difference_in_secs)
-            .unwrap_or(0);
-
-        let write_per_sec = total_write
-            .saturating_sub(prev_total_write)
-            .checked_div(time_difference_in_secs)
-            .unwrap_or(0);
+        let read_per_sec = per_second(total_read, prev_total_read, time_difference_in_secs);
+        let write_per_sec = per_second(total_write, prev_total_write, time_difference_in_secs);
 
         (total_read, total_write, read_per_sec, write_per_sec)
     } else {
@@ -204,18 +241,29 @@ fn read_proc(
 
     let user = uid.and_then(|uid| user_table.uid_to_username(uid).ok());
 
-    let time = if let Ok(ticks_per_sec) = u32::try_from(rustix::param::clock_ticks_per_second()) {
-        if ticks_per_sec == 0 {
-            Duration::ZERO
-        } else {
-            Duration::from_secs(
-                system_uptime.saturating_sub(stat.start_time / ticks_per_sec as u64),
-            )
-        }
-    } else {
-        Duration::ZERO
+    let minor_faults_per_sec = per_second(
+        stat.minor_faults,
+        prev_proc.minor_faults,
+        time_difference_in_secs,
+    );
+    let major_faults_per_sec = per_second(
+        stat.major_faults,
+        prev_proc.major_faults,
+        time_difference_in_secs,
+    );
+
+    // The number of seconds after boot that the process started.
+    let start_secs = match u32::try_from(rustix::param::clock_ticks_per_second()) {
+        Ok(ticks_per_sec) if ticks_per_sec != 0 => Some(stat.start_time / ticks_per_sec as u64),
+        _ => None,
     };
 
+    let time = match start_secs {
+        Some(start_secs) => Duration::from_secs(system_uptime.saturating_sub(start_secs)),
+        None => Duration::ZERO,
+    };
+    let start_time = boot_time + start_secs.unwrap_or(0);
+
     let (command, name) = {
         let comm = stat.comm;
         if let Some(cmdline) = cmdline {
@@ -289,8 +337,31 @@ fn read_proc(
             #[cfg(unix)]
             nice: stat.nice,
             priority: stat.priority,
+            listening_ports: Vec::new(),
+            tcp_rx_per_sec: 0,
+            tcp_tx_per_sec: 0,
+            total_tcp_rx: 0,
+            total_tcp_tx: 0,
+            num_threads: stat.num_threads,
+            fd_count,
+            voluntary_ctx_switches: status.as_ref().map(|s| s.voluntary_ctxt_switches),
+            involuntary_ctx_switches: status.as_ref().map(|s| s.nonvoluntary_ctxt_switches),
+            minor_faults_per_sec,
+            major_faults_per_sec,
+            start_time,
+            tty: tty_name(stat.tty_nr),
+            exe,
+            oom_score,
+            last_cpu: stat.processor,
+            memory_details: None,
+        },
+        PrevProcDetails {
+            total_read_bytes: total_read,
+            total_write_bytes: total_write,
+            cpu_time: new_process_times,
+            minor_faults: stat.minor_faults,
+            major_faults: stat.major_faults,
         },
-        new_process_times,
     ))
 }
 
@@ -335,6 +406,31 @@ fn is_str_numeric(s: &str) -> bool {
     s.chars().all(|c| c.is_ascii_digit())
 }
 
+/// Returns the system uptime in seconds from `/proc/uptime`, falling back to sysinfo
+/// if it can't be read.
+fn system_uptime(procfs: &Path) -> u64 {
+    fs::read_to_string(procfs.join("uptime"))
+        .ok()
+        .and_then(|uptime| {
+            let secs = uptime.split_whitespace().next()?.parse::<f64>().ok()?;
+            Some(secs as u64)
+        })
+        .unwrap_or_else(sysinfo::System::uptime)
+}
+
+/// Returns the system boot time in seconds since the Unix epoch from `/proc/stat`,
+/// falling back to sysinfo if it can't be read.
+fn boot_time(procfs: &Path) -> u64 {
+    fs::read_to_string(procfs.join("stat"))
+        .ok()
+        .and_then(|stat| {
+            stat.lines()
+                .find_map(|line| line.strip_prefix("btime "))
+                .and_then(|btime| btime.trim().parse().ok())
+        })
+        .unwrap_or_else(sysinfo::System::boot_time)
+}
+
 /// General args to keep around for reading proc data.
 #[derive(Copy, Clone)]
 pub(crate) struct ReadProcArgs {
@@ -344,7 +440,9 @@ pub(crate) struct ReadProcArgs {
     pub total_memory: u64,
     pub time_difference_in_secs: u64,
     pub system_uptime: u64,
+    pub boot_time: u64,
     pub get_process_threads: bool,
+    pub process_fields: ProcessFields,
 }
 
 pub(crate) fn linux_process_data(
@@ -379,10 +477,10 @@ pub(crate) fn linux_process_data(
     let CpuUsage {
         mut cpu_usage,
         cpu_fraction,
-    } = cpu_usage_calculation(prev_idle, prev_non_idle)?;
+    } = cpu_usage_calculation(&collector.roots, prev_idle, prev_non_idle)?;
 
     if unnormalized_cpu {
-        let num_processors = collector.sys.system.cpus().len() as f64;
+        let num_processors = crate::collection::cpu::num_cpus(&collector.roots) as f64;
 
         // Note we *divide* here because the later calculation divides `cpu_usage` - in
         // effect, multiplying over the number of cores.
@@ -394,14 +492,16 @@ pub(crate) fn linux_process_data(
     let mut seen_pids: HashSet<Pid> = HashSet::default();
 
     // Note this will only return PIDs of _processes_, not threads. You can get those from /proc/<PID>/task though.
-    let pids = fs::read_dir("/proc")?.flatten().filter_map(|dir| {
-        // Need to filter out non-PID entries.
-        if is_str_numeric(dir.file_name().to_string_lossy().trim()) {
-            Some(dir.path())
-        } else {
-            None
-        }
-    });
+    let pids = fs::read_dir(collector.roots.procfs())?
+        .flatten()
+        .filter_map(|dir| {
+            // Need to filter out non-PID entries.
+            if is_str_numeric(dir.file_name().to_string_lossy().trim()) {
+                Some(dir.path())
+            } else {
+                None
+            }
+        });
 
     let args = ReadProcArgs {
         use_current_cpu_total,
@@ -409,8 +509,10 @@ pub(crate) fn linux_process_data(
         cpu_fraction,
         total_memory,
         time_difference_in_secs,
-        system_uptime: sysinfo::System::uptime(),
+        system_uptime: system_uptime(collector.roots.procfs()),
+        boot_time: boot_time(collector.roots.procfs()),
         get_process_threads: get_threads,
+        process_fields: collector.widgets_to_harvest.process_fields,
     };
 
     // TODO: Maybe pre-allocate these buffers in the future w/ routine cleanup.
@@ -419,14 +521,17 @@ pub(crate) fn linux_process_data(
 
     let mut process_vector: Vec<ProcessHarvest> = pids
         .filter_map(|pid_path| {
-            if let Ok((process, threads)) =
-                Process::from_path(pid_path, &mut buffer, args.get_process_threads)
-            {
+            if let Ok((process, threads)) = Process::from_path(
+                pid_path,
+                &mut buffer,
+                args.get_process_threads,
+                args.process_fields,
+            ) {
                 let pid = process.pid;
                 let prev_proc_details = prev_process_details.entry(pid).or_default();
 
                 #[cfg_attr(not(feature = "gpu"), expect(unused_mut))]
-                if let Ok((mut process_harvest, new_process_times)) =
+                if let Ok((mut process_harvest, new_proc_details)) =
                     read_proc(prev_proc_details, process, args, user_table, None)
                 {
                     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -445,9 +550,7 @@ pub(crate) fn linux_process_data(
                         }
                     }
 
-                    prev_proc_details.cpu_time = new_process_times;
-                    prev_proc_details.total_read_bytes = process_harvest.total_read;
-                    prev_proc_details.total_write_bytes = process_harvest.total_write;
+                    *prev_proc_details = new_proc_details;
 
                     if !threads.is_empty() {
                         process_threads_to_check.insert(pid, threads);
@@ -465,16 +568,16 @@ pub(crate) fn linux_process_data(
     // Get thread data.
     for (pid, tid_paths) in process_threads_to_check {
         for tid_path in tid_paths {
-            if let Ok((process, _)) = Process::from_path(tid_path, &mut buffer, false) {
+            if let Ok((process, _)) =
+                Process::from_path(tid_path, &mut buffer, false, args.process_fields)
+            {
                 let tid = process.pid;
                 let prev_proc_details = prev_process_details.entry(tid).or_default();
 
-                if let Ok((process_harvest, new_process_times)) =
+                if let Ok((process_harvest, new_proc_details)) =
                     read_proc(prev_proc_details, process, args, user_table, Some(pid))
                 {
-                    prev_proc_details.cpu_time = new_process_times;
-                    prev_proc_details.total_read_bytes = process_harvest.total_read;
-                    prev_proc_details.total_write_bytes = process_harvest.total_write;
+                    *prev_proc_details = new_proc_details;
 
                     seen_pids.insert(tid);
                     process_vector.push(process_harvest);
@@ -569,4 +672,96 @@ mod tests {
             "こんにちは"
         );
     }
+
+    #[test]
+    fn test_fixture_cpu_usage() {
+        let (mut prev_idle, mut prev_non_idle) = (0.0, 0.0);
+        let CpuUsage {
+            cpu_usage,
+            cpu_fraction,
+        } = cpu_usage_calculation(&SystemRoots::fixture(), &mut prev_idle, &mut prev_non_idle)
+            .unwrap();
+
+        let (idle, non_idle) = fetch_cpu_usage("cpu  80026 0 4350 107697 316 0 2 2000 0 0");
+
+        assert_eq!((prev_idle, prev_non_idle), (idle, non_idle));
+        assert_eq!(cpu_usage, non_idle);
+        assert_eq!(cpu_fraction, non_idle / (idle + non_idle));
+    }
+
+    #[test]
+    fn test_fixture_uptime() {
+        assert_eq!(system_uptime(SystemRoots::fixture().procfs()), 1937);
+    }
+
+    #[test]
+    fn test_fixture_boot_time() {
+        assert_eq!(boot_time(SystemRoots::fixture().procfs()), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_tty_name() {
+        assert_eq!(tty_name(0), None);
+        assert_eq!(tty_name(0x0401).as_deref(), Some("tty1"));
+        assert_eq!(tty_name(0x0440).as_deref(), Some("ttyS0"));
+        assert_eq!(tty_name(0x8803).as_deref(), Some("pts/3"));
+        assert_eq!(tty_name(0x8900).as_deref(), Some("pts/256"));
+        assert_eq!(tty_name(0x108803).as_deref(), Some("pts/259"));
+    }
+
+    #[test]
+    fn test_fixture_processes() {
+        let mut collector = DataCollector::new(crate::app::DataFilters {
+            disk_filter: None,
+            mount_filter: None,
+            temp_filter: None,
+            net_filter: None,
+        });
+        collector.set_system_roots(SystemRoots::fixture());
+        collector.widgets_to_harvest.process_fields = ProcessFields {
+            fd_count: true,
+            context_switches: true,
+            exe: true,
+            oom_score: true,
+            ..Default::default()
+        };
+
+        let mut processes = linux_process_data(&mut collector, 1).unwrap();
+        processes.sort_unstable_by_key(|p| p.pid);
+
+        assert_eq!(processes.len(), 2);
+
+        let init = &processes[0];
+        assert_eq!(init.pid, 1);
+        assert_eq!(init.parent_pid, Some(0));
+        assert_eq!(init.name, "systemd");
+        assert_eq!(
+            init.command.trim_end(),
+            "/usr/lib/systemd/systemd --switched-root --system"
+        );
+        assert_eq!(init.total_read, 4096);
+        assert_eq!(init.total_write, 8192);
+        assert_eq!(init.process_state.1, 'S');
+        assert!(!init.process_type.is_kernel());
+        assert_eq!(init.num_threads, 1);
+        assert_eq!(init.fd_count, Some(4));
+        assert_eq!(init.voluntary_ctx_switches, Some(1234));
+        assert_eq!(init.involuntary_ctx_switches, Some(56));
+        assert_eq!(init.minor_faults_per_sec, 45864);
+        assert_eq!(init.major_faults_per_sec, 69);
+        assert_eq!(init.start_time, 1_700_000_000);
+        assert_eq!(init.tty, None);
+        assert_eq!(init.exe.as_deref(), Some("/usr/lib/systemd/systemd"));
+        assert_eq!(init.oom_score, Some(667));
+        assert_eq!(init.last_cpu, 1);
+
+        let kworker = &processes[1];
+        assert_eq!(kworker.pid, 42);
+        assert_eq!(kworker.parent_pid, Some(2));
+        assert_eq!(kworker.command, "[kworker/0:1-events]");
+        assert!(kworker.process_type.is_kernel());
+        assert_eq!(kworker.fd_count, None);
+        assert_eq!(kworker.voluntary_ctx_switches, None);
+        assert_eq!(kworker.exe, None);
+    }
 }
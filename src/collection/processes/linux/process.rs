@@ -16,7 +16,7 @@ use rustix::{
     path::Arg,
 };
 
-use crate::collection::processes::{Pid, linux::is_str_numeric};
+use crate::collection::processes::{Pid, ProcessFields, linux::is_str_numeric};
 
 static PAGESIZE: OnceLock<u64> = OnceLock::new();
 
@@ -42,6 +42,15 @@ pub(crate) struct Stat {
     /// The parent process PID.
     pub ppid: Pid,
 
+    /// The controlling terminal's device number, or 0 if there is none.
+    pub tty_nr: i32,
+
+    /// The number of minor faults, which did not need to load a page from disk.
+    pub minor_faults: u64,
+
+    /// The number of major faults, which needed to load a page from disk.
+    pub major_faults: u64,
+
     /// The amount of time this process has been scheduled in user mode in clock
     /// ticks.
     pub utime: u64,
@@ -54,6 +63,9 @@ pub(crate) struct Stat {
     /// memory.
     rss: u64,
 
+    /// The number of threads in the process.
+    pub num_threads: u64,
+
     /// The virtual memory size in bytes.
     pub vsize: u64,
 
@@ -69,6 +81,9 @@ pub(crate) struct Stat {
     /// The nice value (user-settable scheduling hint).
     #[cfg(unix)]
     pub nice: i32,
+
+    /// The CPU the process last ran on.
+    pub processor: u32,
 }
 
 impl Stat {
@@ -102,15 +117,22 @@ impl Stat {
             .ok_or_else(|| anyhow!("missing state"))?;
         let ppid: Pid = next_part(&mut rest)?.parse()?;
 
-        // Skip 4 fields (pgrp, session, tty_nr, tpgid)
-        let mut rest = rest.skip(4);
+        // Skip 2 fields (pgrp, session)
+        let mut rest = rest.skip(2);
+        let tty_nr: i32 = next_part(&mut rest)?.parse()?;
+        // tpgid
+        let _ = next_part(&mut rest)?;
 
         // read flags for kernel thread (PF_KTHREAD from include/linux/sched.h)
         let flags: u32 = next_part(&mut rest)?.parse()?;
         let is_kernel_thread: bool = flags & 0x00200000 != 0;
 
-        // Skip 4 fields (minflt, cminflt, majflt, cmajflt)
-        let mut rest = rest.skip(4);
+        let minor_faults: u64 = next_part(&mut rest)?.parse()?;
+        // cminflt
+        let _ = next_part(&mut rest)?;
+        let major_faults: u64 = next_part(&mut rest)?.parse()?;
+        // cmajflt
+        let _ = next_part(&mut rest)?;
         let utime: u64 = next_part(&mut rest)?.parse()?;
         let stime: u64 = next_part(&mut rest)?.parse()?;
 
@@ -122,8 +144,7 @@ impl Stat {
         let priority: i32 = next_part(&mut rest)?.parse()?;
         // nice
         let nice: i32 = next_part(&mut rest)?.parse()?;
-        // num_threads
-        let _ = next_part(&mut rest)?;
+        let num_threads: u64 = next_part(&mut rest)?.parse()?;
         // itrealvalue
         let _ = next_part(&mut rest)?;
 
@@ -131,18 +152,30 @@ impl Stat {
         let vsize: u64 = next_part(&mut rest)?.parse()?;
         let rss: u64 = next_part(&mut rest)?.parse()?;
 
+        // Skip 14 fields (rsslim through exit_signal). Very old kernels don't
+        // have the processor field, so don't fail if it's missing.
+        let processor: u32 = rest
+            .nth(14)
+            .and_then(|processor| processor.parse().ok())
+            .unwrap_or(0);
+
         Ok(Stat {
             comm,
             state,
             ppid,
+            tty_nr,
+            minor_faults,
+            major_faults,
             utime,
             stime,
             rss,
+            num_threads,
             vsize,
             start_time,
             is_kernel_thread,
             priority,
             nice,
+            processor,
         })
     }
 
@@ -231,6 +264,54 @@ impl Io {
     }
 }
 
+/// A wrapper around the data in `/proc/<PID>/status`.
+///
+/// Note this does not necessarily get all fields, only the ones we use in
+/// bottom.
+pub(crate) struct Status {
+    pub voluntary_ctxt_switches: u64,
+    pub nonvoluntary_ctxt_switches: u64,
+}
+
+impl Status {
+    #[inline]
+    fn from_file(f: File, buffer: &mut String) -> anyhow::Result<Status> {
+        let mut reader = BufReader::new(f);
+
+        let mut voluntary_ctxt_switches = None;
+        let mut nonvoluntary_ctxt_switches = None;
+
+        // The context switch counts are the last lines of the file, so this
+        // reads through most of it.
+        while reader.read_line(buffer)? > 0 {
+            let mut parts = buffer.split_whitespace();
+
+            match (parts.next(), parts.next()) {
+                (Some("voluntary_ctxt_switches:"), Some(value)) => {
+                    voluntary_ctxt_switches = Some(value.parse::<u64>()?);
+                }
+                (Some("nonvoluntary_ctxt_switches:"), Some(value)) => {
+                    nonvoluntary_ctxt_switches = Some(value.parse::<u64>()?);
+                }
+                _ => {}
+            }
+
+            if voluntary_ctxt_switches.is_some() && nonvoluntary_ctxt_switches.is_some() {
+                break;
+            }
+
+            buffer.clear();
+        }
+
+        Ok(Status {
+            voluntary_ctxt_switches: voluntary_ctxt_switches
+                .ok_or_else(|| anyhow!("voluntary_ctxt_switches missing"))?,
+            nonvoluntary_ctxt_switches: nonvoluntary_ctxt_switches
+                .ok_or_else(|| anyhow!("nonvoluntary_ctxt_switches missing"))?,
+        })
+    }
+}
+
 /// A wrapper around a Linux process operations in `/proc/<PID>`.
 ///
 /// Core documentation based on [proc's manpages](https://man7.org/linux/man-pages/man5/proc.5.html).
@@ -240,6 +321,10 @@ pub(crate) struct Process {
     pub stat: Stat,
     pub io: Option<Io>,
     pub cmdline: Option<String>,
+    pub status: Option<Status>,
+    pub fd_count: Option<u64>,
+    pub exe: Option<String>,
+    pub oom_score: Option<u32>,
 }
 
 #[inline]
@@ -259,9 +344,12 @@ impl Process {
     /// will be discarded quickly.
     ///
     /// This takes in a buffer to avoid allocs; this function will clear the buffer.
+    ///
+    /// Files that are only needed for some columns are only read if requested
+    /// in `fields`.
     #[inline]
     pub(crate) fn from_path(
-        pid_path: PathBuf, buffer: &mut String, get_threads: bool,
+        pid_path: PathBuf, buffer: &mut String, get_threads: bool, fields: ProcessFields,
     ) -> anyhow::Result<(Process, Vec<PathBuf>)> {
         buffer.clear();
 
@@ -316,6 +404,46 @@ impl Process {
 
         reset(&mut root, buffer);
 
+        let status = if fields.context_switches {
+            let status = open_at(&mut root, "status", &pid_dir)
+                .and_then(|file| Status::from_file(file, buffer))
+                .ok();
+            reset(&mut root, buffer);
+            status
+        } else {
+            None
+        };
+
+        let fd_count = if fields.fd_count {
+            let fd_count = fd_count(&mut root);
+            reset(&mut root, buffer);
+            fd_count
+        } else {
+            None
+        };
+
+        let exe = if fields.exe {
+            root.push("exe");
+            let exe = rustix::fs::readlinkat(rustix::fs::CWD, root.as_path(), vec![])
+                .ok()
+                .map(|exe| exe.to_string_lossy().into_owned());
+            reset(&mut root, buffer);
+            exe
+        } else {
+            None
+        };
+
+        let oom_score = if fields.oom_score {
+            let oom_score = open_at(&mut root, "oom_score", &pid_dir)
+                .and_then(|mut file| Ok(file.read_to_string(buffer)?))
+                .ok()
+                .and_then(|_| buffer.trim().parse().ok());
+            reset(&mut root, buffer);
+            oom_score
+        } else {
+            None
+        };
+
         let threads = threads(&mut root, pid, get_threads);
 
         Ok((
@@ -325,6 +453,10 @@ impl Process {
                 stat,
                 io,
                 cmdline,
+                status,
+                fd_count,
+                exe,
+                oom_score,
             },
             threads,
         ))
@@ -349,6 +481,29 @@ fn open_at(root: &mut PathBuf, child: &str, fd: &OwnedFd) -> anyhow::Result<File
     Ok(File::from(new_fd))
 }
 
+/// Counts the entries in the process's `fd` directory. This needs the same
+/// permissions as reading the file descriptors themselves.
+#[inline]
+fn fd_count(root: &mut PathBuf) -> Option<u64> {
+    root.push("fd");
+
+    let fd_dir = rustix::fs::openat(
+        rustix::fs::CWD,
+        root.as_path(),
+        OFlags::RDONLY | OFlags::DIRECTORY | OFlags::CLOEXEC,
+        Mode::empty(),
+    )
+    .ok()?;
+
+    let count = rustix::fs::Dir::read_from(fd_dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| !matches!(entry.file_name().to_bytes(), b"." | b".."))
+        .count();
+
+    Some(count as u64)
+}
+
 #[inline]
 fn threads(root: &mut PathBuf, pid: Pid, get_threads: bool) -> Vec<PathBuf> {
     if get_threads {
@@ -0,0 +1,197 @@
+//! Detailed process memory usage from `/proc/<PID>/smaps_rollup`.
+//!
+//! Reading this makes the kernel walk every mapping of the process, so it is
+//! much slower than the rest of a process's details and is only read at its
+//! own, slower rate.
+
+use std::fs;
+
+use nohash::{IntMap, IntSet};
+
+use crate::collection::{
+    SystemRoots,
+    processes::{Bytes, Pid, ProcessHarvest},
+};
+
+/// A process's memory usage, split by how its pages are shared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryDetails {
+    /// Proportional set size, where each shared page is divided between the
+    /// processes sharing it.
+    pub pss: Bytes,
+
+    /// Unique set size, the pages only this process uses.
+    pub uss: Bytes,
+
+    /// Resident pages shared with other processes.
+    pub shared: Bytes,
+
+    /// Pages swapped out.
+    pub swap: Bytes,
+}
+
+impl MemoryDetails {
+    /// Parses the contents of a `smaps_rollup` file, where each value is in kB.
+    fn parse(contents: &str) -> Option<MemoryDetails> {
+        let mut details = MemoryDetails::default();
+        let mut found_pss = false;
+
+        for line in contents.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(field), Some(value)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let Ok(kib) = value.parse::<u64>() else {
+                continue;
+            };
+            let bytes = kib * 1024;
+
+            match field {
+                "Pss:" => {
+                    details.pss = bytes;
+                    found_pss = true;
+                }
+                "Private_Clean:" | "Private_Dirty:" => details.uss += bytes,
+                "Shared_Clean:" | "Shared_Dirty:" => details.shared += bytes,
+                "Swap:" => details.swap = bytes,
+                _ => {}
+            }
+        }
+
+        found_pss.then_some(details)
+    }
+}
+
+/// Reads the memory details of `pid`. This fails without permission to
+/// read the process's memory maps, or for kernel threads, which have none.
+fn read_memory_details(roots: &SystemRoots, pid: Pid) -> Option<MemoryDetails> {
+    let contents = fs::read_to_string(roots.proc_path(format!("{pid}/smaps_rollup"))).ok()?;
+
+    MemoryDetails::parse(&contents)
+}
+
+/// The last memory details read for each process, so that they can be shown
+/// between reads.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryDetailsCache {
+    /// Each process's start time when it was read, to tell reused PIDs apart,
+    /// and its details, if they could be read.
+    entries: IntMap<Pid, (u64, Option<MemoryDetails>)>,
+}
+
+impl MemoryDetailsCache {
+    /// Sets the memory details of `processes`, which must be sorted by PID.
+    ///
+    /// If `targets` is set, only those processes are read. Processes are only
+    /// read if `refresh` is set or they haven't been read yet, and the cached
+    /// details are used otherwise. Returns how many processes were read.
+    pub(crate) fn update(
+        &mut self, processes: &mut [ProcessHarvest], roots: &SystemRoots,
+        targets: Option<&IntSet<Pid>>, refresh: bool,
+    ) -> usize {
+        // Forget processes that have exited.
+        self.entries.retain(|pid, (start_time, _)| {
+            processes
+                .binary_search_by_key(pid, |process| process.pid)
+                .is_ok_and(|index| processes[index].start_time == *start_time)
+        });
+
+        let mut num_read = 0;
+        for process in processes {
+            // Threads share their process's memory, so they would only count
+            // it again.
+            if process.process_type.is_thread() {
+                continue;
+            }
+
+            let is_target = targets.is_none_or(|targets| targets.contains(&process.pid));
+            let cached = self.entries.get(&process.pid).map(|(_, details)| *details);
+
+            process.memory_details = match cached {
+                Some(details) if !(refresh && is_target) => details,
+                None if !is_target => None,
+                _ => {
+                    num_read += 1;
+                    let details = read_memory_details(roots, process.pid);
+                    self.entries
+                        .insert(process.pid, (process.start_time, details));
+
+                    details
+                }
+            };
+        }
+
+        num_read
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_smaps_rollup() {
+        let details = MemoryDetails::parse(
+            "55d0e2a1e000-7ffd4e9f5000 ---p 00000000 00:00 0                          [rollup]
+Rss:               12340 kB
+Pss:                5432 kB
+Pss_Dirty:          2000 kB
+Shared_Clean:       6000 kB
+Shared_Dirty:        340 kB
+Private_Clean:      1000 kB
+Private_Dirty:      5000 kB
+Swap:                128 kB
+SwapPss:              64 kB
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            details,
+            MemoryDetails {
+                pss: 5432 * 1024,
+                uss: 6000 * 1024,
+                shared: 6340 * 1024,
+                swap: 128 * 1024,
+            }
+        );
+
+        assert_eq!(MemoryDetails::parse(""), None);
+    }
+
+    #[test]
+    fn test_cache_reads_targets() {
+        let roots = SystemRoots::fixture();
+        let mut cache = MemoryDetailsCache::default();
+        let mut processes = [1, 2].map(|pid| ProcessHarvest {
+            pid,
+            ..Default::default()
+        });
+
+        // Only PID 1 is visible, and PID 2 has no fixture.
+        let targets = IntSet::from_iter([1]);
+        assert_eq!(
+            cache.update(&mut processes, &roots, Some(&targets), true),
+            1
+        );
+        assert_eq!(processes[0].memory_details.unwrap().pss, 5432 * 1024);
+        assert_eq!(processes[1].memory_details, None);
+
+        // Cached details are used until the next refresh.
+        processes[0].memory_details = None;
+        assert_eq!(
+            cache.update(&mut processes, &roots, Some(&targets), false),
+            0
+        );
+        assert!(processes[0].memory_details.is_some());
+
+        // Without targets, unread processes are read right away.
+        assert_eq!(cache.update(&mut processes, &roots, None, false), 1);
+        assert_eq!(processes[1].memory_details, None);
+        assert_eq!(cache.update(&mut processes, &roots, None, false), 0);
+
+        // A reused PID is read again.
+        processes[0].start_time = 1;
+        assert_eq!(cache.update(&mut processes, &roots, None, false), 1);
+    }
+}
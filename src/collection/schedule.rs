@@ -0,0 +1,318 @@
+//! Scheduling for collecting each subsystem at its own rate.
+//!
+//! The collection loop runs at the shortest of all the rates, and each
+//! subsystem is only collected once its own rate has passed.
+
+use std::time::{Duration, Instant};
+
+/// How often each subsystem is collected, in milliseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollectionRates {
+    pub cpu: u64,
+    pub memory: u64,
+    pub network: u64,
+    pub processes: u64,
+
+    /// Detailed process memory is only read alongside processes, and is
+    /// slow enough to read that it defaults to a multiple of their rate.
+    pub process_memory: u64,
+    pub disks: u64,
+    pub temperatures: u64,
+    pub batteries: u64,
+    pub gpus: u64,
+    pub power: u64,
+
+    /// Custom metrics always use the base rate, as they have their own
+    /// per-metric interval on top of it.
+    pub custom: u64,
+}
+
+impl Default for CollectionRates {
+    fn default() -> Self {
+        Self::uniform(1000)
+    }
+}
+
+impl CollectionRates {
+    /// How many process collections pass between reads of detailed process
+    /// memory by default.
+    pub const PROCESS_MEMORY_FACTOR: u64 = 5;
+
+    /// Collect every subsystem at the same rate, other than detailed process
+    /// memory, which uses its default multiple of it.
+    pub fn uniform(rate_ms: u64) -> Self {
+        Self {
+            cpu: rate_ms,
+            memory: rate_ms,
+            network: rate_ms,
+            processes: rate_ms,
+            process_memory: rate_ms * Self::PROCESS_MEMORY_FACTOR,
+            disks: rate_ms,
+            temperatures: rate_ms,
+            batteries: rate_ms,
+            gpus: rate_ms,
+            power: rate_ms,
+            custom: rate_ms,
+        }
+    }
+
+    /// The shortest rate, which is how often the collection loop must run.
+    /// Detailed process memory isn't included, as it is only read when
+    /// processes are.
+    pub fn tick(&self) -> u64 {
+        [
+            self.cpu,
+            self.memory,
+            self.network,
+            self.processes,
+            self.disks,
+            self.temperatures,
+            self.batteries,
+            self.gpus,
+            self.power,
+            self.custom,
+        ]
+        .into_iter()
+        .min()
+        .unwrap_or(self.custom)
+    }
+}
+
+/// Tracks when a single subsystem was last collected.
+#[derive(Debug)]
+pub(crate) struct SubsystemTimer {
+    rate: Duration,
+    last_run: Instant,
+    due: bool,
+}
+
+impl SubsystemTimer {
+    fn new(rate_ms: u64, last_run: Instant) -> Self {
+        Self {
+            rate: Duration::from_millis(rate_ms),
+            last_run,
+            due: true,
+        }
+    }
+
+    /// Whether this subsystem should be collected in the current update.
+    #[inline]
+    pub(crate) fn is_due(&self) -> bool {
+        self.due
+    }
+
+    /// When this subsystem was last collected, not counting the current update.
+    #[inline]
+    pub(crate) fn last_run(&self) -> Instant {
+        self.last_run
+    }
+
+    /// Collect this subsystem in the current update, even if it isn't due.
+    #[inline]
+    pub(crate) fn force(&mut self) {
+        self.due = true;
+    }
+
+    fn start(&mut self, now: Instant, slack: Duration) {
+        self.due = now.duration_since(self.last_run) + slack >= self.rate;
+    }
+
+    fn finish(&mut self, now: Instant) {
+        if self.due {
+            self.last_run = now;
+        }
+    }
+}
+
+/// The timers for every subsystem.
+#[derive(Debug)]
+pub(crate) struct CollectionSchedule {
+    pub(crate) cpu: SubsystemTimer,
+    pub(crate) memory: SubsystemTimer,
+    pub(crate) network: SubsystemTimer,
+    pub(crate) processes: SubsystemTimer,
+    pub(crate) process_memory: SubsystemTimer,
+    pub(crate) disks: SubsystemTimer,
+    pub(crate) temperatures: SubsystemTimer,
+    pub(crate) batteries: SubsystemTimer,
+    pub(crate) gpus: SubsystemTimer,
+    pub(crate) power: SubsystemTimer,
+    pub(crate) custom: SubsystemTimer,
+
+    /// Allowance for the collection loop waking up slightly early, so a
+    /// subsystem isn't pushed back a whole tick by a few milliseconds.
+    slack: Duration,
+}
+
+impl CollectionSchedule {
+    /// Creates a schedule where every subsystem is due, treating `last_run` as
+    /// the last time each was collected.
+    pub(crate) fn new(rates: &CollectionRates, last_run: Instant) -> Self {
+        Self {
+            cpu: SubsystemTimer::new(rates.cpu, last_run),
+            memory: SubsystemTimer::new(rates.memory, last_run),
+            network: SubsystemTimer::new(rates.network, last_run),
+            processes: SubsystemTimer::new(rates.processes, last_run),
+            process_memory: SubsystemTimer::new(rates.process_memory, last_run),
+            disks: SubsystemTimer::new(rates.disks, last_run),
+            temperatures: SubsystemTimer::new(rates.temperatures, last_run),
+            batteries: SubsystemTimer::new(rates.batteries, last_run),
+            gpus: SubsystemTimer::new(rates.gpus, last_run),
+            power: SubsystemTimer::new(rates.power, last_run),
+            custom: SubsystemTimer::new(rates.custom, last_run),
+            slack: Duration::from_millis(rates.tick() / 2),
+        }
+    }
+
+    fn timers_mut(&mut self) -> [&mut SubsystemTimer; 11] {
+        [
+            &mut self.cpu,
+            &mut self.memory,
+            &mut self.network,
+            &mut self.processes,
+            &mut self.process_memory,
+            &mut self.disks,
+            &mut self.temperatures,
+            &mut self.batteries,
+            &mut self.gpus,
+            &mut self.power,
+            &mut self.custom,
+        ]
+    }
+
+    /// Determines which subsystems are due for an update at `now`.
+    pub(crate) fn start(&mut self, now: Instant) {
+        let slack = self.slack;
+
+        for timer in self.timers_mut() {
+            timer.start(now, slack);
+        }
+
+        // Detailed process memory is applied to the process list, so it can
+        // only be read when that is.
+        self.process_memory.due &= self.processes.due;
+    }
+
+    /// Makes every subsystem due in the current update.
+    pub(crate) fn force_all(&mut self) {
+        for timer in self.timers_mut() {
+            timer.force();
+        }
+    }
+
+    /// Records that all due subsystems were collected at `now`.
+    pub(crate) fn finish(&mut self, now: Instant) {
+        for timer in self.timers_mut() {
+            timer.finish(now);
+        }
+    }
+
+    /// Makes every subsystem due on the next update.
+    pub(crate) fn reset(&mut self) {
+        for timer in self.timers_mut() {
+            timer.last_run = timer
+                .last_run
+                .checked_sub(timer.rate)
+                .unwrap_or(timer.last_run);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tick_is_shortest_rate() {
+        let rates = CollectionRates {
+            cpu: 250,
+            disks: 10_000,
+            ..CollectionRates::uniform(1000)
+        };
+
+        assert_eq!(rates.tick(), 250);
+        assert_eq!(CollectionRates::uniform(2000).tick(), 2000);
+    }
+
+    #[test]
+    fn subsystems_run_at_own_rate() {
+        let rates = CollectionRates {
+            cpu: 250,
+            disks: 1000,
+            ..CollectionRates::uniform(500)
+        };
+        let start = Instant::now();
+        let mut schedule = CollectionSchedule::new(&rates, start);
+
+        let mut cpu_runs = 0;
+        let mut disk_runs = 0;
+        let mut network_runs = 0;
+
+        // Tick every 250ms for two seconds, with a bit of jitter.
+        for tick in 1..=8 {
+            let now = start + Duration::from_millis(tick * 250 + tick % 3);
+            schedule.start(now);
+
+            cpu_runs += schedule.cpu.is_due() as u32;
+            disk_runs += schedule.disks.is_due() as u32;
+            network_runs += schedule.network.is_due() as u32;
+
+            schedule.finish(now);
+        }
+
+        assert_eq!(cpu_runs, 8);
+        assert_eq!(network_runs, 4);
+        assert_eq!(disk_runs, 2);
+    }
+
+    #[test]
+    fn tracks_last_run() {
+        let rates = CollectionRates {
+            disks: 1000,
+            ..CollectionRates::uniform(500)
+        };
+        let start = Instant::now();
+        let mut schedule = CollectionSchedule::new(&rates, start);
+
+        let first = start + Duration::from_millis(500);
+        schedule.start(first);
+        assert!(!schedule.disks.is_due());
+        schedule.finish(first);
+        assert_eq!(schedule.disks.last_run(), start);
+        assert_eq!(schedule.network.last_run(), first);
+
+        schedule.reset();
+        let second = first + Duration::from_millis(1);
+        schedule.start(second);
+        assert!(schedule.disks.is_due());
+        assert!(schedule.network.is_due());
+    }
+
+    #[test]
+    fn process_memory_follows_processes() {
+        let rates = CollectionRates {
+            processes: 1000,
+            process_memory: 1500,
+            ..CollectionRates::uniform(500)
+        };
+        let start = Instant::now();
+        let mut schedule = CollectionSchedule::new(&rates, start);
+
+        let mut memory_runs = Vec::new();
+        for tick in 1..=8 {
+            let now = start + Duration::from_millis(tick * 500);
+            schedule.start(now);
+
+            if schedule.process_memory.is_due() {
+                assert!(schedule.processes.is_due());
+                memory_runs.push(tick);
+            }
+
+            schedule.finish(now);
+        }
+
+        // Due at 1.5s and 3.5s, but held back until processes next run.
+        assert_eq!(memory_runs, [4, 8]);
+        assert_eq!(rates.tick(), 500);
+    }
+}
@@ -11,7 +11,10 @@ use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use super::TempSensorData;
 #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
 use crate::collection::amd::get_amd_name;
-use crate::{app::filter::Filter, collection::linux::utils::is_device_awake};
+use crate::{
+    app::filter::Filter,
+    collection::{SystemRoots, linux::utils::is_device_awake},
+};
 
 const EMPTY_NAME: &str = "Unknown";
 
@@ -30,10 +33,11 @@ fn parse_temp(path: &Path) -> Result<f32> {
 
 /// Get all candidates from hwmon and coretemp. It will also return the number
 /// of entries from hwmon.
-fn get_hwmon_candidates() -> (HashSet<PathBuf>, usize) {
+fn get_hwmon_candidates(roots: &SystemRoots) -> (HashSet<PathBuf>, usize) {
     let mut dirs = HashSet::default();
+    let hwmon_path = roots.sys_path("class/hwmon");
 
-    if let Ok(read_dir) = Path::new("/sys/class/hwmon").read_dir() {
+    if let Ok(read_dir) = hwmon_path.read_dir() {
         for entry in read_dir.flatten() {
             let mut path = entry.path();
 
@@ -60,7 +64,7 @@ fn get_hwmon_candidates() -> (HashSet<PathBuf>, usize) {
 
     let num_hwmon = dirs.len();
 
-    if let Ok(read_dir) = Path::new("/sys/devices/platform").read_dir() {
+    if let Ok(read_dir) = roots.sys_path("devices/platform").read_dir() {
         for entry in read_dir.flatten() {
             if entry.file_name().to_string_lossy().starts_with("coretemp.") {
                 if let Ok(read_dir) = entry.path().join("hwmon").read_dir() {
@@ -75,7 +79,7 @@ fn get_hwmon_candidates() -> (HashSet<PathBuf>, usize) {
                             //
                             // For more info, see https://github.com/giampaolo/psutil/pull/1822/files
                             if let Some(child) = path.file_name() {
-                                let to_check_path = Path::new("/sys/class/hwmon").join(child);
+                                let to_check_path = hwmon_path.join(child);
 
                                 if !dirs.contains(&to_check_path) {
                                     dirs.insert(path);
@@ -200,11 +204,11 @@ fn finalize_name(
 /// the device is already in ACPI D0. This has the notable issue that
 /// once this happens, the device will be *kept* on through the sensor
 /// reading, and not be able to re-enter ACPI D3cold.
-fn hwmon_temperatures(filter: &Option<Filter>) -> HwmonResults {
+fn hwmon_temperatures(roots: &SystemRoots, filter: &Option<Filter>) -> HwmonResults {
     let mut temperatures: Vec<TempSensorData> = vec![];
     let mut seen_names: HashMap<String, u32> = HashMap::default();
 
-    let (dirs, num_hwmon) = get_hwmon_candidates();
+    let (dirs, num_hwmon) = get_hwmon_candidates(roots);
 
     // Note that none of this is async if we ever go back to it, but sysfs is in
     // memory, so in theory none of this should block if we're slightly careful.
@@ -350,8 +354,10 @@ fn hwmon_temperatures(filter: &Option<Filter>) -> HwmonResults {
 ///
 /// See [the Linux kernel documentation](https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-thermal)
 /// for more details.
-fn add_thermal_zone_temperatures(temperatures: &mut Vec<TempSensorData>, filter: &Option<Filter>) {
-    let path = Path::new("/sys/class/thermal");
+fn add_thermal_zone_temperatures(
+    roots: &SystemRoots, temperatures: &mut Vec<TempSensorData>, filter: &Option<Filter>,
+) {
+    let path = roots.sys_path("class/thermal");
     let Ok(read_dir) = path.read_dir() else {
         return;
     };
@@ -391,11 +397,13 @@ fn add_thermal_zone_temperatures(temperatures: &mut Vec<TempSensorData>, filter:
 }
 
 /// Gets temperature sensors and data.
-pub fn get_temperature_data(filter: &Option<Filter>) -> Result<Option<Vec<TempSensorData>>> {
-    let mut results = hwmon_temperatures(filter);
+pub fn get_temperature_data(
+    roots: &SystemRoots, filter: &Option<Filter>,
+) -> Result<Option<Vec<TempSensorData>>> {
+    let mut results = hwmon_temperatures(roots, filter);
 
     if results.num_hwmon == 0 {
-        add_thermal_zone_temperatures(&mut results.temperatures, filter);
+        add_thermal_zone_temperatures(roots, &mut results.temperatures, filter);
     }
 
     Ok(Some(results.temperatures))
@@ -405,7 +413,7 @@ pub fn get_temperature_data(filter: &Option<Filter>) -> Result<Option<Vec<TempSe
 mod tests {
     use rustc_hash::FxHashMap as HashMap;
 
-    use super::finalize_name;
+    use super::*;
 
     #[test]
     fn test_finalize_name() {
@@ -483,4 +491,42 @@ mod tests {
             "Unknown (4)"
         );
     }
+
+    #[test]
+    fn test_fixture_hwmon() {
+        let mut sensors = get_temperature_data(&SystemRoots::fixture(), &None)
+            .unwrap()
+            .unwrap();
+        sensors.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        let sensors: Vec<_> = sensors
+            .into_iter()
+            .map(|sensor| (sensor.name, sensor.temperature))
+            .collect();
+
+        assert_eq!(
+            sensors,
+            [
+                ("k10temp: Tctl".to_string(), Some(45.25)),
+                ("nvme: Composite".to_string(), Some(38.85)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fixture_thermal_zones() {
+        let mut sensors = vec![];
+        add_thermal_zone_temperatures(&SystemRoots::fixture(), &mut sensors, &None);
+
+        let mut names: Vec<_> = sensors.iter().map(|sensor| sensor.name.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["acpitz", "acpitz (1)"]);
+
+        let mut temperatures: Vec<_> = sensors
+            .iter()
+            .filter_map(|sensor| sensor.temperature)
+            .collect();
+        temperatures.sort_unstable_by(|a, b| a.total_cmp(b));
+        assert_eq!(temperatures, [27.8, 29.8]);
+    }
 }
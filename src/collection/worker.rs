@@ -0,0 +1,247 @@
+//! Running data sources that can hang on their own threads, with deadlines.
+//!
+//! Some sources can block for a very long time, such as `statvfs` on a dead NFS
+//! mount or a wedged NVML call. Running these on a worker thread means the
+//! collection thread can give up on them after a deadline, rather than freezing
+//! every other graph. Each source keeps its worker between collections, and
+//! sources that time out are marked as stale and skipped for a backoff period.
+
+use std::{
+    fmt,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use rustc_hash::FxHashMap as HashMap;
+
+/// How long to skip a source after it first times out.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The longest a source will be skipped for before trying it again.
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// A data source running on its own long-lived thread, which is sent requests
+/// over a channel and replies to each in order.
+pub(crate) struct Worker<I, O> {
+    requests: Sender<I>,
+    replies: Receiver<O>,
+
+    /// How many requests haven't been replied to yet. Replies to requests that
+    /// timed out are dropped when they arrive.
+    unanswered: usize,
+}
+
+impl<I, O> fmt::Debug for Worker<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Worker")
+            .field("unanswered", &self.unanswered)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The result of waiting on a [`Worker`].
+pub(crate) enum WorkerResult<T> {
+    /// The worker finished in time.
+    Done(T),
+
+    /// The worker could not be started, or panicked.
+    Failed,
+
+    /// The worker did not finish before the deadline.
+    TimedOut,
+}
+
+impl<I: Send + 'static, O: Send + 'static> Worker<I, O> {
+    /// Starts a thread that runs `f` on each request it is sent.
+    pub(crate) fn spawn(name: &str, mut f: impl FnMut(I) -> O + Send + 'static) -> Self {
+        let (requests, request_receiver) = mpsc::channel::<I>();
+        let (reply_sender, replies) = mpsc::channel();
+
+        // If this fails, the channels are dropped and requests to the worker
+        // return `WorkerResult::Failed`.
+        let _ = thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                for input in request_receiver {
+                    if reply_sender.send(f(input)).is_err() {
+                        break;
+                    }
+                }
+            });
+
+        Self {
+            requests,
+            replies,
+            unanswered: 0,
+        }
+    }
+
+    /// Returns whether the worker is still stuck on an earlier request. Any
+    /// replies that arrived after their deadline are dropped.
+    pub(crate) fn is_busy(&mut self) -> bool {
+        while self.unanswered > 0 {
+            match self.replies.try_recv() {
+                Ok(_) => self.unanswered -= 1,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => self.unanswered = 0,
+            }
+        }
+
+        self.unanswered > 0
+    }
+
+    /// Sends the worker a request. The worker should not be busy, or the reply
+    /// waited on will be for an earlier request.
+    pub(crate) fn send(&mut self, input: I) {
+        if self.requests.send(input).is_ok() {
+            self.unanswered += 1;
+        }
+    }
+
+    /// Waits until `deadline` for the reply to the last request.
+    pub(crate) fn wait_until(&mut self, deadline: Instant) -> WorkerResult<O> {
+        if self.unanswered == 0 {
+            return WorkerResult::Failed;
+        }
+
+        let timeout = deadline.saturating_duration_since(Instant::now());
+
+        match self.replies.recv_timeout(timeout) {
+            Ok(result) => {
+                self.unanswered -= 1;
+                WorkerResult::Done(result)
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                self.unanswered = 0;
+                WorkerResult::Failed
+            }
+            Err(RecvTimeoutError::Timeout) => WorkerResult::TimedOut,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct StaleSource {
+    retry_at: Instant,
+    backoff: Duration,
+}
+
+/// Tracks sources that have timed out, so they can be skipped for a while.
+///
+/// A source is also not retried while its worker is still stuck, so a dead
+/// mount doesn't pile up requests.
+#[derive(Debug, Default)]
+pub(crate) struct StaleSources {
+    sources: HashMap<String, StaleSource>,
+}
+
+impl StaleSources {
+    /// Returns whether the source should be skipped at `now`, given whether
+    /// its worker is still busy.
+    pub(crate) fn is_stale(&mut self, key: &str, now: Instant, is_busy: bool) -> bool {
+        let Some(source) = self.sources.get_mut(key) else {
+            return is_busy;
+        };
+
+        if now < source.retry_at {
+            true
+        } else if is_busy {
+            source.backoff = (source.backoff * 2).min(MAX_BACKOFF);
+            source.retry_at = now + source.backoff;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Marks a source as stale after its worker timed out.
+    pub(crate) fn mark_stale(&mut self, key: &str, now: Instant) {
+        let backoff = match self.sources.get(key) {
+            Some(source) => (source.backoff * 2).min(MAX_BACKOFF),
+            None => INITIAL_BACKOFF,
+        };
+
+        self.sources.insert(
+            key.to_string(),
+            StaleSource {
+                retry_at: now + backoff,
+                backoff,
+            },
+        );
+    }
+
+    /// Marks a source as responsive again.
+    pub(crate) fn mark_responsive(&mut self, key: &str) {
+        self.sources.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn worker_replies() {
+        let mut worker = Worker::spawn("test", |x: u32| x * 2);
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        // The same thread handles every request.
+        for x in 1..=3 {
+            worker.send(x);
+            assert!(matches!(worker.wait_until(deadline), WorkerResult::Done(y) if y == x * 2));
+        }
+        assert!(!worker.is_busy());
+    }
+
+    #[test]
+    fn worker_times_out() {
+        let (unblock, blocked) = mpsc::channel::<()>();
+        let mut worker = Worker::spawn("test", move |block: bool| {
+            if block {
+                let _ = blocked.recv();
+            }
+            block
+        });
+
+        worker.send(true);
+        let deadline = Instant::now() + Duration::from_millis(20);
+        assert!(matches!(
+            worker.wait_until(deadline),
+            WorkerResult::TimedOut
+        ));
+        assert!(worker.is_busy());
+
+        let now = Instant::now();
+        let mut stale = StaleSources::default();
+        stale.mark_stale("/mnt/nfs", now);
+
+        assert!(stale.is_stale("/mnt/nfs", now, true));
+        assert!(!stale.is_stale("/mnt/other", now, false));
+
+        // Still stuck once the backoff is up, so it stays stale for longer.
+        let retry = now + INITIAL_BACKOFF;
+        assert!(stale.is_stale("/mnt/nfs", retry, worker.is_busy()));
+        assert!(stale.is_stale("/mnt/nfs", retry + INITIAL_BACKOFF, true));
+
+        // Once the worker is unstuck, its late reply is dropped and the source
+        // can be retried.
+        unblock.send(()).unwrap();
+        while worker.is_busy() {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(!stale.is_stale("/mnt/nfs", retry + MAX_BACKOFF, worker.is_busy()));
+
+        worker.send(false);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        assert!(matches!(
+            worker.wait_until(deadline),
+            WorkerResult::Done(false)
+        ));
+
+        stale.mark_responsive("/mnt/nfs");
+        assert!(stale.sources.is_empty());
+    }
+}
@@ -25,7 +25,7 @@ const HELP_CONTENTS_TEXT: [&str; 10] = [
 
 // TODO [Help]: Search in help?
 // TODO [Help]: Move to using tables for easier formatting?
-pub(crate) const GENERAL_HELP_TEXT: [&str; 28] = [
+pub(crate) const GENERAL_HELP_TEXT: [&str; 39] = [
     "1 - General",
     "q, Ctrl-c            Quit",
     "Esc                  Close dialog windows, search, widgets, or exit expanded mode",
@@ -44,9 +44,19 @@ pub(crate) const GENERAL_HELP_TEXT: [&str; 28] = [
     "Up, k                Move up within widget",
     "Right, l             Move right within widget",
     "?                    Open help menu",
+    "F12                  Open diagnostics about data collection",
+    ":                    Open the command prompt",
+    "R                    Reload the config file",
+    "Ctrl-s               Save settings changed while running",
     "gg                   Jump to the first entry",
     "G                    Jump to the last entry",
     "e                    Toggle expanding the currently selected widget",
+    "o                    Pick, reorder, and resize the columns of a table",
+    "y                    Copy the PID, row, or cell of the selected table entry",
+    "x                    Export the selected table or graph to a CSV or JSON file",
+    "X                    Capture the screen to a text, ANSI, SVG, or HTML file",
+    "i                    Show or hide a cursor on a graph, moved with Left/Right/Home/End",
+    "1-9                  Switch to a layout tab, if the config sets more than one",
     "+                    Zoom in on chart (decrease time range)",
     "-                    Zoom out on chart (increase time range)",
     "=                    Reset zoom",
@@ -54,6 +64,7 @@ pub(crate) const GENERAL_HELP_TEXT: [&str; 28] = [
     "Ctrl-u, Ctrl-d       Scroll up/down a table by half a page",
     "Mouse scroll         Scroll through the tables or zoom in/out of charts by scrolling up/down",
     "Mouse click          Selects the clicked widget, table entry, dialog option, or tab",
+    "Mouse drag           Resize a table column by dragging the gap after its header",
 ];
 
 const CPU_HELP_TEXT: [&str; 2] = [
@@ -61,7 +72,7 @@ const CPU_HELP_TEXT: [&str; 2] = [
     "Mouse scroll         Scrolling over a CPU core/average shows only that entry on the chart",
 ];
 
-const PROCESS_HELP_TEXT: [&str; 20] = [
+const PROCESS_HELP_TEXT: [&str; 21] = [
     "3 - Process widget",
     "dd, F9, Delete          Kill the selected process",
     "c                       Sort by CPU usage, press again to reverse",
@@ -82,6 +93,7 @@ const PROCESS_HELP_TEXT: [&str; 20] = [
     "C                       Sort by GPU usage, press again to reverse",
     "M                       Sort by GPU memory usage, press again to reverse",
     "z                       Toggle the display of kernel threads",
+    "[, ]                    While frozen, show the processes from one collection back/later",
 ];
 
 const SEARCH_HELP_TEXT: [&str; 51] = [
@@ -381,6 +393,10 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Use basic mode
 #basic = false
 
+# Draw basic mode widgets side by side in some number of lines below the cursor, instead of
+# using the whole terminal. For example, "inline = 4" uses 4 lines.
+#inline_widgets = ["cpu", "mem", "net"]
+
 # Use the old network legend style
 #use_old_network_legend = false
 
@@ -426,6 +442,10 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # Hides the kernel threads
 #hide_k_threads = false
 
+# Where procfs and sysfs are mounted on Linux, e.g. for a host's filesystems bind-mounted into a container.
+#procfs_root = "/proc"
+#sysfs_root = "/sys"
+
 # Hide GPU(s) information
 #disable_gpu = false
 
@@ -438,6 +458,9 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # How much data is stored at once in terms of time.
 #retention = "10m"
 
+# Save settings changed while running (such as tree mode, process columns, and sorting) when exiting
+#remember = false
+
 # Where to place the legend for the memory widget. One of "none", "top-left", "top", "top-right", "left", "right", "bottom-left", "bottom", "bottom-right".
 #memory_legend = "top-right"
 
@@ -543,6 +566,49 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #whole_word = false
 
 
+# How often to collect each type of data. Anything not set uses the general rate.
+#[collection_rates]
+#cpu = "250ms"
+#network = "1s"
+#processes = "2s"
+#process_memory = "10s"
+#disks = "10s"
+#temperatures = "5s"
+#batteries = "30s"
+
+
+# What copying with "y" or ":copy" copies from tables, and how.
+#[clipboard]
+# What to copy: "pid", "row", or "cell" (the column being sorted by). Defaults to "pid".
+#copy = "pid"
+# How to copy: "auto", "osc52", or "file". "auto" uses OSC 52 unless the terminal is
+# known not to support it, and writes to the file otherwise.
+#method = "auto"
+# Where to write copied text when not using OSC 52.
+#file = "/tmp/bottom-clipboard.txt"
+
+
+# Custom metrics, which can be shown with a "custom" widget in the layout.
+#[[custom_metrics]]
+# The name of the metric, shown as the widget title.
+#name = "Queue depth"
+# A command to run, as the program followed by its arguments.
+#command = ["redis-cli", "llen", "jobs"]
+# Alternatively, a file to read.
+# file = "/sys/class/hwmon/hwmon0/temp1_input"
+# How many collections to wait between reads. Defaults to 1.
+#interval = 4
+# A regex to extract values with, one per capture group. If neither this nor
+# json_path is set, the first number found is used.
+#regex = '(\d+)'
+# Alternatively, a list of JSON paths to extract values with.
+# json_path = ["stats.depth"]
+# The unit shown alongside the values.
+#unit = "jobs"
+# A name for each extracted value.
+#labels = ["jobs"]
+
+
 # These are all the components that support custom theming.  Note that colour support
 # will depend on terminal support.
 #[styles] # Uncomment if you want to use custom styling
@@ -595,6 +661,7 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 #text = {color = "gray"}
 #selected_text = {color = "black", bg_color = "light blue"}
 #disabled_text = {color = "dark gray"}
+#restarting_text = {color = "light red"}
 
 # Only on Linux
 #thread_text = {color = "green"}
@@ -604,8 +671,12 @@ pub(crate) const CONFIG_TEXT: &str = r#"# This is a default config file for bott
 # [[row.child]] represents either a widget or a column.
 # [[row.child.child]] represents a widget.
 #
-# All widgets must have the type value set to one of ["cpu", "mem", "proc", "net", "temp", "disk", "empty"].
+# All widgets must have the type value set to one of ["cpu", "mem", "proc", "net", "temp", "disk", "power", "custom", "proc_log", "conn", "empty"].
 # All layout components have a ratio value - if this is not set, then it defaults to 1.
+# To switch between several layouts as tabs, use [[layout]] entries with a name, each with
+# its own [[layout.row]] list, instead of [[row]].
+# To use other rows at some terminal sizes, add [[breakpoint]] entries with any of min_width,
+# max_width, min_height, and max_height, each with its own [[breakpoint.row]] list.
 # The default widget layout:
 #[[row]]
 #  ratio=30
@@ -5,7 +5,10 @@ use std::sync::mpsc::Sender;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 
 use crate::{
-    app::{App, layout_manager::WidgetDirection},
+    app::{
+        App, AppConfigFields, AppSearchState, DataFilters,
+        layout_manager::{UsedWidgets, WidgetDirection},
+    },
     collection::Data,
 };
 
@@ -17,6 +20,7 @@ pub enum BottomEvent {
     MouseInput(MouseEvent),
     PasteEvent(String),
     Update(Box<Data>),
+    ReloadConfig,
     Clean,
     Terminate,
 }
@@ -25,10 +29,30 @@ pub enum BottomEvent {
 #[derive(Debug)]
 pub enum CollectionThreadEvent {
     Reset,
+
+    /// Change which data is collected, such as when process columns are shown
+    /// or hidden.
+    SetUsedWidgets(UsedWidgets),
+
+    /// Change which processes are shown, which are the only ones whose
+    /// detailed memory is read if there are many processes.
+    #[cfg(target_os = "linux")]
+    SetVisiblePids(Vec<crate::collection::processes::Pid>),
+
+    /// Apply new settings after the config was reloaded.
+    Reconfigure {
+        app_config_fields: Box<AppConfigFields>,
+        filters: DataFilters,
+        used_widgets: UsedWidgets,
+    },
 }
 
 /// Handle a [`MouseEvent`].
 pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
+    if app.command_state.is_showing {
+        return;
+    }
+
     match event.kind {
         MouseEventKind::ScrollUp => app.handle_scroll_up(),
         MouseEventKind::ScrollDown => app.handle_scroll_down(),
@@ -45,16 +69,65 @@ pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
                 }
             }
         }
+        MouseEventKind::Drag(crossterm::event::MouseButton::Left)
+            if !app.app_config_fields.disable_click =>
+        {
+            app.on_left_mouse_drag(event.column);
+        }
+        MouseEventKind::Up(crossterm::event::MouseButton::Left) => app.on_left_mouse_release(),
+        MouseEventKind::Moved => app.on_mouse_move(event.column, event.row),
         _ => {}
     };
 }
 
+/// Handles a key press while the command prompt is open.
+fn handle_command_key(event: KeyEvent, app: &mut App) {
+    let is_control = event.modifiers == KeyModifiers::CONTROL;
+    if !is_control && !event.modifiers.difference(KeyModifiers::SHIFT).is_empty() {
+        return;
+    }
+
+    match event.code {
+        KeyCode::Esc => app.close_command_prompt(),
+        KeyCode::Enter => app.submit_command(),
+        KeyCode::Tab => app.cycle_command_completion(true),
+        KeyCode::BackTab => app.cycle_command_completion(false),
+        KeyCode::Up => app.browse_command_history(true),
+        KeyCode::Down => app.browse_command_history(false),
+        KeyCode::Left => app.command_state.input.move_cursor_left(),
+        KeyCode::Right => app.command_state.input.move_cursor_right(),
+        KeyCode::Home => app.command_state.input.move_cursor_to_end(true),
+        KeyCode::End => app.command_state.input.move_cursor_to_end(false),
+        KeyCode::Char('a') if is_control => app.command_state.input.move_cursor_to_end(true),
+        KeyCode::Char('e') if is_control => app.command_state.input.move_cursor_to_end(false),
+        KeyCode::Char('u') if is_control => {
+            app.edit_command_input(|input| input.set_query(String::new()))
+        }
+        KeyCode::Backspace => app.on_command_backspace(),
+        KeyCode::Char('h') if is_control => app.on_command_backspace(),
+        KeyCode::Delete => app.edit_command_input(AppSearchState::delete_forward),
+        KeyCode::Char(caught_char) if !is_control => {
+            app.edit_command_input(|input| input.insert_str(caught_char.encode_utf8(&mut [0; 4])))
+        }
+        _ => {}
+    }
+}
+
 /// Handle a [`KeyEvent`].
 pub fn handle_key_event_or_break(
     event: KeyEvent, app: &mut App, reset_sender: &Sender<CollectionThreadEvent>,
 ) -> bool {
     // c_debug!("KeyEvent: {event:?}");
 
+    if app.command_state.is_showing {
+        if event.modifiers == KeyModifiers::CONTROL && event.code == KeyCode::Char('c') {
+            return true;
+        }
+
+        handle_command_key(event, app);
+        return std::mem::take(&mut app.command_state.is_quit_requested);
+    }
+
     if event.modifiers.is_empty() {
         match event.code {
             KeyCode::Char('q') if !app.is_in_search_widget() => return true,
@@ -77,6 +150,7 @@ pub fn handle_key_event_or_break(
             KeyCode::F(5) => app.toggle_tree_mode(),
             KeyCode::F(6) => app.toggle_sort_menu(),
             KeyCode::F(9) => app.kill_current_process(),
+            KeyCode::F(12) => app.toggle_diagnostics(),
             KeyCode::PageDown => app.on_page_down(),
             KeyCode::PageUp => app.on_page_up(),
             _ => {}
@@ -108,6 +182,7 @@ pub fn handle_key_event_or_break(
                         app.reset();
                     }
                 }
+                KeyCode::Char('s') => app.is_settings_save_requested = true,
                 KeyCode::Char('a') => app.skip_cursor_beginning(),
                 KeyCode::Char('e') => app.skip_cursor_end(),
                 KeyCode::Char('u') if app.is_in_search_widget() => app.clear_search(),
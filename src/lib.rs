@@ -10,6 +10,7 @@
 pub(crate) mod app;
 mod utils {
     pub(crate) mod cancellation_token;
+    pub(crate) mod clipboard;
     pub(crate) mod conversion;
     pub(crate) mod data_units;
     pub(crate) mod general;
@@ -28,6 +29,7 @@ use std::{
     boxed::Box,
     io::{Write, stderr, stdout},
     panic::{self, PanicHookInfo},
+    path::{Path, PathBuf},
     sync::{
         Arc,
         mpsc::{self, Receiver, Sender},
@@ -37,21 +39,33 @@ use std::{
 };
 
 use app::{App, AppConfigFields, DataFilters, layout_manager::UsedWidgets};
+use canvas::capture::{CaptureFormat, write_capture};
 use crossterm::{
-    cursor::{Hide, Show},
+    cursor::{Hide, MoveTo, Show},
     event::{
         DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
         Event, KeyEventKind, MouseEventKind, poll, read,
     },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
+    },
 };
 use event::{BottomEvent, CollectionThreadEvent, handle_key_event_or_break, handle_mouse_event};
-use options::{args, get_or_create_config, init_app};
-use tui::{Terminal, backend::CrosstermBackend};
+use options::{
+    args::{self, BottomArgs},
+    get_config_path, get_or_create_config, init_app, persist, read_config,
+    watcher::ConfigWatcher,
+};
+use tui::{
+    Terminal, TerminalOptions, Viewport,
+    backend::{CrosstermBackend, TestBackend},
+    layout::Rect,
+};
 #[allow(unused_imports, reason = "this is needed if logging is enabled")]
 use utils::logging::*;
-use utils::{cancellation_token::CancellationToken, conversion::*};
+use utils::{cancellation_token::CancellationToken, clipboard::copy_text, conversion::*};
 
 use crate::collection::Data;
 
@@ -64,27 +78,198 @@ fn try_drawing(
     terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>, app: &mut App,
     painter: &mut canvas::Painter,
 ) -> anyhow::Result<()> {
-    if let Err(err) = painter.draw_data(terminal, app) {
-        cleanup_terminal(terminal)?;
-        Err(err.into())
-    } else {
-        Ok(())
+    let draw_start = Instant::now();
+    let result = painter.draw_data(terminal, app).map(|frame| {
+        app.pending_capture.take().map(|(format, path)| {
+            write_capture(frame.buffer, format, &path, &painter.styles)
+                .map(|_| format!("Captured the screen to {}.", path.display()))
+        })
+    });
+    app.diagnostics_dialog_state.draw_duration = draw_start.elapsed();
+
+    match result {
+        Err(err) => {
+            cleanup_terminal(terminal, app.app_config_fields.inline_height.is_some())?;
+            Err(err.into())
+        }
+        Ok(None) => Ok(()),
+        Ok(Some(capture)) => {
+            // Say where the capture went, which needs another frame.
+            match capture {
+                Ok(message) => {
+                    app.open_command_prompt();
+                    app.command_state.message = Some(message);
+                }
+                Err(err) => {
+                    app.config_error = Some(format!("{err:#}"));
+                    app.is_force_redraw = true;
+                }
+            }
+            try_drawing(terminal, app, painter)
+        }
     }
 }
 
-/// Clean up the terminal before returning it to the user.
+/// Draws one frame of `size` to `path` once data has been collected
+/// `collections` times, without using the terminal.
+fn capture_headless(
+    mut app: App, mut painter: canvas::Painter, path: &Path, size: Rect, collections: u64,
+) -> anyhow::Result<()> {
+    let cancellation_token = Arc::new(CancellationToken::default());
+    let (sender, receiver) = mpsc::channel();
+    let (_collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
+    let _collection_thread = create_collection_thread(
+        sender,
+        collection_thread_ctrl_receiver,
+        cancellation_token.clone(),
+        &app.app_config_fields,
+        app.filters.clone(),
+        app.used_widgets,
+    );
+
+    let mut cleaner = DataCleaner::new(&app.app_config_fields);
+    let mut collected = 0;
+    while collected < collections {
+        if let BottomEvent::Update(data) = receiver.recv()? {
+            app.data_store.eat_data(data, &app.app_config_fields);
+            cleaner.clean_if_due(&mut app);
+            collected += 1;
+        }
+    }
+    cancellation_token.cancel();
+
+    app.force_widget_data_update();
+    app.update_data();
+
+    let mut terminal = Terminal::new(TestBackend::new(size.width, size.height))?;
+    let frame = painter.draw_data(&mut terminal, &mut app)?;
+    write_capture(
+        frame.buffer,
+        CaptureFormat::from_path(path),
+        path,
+        &painter.styles,
+    )
+}
+
+/// Cleans up data older than the retention period, for when there is no
+/// cleaning thread, such as in batch mode.
+struct DataCleaner {
+    interval: Duration,
+    last_cleaned: Instant,
+}
+
+impl DataCleaner {
+    fn new(config: &AppConfigFields) -> Self {
+        DataCleaner {
+            interval: cleaning_interval(config),
+            last_cleaned: Instant::now(),
+        }
+    }
+
+    fn clean_if_due(&mut self, app: &mut App) {
+        if self.last_cleaned.elapsed() >= self.interval {
+            app.data_store
+                .clean_data(Duration::from_millis(app.app_config_fields.retention_ms));
+            self.last_cleaned = Instant::now();
+        }
+    }
+}
+
+/// How often to clean up data older than the retention period.
+fn cleaning_interval(config: &AppConfigFields) -> Duration {
+    Duration::from_millis(config.retention_ms + 60000)
+}
+
+/// Prints the process list as text every time processes are collected,
+/// instead of drawing the interface.
+fn run_batch(mut app: App, args: &args::ProcessArgs) -> anyhow::Result<()> {
+    app.prepare_batch(args.batch_query.as_deref(), args.batch_sort.as_deref())
+        .map_err(anyhow::Error::msg)?;
+
+    let cancellation_token = Arc::new(CancellationToken::default());
+    let (sender, receiver) = mpsc::channel();
+    let (_collection_thread_ctrl_sender, collection_thread_ctrl_receiver) = mpsc::channel();
+    let _collection_thread = create_collection_thread(
+        sender,
+        collection_thread_ctrl_receiver,
+        cancellation_token.clone(),
+        &app.app_config_fields,
+        app.filters.clone(),
+        app.used_widgets,
+    );
+
+    let mut stdout = stdout().lock();
+    let mut cleaner = DataCleaner::new(&app.app_config_fields);
+    let mut is_first_collection = true;
+    let mut printed = 0;
+    while args
+        .batch_iterations
+        .is_none_or(|iterations| printed < iterations)
+    {
+        let BottomEvent::Update(data) = receiver.recv()? else {
+            continue;
+        };
+        let has_processes = data.list_of_processes.is_some();
+        app.data_store.eat_data(data, &app.app_config_fields);
+        cleaner.clean_if_due(&mut app);
+
+        // Other data may be collected more often than processes, so only print
+        // when processes were collected.
+        if !has_processes {
+            continue;
+        }
+
+        // Process CPU usage needs two collections, so skip the first.
+        if std::mem::take(&mut is_first_collection) {
+            continue;
+        }
+
+        app.force_widget_data_update();
+        app.update_data();
+
+        let Some(text) = app.batch_text(args.batch_rows) else {
+            break;
+        };
+        let separator = if printed == 0 { "" } else { "\n" };
+        let result = write!(stdout, "{separator}{text}").and_then(|_| stdout.flush());
+
+        // Stop quietly if the output was closed, such as when piped to `head`.
+        match result {
+            Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => break,
+            result => result?,
+        }
+        printed += 1;
+    }
+
+    cancellation_token.cancel();
+    Ok(())
+}
+
+/// Clean up the terminal before returning it to the user. In inline mode, the
+/// lines that were drawn in are cleared, leaving the cursor where bottom
+/// started.
 fn cleanup_terminal(
-    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>, is_inline: bool,
 ) -> anyhow::Result<()> {
     disable_raw_mode()?;
 
-    execute!(
-        terminal.backend_mut(),
-        DisableMouseCapture,
-        DisableBracketedPaste,
-        LeaveAlternateScreen,
-        Show,
-    )?;
+    if is_inline {
+        terminal.clear()?;
+        execute!(
+            terminal.backend_mut(),
+            DisableMouseCapture,
+            DisableBracketedPaste,
+            Show,
+        )?;
+    } else {
+        execute!(
+            terminal.backend_mut(),
+            DisableMouseCapture,
+            DisableBracketedPaste,
+            LeaveAlternateScreen,
+            Show,
+        )?;
+    }
     terminal.show_cursor()?;
 
     Ok(())
@@ -104,22 +289,48 @@ fn check_if_terminal() {
     }
 }
 
-/// This manually resets stdout back to normal state.
-pub fn reset_stdout() {
+/// How bottom has set up the terminal, so it can be reset if bottom exits early.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminalMode {
+    /// Drawing over the whole terminal in the alternate screen.
+    Fullscreen,
+
+    /// Drawing in the lines starting at row `top`, below the cursor.
+    Inline { top: u16 },
+}
+
+/// This manually resets stdout back to normal state. In inline mode, the lines
+/// that were drawn in are cleared rather than leaving the alternate screen.
+pub fn reset_stdout(mode: TerminalMode) {
     let mut stdout = stdout();
     let _ = disable_raw_mode();
-    let _ = execute!(
-        stdout,
-        DisableMouseCapture,
-        DisableBracketedPaste,
-        LeaveAlternateScreen,
-        Show,
-    );
+
+    match mode {
+        TerminalMode::Fullscreen => {
+            let _ = execute!(
+                stdout,
+                DisableMouseCapture,
+                DisableBracketedPaste,
+                LeaveAlternateScreen,
+                Show,
+            );
+        }
+        TerminalMode::Inline { top } => {
+            let _ = execute!(
+                stdout,
+                DisableMouseCapture,
+                DisableBracketedPaste,
+                MoveTo(0, top),
+                Clear(ClearType::FromCursorDown),
+                Show,
+            );
+        }
+    }
 }
 
 /// A panic hook to properly restore the terminal in the case of a panic.
 /// Originally based on [spotify-tui's implementation](https://github.com/Rigellute/spotify-tui/blob/master/src/main.rs).
-fn panic_hook(panic_info: &PanicHookInfo<'_>) {
+fn panic_hook(panic_info: &PanicHookInfo<'_>, mode: TerminalMode) {
     let msg = match panic_info.payload().downcast_ref::<&'static str>() {
         Some(s) => *s,
         None => match panic_info.payload().downcast_ref::<String>() {
@@ -130,7 +341,7 @@ fn panic_hook(panic_info: &PanicHookInfo<'_>) {
 
     let backtrace = format!("{:?}", std::backtrace::Backtrace::capture());
 
-    reset_stdout();
+    reset_stdout(mode);
 
     // Print stack trace. Must be done after!
     if let Some(panic_info) = panic_info.location() {
@@ -190,8 +401,10 @@ fn create_input_thread(
                                 }
                             }
                             Event::Mouse(mouse) => match mouse.kind {
-                                MouseEventKind::Moved | MouseEventKind::Drag(..) => {}
-                                MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                                MouseEventKind::Moved
+                                | MouseEventKind::ScrollDown
+                                | MouseEventKind::ScrollUp
+                                | MouseEventKind::Drag(..) => {
                                     if Instant::now().duration_since(mouse_timer).as_millis() >= 20
                                     {
                                         if sender.send(BottomEvent::MouseInput(mouse)).is_err() {
@@ -217,33 +430,47 @@ fn create_input_thread(
     })
 }
 
+/// Applies the collection-related settings to a [`DataCollector`](collection::DataCollector).
+fn configure_collector(
+    data_collector: &mut collection::DataCollector, app_config_fields: &AppConfigFields,
+    filters: DataFilters, used_widget_set: UsedWidgets,
+) {
+    data_collector.set_filters(filters);
+    data_collector.set_collection(used_widget_set);
+    data_collector.set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
+    data_collector.set_unnormalized_cpu(app_config_fields.unnormalized_cpu);
+    data_collector.set_show_average_cpu(app_config_fields.show_average_cpu);
+    data_collector.set_get_process_threads(app_config_fields.get_process_threads);
+    #[cfg(feature = "zfs")]
+    data_collector.set_free_arc_mem(app_config_fields.free_arc);
+    #[cfg(target_os = "linux")]
+    data_collector.set_system_roots(app_config_fields.system_roots.clone());
+    data_collector.set_custom_metrics(app_config_fields.custom_metrics.clone());
+    data_collector.set_collection_rates(&app_config_fields.collection_rates);
+}
+
 /// Create a thread to handle data collection.
 fn create_collection_thread(
     sender: Sender<BottomEvent>, control_receiver: Receiver<CollectionThreadEvent>,
     cancellation_token: Arc<CancellationToken>, app_config_fields: &AppConfigFields,
     filters: DataFilters, used_widget_set: UsedWidgets,
 ) -> JoinHandle<()> {
-    let use_current_cpu_total = app_config_fields.use_current_cpu_total;
-    let unnormalized_cpu = app_config_fields.unnormalized_cpu;
-    let show_average_cpu = app_config_fields.show_average_cpu;
-    let update_sleep = app_config_fields.update_rate;
-    let get_process_threads = app_config_fields.get_process_threads;
-    #[cfg(feature = "zfs")]
-    let get_arc_free = app_config_fields.free_arc;
+    let app_config_fields = app_config_fields.clone();
 
     thread::spawn(move || {
-        let mut data_collector = collection::DataCollector::new(filters);
-
-        data_collector.set_collection(used_widget_set);
-        data_collector.set_use_current_cpu_total(use_current_cpu_total);
-        data_collector.set_unnormalized_cpu(unnormalized_cpu);
-        data_collector.set_show_average_cpu(show_average_cpu);
-        data_collector.set_get_process_threads(get_process_threads);
-        #[cfg(feature = "zfs")]
-        data_collector.set_free_arc_mem(get_arc_free);
+        let mut data_collector = collection::DataCollector::new(filters.clone());
+        let mut update_sleep = app_config_fields.collection_rates.tick();
+
+        configure_collector(
+            &mut data_collector,
+            &app_config_fields,
+            filters,
+            used_widget_set,
+        );
 
         data_collector.update_data();
         data_collector.data = Data::default();
+        data_collector.reset_schedule();
 
         // Tiny sleep I guess? To go between the first update above and the first update in the loop.
         std::thread::sleep(Duration::from_millis(5));
@@ -256,12 +483,33 @@ fn create_collection_thread(
                 }
             }
 
-            if let Ok(message) = control_receiver.try_recv() {
+            while let Ok(message) = control_receiver.try_recv() {
                 // trace!("Received message in collection thread: {message:?}");
                 match message {
                     CollectionThreadEvent::Reset => {
                         data_collector.data.cleanup();
                     }
+                    CollectionThreadEvent::SetUsedWidgets(used_widgets) => {
+                        data_collector.set_collection(used_widgets);
+                    }
+                    #[cfg(target_os = "linux")]
+                    CollectionThreadEvent::SetVisiblePids(pids) => {
+                        data_collector.set_visible_pids(pids);
+                    }
+                    CollectionThreadEvent::Reconfigure {
+                        app_config_fields,
+                        filters,
+                        used_widgets,
+                    } => {
+                        update_sleep = app_config_fields.collection_rates.tick();
+                        configure_collector(
+                            &mut data_collector,
+                            &app_config_fields,
+                            filters,
+                            used_widgets,
+                        );
+                        data_collector.reset_schedule();
+                    }
                 }
             }
 
@@ -289,9 +537,108 @@ fn create_collection_thread(
     })
 }
 
-/// Main code to call to start bottom.
+/// How often to check whether the config file has changed.
+const CONFIG_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Create a thread that watches the config file, and asks the main thread to
+/// reload it when it changes.
+fn create_config_watcher_thread(
+    sender: Sender<BottomEvent>, cancellation_token: Arc<CancellationToken>, path: PathBuf,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut watcher = ConfigWatcher::new(path);
+
+        loop {
+            if cancellation_token.sleep_with_cancellation(CONFIG_WATCH_INTERVAL) {
+                break;
+            }
+
+            if watcher.has_changed() && sender.send(BottomEvent::ReloadConfig).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Reloads the config, and applies it to the app, painter, and collection thread
+/// while keeping any collected data. If the config is invalid, the error is shown
+/// in the app instead.
+fn reload_config(
+    args: &BottomArgs, config_path: Option<&Path>, app: &mut App, painter: &mut canvas::Painter,
+    collection_thread_ctrl_sender: &Sender<CollectionThreadEvent>,
+) -> anyhow::Result<()> {
+    let Some(config_path) = config_path else {
+        app.config_error = Some("bottom could not find a config file to reload.".to_string());
+        return Ok(());
+    };
+
+    let reloaded = read_config(config_path).and_then(|config| init_app(args.clone(), config));
+
+    match reloaded {
+        Ok((new_app, ..))
+            if new_app.app_config_fields.inline_height != app.app_config_fields.inline_height =>
+        {
+            app.config_error = Some(
+                "Restart bottom to apply changes to 'inline', the config was not reloaded."
+                    .to_string(),
+            );
+            app.is_force_redraw = true;
+        }
+        Ok((new_app, layout_tabs, styling)) => {
+            if new_app.app_config_fields.disable_click != app.app_config_fields.disable_click {
+                if new_app.app_config_fields.disable_click {
+                    execute!(stdout(), DisableMouseCapture)?;
+                } else {
+                    execute!(stdout(), EnableMouseCapture)?;
+                }
+            }
+
+            let _ = collection_thread_ctrl_sender.send(CollectionThreadEvent::Reconfigure {
+                app_config_fields: Box::new(new_app.app_config_fields.clone()),
+                filters: new_app.filters.clone(),
+                used_widgets: new_app.used_widgets,
+            });
+
+            *painter = canvas::Painter::init(layout_tabs, styling)?;
+            app.apply_reloaded_config(new_app);
+        }
+        Err(err) => {
+            app.config_error = Some(format!(
+                "The config is invalid, so the old one is still being used.\n{err:#}"
+            ));
+            app.is_force_redraw = true;
+        }
+    }
+
+    Ok(())
+}
+
+/// Tells the collection thread if the process details needed by process widgets
+/// changed, such as after a column was shown or a search was typed.
+fn send_process_fields(
+    app: &mut App, collection_thread_ctrl_sender: &Sender<CollectionThreadEvent>,
+) {
+    if app.refresh_process_fields() {
+        let _ = collection_thread_ctrl_sender
+            .send(CollectionThreadEvent::SetUsedWidgets(app.used_widgets));
+    }
+}
+
+/// Tells the collection thread if the processes shown by process widgets that
+/// need their detailed memory changed, such as after scrolling.
+#[cfg(target_os = "linux")]
+fn send_visible_pids(app: &mut App, collection_thread_ctrl_sender: &Sender<CollectionThreadEvent>) {
+    if app.refresh_visible_pids() {
+        let _ = collection_thread_ctrl_sender.send(CollectionThreadEvent::SetVisiblePids(
+            app.visible_pids.clone(),
+        ));
+    }
+}
+
+/// Main code to call to start bottom. `terminal_mode` is set once the terminal
+/// is set up, so it can be reset with [`reset_stdout`] if this returns an error.
 #[inline]
-pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
+pub fn start_bottom(terminal_mode: &mut Option<TerminalMode>) -> anyhow::Result<()> {
     // let _profiler = dhat::Profiler::new_heap();
 
     let args = args::get_args();
@@ -308,12 +655,37 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
 
     // Read from config file.
     let config = get_or_create_config(args.general.config_location.as_deref())?;
-
-    // Create the "app" and initialize a bunch of stuff.
-    let (mut app, widget_layout, styling) = init_app(args, config)?;
+    let config_path = get_config_path(args.general.config_location.as_deref());
+
+    // Create the "app" and initialize a bunch of stuff. The arguments are kept
+    // around in case the config is reloaded.
+    let (mut app, layout_tabs, styling) = init_app(args.clone(), config)?;
+
+    // Restore the UI state from the last run, if settings are remembered.
+    if app.app_config_fields.remember_settings {
+        if let Some(state) =
+            persist::state_file_path().and_then(|path| persist::read_ui_state(&path))
+        {
+            state.apply_to(&mut app);
+            app.refresh_process_fields();
+        }
+    }
 
     // Create painter and set colours.
-    let mut painter = canvas::Painter::init(widget_layout, styling)?;
+    let mut painter = canvas::Painter::init(layout_tabs, styling)?;
+
+    if args.process.batch {
+        return run_batch(app, &args.process);
+    }
+
+    // A capture only draws one frame to a file, so it doesn't need the terminal.
+    if let Some(path) = &args.general.capture {
+        let size = args.general.capture_size.unwrap_or_default();
+        let size = Rect::new(0, 0, size.width, size.height);
+        let collections = args.general.capture_after.unwrap_or(2);
+
+        return capture_headless(app, painter, path, size, collections);
+    }
 
     // Check if the current environment is in a terminal.
     check_if_terminal();
@@ -340,11 +712,16 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
         &app.app_config_fields,
     );
 
+    // Set up the config watcher thread.
+    let _config_watcher_thread = config_path
+        .clone()
+        .map(|path| create_config_watcher_thread(sender.clone(), cancellation_token.clone(), path));
+
     // Set up the cleaning loop thread.
     let _cleaning_thread = {
         let cancellation_token = cancellation_token.clone();
         let cleaning_sender = sender.clone();
-        let offset_wait = Duration::from_millis(app.app_config_fields.retention_ms + 60000);
+        let offset_wait = cleaning_interval(&app.app_config_fields);
         thread::spawn(move || {
             loop {
                 if cancellation_token.sleep_with_cancellation(offset_wait) {
@@ -359,10 +736,20 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
     };
 
     // Set up tui and crossterm
-    *enable_error_hook = true;
+    let inline_height = app.app_config_fields.inline_height;
+    *terminal_mode = Some(match inline_height {
+        Some(_) => TerminalMode::Inline {
+            top: crossterm::cursor::position().map_or(0, |(_, row)| row),
+        },
+        None => TerminalMode::Fullscreen,
+    });
 
     let mut stdout_val = stdout();
-    execute!(stdout_val, Hide, EnterAlternateScreen, EnableBracketedPaste)?;
+    if inline_height.is_some() {
+        execute!(stdout_val, Hide, EnableBracketedPaste)?;
+    } else {
+        execute!(stdout_val, Hide, EnterAlternateScreen, EnableBracketedPaste)?;
+    }
     if app.app_config_fields.disable_click {
         execute!(stdout_val, DisableMouseCapture)?;
     } else {
@@ -370,10 +757,30 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
     }
     enable_raw_mode()?;
 
-    let mut terminal = Terminal::new(CrosstermBackend::new(stdout_val))?;
+    // Inline mode draws in a fixed number of lines below the cursor, leaving
+    // the rest of the terminal alone.
+    let viewport = match inline_height {
+        Some(height) => Viewport::Inline(height),
+        None => Viewport::Fullscreen,
+    };
+    let mut terminal = Terminal::with_options(
+        CrosstermBackend::new(stdout_val),
+        TerminalOptions { viewport },
+    )?;
     terminal.clear()?;
     terminal.hide_cursor()?;
 
+    // The inline viewport may have scrolled the terminal to fit, so use where
+    // it actually is.
+    let mode = if inline_height.is_some() {
+        TerminalMode::Inline {
+            top: terminal.get_frame().area().y,
+        }
+    } else {
+        TerminalMode::Fullscreen
+    };
+    *terminal_mode = Some(mode);
+
     #[cfg(target_os = "freebsd")]
     let _stderr_fd = {
         // A really ugly band-aid to suppress stderr warnings on FreeBSD due to sysinfo.
@@ -387,7 +794,7 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
     };
 
     // Set panic hook
-    panic::set_hook(Box::new(panic_hook));
+    panic::set_hook(Box::new(move |panic_info| panic_hook(panic_info, mode)));
 
     // Set termination hook
     ctrlc::set_handler(move || {
@@ -396,6 +803,13 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
         let _ = sender.send(BottomEvent::Terminate);
     })?;
 
+    // With a config file to reload, SIGHUP reloads it rather than exiting. This
+    // must come after the handler above, as that also handles SIGHUP.
+    #[cfg(unix)]
+    if config_path.is_some() {
+        options::watcher::reload_on_sighup()?;
+    }
+
     let mut first_run = true;
 
     // Draw once first to initialize the canvas, so it doesn't feel like it's
@@ -413,16 +827,55 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
                     if handle_key_event_or_break(event, &mut app, &collection_thread_ctrl_sender) {
                         break;
                     }
+
+                    if std::mem::take(&mut app.is_config_reload_requested) {
+                        reload_config(
+                            &args,
+                            config_path.as_deref(),
+                            &mut app,
+                            &mut painter,
+                            &collection_thread_ctrl_sender,
+                        )?;
+                    }
+
+                    if std::mem::take(&mut app.is_settings_save_requested) {
+                        if let Err(err) = persist::save_settings(&app, config_path.as_deref()) {
+                            app.config_error = Some(format!("{err:#}"));
+                            app.is_force_redraw = true;
+                        }
+                    }
+
+                    if let Some(text) = app.pending_copy.take() {
+                        let clipboard = &app.app_config_fields.clipboard;
+                        if let Err(err) = copy_text(clipboard, &text, terminal.backend_mut()) {
+                            app.config_error = Some(format!("{err:#}"));
+                            app.is_force_redraw = true;
+                        }
+                    }
+
+                    send_process_fields(&mut app, &collection_thread_ctrl_sender);
                     app.update_data();
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
+                BottomEvent::ReloadConfig => {
+                    reload_config(
+                        &args,
+                        config_path.as_deref(),
+                        &mut app,
+                        &mut painter,
+                        &collection_thread_ctrl_sender,
+                    )?;
+                    try_drawing(&mut terminal, &mut app, &mut painter)?;
+                }
                 BottomEvent::MouseInput(event) => {
                     handle_mouse_event(event, &mut app);
+                    send_process_fields(&mut app, &collection_thread_ctrl_sender);
                     app.update_data();
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
                 BottomEvent::PasteEvent(paste) => {
                     app.handle_paste(paste);
+                    send_process_fields(&mut app, &collection_thread_ctrl_sender);
                     app.update_data();
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
@@ -438,38 +891,7 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
 
                     if !app.data_store.is_frozen() {
                         // Convert all data into data for the displayed widgets.
-
-                        if app.used_widgets.use_disk {
-                            for disk in app.states.disk_state.widget_states.values_mut() {
-                                disk.force_data_update();
-                            }
-                        }
-
-                        if app.used_widgets.use_temp {
-                            for temp in app.states.temp_state.widget_states.values_mut() {
-                                temp.force_data_update();
-                            }
-                        }
-
-                        if app.used_widgets.use_proc {
-                            for proc in app.states.proc_state.widget_states.values_mut() {
-                                proc.force_data_update();
-                            }
-                        }
-
-                        if app.used_widgets.use_cpu {
-                            for cpu in app.states.cpu_state.widget_states.values_mut() {
-                                cpu.force_data_update();
-                            }
-                        }
-
-                        #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
-                        if app.used_widgets.use_gpu {
-                            for gpu in app.states.gpu_state.widget_states.values_mut() {
-                                gpu.force_data_update();
-                            }
-                        }
-
+                        app.force_widget_data_update();
                         app.update_data();
                         try_drawing(&mut terminal, &mut app, &mut painter)?;
                     }
@@ -479,13 +901,20 @@ pub fn start_bottom(enable_error_hook: &mut bool) -> anyhow::Result<()> {
                         .clean_data(Duration::from_millis(app.app_config_fields.retention_ms));
                 }
             }
+
+            #[cfg(target_os = "linux")]
+            send_visible_pids(&mut app, &collection_thread_ctrl_sender);
         }
     }
 
     // I think doing it in this order is safe...
     // TODO: maybe move the cancellation token to the ctrl-c handler?
     cancellation_token.cancel();
-    cleanup_terminal(&mut terminal)?;
+    cleanup_terminal(&mut terminal, inline_height.is_some())?;
+
+    if app.app_config_fields.remember_settings {
+        persist::save_settings(&app, config_path.as_deref())?;
+    }
 
     Ok(())
 }
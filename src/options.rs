@@ -5,6 +5,8 @@
 pub mod args;
 pub mod config;
 mod error;
+pub(crate) mod persist;
+pub(crate) mod watcher;
 
 use std::{
     convert::TryInto,
@@ -28,13 +30,24 @@ use starship_battery::Manager;
 
 use self::{
     args::BottomArgs,
-    config::{IgnoreList, StringOrNum, layout::Row},
+    config::{
+        IgnoreList, StringOrNum,
+        layout::{BreakpointConfig, Row},
+    },
 };
 use crate::{
     app::{filter::Filter, layout_manager::*, *},
     canvas::components::time_graph::LegendPosition,
+    collection::{
+        custom::{CustomMetric, CustomMetricExtractor, CustomMetricSource, DEFAULT_NUMBER_REGEX},
+        processes::ProcessFields,
+        schedule::CollectionRates,
+    },
     constants::*,
-    utils::data_units::DataUnit,
+    utils::{
+        clipboard::{ClipboardSettings, CopyMethod, CopyTarget, default_clipboard_file},
+        data_units::DataUnit,
+    },
     widgets::*,
 };
 
@@ -89,7 +102,7 @@ const DEFAULT_CONFIG_FILE_LOCATION: &str = "bottom/bottom.toml";
 ///
 /// XXX: For macOS, we additionally will manually check `$XDG_CONFIG_HOME` as well first
 /// before falling back to `dirs`.
-fn get_config_path(override_config_path: Option<&Path>) -> Option<PathBuf> {
+pub(crate) fn get_config_path(override_config_path: Option<&Path>) -> Option<PathBuf> {
     if let Some(conf_loc) = override_config_path {
         return Some(conf_loc.to_path_buf());
     } else if let Some(home_path) = dirs::home_dir() {
@@ -209,8 +222,19 @@ pub(crate) fn get_or_create_config(config_path: Option<&Path>) -> anyhow::Result
     }
 }
 
+/// Reads the config at `config_path` again, such as after it has changed. Unlike
+/// [`get_or_create_config`], this never creates a file, and falls back to the
+/// default config if there is no file.
+pub(crate) fn read_config(config_path: &Path) -> anyhow::Result<Config> {
+    match fs::read_to_string(config_path) {
+        Ok(config_string) => Ok(toml_edit::de::from_str(&config_string)?),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
 /// Initialize the app.
-pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomLayout, Styles)> {
+pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, Vec<LayoutTab>, Styles)> {
     use BottomWidgetType::*;
 
     // Since everything takes a reference, but we want to take ownership here to
@@ -220,7 +244,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
 
     let styling = Styles::new(args, config)?;
 
-    let (widget_layout, default_widget_id, default_widget_type_option) =
+    let (layout_tabs, default_widget_id, default_widget_type_option) =
         get_widget_layout(args, config)
             .context("Found an issue while trying to build the widget layout.")?;
 
@@ -228,7 +252,8 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let autohide_time = is_flag_enabled!(autohide_time, args.general, config);
     let default_time_value = get_default_time_value(args, config, retention_ms)?;
 
-    let use_basic_mode = is_flag_enabled!(basic, args.general, config);
+    let inline_height = get_inline_height(args, config)?;
+    let use_basic_mode = is_flag_enabled!(basic, args.general, config) || inline_height.is_some();
     let expanded = is_flag_enabled!(expanded, args.general, config);
     #[cfg(feature = "zfs")]
     let free_arc = is_flag_enabled!(free_arc, args.memory, config);
@@ -257,11 +282,16 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let mut mem_state_map: HashMap<u64, MemWidgetState> = HashMap::default();
     let mut net_state_map: HashMap<u64, NetWidgetState> = HashMap::default();
     let mut proc_state_map: HashMap<u64, ProcWidgetState> = HashMap::default();
+    let mut proc_log_state_map: HashMap<u64, ProcessLogWidgetState> = HashMap::default();
+    let mut conn_state_map: HashMap<u64, ConnectionTableWidgetState> = HashMap::default();
     let mut temp_state_map: HashMap<u64, TempWidgetState> = HashMap::default();
     let mut disk_state_map: HashMap<u64, DiskTableWidget> = HashMap::default();
     let mut battery_state_map: HashMap<u64, BatteryWidgetState> = HashMap::default();
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     let mut gpu_state_map: HashMap<u64, GpuWidgetState> = HashMap::default();
+    let mut power_state_map: HashMap<u64, PowerWidgetState> = HashMap::default();
+    let mut custom_state_map: HashMap<u64, CustomWidgetState> = HashMap::default();
+    let mut used_custom_metrics = HashSet::default();
 
     let autohide_timer = if autohide_time {
         Some(Instant::now())
@@ -271,7 +301,7 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
 
     let mut initial_widget_id: u64 = default_widget_id;
     let mut initial_widget_type = Proc;
-    let is_custom_layout = config.row.is_some();
+    let is_custom_layout = config.row.is_some() || config.layout.is_some();
     let mut used_widget_set = HashSet::default();
 
     let network_unit_type = get_network_unit_type(args, config);
@@ -295,9 +325,12 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     let network_legend_position = get_network_legend_position(args, config)?;
     let memory_legend_position = get_memory_legend_position(args, config)?;
 
+    let update_rate = get_update_rate(args, config)?;
+
     // TODO: Can probably just reuse the options struct.
-    let app_config_fields = AppConfigFields {
-        update_rate: get_update_rate(args, config)?,
+    let mut app_config_fields = AppConfigFields {
+        update_rate,
+        collection_rates: get_collection_rates(config, update_rate)?,
         temperature_type: get_temperature(args, config)
             .context("Update 'temperature_type' in your config file.")?,
         show_average_cpu: get_show_average_cpu(args, config),
@@ -307,13 +340,16 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         unnormalized_cpu: is_flag_enabled!(unnormalized_cpu, args.process, config),
         get_process_threads: is_flag_enabled_new!(get_threads, args.process, config.processes),
         use_basic_mode,
+        inline_height,
+        inline_widgets: get_inline_widgets(args, config)?,
         default_time_value,
         time_interval: get_time_interval(args, config, retention_ms)?,
         hide_time: is_flag_enabled!(hide_time, args.general, config),
         autohide_time,
         use_old_network_legend: is_flag_enabled!(use_old_network_legend, args.network, config),
         table_gap: u16::from(!(is_flag_enabled!(hide_table_gap, args.general, config))),
-        disable_click: is_flag_enabled!(disable_click, args.general, config),
+        disable_click: is_flag_enabled!(disable_click, args.general, config)
+            || inline_height.is_some(),
         disable_keys: is_flag_enabled!(disable_keys, args.general, config),
         enable_gpu: get_enable_gpu(args, config),
         enable_cache_memory: get_enable_cache_memory(args, config),
@@ -327,6 +363,9 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         is_read_only,
         #[cfg(target_os = "linux")]
         hide_k_threads,
+        #[cfg(target_os = "linux")]
+        system_roots: get_system_roots(args, config)?,
+        custom_metrics: get_custom_metrics(config)?,
         memory_legend_position,
         network_legend_position,
         network_scale_type,
@@ -335,6 +374,8 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         retention_ms,
         dedicated_average_row: get_dedicated_avg_row(config),
         default_tree_collapse: is_default_tree_collapsed,
+        remember_settings: is_flag_enabled!(remember, args.general, config),
+        clipboard: get_clipboard_settings(config)?,
         #[cfg(feature = "zfs")]
         free_arc,
     };
@@ -347,129 +388,174 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         is_command: is_default_command,
     };
 
-    for row in &widget_layout.rows {
-        for col in &row.children {
-            for col_row in &col.children {
-                for widget in &col_row.children {
-                    widget_map.insert(widget.widget_id, widget.clone());
-                    if let Some(default_widget_type) = &default_widget_type_option {
-                        if !is_custom_layout || use_basic_mode {
-                            match widget.widget_type {
-                                BasicCpu => {
-                                    if let Cpu = *default_widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = Cpu;
-                                    }
-                                }
-                                BasicMem => {
-                                    if let Mem = *default_widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = Cpu;
-                                    }
-                                }
-                                BasicNet => {
-                                    if let Net = *default_widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = Cpu;
-                                    }
-                                }
-                                _ => {
-                                    if *default_widget_type == widget.widget_type {
-                                        initial_widget_id = widget.widget_id;
-                                        initial_widget_type = widget.widget_type.clone();
-                                    }
-                                }
-                            }
+    // The base layout's widgets come last, so they're the ones left in the
+    // widget map for widgets shared with breakpoint layouts.
+    for widget in layout_tabs.iter().flat_map(|tab| tab.widgets()) {
+        widget_map.insert(widget.widget_id, widget.clone());
+        if let Some(default_widget_type) = &default_widget_type_option {
+            if !is_custom_layout || use_basic_mode {
+                match widget.widget_type {
+                    BasicCpu => {
+                        if let Cpu = *default_widget_type {
+                            initial_widget_id = widget.widget_id;
+                            initial_widget_type = Cpu;
                         }
                     }
-
-                    used_widget_set.insert(widget.widget_type.clone());
-
-                    match widget.widget_type {
-                        Cpu => {
-                            cpu_state_map.insert(
-                                widget.widget_id,
-                                CpuWidgetState::new(
-                                    &app_config_fields,
-                                    default_cpu_selection,
-                                    default_time_value,
-                                    autohide_timer,
-                                    &styling,
-                                ),
-                            );
-                        }
-                        Mem => {
-                            mem_state_map.insert(
-                                widget.widget_id,
-                                MemWidgetState::init(default_time_value, autohide_timer),
-                            );
+                    BasicMem => {
+                        if let Mem = *default_widget_type {
+                            initial_widget_id = widget.widget_id;
+                            initial_widget_type = Cpu;
                         }
-                        Net => {
-                            net_state_map.insert(
-                                widget.widget_id,
-                                NetWidgetState::init(default_time_value, autohide_timer),
-                            );
-                        }
-                        Proc => {
-                            let mode = if is_grouped {
-                                ProcWidgetMode::Grouped
-                            } else if is_default_tree {
-                                ProcWidgetMode::Tree(TreeCollapsed::new(is_default_tree_collapsed))
-                            } else {
-                                ProcWidgetMode::Normal
-                            };
-
-                            proc_state_map.insert(
-                                widget.widget_id,
-                                ProcWidgetState::new(
-                                    &app_config_fields,
-                                    mode,
-                                    table_config,
-                                    &styling,
-                                    &proc_columns,
-                                ),
-                            );
-                        }
-                        Disk => {
-                            disk_state_map.insert(
-                                widget.widget_id,
-                                DiskTableWidget::new(
-                                    &app_config_fields,
-                                    &styling,
-                                    config.disk.as_ref().and_then(|cfg| cfg.columns.as_deref()),
-                                ),
-                            );
-                        }
-                        Temp => {
-                            temp_state_map.insert(
-                                widget.widget_id,
-                                TempWidgetState::new(&app_config_fields, &styling),
-                            );
-                        }
-                        Battery => {
-                            battery_state_map
-                                .insert(widget.widget_id, BatteryWidgetState::default());
+                    }
+                    BasicNet => {
+                        if let Net = *default_widget_type {
+                            initial_widget_id = widget.widget_id;
+                            initial_widget_type = Cpu;
                         }
-                        #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
-                        Gpu => {
-                            gpu_state_map.insert(
-                                widget.widget_id,
-                                GpuWidgetState::new(
-                                    &app_config_fields,
-                                    default_time_value,
-                                    autohide_timer,
-                                    &styling,
-                                ),
-                            );
+                    }
+                    _ => {
+                        if *default_widget_type == widget.widget_type {
+                            initial_widget_id = widget.widget_id;
+                            initial_widget_type = widget.widget_type.clone();
                         }
-                        _ => {}
                     }
                 }
             }
         }
+
+        used_widget_set.insert(widget.widget_type.clone());
+
+        match widget.widget_type {
+            Cpu => {
+                cpu_state_map.insert(
+                    widget.widget_id,
+                    CpuWidgetState::new(
+                        &app_config_fields,
+                        default_cpu_selection,
+                        default_time_value,
+                        autohide_timer,
+                        &styling,
+                    ),
+                );
+            }
+            Mem => {
+                mem_state_map.insert(
+                    widget.widget_id,
+                    MemWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        GraphLegend::new(app_config_fields.memory_legend_position),
+                    ),
+                );
+            }
+            Net => {
+                net_state_map.insert(
+                    widget.widget_id,
+                    NetWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        GraphLegend::new(app_config_fields.network_legend_position),
+                    ),
+                );
+            }
+            Power => {
+                power_state_map.insert(
+                    widget.widget_id,
+                    PowerWidgetState::init(
+                        default_time_value,
+                        autohide_timer,
+                        GraphLegend::default(),
+                    ),
+                );
+            }
+            Custom => {
+                let metric = get_widget_custom_metric(
+                    &app_config_fields.custom_metrics,
+                    widget.custom_metric.as_deref(),
+                )?;
+                used_custom_metrics.insert(metric.name.clone());
+                custom_state_map.insert(
+                    widget.widget_id,
+                    CustomWidgetState::init(metric, default_time_value, autohide_timer),
+                );
+            }
+            Proc => {
+                let mode = if is_grouped {
+                    ProcWidgetMode::Grouped
+                } else if is_default_tree {
+                    ProcWidgetMode::Tree(TreeCollapsed::new(is_default_tree_collapsed))
+                } else {
+                    ProcWidgetMode::Normal
+                };
+
+                let mut state = ProcWidgetState::new(
+                    &app_config_fields,
+                    mode,
+                    table_config,
+                    &styling,
+                    &proc_columns,
+                );
+                if let Some(cfg) = &config.processes {
+                    if let Some(column) = &cfg.sort {
+                        state.set_sort_from_config(column.into(), cfg.sort_descending);
+                    }
+                }
+
+                proc_state_map.insert(widget.widget_id, state);
+            }
+            ProcLog => {
+                proc_log_state_map.insert(
+                    widget.widget_id,
+                    ProcessLogWidgetState::new(&app_config_fields, &styling),
+                );
+            }
+            Conn => {
+                conn_state_map.insert(
+                    widget.widget_id,
+                    ConnectionTableWidgetState::new(&app_config_fields, &styling),
+                );
+            }
+            Disk => {
+                disk_state_map.insert(
+                    widget.widget_id,
+                    DiskTableWidget::new(
+                        &app_config_fields,
+                        &styling,
+                        config.disk.as_ref().and_then(|cfg| cfg.columns.as_deref()),
+                    ),
+                );
+            }
+            Temp => {
+                temp_state_map.insert(
+                    widget.widget_id,
+                    TempWidgetState::new(&app_config_fields, &styling),
+                );
+            }
+            Battery => {
+                battery_state_map.insert(widget.widget_id, BatteryWidgetState::default());
+            }
+            #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+            Gpu => {
+                gpu_state_map.insert(
+                    widget.widget_id,
+                    GpuWidgetState::new(
+                        &app_config_fields,
+                        default_time_value,
+                        autohide_timer,
+                        &styling,
+                    ),
+                );
+            }
+            _ => {}
+        }
     }
 
-    let basic_table_widget_state = if use_basic_mode {
+    // Only collect the custom metrics that are actually shown.
+    app_config_fields
+        .custom_metrics
+        .retain(|metric| used_custom_metrics.contains(&metric.name));
+
+    let basic_table_widget_state = if use_basic_mode && inline_height.is_none() {
         Some(match initial_widget_type {
             Proc | Disk | Temp => BasicTableWidgetState {
                 currently_displayed_widget_type: initial_widget_type,
@@ -503,10 +589,15 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         use_cache: use_mem && get_enable_cache_memory(args, config),
         use_gpu,
         use_net: used_widget_set.contains(&Net) || used_widget_set.contains(&BasicNet),
-        use_proc: used_widget_set.contains(&Proc),
+        use_proc: used_widget_set.contains(&Proc) || used_widget_set.contains(&ProcLog),
         use_disk: used_widget_set.contains(&Disk),
         use_temp: used_widget_set.contains(&Temp),
         use_battery: used_widget_set.contains(&Battery),
+        use_power: used_widget_set.contains(&Power),
+        use_custom: used_widget_set.contains(&Custom),
+        use_connections: used_widget_set.contains(&Conn),
+        // Set from the process widgets once they are built.
+        process_fields: ProcessFields::default(),
     };
 
     let (disk_name_filter, disk_mount_filter) = {
@@ -538,11 +629,15 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
         mem_state: MemState::init(mem_state_map),
         net_state: NetState::init(net_state_map),
         proc_state: ProcState::init(proc_state_map),
+        proc_log_state: ProcLogState::init(proc_log_state_map),
+        conn_state: ConnState::init(conn_state_map),
         temp_state: TempState::init(temp_state_map),
         disk_state: DiskState::init(disk_state_map),
         battery_state: AppBatteryState::init(battery_state_map),
         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
         gpu_state: GpuState::init(gpu_state_map),
+        power_state: PowerState::init(power_state_map),
+        custom_state: CustomState::init(custom_state_map),
         basic_table_widget_state,
     };
 
@@ -558,83 +653,227 @@ pub(crate) fn init_app(args: BottomArgs, config: Config) -> Result<(App, BottomL
     };
     let is_expanded = expanded && !use_basic_mode;
 
-    Ok((
-        App::new(
-            app_config_fields,
-            states,
-            widget_map,
-            current_widget,
-            used_widgets,
-            filters,
-            is_expanded,
-        ),
-        widget_layout,
-        styling,
-    ))
+    let mut app = App::new(
+        app_config_fields,
+        states,
+        widget_map,
+        current_widget,
+        used_widgets,
+        filters,
+        is_expanded,
+    );
+    app.tab_state = AppTabState::new(&layout_tabs, initial_widget_id);
+    app.refresh_process_fields();
+
+    Ok((app, layout_tabs, styling))
 }
 
 fn get_widget_layout(
     args: &BottomArgs, config: &Config,
-) -> OptionResult<(BottomLayout, u64, Option<BottomWidgetType>)> {
+) -> OptionResult<(Vec<LayoutTab>, u64, Option<BottomWidgetType>)> {
     let cpu_left_legend = is_flag_enabled!(cpu_left_legend, args.cpu, config);
 
     let (default_widget_type, mut default_widget_count) =
         get_default_widget_and_count(args, config)?;
     let mut default_widget_id = 1;
 
-    let bottom_layout = if is_flag_enabled!(basic, args.general, config) {
+    // Inline mode only has the widgets it shows, so nothing else is collected or
+    // can be selected.
+    if get_inline_height(args, config)?.is_some() {
+        let widget_types = get_inline_widgets(args, config)?;
+        let layout = BottomLayout::init_inline(&widget_types);
+        let default_widget_id = layout
+            .widgets()
+            .next()
+            .map(|widget| widget.widget_id)
+            .expect("inline mode should have at least one widget");
+        let tabs = vec![LayoutTab {
+            name: String::default(),
+            layout,
+            breakpoints: Vec::new(),
+        }];
+
+        return Ok((tabs, default_widget_id, widget_types.first().cloned()));
+    }
+
+    if is_flag_enabled!(basic, args.general, config) {
         default_widget_id = DEFAULT_WIDGET_ID;
 
-        BottomLayout::init_basic_default(get_use_battery(args, config))
-    } else {
-        let ref_row: Vec<Row>; // Required to handle reference
-        let rows = match &config.row {
-            Some(r) => r,
-            None => {
-                ref_row = toml_edit::de::from_str::<Config>(if get_use_battery(args, config) {
+        let layout = BottomLayout::init_basic_default(get_use_battery(args, config));
+        let tabs = vec![LayoutTab {
+            name: String::default(),
+            layout,
+            breakpoints: Vec::new(),
+        }];
+
+        return Ok((tabs, default_widget_id, default_widget_type));
+    }
+
+    let named_rows: Vec<(String, Vec<Row>, &[BreakpointConfig])> = match (
+        &config.layout,
+        &config.row,
+    ) {
+        (Some(_), Some(_)) => {
+            return Err(OptionError::config(
+                "use either '[[layout]]' or '[[row]]' to set the layout, not both.",
+            ));
+        }
+        (Some(_), None) if config.breakpoint.is_some() => {
+            return Err(OptionError::config(
+                "set breakpoints with '[[layout.breakpoint]]' under each '[[layout]]', not '[[breakpoint]]'.",
+            ));
+        }
+        (Some(layouts), None) => {
+            if layouts.is_empty() {
+                return Err(OptionError::config(
+                    "have at least one layout under the '[[layout]]' section.",
+                ));
+            }
+
+            let mut names = HashSet::default();
+            for layout in layouts {
+                if !names.insert(layout.name.as_str()) {
+                    return Err(OptionError::config(format!(
+                        "give each layout a unique name, but '{}' is used more than once.",
+                        layout.name
+                    )));
+                }
+            }
+
+            layouts
+                .iter()
+                .map(|layout| {
+                    (
+                        layout.name.clone(),
+                        layout.row.clone(),
+                        layout.breakpoint.as_deref().unwrap_or_default(),
+                    )
+                })
+                .collect()
+        }
+        (None, rows) => {
+            let rows = match rows {
+                Some(rows) => rows.clone(),
+                None => toml_edit::de::from_str::<Config>(if get_use_battery(args, config) {
                     DEFAULT_BATTERY_LAYOUT
                 } else {
                     DEFAULT_LAYOUT
                 })?
                 .row
-                .expect("This cannot (like it really shouldn't) fail!");
-                &ref_row
-            }
-        };
+                .expect("This cannot (like it really shouldn't) fail!"),
+            };
+
+            vec![(
+                String::default(),
+                rows,
+                config.breakpoint.as_deref().unwrap_or_default(),
+            )]
+        }
+    };
 
-        let mut iter_id = 0; // A lazy way of forcing unique IDs *shrugs*
-        let mut total_height_ratio = 0;
+    let mut iter_id = 0; // A lazy way of forcing unique IDs *shrugs*
+    let mut tabs = Vec::with_capacity(named_rows.len());
 
-        let mut ret_bottom_layout = BottomLayout {
-            rows: rows
-                .iter()
-                .map(|row| {
-                    row.convert_row_to_bottom_row(
-                        &mut iter_id,
-                        &mut total_height_ratio,
-                        &mut default_widget_id,
-                        &default_widget_type,
-                        &mut default_widget_count,
-                        cpu_left_legend,
-                    )
-                    .map_err(|err| OptionError::config(err.to_string()))
-                })
-                .collect::<OptionResult<Vec<_>>>()?,
-            total_row_height_ratio: total_height_ratio,
-        };
+    for (name, rows, breakpoint_configs) in named_rows {
+        let start_id = iter_id;
+
+        let mut layout = convert_rows(
+            &rows,
+            &mut iter_id,
+            &mut default_widget_id,
+            &default_widget_type,
+            &mut default_widget_count,
+            cpu_left_legend,
+        )?;
 
         // Confirm that we have at least ONE widget left - if not, error out!
-        if iter_id > 0 {
-            ret_bottom_layout.get_movement_mappings();
-            ret_bottom_layout
-        } else {
-            return Err(OptionError::config(
-                "have at least one widget under the '[[row]]' section.",
-            ));
+        if iter_id == start_id {
+            return Err(if name.is_empty() {
+                OptionError::config("have at least one widget under the '[[row]]' section.")
+            } else {
+                OptionError::config(format!("have at least one widget in the '{name}' layout."))
+            });
         }
-    };
 
-    Ok((bottom_layout, default_widget_id, default_widget_type))
+        layout.get_movement_mappings();
+
+        let mut breakpoints = Vec::with_capacity(breakpoint_configs.len());
+        for breakpoint in breakpoint_configs {
+            if breakpoint.min_width.is_none()
+                && breakpoint.max_width.is_none()
+                && breakpoint.min_height.is_none()
+                && breakpoint.max_height.is_none()
+            {
+                return Err(OptionError::config(
+                    "set at least one of 'min_width', 'max_width', 'min_height', or 'max_height' for each breakpoint.",
+                ));
+            }
+
+            // Only the base layout decides which widget is selected by default.
+            let start_id = iter_id;
+            let mut breakpoint_layout = convert_rows(
+                &breakpoint.row,
+                &mut iter_id,
+                &mut 0,
+                &None,
+                &mut 0,
+                cpu_left_legend,
+            )?;
+
+            if iter_id == start_id {
+                return Err(OptionError::config(
+                    "have at least one widget under each '[[breakpoint]]' section.",
+                ));
+            }
+
+            breakpoint_layout.share_widget_ids(&layout);
+            breakpoint_layout.get_movement_mappings();
+            breakpoints.push(LayoutBreakpoint {
+                min_width: breakpoint.min_width,
+                max_width: breakpoint.max_width,
+                min_height: breakpoint.min_height,
+                max_height: breakpoint.max_height,
+                layout: breakpoint_layout,
+            });
+        }
+
+        tabs.push(LayoutTab {
+            name,
+            layout,
+            breakpoints,
+        });
+    }
+
+    Ok((tabs, default_widget_id, default_widget_type))
+}
+
+/// Converts config rows into a [`BottomLayout`], without movement mappings.
+fn convert_rows(
+    rows: &[Row], iter_id: &mut u64, default_widget_id: &mut u64,
+    default_widget_type: &Option<BottomWidgetType>, default_widget_count: &mut u64,
+    cpu_left_legend: bool,
+) -> OptionResult<BottomLayout> {
+    let mut total_height_ratio = 0;
+
+    let rows = rows
+        .iter()
+        .map(|row| {
+            row.convert_row_to_bottom_row(
+                iter_id,
+                &mut total_height_ratio,
+                default_widget_id,
+                default_widget_type,
+                default_widget_count,
+                cpu_left_legend,
+            )
+            .map_err(|err| OptionError::config(err.to_string()))
+        })
+        .collect::<OptionResult<Vec<_>>>()?;
+
+    Ok(BottomLayout {
+        rows,
+        total_row_height_ratio: total_height_ratio,
+    })
 }
 
 #[inline]
@@ -736,6 +975,97 @@ fn get_update_rate(args: &BottomArgs, config: &Config) -> OptionResult<u64> {
     )
 }
 
+/// How quickly we update each subsystem. Anything not set uses the general
+/// update rate.
+fn get_collection_rates(config: &Config, update_rate: u64) -> OptionResult<CollectionRates> {
+    let rates = config.collection_rates.as_ref();
+
+    macro_rules! rate {
+        ($field:ident, $setting:literal) => {{
+            let rate: OptionResult<u64> = parse_ms_option!(
+                None::<&String>,
+                rates.and_then(|rates| rates.$field.as_ref()),
+                update_rate,
+                $setting,
+                Some(250),
+                None,
+            );
+
+            rate?
+        }};
+    }
+
+    let processes = rate!(processes, "collection_rates.processes");
+    let process_memory: OptionResult<u64> = parse_ms_option!(
+        None::<&String>,
+        rates.and_then(|rates| rates.process_memory.as_ref()),
+        processes * CollectionRates::PROCESS_MEMORY_FACTOR,
+        "collection_rates.process_memory",
+        Some(250),
+        None,
+    );
+
+    Ok(CollectionRates {
+        cpu: rate!(cpu, "collection_rates.cpu"),
+        memory: rate!(memory, "collection_rates.memory"),
+        network: rate!(network, "collection_rates.network"),
+        processes,
+        process_memory: process_memory?,
+        disks: rate!(disks, "collection_rates.disks"),
+        temperatures: rate!(temperatures, "collection_rates.temperatures"),
+        batteries: rate!(batteries, "collection_rates.batteries"),
+        gpus: rate!(gpus, "collection_rates.gpus"),
+        power: rate!(power, "collection_rates.power"),
+        custom: update_rate,
+    })
+}
+
+/// Where procfs and sysfs are mounted.
+#[cfg(target_os = "linux")]
+fn get_system_roots(
+    args: &BottomArgs, config: &Config,
+) -> OptionResult<crate::collection::SystemRoots> {
+    fn get_root(
+        arg: &Option<PathBuf>, config: Option<&PathBuf>, setting: &str,
+    ) -> OptionResult<Option<PathBuf>> {
+        if let Some(path) = arg {
+            if !path.is_dir() {
+                return Err(OptionError::arg(format!(
+                    "'--{setting}' must be a directory, but '{}' is not",
+                    path.display()
+                )));
+            }
+
+            Ok(Some(path.clone()))
+        } else if let Some(path) = config {
+            if !path.is_dir() {
+                return Err(OptionError::config(format!(
+                    "'{setting}' must be a directory, but '{}' is not",
+                    path.display()
+                )));
+            }
+
+            Ok(Some(path.clone()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    let flags = config.flags.as_ref();
+    let procfs = get_root(
+        &args.general.procfs_root,
+        flags.and_then(|flags| flags.procfs_root.as_ref()),
+        "procfs_root",
+    )?;
+    let sysfs = get_root(
+        &args.general.sysfs_root,
+        flags.and_then(|flags| flags.sysfs_root.as_ref()),
+        "sysfs_root",
+    )?;
+
+    Ok(crate::collection::SystemRoots::new(procfs, sysfs))
+}
+
 fn get_temperature(args: &BottomArgs, config: &Config) -> OptionResult<TemperatureType> {
     if args.temperature.fahrenheit {
         return Ok(TemperatureType::Fahrenheit);
@@ -751,6 +1081,28 @@ fn get_temperature(args: &BottomArgs, config: &Config) -> OptionResult<Temperatu
     Ok(TemperatureType::Celsius)
 }
 
+fn get_clipboard_settings(config: &Config) -> OptionResult<ClipboardSettings> {
+    let clipboard = config.clipboard.as_ref();
+
+    let target = match clipboard.and_then(|clipboard| clipboard.copy.as_deref()) {
+        Some(target) => parse_config_value!(CopyTarget::from_str(target), "clipboard.copy")?,
+        None => CopyTarget::default(),
+    };
+    let method = match clipboard.and_then(|clipboard| clipboard.method.as_deref()) {
+        Some(method) => parse_config_value!(CopyMethod::from_str(method), "clipboard.method")?,
+        None => CopyMethod::default(),
+    };
+    let file = clipboard
+        .and_then(|clipboard| clipboard.file.clone())
+        .unwrap_or_else(default_clipboard_file);
+
+    Ok(ClipboardSettings {
+        target,
+        method,
+        file,
+    })
+}
+
 /// Yes, this function gets whether to show average CPU (true) or not (false).
 fn get_show_average_cpu(args: &BottomArgs, config: &Config) -> bool {
     if args.cpu.hide_avg_cpu {
@@ -869,6 +1221,68 @@ fn get_default_widget_and_count(
     }
 }
 
+/// Returns how many lines to draw in for inline mode, if it is enabled.
+fn get_inline_height(args: &BottomArgs, config: &Config) -> OptionResult<Option<u16>> {
+    if let Some(height) = args.general.inline {
+        if height == 0 {
+            return Err(OptionError::invalid_arg_value("inline"));
+        }
+
+        Ok(Some(height))
+    } else if let Some(height) = config.flags.as_ref().and_then(|flags| flags.inline) {
+        if height == 0 {
+            return Err(OptionError::invalid_config_value("inline"));
+        }
+
+        Ok(Some(height))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Returns the widgets to show in inline mode.
+fn get_inline_widgets(args: &BottomArgs, config: &Config) -> OptionResult<Vec<BottomWidgetType>> {
+    /// Parses the widget names, skipping repeats. Each widget is shown at most
+    /// once, and at least one must be given.
+    fn parse(names: &[String]) -> Option<Vec<BottomWidgetType>> {
+        let mut widget_types = Vec::with_capacity(names.len());
+        for name in names {
+            match name.parse() {
+                Ok(
+                    widget_type @ (BottomWidgetType::Cpu
+                    | BottomWidgetType::Mem
+                    | BottomWidgetType::Net),
+                ) => {
+                    if !widget_types.contains(&widget_type) {
+                        widget_types.push(widget_type);
+                    }
+                }
+                _ => return None,
+            }
+        }
+
+        (!widget_types.is_empty()).then_some(widget_types)
+    }
+
+    if let Some(names) = &args.general.inline_widgets {
+        parse(names).ok_or_else(|| OptionError::invalid_arg_value("inline_widgets"))
+    } else if let Some(names) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.inline_widgets.as_ref())
+    {
+        parse(names).ok_or_else(|| {
+            OptionError::config("set 'inline_widgets' to a list of \"cpu\", \"mem\", or \"net\".")
+        })
+    } else {
+        Ok(vec![
+            BottomWidgetType::Cpu,
+            BottomWidgetType::Mem,
+            BottomWidgetType::Net,
+        ])
+    }
+}
+
 #[cfg(feature = "battery")]
 fn get_use_battery(args: &BottomArgs, config: &Config) -> bool {
     // TODO: Move this so it's dynamic in the app itself and automatically hide if
@@ -969,6 +1383,125 @@ fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> OptionResult<Option<Filt
     }
 }
 
+/// Parses and validates the `[[custom_metrics]]` entries.
+fn get_custom_metrics(config: &Config) -> OptionResult<Vec<CustomMetric>> {
+    let Some(metrics) = &config.custom_metrics else {
+        return Ok(Vec::new());
+    };
+
+    let mut names = HashSet::default();
+    metrics
+        .iter()
+        .map(|metric| {
+            let name = &metric.name;
+            if name.is_empty() {
+                return Err(OptionError::config(
+                    "each entry in 'custom_metrics' must have a non-empty 'name'",
+                ));
+            }
+            if !names.insert(name.as_str()) {
+                return Err(OptionError::config(format!(
+                    "the custom metric name '{name}' is used more than once"
+                )));
+            }
+
+            let source = match (&metric.command, &metric.file) {
+                (Some(command), None) if !command.is_empty() => {
+                    CustomMetricSource::Command(command.clone())
+                }
+                (Some(_), None) => {
+                    return Err(OptionError::config(format!(
+                        "the 'command' of custom metric '{name}' must not be empty"
+                    )));
+                }
+                (None, Some(file)) => CustomMetricSource::File(file.clone()),
+                _ => {
+                    return Err(OptionError::config(format!(
+                        "custom metric '{name}' must set exactly one of 'command' or 'file'"
+                    )));
+                }
+            };
+
+            let interval = metric.interval.unwrap_or(1);
+            if interval == 0 {
+                return Err(OptionError::config(format!(
+                    "the 'interval' of custom metric '{name}' must be at least 1"
+                )));
+            }
+
+            let extractor = match (&metric.regex, &metric.json_path) {
+                (Some(_), Some(_)) => {
+                    return Err(OptionError::config(format!(
+                        "custom metric '{name}' cannot set both 'regex' and 'json_path'"
+                    )));
+                }
+                (Some(regex), None) => {
+                    CustomMetricExtractor::Regex(Regex::new(regex).map_err(|err| {
+                        OptionError::config(format!(
+                            "the 'regex' of custom metric '{name}' is invalid: {err}"
+                        ))
+                    })?)
+                }
+                (None, Some(paths)) if !paths.is_empty() => {
+                    CustomMetricExtractor::JsonPath(paths.clone())
+                }
+                (None, Some(_)) => {
+                    return Err(OptionError::config(format!(
+                        "the 'json_path' of custom metric '{name}' must not be empty"
+                    )));
+                }
+                (None, None) => CustomMetricExtractor::Regex(
+                    Regex::new(DEFAULT_NUMBER_REGEX)
+                        .expect("the default custom metric regex should be valid"),
+                ),
+            };
+
+            let labels = match &metric.labels {
+                Some(labels) if labels.len() != extractor.num_values() => {
+                    return Err(OptionError::config(format!(
+                        "custom metric '{name}' has {} 'labels', but extracts {} values",
+                        labels.len(),
+                        extractor.num_values()
+                    )));
+                }
+                Some(labels) => labels.clone(),
+                None => extractor.default_labels(name),
+            };
+
+            Ok(CustomMetric {
+                name: name.clone(),
+                source,
+                interval,
+                extractor,
+                unit: metric.unit.clone(),
+                labels,
+            })
+        })
+        .collect()
+}
+
+/// Finds the custom metric that a `custom` widget should show. If the widget
+/// does not name one, the first configured metric is used.
+fn get_widget_custom_metric<'a>(
+    metrics: &'a [CustomMetric], requested: Option<&str>,
+) -> OptionResult<&'a CustomMetric> {
+    match requested {
+        Some(requested) => metrics
+            .iter()
+            .find(|metric| metric.name == requested)
+            .ok_or_else(|| {
+                OptionError::config(format!(
+                    "a 'custom' widget uses the metric '{requested}', but no entry in 'custom_metrics' has that name"
+                ))
+            }),
+        None => metrics.first().ok_or_else(|| {
+            OptionError::config(
+                "a 'custom' widget was set in the layout, but no 'custom_metrics' were defined",
+            )
+        }),
+    }
+}
+
 fn get_network_unit_type(args: &BottomArgs, config: &Config) -> DataUnit {
     if args.network.network_use_bytes {
         return DataUnit::Byte;
@@ -1065,14 +1598,23 @@ fn get_memory_legend_position(
 mod test {
     use clap::Parser;
 
-    use super::{Config, get_time_interval};
+    use super::{
+        Config, get_collection_rates, get_custom_metrics, get_time_interval,
+        get_widget_custom_metric, read_config,
+    };
     use crate::{
-        app::App,
+        app::{
+            App,
+            data::TypedTemperature,
+            layout_manager::{BottomLayout, BottomWidgetType},
+        },
         args::BottomArgs,
+        collection::{Data, schedule::CollectionRates, temperature::TempSensorData},
         options::{
             config::flags::GeneralConfig, get_default_time_value, get_retention, get_update_rate,
             try_parse_ms,
         },
+        widgets::ProcWidgetColumn,
     };
 
     #[test]
@@ -1234,6 +1776,109 @@ mod test {
         assert_eq!(get_retention(&args, &config), Ok(600000));
     }
 
+    #[test]
+    fn config_custom_metrics() {
+        use crate::collection::custom::CustomMetricSource;
+
+        let parse = |text: &str| {
+            let config: Config = toml_edit::de::from_str(text).unwrap();
+            get_custom_metrics(&config)
+        };
+
+        let metrics = parse(
+            r#"
+            [[custom_metrics]]
+            name = "queue"
+            command = ["echo", "1"]
+
+            [[custom_metrics]]
+            name = "stats"
+            file = "/tmp/stats.json"
+            json_path = ["a", "b.c"]
+            unit = "ops"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(
+            metrics[0].source,
+            CustomMetricSource::Command(vec!["echo".to_string(), "1".to_string()])
+        );
+        assert_eq!(metrics[0].interval, 1);
+        assert_eq!(metrics[0].labels, vec!["queue"]);
+        assert_eq!(metrics[1].labels, vec!["a", "b.c"]);
+        assert_eq!(metrics[1].unit.as_deref(), Some("ops"));
+
+        assert_eq!(
+            get_widget_custom_metric(&metrics, None).map(|m| m.name.as_str()),
+            Ok("queue")
+        );
+        assert_eq!(
+            get_widget_custom_metric(&metrics, Some("stats")).map(|m| m.name.as_str()),
+            Ok("stats")
+        );
+        assert!(get_widget_custom_metric(&metrics, Some("missing")).is_err());
+        assert!(get_widget_custom_metric(&[], None).is_err());
+
+        // Both or neither of a command and file.
+        assert!(parse("[[custom_metrics]]\nname = \"a\"").is_err());
+        assert!(
+            parse("[[custom_metrics]]\nname = \"a\"\ncommand = [\"x\"]\nfile = \"/x\"").is_err()
+        );
+
+        // Duplicate names.
+        assert!(
+            parse("[[custom_metrics]]\nname = \"a\"\nfile = \"/x\"\n[[custom_metrics]]\nname = \"a\"\nfile = \"/y\"")
+                .is_err()
+        );
+
+        // Bad regex, and mismatched labels.
+        assert!(parse("[[custom_metrics]]\nname = \"a\"\nfile = \"/x\"\nregex = \"(\"").is_err());
+        assert!(
+            parse("[[custom_metrics]]\nname = \"a\"\nfile = \"/x\"\nlabels = [\"b\", \"c\"]")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn config_collection_rates() {
+        let parse = |text: &str| {
+            let config: Config = toml_edit::de::from_str(text).unwrap();
+            get_collection_rates(&config, 1000)
+        };
+
+        assert_eq!(parse(""), Ok(CollectionRates::uniform(1000)));
+
+        let rates = parse(
+            r#"
+            [collection_rates]
+            cpu = "250ms"
+            processes = 2000
+            disks = "10s"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(rates.cpu, 250);
+        assert_eq!(rates.processes, 2000);
+        assert_eq!(rates.process_memory, 10_000);
+        assert_eq!(rates.disks, 10_000);
+        assert_eq!(rates.network, 1000);
+        assert_eq!(rates.custom, 1000);
+        assert_eq!(rates.tick(), 250);
+
+        assert_eq!(
+            parse("[collection_rates]\nprocess_memory = \"3s\"")
+                .unwrap()
+                .process_memory,
+            3000
+        );
+
+        assert!(parse("[collection_rates]\ncpu = \"100ms\"").is_err());
+        assert!(parse("[collection_rates]\ncpu = \"soon\"").is_err());
+    }
+
     fn create_app(args: BottomArgs) -> App {
         let config = Config::default();
         super::init_app(args, config).unwrap().0
@@ -1249,8 +1894,16 @@ mod test {
         let default_app = create_app(BottomArgs::parse_from(["btm"]));
 
         // Skip battery since it's tricky to test depending on the platform/features
-        // we're testing with.
-        let skip = ["help", "version", "celsius", "battery", "generate_schema"];
+        // we're testing with. Batch mode is started instead of the interface
+        // rather than being an app setting.
+        let skip = [
+            "help",
+            "version",
+            "celsius",
+            "battery",
+            "generate_schema",
+            "batch",
+        ];
 
         for arg in app.get_arguments().collect::<Vec<_>>() {
             let arg_name = arg
@@ -1328,4 +1981,300 @@ mod test {
         // let case_3 = case_1;
         // assert_eq!(get_config_path(None), Some(case_1));
     }
+
+    #[test]
+    fn reloads_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bottom.toml");
+
+        // A missing config is treated as the default one.
+        let config = read_config(&path).unwrap();
+        let mut app = super::init_app(BottomArgs::parse_from(["btm"]), config)
+            .unwrap()
+            .0;
+        let selected = app.current_widget.widget_id;
+        assert!(!app.app_config_fields.use_dot);
+
+        std::fs::write(&path, "[flags]\ndot_marker = true\n").unwrap();
+        let config = read_config(&path).unwrap();
+        let reloaded = super::init_app(BottomArgs::parse_from(["btm"]), config)
+            .unwrap()
+            .0;
+
+        app.data_store.toggle_frozen();
+        app.apply_reloaded_config(reloaded);
+        assert!(app.app_config_fields.use_dot);
+        assert!(app.data_store.is_frozen());
+        assert_eq!(app.current_widget.widget_id, selected);
+
+        std::fs::write(&path, "[flags]\ndot_marker = 5\n").unwrap();
+        assert!(read_config(&path).is_err());
+    }
+
+    #[test]
+    fn reload_keeps_widget_state() {
+        let init = |text: &str| {
+            let config: Config = toml_edit::de::from_str(text).unwrap();
+            super::init_app(BottomArgs::parse_from(["btm"]), config)
+                .unwrap()
+                .0
+        };
+
+        let mut app = init("");
+        let mut data = Box::new(Data::default());
+        data.temperature_sensors = Some(vec![TempSensorData {
+            name: "cpu".into(),
+            temperature: Some(50.0),
+        }]);
+        app.data_store.eat_data(data, &app.app_config_fields);
+
+        let proc_id = *app.states.proc_state.widget_states.keys().next().unwrap();
+        let proc = app.states.proc_state.get_mut_widget_state(proc_id).unwrap();
+        proc.proc_search.search_state.current_search_query = "btm".into();
+        proc.update_query();
+        proc.set_sort_column(ProcWidgetColumn::PidOrCount, false);
+
+        let mem_id = *app.states.mem_state.widget_states.keys().next().unwrap();
+        let mem = app.states.mem_state.widget_states.get_mut(&mem_id).unwrap();
+        mem.current_display_time = 30_000;
+
+        app.apply_reloaded_config(init("[flags]\ntemperature_type = \"f\"\n"));
+
+        let proc = app.states.proc_state.get_widget_state(proc_id).unwrap();
+        assert_eq!(proc.current_search_query(), "btm");
+        assert_eq!(
+            proc.sort_column(),
+            Some((ProcWidgetColumn::PidOrCount, false))
+        );
+        assert_eq!(
+            app.states.mem_state.widget_states[&mem_id].current_display_time,
+            30_000
+        );
+
+        // The stored temperatures are converted rather than shown in Celsius
+        // until the next update.
+        assert_eq!(
+            app.data_store.get_data().temp_data[0].temperature,
+            Some(TypedTemperature::Fahrenheit(122))
+        );
+    }
+
+    #[test]
+    fn config_layout_tabs() {
+        let init = |text: &str| {
+            let config: Config = toml_edit::de::from_str(text).unwrap();
+            super::init_app(BottomArgs::parse_from(["btm"]), config)
+        };
+
+        let (mut app, tabs, _) = init(
+            r#"
+            [[layout]]
+            name = "overview"
+            [[layout.row]]
+            [[layout.row.child]]
+            type = "cpu"
+            [[layout.row.child]]
+            type = "mem"
+
+            [[layout]]
+            name = "io"
+            [[layout.row]]
+            [[layout.row.child]]
+            type = "disk"
+            [[layout.row.child]]
+            type = "proc"
+            default = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(tabs.len(), 2);
+        assert_eq!(app.tab_state.names, vec!["overview", "io"]);
+
+        // Widgets from every tab are collected, and each has its own ID.
+        assert!(app.used_widgets.use_cpu && app.used_widgets.use_disk);
+        let ids = tabs
+            .iter()
+            .flat_map(|tab| tab.layout.widgets())
+            .map(|widget| widget.widget_id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), app.widget_map.len());
+
+        // The default widget is on the second tab.
+        assert_eq!(app.tab_state.current, 1);
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Proc);
+        let proc_id = app.current_widget.widget_id;
+
+        app.select_tab(0);
+        assert_eq!(app.tab_state.current, 0);
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Cpu);
+
+        // Switching back selects the widget that was selected before.
+        app.select_tab(5);
+        assert_eq!(app.tab_state.current, 0);
+        app.select_tab(1);
+        assert_eq!(app.current_widget.widget_id, proc_id);
+
+        assert!(init("[[layout]]\nname = \"a\"\nrow = []").is_err());
+        assert!(
+            init("[[layout]]\nname = \"a\"\n[[layout.row]]\n[[layout.row.child]]\ntype = \"cpu\"\n[[layout]]\nname = \"a\"\n[[layout.row]]\n[[layout.row.child]]\ntype = \"mem\"")
+                .is_err()
+        );
+        assert!(
+            init("[[row]]\n[[row.child]]\ntype = \"cpu\"\n[[layout]]\nname = \"a\"\n[[layout.row]]\n[[layout.row.child]]\ntype = \"cpu\"")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn config_layout_breakpoints() {
+        let init = |text: &str| {
+            let config: Config = toml_edit::de::from_str(text).unwrap();
+            super::init_app(BottomArgs::parse_from(["btm"]), config)
+        };
+
+        let (mut app, tabs, _) = init(
+            r#"
+            [[row]]
+            [[row.child]]
+            type = "cpu"
+            [[row.child]]
+            type = "proc"
+            default = true
+            [[row.child]]
+            type = "disk"
+
+            [[breakpoint]]
+            max_width = 80
+            [[breakpoint.row]]
+            [[breakpoint.row.child]]
+            type = "proc"
+            [[breakpoint.row]]
+            [[breakpoint.row.child]]
+            type = "temp"
+            "#,
+        )
+        .unwrap();
+
+        let tab = &tabs[0];
+        assert_eq!(tab.breakpoint_index(120, 40), None);
+        assert_eq!(tab.breakpoint_index(80, 40), Some(0));
+
+        // Matching widgets share IDs, including the process search and sort widgets.
+        let ids = |layout: &BottomLayout, widget_type: BottomWidgetType| {
+            layout
+                .widgets()
+                .filter(|widget| widget.widget_type == widget_type)
+                .map(|widget| widget.widget_id)
+                .collect::<Vec<_>>()
+        };
+        let narrow = tab.layout_at(Some(0));
+        for widget_type in [
+            BottomWidgetType::Proc,
+            BottomWidgetType::ProcSearch,
+            BottomWidgetType::ProcSort,
+        ] {
+            assert_eq!(
+                ids(&tab.layout, widget_type.clone()),
+                ids(narrow, widget_type)
+            );
+        }
+
+        // Widgets only in the breakpoint layout still get states.
+        assert!(app.used_widgets.use_temp);
+        let temp_id = ids(narrow, BottomWidgetType::Temp)[0];
+        assert!(app.states.temp_state.widget_states.contains_key(&temp_id));
+
+        // Switching layouts keeps the selected widget if it is in both, and
+        // otherwise picks another one.
+        let proc_id = app.current_widget.widget_id;
+        assert!(app.current_widget.right_neighbour.is_some());
+        app.use_layout(narrow);
+        assert_eq!(app.current_widget.widget_id, proc_id);
+        assert_eq!(app.current_widget.right_neighbour, None);
+
+        app.current_widget = app.widget_map[&temp_id].clone();
+        app.use_layout(&tab.layout);
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::Cpu);
+
+        assert!(
+            init("[[breakpoint]]\n[[breakpoint.row]]\n[[breakpoint.row.child]]\ntype = \"cpu\"")
+                .is_err()
+        );
+        assert!(init("[[breakpoint]]\nmin_width = 10\nrow = []").is_err());
+        assert!(
+            init("[[layout]]\nname = \"a\"\n[[layout.row]]\n[[layout.row.child]]\ntype = \"cpu\"\n[[breakpoint]]\nmin_width = 10\n[[breakpoint.row]]\n[[breakpoint.row.child]]\ntype = \"cpu\"")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn inline_mode() {
+        let init = |args: &[&str], text: &str| {
+            let config: Config = toml_edit::de::from_str(text).unwrap();
+            super::init_app(BottomArgs::parse_from(args), config).map(|(app, ..)| app)
+        };
+
+        let app = init(&["btm", "--inline", "4", "--inline_widgets", "net,cpu"], "").unwrap();
+        let fields = &app.app_config_fields;
+        assert_eq!(fields.inline_height, Some(4));
+        assert_eq!(
+            fields.inline_widgets,
+            vec![BottomWidgetType::Net, BottomWidgetType::Cpu]
+        );
+        assert!(fields.use_basic_mode && fields.disable_click);
+
+        // Only the inline widgets are in the layout and collected, and the first
+        // one is selected.
+        assert_eq!(app.current_widget.widget_type, BottomWidgetType::BasicNet);
+        assert_eq!(app.widget_map.len(), 2);
+        assert!(app.states.basic_table_widget_state.is_none());
+        let used = &app.used_widgets;
+        assert!(used.use_net && used.use_cpu && !used.use_mem);
+        assert!(!used.use_proc && !used.use_disk && !used.use_temp && !used.use_battery);
+
+        let app = init(&["btm", "--inline", "4", "--inline_widgets", "mem,mem"], "").unwrap();
+        assert_eq!(
+            app.app_config_fields.inline_widgets,
+            vec![BottomWidgetType::Mem]
+        );
+
+        let app = init(&["btm"], "[flags]\ninline = 3").unwrap();
+        assert_eq!(app.app_config_fields.inline_height, Some(3));
+        assert_eq!(app.app_config_fields.inline_widgets.len(), 3);
+
+        let app = init(&["btm"], "").unwrap();
+        assert_eq!(app.app_config_fields.inline_height, None);
+        assert!(!app.app_config_fields.use_basic_mode);
+
+        assert!(init(&["btm"], "[flags]\ninline = 0").is_err());
+        assert!(init(&["btm"], "[flags]\ninline = 3\ninline_widgets = [\"proc\"]").is_err());
+        assert!(init(&["btm"], "[flags]\ninline = 3\ninline_widgets = []").is_err());
+    }
+
+    #[test]
+    fn config_clipboard() {
+        use crate::utils::clipboard::{CopyMethod, CopyTarget, default_clipboard_file};
+
+        let init = |text: &str| {
+            let config: Config = toml_edit::de::from_str(text).unwrap();
+            super::init_app(BottomArgs::parse_from(["btm"]), config).map(|(app, ..)| app)
+        };
+
+        let app = init("").unwrap();
+        let clipboard = &app.app_config_fields.clipboard;
+        assert_eq!(clipboard.target, CopyTarget::Pid);
+        assert_eq!(clipboard.method, CopyMethod::Auto);
+        assert_eq!(clipboard.file, default_clipboard_file());
+
+        let app =
+            init("[clipboard]\ncopy = \"row\"\nmethod = \"file\"\nfile = \"/tmp/copied\"").unwrap();
+        let clipboard = &app.app_config_fields.clipboard;
+        assert_eq!(clipboard.target, CopyTarget::Row);
+        assert_eq!(clipboard.method, CopyMethod::File);
+        assert_eq!(clipboard.file, std::path::PathBuf::from("/tmp/copied"));
+
+        assert!(init("[clipboard]\ncopy = \"everything\"").is_err());
+        assert!(init("[clipboard]\nmethod = \"fax\"").is_err());
+    }
 }
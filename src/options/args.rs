@@ -41,7 +41,7 @@ const CHART_WIDGET_POSITIONS: [&str; 9] = [
 ];
 
 /// Represents the arguments that can be passed in to bottom.
-#[derive(Parser, Debug)]
+#[derive(Parser, Clone, Debug)]
 #[command(
     name = crate_name!(),
     version = VERSION,
@@ -110,6 +110,38 @@ pub struct GeneralArgs {
     )]
     pub basic: bool,
 
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        help = "Draws one frame to a file instead of the terminal, then exits.",
+        long_help = "Draws one frame to a file instead of the terminal, then exits, without needing a terminal. \
+                    The format is picked from the file extension: '.svg', '.html', '.ansi' for text with colour \
+                    escape codes, or plain text otherwise. See --capture_size and --capture_after."
+    )]
+    pub capture: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "COUNT",
+        help = "Sets how many times data is collected before --capture draws.",
+        long_help = "Sets how many times data is collected before --capture draws its frame. Defaults to 2, \
+                    as some values such as CPU usage need two collections.",
+        alias = "capture-after"
+    )]
+    pub capture_after: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "WIDTHxHEIGHT",
+        help = "Sets the size of the frame drawn by --capture.",
+        long_help = "Sets the size of the frame drawn by --capture, in columns and rows. Defaults to 120x40, \
+                    and can be at most 1000x1000.",
+        value_parser = parse_capture_size,
+        alias = "capture-size"
+    )]
+    pub capture_size: Option<CaptureSize>,
+
     #[arg(
         short = 'C',
         long,
@@ -247,6 +279,43 @@ pub struct GeneralArgs {
     #[arg(long, action = ArgAction::SetTrue, help = "Hides the time scale from being shown.", alias = "hide-time")]
     pub hide_time: bool,
 
+    #[arg(
+        long,
+        value_name = "LINES",
+        help = "Draws in the given number of lines below the cursor, rather than the whole terminal.",
+        long_help = "Draws a compact view in the given number of lines below the cursor, rather than taking \
+                    over the whole terminal. The rest of the terminal and its scrollback are left as they \
+                    were, and the lines are cleared on exit. This shows basic mode widgets side by side, set \
+                    with --inline_widgets, and disables mouse clicks."
+    )]
+    pub inline: Option<u16>,
+
+    #[arg(
+        long,
+        value_name = "WIDGETS",
+        value_delimiter = ',',
+        value_parser = ["cpu", "mem", "memory", "net", "network"],
+        help = "Sets the widgets shown in inline mode.",
+        long_help = "Sets the widgets shown side by side in inline mode (--inline), as a comma-separated \
+                    list. Defaults to 'cpu,mem,net'.",
+        alias = "inline-widgets"
+    )]
+    pub inline_widgets: Option<Vec<String>>,
+
+    #[cfg(target_os = "linux")]
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::DirPath,
+        help = "Sets where procfs is mounted.",
+        long_help = "Sets where procfs is mounted, which is read for data such as processes and disk I/O. \
+                    Defaults to '/proc'. For example, this can be set to a host's '/proc' that has been \
+                    bind-mounted into a container. Note that CPU, memory, and network usage are not \
+                    affected by this.",
+        alias = "procfs-root"
+    )]
+    pub procfs_root: Option<PathBuf>,
+
     #[arg(
         short = 'r',
         long,
@@ -258,13 +327,25 @@ pub struct GeneralArgs {
     )]
     pub rate: Option<String>,
 
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Saves settings changed while running when exiting.",
+        long_help = "Saves settings changed while running when exiting, as if Ctrl-s was pressed. Settings \
+                    with a config option, such as tree mode and the process columns, are written into the \
+                    config file, while UI state such as the selected widget and legends is written \
+                    to a separate state file, which is only restored when this is set."
+    )]
+    pub remember: bool,
+
     #[arg(
         long,
         value_name = "TIME",
         help = "How far back data will be stored up to.",
         long_help = "How far back data will be stored up to. Either a number in milliseconds or a 'human duration' \
                     (e.g. 10m, 1h). Defaults to 10 minutes, and must be at least  1 minute. Larger values \
-                    may result in higher memory usage."
+                    may result in higher memory usage. Data older than 10 minutes is downsampled into \
+                    coarser buckets to keep long retention periods cheap."
     )]
     pub retention: Option<String>,
 
@@ -276,6 +357,19 @@ pub struct GeneralArgs {
     )]
     pub show_table_scroll_position: bool,
 
+    #[cfg(target_os = "linux")]
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::DirPath,
+        help = "Sets where sysfs is mounted.",
+        long_help = "Sets where sysfs is mounted, which is read for data such as temperatures and power. \
+                    Defaults to '/sys'. For example, this can be set to a host's '/sys' that has been \
+                    bind-mounted into a container.",
+        alias = "sysfs-root"
+    )]
+    pub sysfs_root: Option<PathBuf>,
+
     #[arg(
         short = 'd',
         long,
@@ -293,6 +387,55 @@ pub struct GeneralArgs {
 #[derive(Args, Clone, Debug, Default)]
 #[command(next_help_heading = "Process Options", rename_all = "snake_case")]
 pub struct ProcessArgs {
+    #[arg(
+        long,
+        action = ArgAction::SetTrue,
+        help = "Prints the process list as text instead of drawing the interface.",
+        long_help = "Prints the process list as plain aligned text every time processes are collected, instead of \
+                    drawing the interface, like 'top -b'. This uses the same columns, sorting, tree, and \
+                    grouping settings as the process widget. See --batch_iterations, --batch_rows, \
+                    --batch_query, and --batch_sort."
+    )]
+    pub batch: bool,
+
+    #[arg(
+        long,
+        value_name = "COUNT",
+        help = "Sets how many times --batch prints the process list before exiting.",
+        long_help = "Sets how many times --batch prints the process list before exiting. If not set, it \
+                    prints until stopped.",
+        alias = "batch-iterations"
+    )]
+    pub batch_iterations: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "QUERY",
+        help = "Filters the processes printed by --batch.",
+        long_help = "Filters the processes printed by --batch with a search query, which uses the same \
+                    syntax as searching in the process widget, such as 'cpu > 5 and user=root'.",
+        alias = "batch-query"
+    )]
+    pub batch_query: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "COUNT",
+        help = "Sets the most processes printed each time by --batch.",
+        alias = "batch-rows"
+    )]
+    pub batch_rows: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "COLUMN",
+        help = "Sets the column that --batch sorts by.",
+        long_help = "Sets the column that --batch sorts by, such as 'mem' or 'pid'. Defaults to the sort \
+                    column of the process widget.",
+        alias = "batch-sort"
+    )]
+    pub batch_sort: Option<String>,
+
     #[arg(
         short = 'S',
         long,
@@ -451,6 +594,47 @@ pub struct TemperatureArgs {
     pub kelvin: bool,
 }
 
+/// The size of the frame drawn by `--capture`, in columns and rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CaptureSize {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Default for CaptureSize {
+    fn default() -> Self {
+        CaptureSize {
+            width: 120,
+            height: 40,
+        }
+    }
+}
+
+impl CaptureSize {
+    /// The largest width or height of a capture. Frames are drawn into a buffer
+    /// of every cell, so a huge size would run out of memory.
+    pub const MAX_DIMENSION: u16 = 1000;
+}
+
+/// Parses a capture size such as `120x40`.
+fn parse_capture_size(size: &str) -> Result<CaptureSize, String> {
+    let error = || "use WIDTHxHEIGHT with a non-zero width and height, such as 120x40".to_string();
+
+    let (width, height) = size.split_once(['x', 'X']).ok_or_else(error)?;
+    let width = width.trim().parse::<u16>().map_err(|_| error())?;
+    let height = height.trim().parse::<u16>().map_err(|_| error())?;
+    if width == 0 || height == 0 {
+        return Err(error());
+    }
+
+    let max = CaptureSize::MAX_DIMENSION;
+    if width > max || height > max {
+        return Err(format!("the width and height can each be at most {max}"));
+    }
+
+    Ok(CaptureSize { width, height })
+}
+
 /// The default selection of the CPU widget. If the given selection is invalid,
 /// we will fall back to all.
 #[derive(Clone, Copy, Debug, Default)]
@@ -702,6 +886,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn capture_sizes() {
+        assert_eq!(
+            parse_capture_size("120x40"),
+            Ok(CaptureSize {
+                width: 120,
+                height: 40
+            })
+        );
+        assert!(parse_capture_size("120").is_err());
+        assert!(parse_capture_size("0x40").is_err());
+        assert!(parse_capture_size("0x0").is_err());
+
+        assert!(parse_capture_size("1000x1000").is_ok());
+        assert!(parse_capture_size("1001x40").is_err());
+        assert!(parse_capture_size("65535x65535").is_err());
+    }
+
     #[test]
     fn catch_incorrect_long_args() {
         // Set this to allow certain ones through if needed.
@@ -1,4 +1,7 @@
+pub mod clipboard;
+pub mod collection_rates;
 pub mod cpu;
+pub mod custom_metrics;
 pub mod disk;
 pub mod flags;
 mod ignore_list;
@@ -16,7 +19,14 @@ use style::StyleConfig;
 use temperature::TempConfig;
 
 pub use self::ignore_list::IgnoreList;
-use self::{cpu::CpuConfig, layout::Row, process::ProcessesConfig};
+use self::{
+    clipboard::ClipboardConfig,
+    collection_rates::CollectionRatesConfig,
+    cpu::CpuConfig,
+    custom_metrics::CustomMetricConfig,
+    layout::{BreakpointConfig, LayoutConfig, Row},
+    process::ProcessesConfig,
+};
 
 /// Overall config for `bottom`.
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -26,11 +36,16 @@ pub struct Config {
     pub(crate) flags: Option<GeneralConfig>,
     pub(crate) styles: Option<StyleConfig>,
     pub(crate) row: Option<Vec<Row>>,
+    pub(crate) layout: Option<Vec<LayoutConfig>>,
+    pub(crate) breakpoint: Option<Vec<BreakpointConfig>>,
     pub(crate) processes: Option<ProcessesConfig>,
     pub(crate) disk: Option<DiskConfig>,
     pub(crate) temperature: Option<TempConfig>,
     pub(crate) network: Option<NetworkConfig>,
     pub(crate) cpu: Option<CpuConfig>,
+    pub(crate) custom_metrics: Option<Vec<CustomMetricConfig>>,
+    pub(crate) collection_rates: Option<CollectionRatesConfig>,
+    pub(crate) clipboard: Option<ClipboardConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// What is copied from tables, and how.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct ClipboardConfig {
+    /// What to copy from the selected row: "pid", "row", or "cell".
+    pub(crate) copy: Option<String>,
+
+    /// How to copy: "auto", "osc52", or "file".
+    pub(crate) method: Option<String>,
+
+    /// Where to write copied text when not using OSC 52.
+    pub(crate) file: Option<PathBuf>,
+}
@@ -0,0 +1,21 @@
+use serde::Deserialize;
+
+use super::StringOrNum;
+
+/// How often to collect each subsystem. Any rate that isn't set uses the
+/// general `rate`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct CollectionRatesConfig {
+    pub(crate) cpu: Option<StringOrNum>,
+    pub(crate) memory: Option<StringOrNum>,
+    pub(crate) network: Option<StringOrNum>,
+    pub(crate) processes: Option<StringOrNum>,
+    pub(crate) process_memory: Option<StringOrNum>,
+    pub(crate) disks: Option<StringOrNum>,
+    pub(crate) temperatures: Option<StringOrNum>,
+    pub(crate) batteries: Option<StringOrNum>,
+    pub(crate) gpus: Option<StringOrNum>,
+    pub(crate) power: Option<StringOrNum>,
+}
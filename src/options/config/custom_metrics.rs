@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// A user-defined metric, read from a command or file.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub(crate) struct CustomMetricConfig {
+    /// The name of the metric. This is used as the widget title, and to refer
+    /// to the metric from a `custom` widget in the layout.
+    pub(crate) name: String,
+
+    /// A command to run, as a list of the program followed by its arguments.
+    /// Its standard output is used. Cannot be set with `file`.
+    pub(crate) command: Option<Vec<String>>,
+
+    /// A file to read. Cannot be set with `command`.
+    pub(crate) file: Option<PathBuf>,
+
+    /// How many collections to wait between each read. Defaults to 1.
+    pub(crate) interval: Option<u64>,
+
+    /// A regex to extract values with. Each capture group is a value; if there
+    /// are no capture groups, the whole match is used. Cannot be set with
+    /// `json_path`.
+    pub(crate) regex: Option<String>,
+
+    /// A list of dotted paths into a JSON document to extract values with, like
+    /// `stats.queues.0.depth`. Cannot be set with `regex`.
+    pub(crate) json_path: Option<Vec<String>>,
+
+    /// The unit of the values, shown alongside them.
+    pub(crate) unit: Option<String>,
+
+    /// A name for each extracted value, shown in the legend.
+    pub(crate) labels: Option<Vec<String>>,
+}
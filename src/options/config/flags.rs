@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
 use super::StringOrNum;
@@ -19,6 +21,8 @@ pub(crate) struct GeneralConfig {
     pub(crate) whole_word: Option<bool>,
     pub(crate) regex: Option<bool>,
     pub(crate) basic: Option<bool>,
+    pub(crate) inline: Option<u16>,
+    pub(crate) inline_widgets: Option<Vec<String>>,
     pub(crate) default_time_value: Option<StringOrNum>,
     pub(crate) time_delta: Option<StringOrNum>,
     pub(crate) autohide_time: Option<bool>,
@@ -43,6 +47,10 @@ pub(crate) struct GeneralConfig {
     pub(crate) read_only: Option<bool>,
     // #[cfg(target_os = "linux")]
     pub(crate) hide_k_threads: Option<bool>,
+    // #[cfg(target_os = "linux")]
+    pub(crate) procfs_root: Option<PathBuf>,
+    // #[cfg(target_os = "linux")]
+    pub(crate) sysfs_root: Option<PathBuf>,
     // #[cfg(feature = "zfs")]
     pub(crate) free_arc: Option<bool>,
     pub(crate) network_use_bytes: Option<bool>,
@@ -53,4 +61,5 @@ pub(crate) struct GeneralConfig {
     pub(crate) retention: Option<StringOrNum>,
     pub(crate) average_cpu_row: Option<bool>, // FIXME: This makes no sense outside of basic mode, add a basic mode config section.
     pub(crate) tree_collapse: Option<bool>,
+    pub(crate) remember: Option<bool>,
 }
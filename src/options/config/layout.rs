@@ -13,6 +13,37 @@ pub struct Row {
     pub child: Option<Vec<RowChildren>>,
 }
 
+/// A named layout, shown as a tab along with any others.
+#[derive(Clone, Deserialize, Debug, Serialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub struct LayoutConfig {
+    /// The name shown on the layout's tab.
+    pub name: String,
+
+    /// The rows of the layout, the same as the top-level `[[row]]` list.
+    pub row: Vec<Row>,
+
+    /// Alternative rows to use at certain terminal sizes.
+    pub breakpoint: Option<Vec<BreakpointConfig>>,
+}
+
+/// Alternative rows that replace a layout's rows while the terminal size is
+/// within the given bounds. All bounds are inclusive, and unset ones always
+/// match.
+#[derive(Clone, Deserialize, Debug, Serialize)]
+#[cfg_attr(feature = "generate_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(test, serde(deny_unknown_fields), derive(PartialEq, Eq))]
+pub struct BreakpointConfig {
+    pub min_width: Option<u16>,
+    pub max_width: Option<u16>,
+    pub min_height: Option<u16>,
+    pub max_height: Option<u16>,
+
+    /// The rows to use, in the same format as the `[[row]]` list.
+    pub row: Vec<Row>,
+}
+
 fn new_cpu(cpu_left_legend: bool, iter_id: &mut u64) -> BottomColRow {
     let cpu_id = *iter_id;
     *iter_id += 1;
@@ -115,10 +146,10 @@ impl Row {
                                 .total_col_row_ratio(2)
                                 .ratio(width_ratio)
                             }
-                            _ => BottomCol::new(vec![BottomColRow::new(vec![BottomWidget::new(
-                                widget_type,
-                                *iter_id,
-                            )])])
+                            _ => BottomCol::new(vec![BottomColRow::new(vec![
+                                BottomWidget::new(widget_type, *iter_id)
+                                    .custom_metric(widget.metric.clone()),
+                            ])])
                             .ratio(width_ratio),
                         });
                     }
@@ -186,10 +217,10 @@ impl Row {
                                     total_col_row_ratio += col_row_height_ratio;
 
                                     col_row_children.push(
-                                        BottomColRow::new(vec![BottomWidget::new(
-                                            widget_type,
-                                            *iter_id,
-                                        )])
+                                        BottomColRow::new(vec![
+                                            BottomWidget::new(widget_type, *iter_id)
+                                                .custom_metric(widget.metric.clone()),
+                                        ])
                                         .ratio(col_row_height_ratio),
                                     )
                                 }
@@ -238,6 +269,9 @@ pub struct FinalWidget {
     #[serde(rename = "type")]
     pub widget_type: String,
     pub default: Option<bool>,
+    /// The name of the `[[custom_metrics]]` entry to show. Only used by the
+    /// `custom` widget.
+    pub metric: Option<String>,
 }
 
 #[cfg(test)]
@@ -13,6 +13,12 @@ pub(crate) struct ProcessesConfig {
 
     /// Whether to get process child threads.
     pub get_threads: Option<bool>,
+
+    /// The column to sort process widgets by.
+    pub sort: Option<ProcColumn>,
+
+    /// Whether to sort in descending order. Defaults to the sort column's usual order.
+    pub sort_descending: Option<bool>,
 }
 
 #[cfg(test)]
@@ -124,6 +124,7 @@ pub struct Styles {
     pub(crate) low_battery: Style,
     pub(crate) invalid_query_style: Style,
     pub(crate) disabled_text_style: Style,
+    pub(crate) restarting_text_style: Style,
     #[cfg(target_os = "linux")]
     pub(crate) thread_text_style: Style,
     pub(crate) border_type: BorderType,
@@ -211,6 +212,7 @@ impl Styles {
         set_style!(self.text_style, config.widgets, text);
         set_style!(self.selected_text_style, config.widgets, selected_text);
         set_style!(self.disabled_text_style, config.widgets, disabled_text);
+        set_style!(self.restarting_text_style, config.widgets, restarting_text);
 
         #[cfg(target_os = "linux")]
         {
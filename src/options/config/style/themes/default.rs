@@ -66,6 +66,7 @@ impl Styles {
             low_battery: color!(Color::Red),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: color!(Color::DarkGray),
+            restarting_text_style: color!(Color::LightRed),
             border_type: BorderType::Plain,
             #[cfg(target_os = "linux")]
             thread_text_style: color!(Color::Green),
@@ -66,6 +66,7 @@ impl Styles {
             low_battery: hex!("#fb4934"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#665c54"),
+            restarting_text_style: hex!("#fb4934"),
             border_type: BorderType::Plain,
             #[cfg(target_os = "linux")]
             thread_text_style: hex!("#458588"),
@@ -131,6 +132,7 @@ impl Styles {
             low_battery: hex!("#cc241d"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#d5c4a1"),
+            restarting_text_style: hex!("#cc241d"),
             border_type: BorderType::Plain,
             #[cfg(target_os = "linux")]
             thread_text_style: hex!("#458588"),
@@ -54,6 +54,7 @@ impl Styles {
             low_battery: hex!("#bf616a"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#4c566a"),
+            restarting_text_style: hex!("#bf616a"),
             border_type: BorderType::Plain,
             #[cfg(target_os = "linux")]
             thread_text_style: hex!("#a3be8c"),
@@ -107,6 +108,7 @@ impl Styles {
             low_battery: hex!("#bf616a"),
             invalid_query_style: color!(Color::Red),
             disabled_text_style: hex!("#d8dee9"),
+            restarting_text_style: hex!("#bf616a"),
             border_type: BorderType::Plain,
             #[cfg(target_os = "linux")]
             thread_text_style: hex!("#a3be8c"),
@@ -31,6 +31,10 @@ pub(crate) struct WidgetStyle {
     /// on Linux at the moment.
     pub(crate) thread_text: Option<TextStyleConfig>,
 
+    /// Text styling for text when representing processes that keep
+    /// restarting.
+    pub(crate) restarting_text: Option<TextStyleConfig>,
+
     /// Widget borders type.
     pub(crate) widget_border_type: Option<WidgetBorderType>,
 }
@@ -0,0 +1,674 @@
+//! Saving settings that were changed while running, so they are kept for the
+//! next run.
+//!
+//! Settings that have a config option are written back into the config file with
+//! `toml_edit`, which keeps its comments and ordering. The rest of the UI state,
+//! such as the selected widget, goes into a separate state file instead.
+
+use std::{
+    collections::HashMap,
+    fs,
+    hash::BuildHasher,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, bail};
+use serde::{Deserialize, Serialize};
+use toml_edit::{Array, DocumentMut, Value};
+
+use crate::{
+    app::{App, data::TemperatureType},
+    canvas::components::{data_table::PickableColumns, time_graph::LegendPosition},
+    widgets::{
+        DEFAULT_COLUMNS, DEFAULT_DISK_COLUMNS, DiskColumn, GraphLegend, ProcColumn,
+        ProcWidgetColumn, ProcWidgetMode, ProcWidgetState,
+    },
+};
+
+/// How process widgets are sorted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ProcessSort {
+    /// The column's name, as used in the config file.
+    pub(crate) column: String,
+    pub(crate) descending: bool,
+}
+
+/// The legend of a graph widget.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct LegendState {
+    /// The ID of the graph widget.
+    pub(crate) widget: u64,
+
+    /// The legend's position, as used in the config file.
+    pub(crate) position: String,
+
+    #[serde(default)]
+    pub(crate) hidden: bool,
+}
+
+/// UI state that is kept between runs, but doesn't belong in the config.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct UiState {
+    /// The ID of the selected widget.
+    pub(crate) selected_widget: Option<u64>,
+
+    /// Whether the selected widget is expanded.
+    #[serde(default)]
+    pub(crate) expanded: bool,
+
+    /// How process widgets are sorted.
+    pub(crate) process_sort: Option<ProcessSort>,
+
+    /// Whether the basic mode memory widget shows percentages.
+    #[serde(default)]
+    pub(crate) basic_mem_percent: bool,
+
+    /// The legend of each graph widget that has one inside it.
+    #[serde(default)]
+    pub(crate) legends: Vec<LegendState>,
+}
+
+impl UiState {
+    pub(crate) fn from_app(app: &App) -> Self {
+        let process_sort = selected_process_widget(app)
+            .and_then(ProcWidgetState::sort_column)
+            .map(|(column, descending)| ProcessSort {
+                column: column.config_name().to_string(),
+                descending,
+            });
+
+        let mut legends = app
+            .states
+            .graph_legends()
+            .map(|(widget, legend)| LegendState {
+                widget,
+                position: legend.position.config_name().to_string(),
+                hidden: legend.is_hidden,
+            })
+            .collect::<Vec<_>>();
+        legends.sort_unstable_by_key(|legend| legend.widget);
+
+        Self {
+            selected_widget: Some(app.current_widget.widget_id),
+            expanded: app.is_expanded,
+            process_sort,
+            basic_mem_percent: app.basic_mode_use_percent,
+            legends,
+        }
+    }
+
+    /// Applies the state to `app`, skipping anything that no longer matches the
+    /// layout or columns.
+    pub(crate) fn apply_to(&self, app: &mut App) {
+        if let Some(widget) = self.selected_widget.and_then(|id| app.widget_map.get(&id)) {
+            app.show_widget(widget.clone());
+            app.is_expanded |= self.expanded && !app.app_config_fields.use_basic_mode;
+        }
+
+        if let Some(sort) = &self.process_sort {
+            if let Some(column) = parse_column(&sort.column) {
+                for state in app.states.proc_state.widget_states.values_mut() {
+                    state.set_sort_column(column, sort.descending);
+                }
+            }
+        }
+
+        app.basic_mode_use_percent = self.basic_mem_percent;
+
+        for state in &self.legends {
+            let (Some(legend), Ok(position)) = (
+                app.states.graph_legend_mut(state.widget),
+                state.position.parse(),
+            ) else {
+                continue;
+            };
+
+            *legend = GraphLegend {
+                position,
+                is_hidden: state.hidden,
+            };
+        }
+    }
+}
+
+/// The settings that are written back into the config file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ConfigSettings {
+    pub(crate) temperature_type: TemperatureType,
+
+    /// Settings taken from the selected process widget, or the first one if a
+    /// process widget isn't selected.
+    pub(crate) processes: Option<ProcessSettings>,
+
+    /// The columns of the selected disk widget, or the first one if a disk
+    /// widget isn't selected.
+    pub(crate) disk_columns: Option<Vec<DiskColumn>>,
+
+    /// The legends of the selected memory and network widgets, or the first
+    /// ones of each if they aren't selected.
+    pub(crate) memory_legend: Option<GraphLegend>,
+    pub(crate) network_legend: Option<GraphLegend>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ProcessSettings {
+    pub(crate) tree: bool,
+    pub(crate) group_processes: bool,
+    pub(crate) process_command: bool,
+    pub(crate) process_memory_as_value: bool,
+    pub(crate) columns: Vec<ProcWidgetColumn>,
+
+    /// The column sorted by and whether it is descending.
+    pub(crate) sort: Option<(ProcWidgetColumn, bool)>,
+
+    /// Whether the widget is sorted by its default column and order.
+    pub(crate) is_default_sort: bool,
+
+    /// Whether the sort order is the sorted column's usual order.
+    pub(crate) is_default_sort_order: bool,
+}
+
+impl ConfigSettings {
+    pub(crate) fn from_app(app: &App) -> Self {
+        let selected_id = app.current_widget.widget_id;
+        let processes = selected_process_widget(app).map(|state| ProcessSettings {
+            tree: matches!(state.mode, ProcWidgetMode::Tree(_)),
+            group_processes: matches!(state.mode, ProcWidgetMode::Grouped),
+            process_command: state.is_using_command(),
+            process_memory_as_value: !state.is_mem_percent(),
+            columns: state.column_mapping.iter().copied().collect(),
+            sort: state.sort_column(),
+            is_default_sort: state.is_default_sort(),
+            is_default_sort_order: state.is_default_sort_order(),
+        });
+
+        let disk_columns = selected_or_first(&app.states.disk_state.widget_states, selected_id)
+            .map(PickableColumns::shown_columns);
+        let memory_legend = selected_or_first(&app.states.mem_state.widget_states, selected_id)
+            .map(|state| state.legend);
+        let network_legend = selected_or_first(&app.states.net_state.widget_states, selected_id)
+            .map(|state| state.legend);
+
+        Self {
+            temperature_type: app.app_config_fields.temperature_type,
+            processes,
+            disk_columns,
+            memory_legend,
+            network_legend,
+        }
+    }
+
+    /// Writes the settings into `doc`. To avoid filling an untouched config with
+    /// defaults, a key that isn't there yet is only added if its value differs
+    /// from the default.
+    fn write_to(&self, doc: &mut DocumentMut) -> anyhow::Result<()> {
+        set_value(
+            doc,
+            "flags",
+            "temperature_type",
+            self.temperature_type.config_name().into(),
+            self.temperature_type == TemperatureType::default(),
+        )?;
+
+        if let Some(processes) = &self.processes {
+            let flags = [
+                ("tree", processes.tree),
+                ("group_processes", processes.group_processes),
+                ("process_command", processes.process_command),
+                ("process_memory_as_value", processes.process_memory_as_value),
+            ];
+
+            for (key, enabled) in flags {
+                set_value(doc, "flags", key, enabled.into(), !enabled)?;
+            }
+
+            let columns = processes
+                .columns
+                .iter()
+                .map(ProcWidgetColumn::config_name)
+                .collect::<Array>();
+
+            set_value(
+                doc,
+                "processes",
+                "columns",
+                columns.into(),
+                processes.columns == DEFAULT_COLUMNS,
+            )?;
+
+            if let Some((column, descending)) = processes.sort {
+                set_value(
+                    doc,
+                    "processes",
+                    "sort",
+                    column.config_name().into(),
+                    processes.is_default_sort,
+                )?;
+                set_value(
+                    doc,
+                    "processes",
+                    "sort_descending",
+                    descending.into(),
+                    processes.is_default_sort_order,
+                )?;
+            }
+        }
+
+        if let Some(disk_columns) = &self.disk_columns {
+            let columns = disk_columns
+                .iter()
+                .map(DiskColumn::config_name)
+                .collect::<Array>();
+
+            set_value(
+                doc,
+                "disk",
+                "columns",
+                columns.into(),
+                disk_columns == &DEFAULT_DISK_COLUMNS,
+            )?;
+        }
+
+        let legends = [
+            ("memory_legend", self.memory_legend),
+            ("network_legend", self.network_legend),
+        ];
+        for (key, legend) in legends {
+            if let Some(legend) = legend {
+                let position = legend.shown_position();
+
+                set_value(
+                    doc,
+                    "flags",
+                    key,
+                    position.map_or("none", |p| p.config_name()).into(),
+                    position == Some(LegendPosition::default()),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sets `key` in `table` to `value`, keeping any comments around an existing
+/// value. Nothing is added if `is_default` is set and the key isn't there yet.
+fn set_value(
+    doc: &mut DocumentMut, table: &str, key: &str, mut value: Value, is_default: bool,
+) -> anyhow::Result<()> {
+    let exists = doc.get(table).and_then(|t| t.get(key)).is_some();
+    if is_default && !exists {
+        return Ok(());
+    }
+
+    let Some(table_like) = doc
+        .entry(table)
+        .or_insert_with(toml_edit::table)
+        .as_table_like_mut()
+    else {
+        bail!("'{table}' in the config file is not a table.");
+    };
+
+    // Replace the value in place, as inserting would also replace the key and
+    // lose any comments above it.
+    match table_like.get_mut(key) {
+        Some(item) => {
+            if let Some(old) = item.as_value() {
+                *value.decor_mut() = old.decor().clone();
+            }
+            *item = value.into();
+        }
+        None => {
+            table_like.insert(key, value.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the state of the widget with `selected_id` in `widget_states`, or
+/// the first one if that widget isn't one of them.
+fn selected_or_first<T, S: BuildHasher>(
+    widget_states: &HashMap<u64, T, S>, selected_id: u64,
+) -> Option<&T> {
+    widget_states.get(&selected_id).or_else(|| {
+        widget_states
+            .keys()
+            .min()
+            .and_then(|id| widget_states.get(id))
+    })
+}
+
+/// Returns the selected process widget, or the first one if a process widget
+/// isn't selected.
+fn selected_process_widget(app: &App) -> Option<&ProcWidgetState> {
+    selected_or_first(
+        &app.states.proc_state.widget_states,
+        app.current_widget.widget_id,
+    )
+}
+
+fn parse_column(name: &str) -> Option<ProcWidgetColumn> {
+    use serde::de::{IntoDeserializer, value::Error};
+
+    ProcColumn::deserialize(IntoDeserializer::<Error>::into_deserializer(name))
+        .ok()
+        .map(|column| ProcWidgetColumn::from(&column))
+}
+
+/// Returns where the state file is stored.
+pub(crate) fn state_file_path() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .map(|dir| dir.join("bottom").join("state.toml"))
+}
+
+/// Reads the state file at `path`. A missing or invalid state file is ignored,
+/// as it only holds what was last shown.
+pub(crate) fn read_ui_state(path: &Path) -> Option<UiState> {
+    let contents = fs::read_to_string(path).ok()?;
+    toml_edit::de::from_str(&contents).ok()
+}
+
+fn write_ui_state(path: &Path, state: &UiState) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    write_atomically(path, &toml_edit::ser::to_string_pretty(state)?)?;
+
+    Ok(())
+}
+
+/// Writes the settings into the config at `config_path`, creating it if needed.
+fn write_config_settings(config_path: &Path, settings: &ConfigSettings) -> anyhow::Result<()> {
+    let mut doc = match fs::read_to_string(config_path) {
+        Ok(contents) => contents.parse::<DocumentMut>()?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => DocumentMut::new(),
+        Err(err) => return Err(err.into()),
+    };
+
+    settings.write_to(&mut doc)?;
+    write_atomically(config_path, &doc.to_string())?;
+
+    Ok(())
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it over
+/// `path`, so the file is never left half written. A symlinked file is replaced
+/// at its target, keeping the link.
+fn write_atomically(path: &Path, contents: &str) -> anyhow::Result<()> {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("{} is not a file.", path.display()))?;
+
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = fs::write(&temp_path, contents)
+        .and_then(|_| match fs::metadata(&path) {
+            Ok(metadata) => fs::set_permissions(&temp_path, metadata.permissions()),
+            Err(_) => Ok(()),
+        })
+        .and_then(|_| fs::rename(&temp_path, &path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    Ok(result?)
+}
+
+/// Saves the settings changed while running in `app` into the config at
+/// `config_path`. The UI state is also saved to the state file if settings are
+/// remembered, as it is only restored then.
+pub(crate) fn save_settings(app: &App, config_path: Option<&Path>) -> anyhow::Result<()> {
+    // The state file is written first, as writing the config causes a reload.
+    if app.app_config_fields.remember_settings {
+        let state_path =
+            state_file_path().context("bottom could not find where to save its state.")?;
+        write_ui_state(&state_path, &UiState::from_app(app)).with_context(|| {
+            format!(
+                "Could not write the state file at {}.",
+                state_path.display()
+            )
+        })?;
+    }
+
+    if let Some(config_path) = config_path {
+        write_config_settings(config_path, &ConfigSettings::from_app(app)).with_context(|| {
+            format!(
+                "Could not save settings to the config file at {}.",
+                config_path.display()
+            )
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+
+    use super::*;
+    use crate::{args::BottomArgs, options::config::Config};
+
+    fn create_app() -> App {
+        super::super::init_app(BottomArgs::parse_from(["btm"]), Config::default())
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn ui_state_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("state.toml");
+
+        let state = UiState {
+            selected_widget: Some(4),
+            expanded: true,
+            process_sort: Some(ProcessSort {
+                column: "Mem%".to_string(),
+                descending: false,
+            }),
+            basic_mem_percent: true,
+            legends: vec![LegendState {
+                widget: 2,
+                position: "bottom-left".to_string(),
+                hidden: true,
+            }],
+        };
+
+        assert_eq!(read_ui_state(&path), None);
+        write_ui_state(&path, &state).unwrap();
+        assert_eq!(read_ui_state(&path), Some(state));
+
+        fs::write(&path, "selected_widget = \"oops\"").unwrap();
+        assert_eq!(read_ui_state(&path), None);
+    }
+
+    #[test]
+    fn applies_ui_state() {
+        let mut app = create_app();
+        let proc_id = *app.states.proc_state.widget_states.keys().min().unwrap();
+        let mem_id = *app.states.mem_state.widget_states.keys().min().unwrap();
+
+        let state = UiState {
+            selected_widget: Some(proc_id),
+            expanded: true,
+            process_sort: Some(ProcessSort {
+                column: "pid".to_string(),
+                descending: true,
+            }),
+            basic_mem_percent: false,
+            legends: vec![
+                LegendState {
+                    widget: mem_id,
+                    position: "left".to_string(),
+                    hidden: true,
+                },
+                LegendState {
+                    widget: proc_id,
+                    position: "left".to_string(),
+                    hidden: true,
+                },
+            ],
+        };
+        state.apply_to(&mut app);
+
+        assert_eq!(app.current_widget.widget_id, proc_id);
+        assert!(app.is_expanded);
+        assert_eq!(
+            app.states.proc_state.widget_states[&proc_id].sort_column(),
+            Some((ProcWidgetColumn::PidOrCount, true))
+        );
+        assert_eq!(
+            app.states.mem_state.widget_states[&mem_id].legend,
+            GraphLegend {
+                position: LegendPosition::Left,
+                is_hidden: true,
+            }
+        );
+        assert!(UiState::from_app(&app).legends.contains(&state.legends[0]));
+        assert_eq!(
+            UiState::from_app(&app).process_sort,
+            Some(ProcessSort {
+                column: "PID".to_string(),
+                descending: true,
+            })
+        );
+
+        // Widgets that don't exist anymore are skipped.
+        UiState {
+            selected_widget: Some(9999),
+            ..Default::default()
+        }
+        .apply_to(&mut app);
+        assert_eq!(app.current_widget.widget_id, proc_id);
+    }
+
+    #[test]
+    fn writes_config_keeping_comments() {
+        let mut doc = r#"# My config.
+
+[flags]
+# Some comment.
+tree = false # Trailing comment.
+rate = "2s"
+"#
+        .parse::<DocumentMut>()
+        .unwrap();
+
+        let settings = ConfigSettings {
+            temperature_type: TemperatureType::Celsius,
+            processes: Some(ProcessSettings {
+                tree: true,
+                group_processes: false,
+                process_command: false,
+                process_memory_as_value: true,
+                columns: vec![ProcWidgetColumn::Cpu, ProcWidgetColumn::PidOrCount],
+                sort: Some((ProcWidgetColumn::PidOrCount, true)),
+                is_default_sort: false,
+                is_default_sort_order: false,
+            }),
+            disk_columns: Some(vec![DiskColumn::Mount, DiskColumn::FreePercent]),
+            memory_legend: Some(GraphLegend {
+                position: LegendPosition::Left,
+                is_hidden: true,
+            }),
+            network_legend: Some(GraphLegend::default()),
+        };
+        settings.write_to(&mut doc).unwrap();
+
+        assert_eq!(
+            doc.to_string(),
+            r#"# My config.
+
+[flags]
+# Some comment.
+tree = true # Trailing comment.
+rate = "2s"
+process_memory_as_value = true
+memory_legend = "none"
+
+[processes]
+columns = ["CPU%", "PID"]
+sort = "PID"
+sort_descending = true
+
+[disk]
+columns = ["Mount", "Free%"]
+"#
+        );
+    }
+
+    #[test]
+    fn sort_round_trip() {
+        let init = |text: &str| {
+            let config: Config = toml_edit::de::from_str(text).unwrap();
+            super::super::init_app(BottomArgs::parse_from(["btm"]), config)
+                .unwrap()
+                .0
+        };
+        let sort = |app: &App| selected_process_widget(app).unwrap().sort_column();
+
+        let app = init("[processes]\nsort = \"pid\"");
+        assert_eq!(sort(&app), Some((ProcWidgetColumn::PidOrCount, false)));
+
+        let app = init("[processes]\nsort = \"mem%\"\nsort_descending = false");
+        assert_eq!(sort(&app), Some((ProcWidgetColumn::Mem, false)));
+
+        let mut doc = DocumentMut::new();
+        ConfigSettings::from_app(&app).write_to(&mut doc).unwrap();
+        let config: Config = toml_edit::de::from_str(&doc.to_string()).unwrap();
+        let app = super::super::init_app(BottomArgs::parse_from(["btm"]), config)
+            .unwrap()
+            .0;
+        assert_eq!(sort(&app), Some((ProcWidgetColumn::Mem, false)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn writes_config_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target.toml");
+        let link = dir.path().join("bottom.toml");
+        fs::write(&target, "[flags]\nrate = \"2s\"\n").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let settings = ConfigSettings {
+            temperature_type: TemperatureType::Kelvin,
+            processes: None,
+            disk_columns: None,
+            memory_legend: None,
+            network_legend: None,
+        };
+        write_config_settings(&link, &settings).unwrap();
+
+        // The link is kept, and no temporary files are left behind.
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(
+            fs::read_to_string(&target).unwrap(),
+            "[flags]\nrate = \"2s\"\ntemperature_type = \"kelvin\"\n"
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn untouched_settings_are_not_written() {
+        let app = create_app();
+        let mut doc = DocumentMut::new();
+
+        ConfigSettings::from_app(&app).write_to(&mut doc).unwrap();
+        assert_eq!(doc.to_string(), "");
+    }
+}
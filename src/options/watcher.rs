@@ -0,0 +1,185 @@
+//! Watching the config file for changes, so it can be reloaded while running.
+//!
+//! On Linux this uses inotify on the config file's directory, as many editors
+//! save by writing a new file and renaming it over the old one. Elsewhere, or if
+//! inotify can't be set up, this falls back to checking the modified time.
+//!
+//! On Unix, a reload can also be asked for by sending bottom a SIGHUP.
+
+#[cfg(unix)]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Set when a SIGHUP is received, until the watcher sees it.
+#[cfg(unix)]
+static SIGHUP_RECEIVED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+extern "C" fn on_sighup(_: libc::c_int) {
+    SIGHUP_RECEIVED.store(true, Ordering::Relaxed);
+}
+
+/// Makes a SIGHUP reload the config instead of exiting. This replaces the
+/// handler set by `ctrlc`, so it must be called after that is set.
+#[cfg(unix)]
+pub(crate) fn reload_on_sighup() -> std::io::Result<()> {
+    // SAFETY: The handler only stores to an atomic, which is async-signal-safe,
+    // and the sigaction struct is fully initialized before it is used.
+    let result = unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = on_sighup as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+
+        libc::sigaction(libc::SIGHUP, &action, std::ptr::null_mut())
+    };
+
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+/// Watches a config file for changes.
+pub(crate) struct ConfigWatcher {
+    path: PathBuf,
+    last_modified: Option<SystemTime>,
+    #[cfg(target_os = "linux")]
+    inotify: Option<rustix::fd::OwnedFd>,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(target_os = "linux")]
+fn init_inotify(path: &Path) -> Option<rustix::fd::OwnedFd> {
+    use rustix::fs::inotify::{self, CreateFlags, WatchFlags};
+
+    let parent = path.parent().filter(|p| !p.as_os_str().is_empty())?;
+    let inotify = inotify::init(CreateFlags::NONBLOCK | CreateFlags::CLOEXEC).ok()?;
+
+    inotify::add_watch(
+        &inotify,
+        parent,
+        WatchFlags::CLOSE_WRITE | WatchFlags::MOVED_TO | WatchFlags::CREATE | WatchFlags::DELETE,
+    )
+    .ok()?;
+
+    Some(inotify)
+}
+
+impl ConfigWatcher {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            last_modified: modified_time(&path),
+            #[cfg(target_os = "linux")]
+            inotify: init_inotify(&path),
+            path,
+        }
+    }
+
+    /// Returns whether the config file has changed, or a SIGHUP was received,
+    /// since the last call.
+    pub(crate) fn has_changed(&mut self) -> bool {
+        let file_changed = self.file_has_changed();
+
+        #[cfg(unix)]
+        if SIGHUP_RECEIVED.swap(false, Ordering::Relaxed) {
+            return true;
+        }
+
+        file_changed
+    }
+
+    fn file_has_changed(&mut self) -> bool {
+        #[cfg(target_os = "linux")]
+        if let Some(inotify) = &self.inotify {
+            return self.read_inotify_events(inotify);
+        }
+
+        let modified = modified_time(&self.path);
+        if modified != self.last_modified {
+            self.last_modified = modified;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Drains any pending inotify events, returning whether any were for the
+    /// config file.
+    #[cfg(target_os = "linux")]
+    fn read_inotify_events(&self, inotify: &rustix::fd::OwnedFd) -> bool {
+        use std::{ffi::OsStr, mem::MaybeUninit, os::unix::ffi::OsStrExt};
+
+        use rustix::fs::inotify::Reader;
+
+        let file_name = self.path.file_name();
+        let mut buf = [MaybeUninit::uninit(); 1024];
+        let mut reader = Reader::new(inotify, &mut buf);
+        let mut changed = false;
+
+        // This stops once there are no more events, as the descriptor is non-blocking.
+        while let Ok(event) = reader.next() {
+            if let Some(name) = event.file_name() {
+                if Some(OsStr::from_bytes(name.to_bytes())) == file_name {
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::Mutex, thread, time::Duration};
+
+    use super::*;
+
+    /// SIGHUPs are seen by every watcher, so tests that check for changes can't
+    /// run at the same time.
+    static WATCHER_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn detects_changes() {
+        let _lock = WATCHER_LOCK.lock().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bottom.toml");
+        fs::write(&path, "[flags]\n").unwrap();
+
+        let mut watcher = ConfigWatcher::new(path.clone());
+        fs::write(dir.path().join("other.toml"), "").unwrap();
+        assert!(!watcher.has_changed());
+
+        // Make sure the modified time actually differs on coarse filesystems.
+        thread::sleep(Duration::from_millis(20));
+        fs::write(&path, "[flags]\nrate = \"2s\"\n").unwrap();
+        assert!(watcher.has_changed());
+        assert!(!watcher.has_changed());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reloads_on_sighup() {
+        let _lock = WATCHER_LOCK.lock().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bottom.toml");
+        fs::write(&path, "[flags]\n").unwrap();
+
+        let mut watcher = ConfigWatcher::new(path);
+        reload_on_sighup().unwrap();
+
+        // SAFETY: This only sends a signal to this process, which is handled above.
+        assert_eq!(unsafe { libc::raise(libc::SIGHUP) }, 0);
+        assert!(watcher.has_changed());
+        assert!(!watcher.has_changed());
+    }
+}
@@ -0,0 +1,201 @@
+//! Copying text to the clipboard with the OSC 52 escape sequence, which most
+//! terminals support even over SSH, or to a file otherwise.
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+/// What to copy from the selected row of a table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CopyTarget {
+    /// The PIDs of a process, or the name of the row in tables without PIDs.
+    #[default]
+    Pid,
+
+    /// All of the shown cells, separated by tabs.
+    Row,
+
+    /// The cell in the column being sorted by.
+    Cell,
+}
+
+impl std::str::FromStr for CopyTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pid" => Ok(CopyTarget::Pid),
+            "row" => Ok(CopyTarget::Row),
+            "cell" => Ok(CopyTarget::Cell),
+            _ => Err(format!(
+                "'{s}' is an invalid copy target, use one of: [pid, row, cell]."
+            )),
+        }
+    }
+}
+
+/// How copied text is delivered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CopyMethod {
+    /// Use OSC 52, unless the terminal is known not to support it.
+    #[default]
+    Auto,
+    Osc52,
+    File,
+}
+
+impl std::str::FromStr for CopyMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(CopyMethod::Auto),
+            "osc52" => Ok(CopyMethod::Osc52),
+            "file" => Ok(CopyMethod::File),
+            _ => Err(format!(
+                "'{s}' is an invalid copy method, use one of: [auto, osc52, file]."
+            )),
+        }
+    }
+}
+
+/// The settings for copying from tables.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClipboardSettings {
+    pub target: CopyTarget,
+    pub method: CopyMethod,
+
+    /// Where copied text is written when not using OSC 52.
+    pub file: PathBuf,
+}
+
+/// Returns where copied text is written by default when not using OSC 52.
+pub fn default_clipboard_file() -> PathBuf {
+    dirs::cache_dir()
+        .map(|dir| dir.join("bottom"))
+        .unwrap_or_else(std::env::temp_dir)
+        .join("clipboard.txt")
+}
+
+/// Whether a terminal with the `TERM` value `term` is known not to support
+/// OSC 52, such as the Linux console.
+fn lacks_osc52(term: Option<&str>) -> bool {
+    matches!(term, None | Some("" | "dumb" | "linux"))
+}
+
+/// Encodes `bytes` as standard base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (index, &byte)| {
+                group | (u32::from(byte) << (16 - index * 8))
+            });
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - index * 6)) as usize & 0x3f] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Returns the OSC 52 sequence that sets the clipboard to `text`.
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+/// Where copied text ended up.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CopyDestination {
+    Terminal,
+    File(PathBuf),
+}
+
+/// Copies `text` using `settings`, writing the OSC 52 sequence to `terminal`.
+pub fn copy_text(
+    settings: &ClipboardSettings, text: &str, terminal: &mut impl Write,
+) -> anyhow::Result<CopyDestination> {
+    let use_file = match settings.method {
+        CopyMethod::Auto => lacks_osc52(std::env::var("TERM").ok().as_deref()),
+        CopyMethod::Osc52 => false,
+        CopyMethod::File => true,
+    };
+
+    if use_file {
+        write_file(&settings.file, text).with_context(|| {
+            format!(
+                "Could not write the copied text to {}.",
+                settings.file.display()
+            )
+        })?;
+        Ok(CopyDestination::File(settings.file.clone()))
+    } else {
+        terminal
+            .write_all(osc52_sequence(text).as_bytes())
+            .and_then(|_| terminal.flush())
+            .context("Could not send the copied text to the terminal.")?;
+        Ok(CopyDestination::Terminal)
+    }
+}
+
+fn write_file(path: &Path, text: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encodes_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64("ü 1234".as_bytes()), "w7wgMTIzNA==");
+    }
+
+    #[test]
+    fn copies_with_osc52_or_file() {
+        assert_eq!(osc52_sequence("1234"), "\x1b]52;c;MTIzNA==\x07");
+        assert!(lacks_osc52(Some("linux")));
+        assert!(!lacks_osc52(Some("xterm-256color")));
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut settings = ClipboardSettings {
+            target: CopyTarget::Pid,
+            method: CopyMethod::Osc52,
+            file: dir.path().join("nested").join("clipboard.txt"),
+        };
+
+        let mut terminal = Vec::new();
+        let destination = copy_text(&settings, "1234", &mut terminal).unwrap();
+        assert_eq!(destination, CopyDestination::Terminal);
+        assert_eq!(terminal, b"\x1b]52;c;MTIzNA==\x07");
+
+        settings.method = CopyMethod::File;
+        let mut terminal = Vec::new();
+        let destination = copy_text(&settings, "1234", &mut terminal).unwrap();
+        assert_eq!(destination, CopyDestination::File(settings.file.clone()));
+        assert!(terminal.is_empty());
+        assert_eq!(fs::read_to_string(&settings.file).unwrap(), "1234");
+    }
+}
@@ -1,4 +1,7 @@
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    time::{Instant, SystemTime},
+};
 
 #[inline]
 pub(crate) const fn sort_partial_fn<T: PartialOrd>(is_descending: bool) -> fn(T, T) -> Ordering {
@@ -72,6 +75,24 @@ pub(crate) fn saturating_log10(value: f64) -> f64 {
     if value > 0.0 { value.log10() } else { 0.0 }
 }
 
+/// Returns the time of day that `time` was at in UTC, as `HH:MM:SS`.
+pub(crate) fn utc_time_of_day(time: Instant) -> String {
+    let wall = SystemTime::now()
+        .checked_sub(Instant::now().saturating_duration_since(time))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    humantime::format_rfc3339_seconds(wall).to_string()[11..19].to_string()
+}
+
+/// Returns `secs` since the Unix epoch as a date and time in UTC, as
+/// `YYYY-MM-DD HH:MM:SS`.
+#[cfg(target_os = "linux")]
+pub(crate) fn utc_date_time(secs: u64) -> String {
+    let time = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+
+    humantime::format_rfc3339_seconds(time).to_string()[..19].replacen('T', " ", 1)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -117,4 +138,11 @@ mod test {
         y.sort_by(|a, b| sort_partial_fn(true)(a, b));
         assert_eq!(y, vec![16.15, 15.0, 1.0, -1.0, -100.0, -100.0, -100.1]);
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_utc_date_time() {
+        assert_eq!(utc_date_time(0), "1970-01-01 00:00:00");
+        assert_eq!(utc_date_time(1_700_000_000), "2023-11-14 22:13:20");
+    }
 }
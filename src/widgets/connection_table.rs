@@ -0,0 +1,322 @@
+use std::{borrow::Cow, cmp::max, num::NonZeroU16};
+
+use crate::{
+    app::AppConfigFields,
+    canvas::components::data_table::{
+        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
+        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+    },
+    collection::connections::ConnectionHarvest,
+    options::config::style::Styles,
+    utils::{clipboard::CopyTarget, general::sort_partial_fn},
+};
+
+#[derive(Clone, Debug)]
+pub struct ConnectionWidgetData {
+    pub connection: ConnectionHarvest,
+}
+
+impl ConnectionWidgetData {
+    fn text(&self, column: &ConnectionColumn) -> String {
+        let connection = &self.connection;
+
+        match column {
+            ConnectionColumn::Protocol => connection.protocol.as_str().to_string(),
+            ConnectionColumn::Local => connection.local_address.clone(),
+            ConnectionColumn::Remote => connection.remote_address.clone(),
+            ConnectionColumn::State => connection.state.to_string(),
+            ConnectionColumn::Pid => match connection.pid {
+                Some(pid) => pid.to_string(),
+                None => "N/A".to_string(),
+            },
+            ConnectionColumn::Process => connection
+                .process_name
+                .clone()
+                .unwrap_or_else(|| "N/A".to_string()),
+            ConnectionColumn::User => match &connection.user {
+                Some(user) => user.to_string(),
+                None => "N/A".to_string(),
+            },
+        }
+    }
+
+    /// Whether any column contains every term in `terms`, which must be
+    /// lowercase.
+    fn matches(&self, terms: &[String]) -> bool {
+        let text = ConnectionColumn::ALL
+            .iter()
+            .map(|column| self.text(column).to_lowercase())
+            .collect::<Vec<_>>();
+
+        terms
+            .iter()
+            .all(|term| text.iter().any(|cell| cell.contains(term.as_str())))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionColumn {
+    Protocol,
+    Local,
+    Remote,
+    State,
+    Pid,
+    Process,
+    User,
+}
+
+impl ConnectionColumn {
+    const ALL: [ConnectionColumn; 7] = [
+        ConnectionColumn::Protocol,
+        ConnectionColumn::Local,
+        ConnectionColumn::Remote,
+        ConnectionColumn::State,
+        ConnectionColumn::Pid,
+        ConnectionColumn::Process,
+        ConnectionColumn::User,
+    ];
+}
+
+impl ColumnHeader for ConnectionColumn {
+    fn text(&self) -> Cow<'static, str> {
+        match self {
+            ConnectionColumn::Protocol => "Proto".into(),
+            ConnectionColumn::Local => "Local".into(),
+            ConnectionColumn::Remote => "Remote".into(),
+            ConnectionColumn::State => "State".into(),
+            ConnectionColumn::Pid => "PID".into(),
+            ConnectionColumn::Process => "Process".into(),
+            ConnectionColumn::User => "User".into(),
+        }
+    }
+}
+
+impl DataToCell<ConnectionColumn> for ConnectionWidgetData {
+    fn to_cell_text(
+        &self, column: &ConnectionColumn, _calculated_width: NonZeroU16,
+    ) -> Option<Cow<'static, str>> {
+        Some(self.text(column).into())
+    }
+
+    fn column_widths<C: DataTableColumn<ConnectionColumn>>(
+        data: &[ConnectionWidgetData], columns: &[C],
+    ) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        columns
+            .iter()
+            .map(|column| {
+                data.iter().fold(0, |acc, row| {
+                    max(acc, row.text(column.inner()).len() as u16)
+                })
+            })
+            .collect()
+    }
+}
+
+impl SortsRow for ConnectionColumn {
+    type DataType = ConnectionWidgetData;
+
+    fn sort_data(&self, data: &mut [Self::DataType], descending: bool) {
+        match self {
+            ConnectionColumn::Protocol => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(a.connection.protocol, b.connection.protocol)
+            }),
+            ConnectionColumn::Local => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(
+                    &a.connection.local_address,
+                    &b.connection.local_address,
+                )
+            }),
+            ConnectionColumn::Remote => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(
+                    &a.connection.remote_address,
+                    &b.connection.remote_address,
+                )
+            }),
+            ConnectionColumn::State => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(a.connection.state, b.connection.state)
+            }),
+            ConnectionColumn::Pid => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.connection.pid, b.connection.pid))
+            }
+            ConnectionColumn::Process => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(&a.connection.process_name, &b.connection.process_name)
+            }),
+            ConnectionColumn::User => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(&a.connection.user, &b.connection.user)
+            }),
+        }
+    }
+}
+
+fn create_column(column: ConnectionColumn) -> SortColumn<ConnectionColumn> {
+    match column {
+        ConnectionColumn::Local => SortColumn::soft(column, Some(0.3)),
+        ConnectionColumn::Remote => SortColumn::soft(column, Some(0.25)),
+        ConnectionColumn::Process => SortColumn::soft(column, Some(0.15)),
+        ConnectionColumn::User => SortColumn::soft(column, Some(0.1)),
+        ConnectionColumn::Protocol | ConnectionColumn::State | ConnectionColumn::Pid => {
+            SortColumn::new(column)
+        }
+    }
+}
+
+/// A table of network connections and the processes that own them.
+pub struct ConnectionTableWidgetState {
+    pub table: SortDataTable<ConnectionWidgetData, ConnectionColumn>,
+    pub force_update_data: bool,
+
+    /// The text that shown connections must match.
+    query: String,
+}
+
+impl ConnectionTableWidgetState {
+    pub(crate) fn new(config: &AppConfigFields, palette: &Styles) -> Self {
+        let columns = ConnectionColumn::ALL.map(create_column);
+
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: Some(" Connections ".into()),
+                table_gap: config.table_gap,
+                left_to_right: true,
+                is_basic: config.use_basic_mode,
+                show_table_scroll_position: config.show_table_scroll_position,
+                show_current_entry_when_unfocused: false,
+            },
+            sort_index: 0,
+            order: SortOrder::Ascending,
+        };
+
+        let styling = DataTableStyling::from_palette(palette);
+
+        Self {
+            table: SortDataTable::new_sortable(columns, props, styling),
+            force_update_data: false,
+            query: String::new(),
+        }
+    }
+
+    /// Forces an update of the data stored.
+    #[inline]
+    pub fn force_data_update(&mut self) {
+        self.force_update_data = true;
+    }
+
+    /// Keeps the query, sorting, and scroll position of `old`, the state of
+    /// this widget before the config was reloaded.
+    pub(crate) fn keep_runtime_state(&mut self, old: Self) {
+        self.set_query(&old.query);
+        self.table.keep_state_from(&old.table);
+    }
+
+    /// Returns the text that shown connections must match.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Only shows connections with every space-separated term in `query` in
+    /// one of their columns, ignoring case. An empty query shows every
+    /// connection.
+    pub fn set_query(&mut self, query: &str) {
+        self.query = query.trim().to_string();
+
+        self.table.props.title = Some(if self.query.is_empty() {
+            " Connections ".into()
+        } else {
+            format!(" Connections: {} ", self.query).into()
+        });
+        self.force_data_update();
+    }
+
+    /// Update the current table data.
+    pub fn set_table_data(&mut self, connections: &[ConnectionHarvest]) {
+        let terms = self
+            .query
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>();
+
+        let mut data = connections
+            .iter()
+            .map(|connection| ConnectionWidgetData {
+                connection: connection.clone(),
+            })
+            .filter(|row| row.matches(&terms))
+            .collect::<Vec<_>>();
+
+        // Keep rows that sort equally in a stable order between updates.
+        data.sort_by(|a, b| {
+            (a.connection.protocol, &a.connection.local_address)
+                .cmp(&(b.connection.protocol, &b.connection.local_address))
+        });
+        if let Some(column) = self.table.columns.get(self.table.sort_index()) {
+            column.sort_by(&mut data, self.table.order());
+        }
+        self.table.set_data(data);
+        self.force_update_data = false;
+    }
+
+    /// Returns the text to copy from the selected row.
+    pub fn copy_text(&self, target: CopyTarget) -> Option<String> {
+        match target {
+            CopyTarget::Pid => self
+                .table
+                .current_item()
+                .and_then(|row| row.connection.pid)
+                .map(|pid| pid.to_string()),
+            CopyTarget::Row => self.table.current_row_text(),
+            CopyTarget::Cell => self
+                .table
+                .current_cell_text(self.table.sort_index())
+                .map(Cow::into_owned),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::collection::connections::Protocol;
+
+    fn connection(
+        protocol: Protocol, local_address: &str, state: &'static str,
+    ) -> ConnectionHarvest {
+        ConnectionHarvest {
+            protocol,
+            local_address: local_address.to_string(),
+            remote_address: "*".to_string(),
+            state,
+            listening_port: None,
+            inode: 0,
+            uid: None,
+            user: None,
+            pid: Some(1),
+            process_name: Some("sshd".to_string()),
+        }
+    }
+
+    #[test]
+    fn filters_connections_with_queries() {
+        let connections = [
+            connection(Protocol::Tcp, "0.0.0.0:22", "LISTEN"),
+            connection(Protocol::Udp, "127.0.0.53:53", "UNCONN"),
+            connection(Protocol::Unix, "/run/systemd/notify", "UNCONN"),
+        ];
+
+        let mut state =
+            ConnectionTableWidgetState::new(&AppConfigFields::default(), &Styles::default());
+        state.set_table_data(&connections);
+        assert_eq!(state.table.shown_rows().1.len(), 3);
+
+        state.set_query("unconn UDP");
+        state.set_table_data(&connections);
+        let (_, rows) = state.table.shown_rows();
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].iter().any(|cell| cell == "127.0.0.53:53"));
+
+        state.set_query(" ");
+        assert_eq!(state.query(), "");
+    }
+}
@@ -172,6 +172,13 @@ impl CpuWidgetState {
         self.force_update_data = true;
     }
 
+    /// Keeps the zoom and selected legend row of `old`, the state of this
+    /// widget before the config was reloaded.
+    pub(crate) fn keep_runtime_state(&mut self, old: Self) {
+        self.current_display_time = old.current_display_time;
+        self.table.keep_position_from(&old.table);
+    }
+
     pub fn set_legend_data(&mut self, data: &[CpuData]) {
         self.table.set_data(
             std::iter::once(CpuWidgetTableData::All)
@@ -0,0 +1,41 @@
+use std::time::Instant;
+
+use super::GraphLegend;
+use crate::collection::custom::CustomMetric;
+
+pub struct CustomWidgetState {
+    /// The name of the custom metric shown.
+    pub metric: String,
+
+    /// The unit of the custom metric, if any.
+    pub unit: Option<String>,
+
+    /// The name of each value of the custom metric.
+    pub labels: Vec<String>,
+
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+    pub legend: GraphLegend,
+}
+
+impl CustomWidgetState {
+    pub fn init(
+        metric: &CustomMetric, current_display_time: u64, autohide_timer: Option<Instant>,
+    ) -> Self {
+        CustomWidgetState {
+            metric: metric.name.clone(),
+            unit: metric.unit.clone(),
+            labels: metric.labels.clone(),
+            current_display_time,
+            autohide_timer,
+            legend: GraphLegend::default(),
+        }
+    }
+
+    /// Keeps the zoom and legend of `old`, the state of this widget before the
+    /// config was reloaded.
+    pub(crate) fn keep_runtime_state(&mut self, old: Self) {
+        self.current_display_time = old.current_display_time;
+        self.legend = old.legend;
+    }
+}
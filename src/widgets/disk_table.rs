@@ -5,13 +5,13 @@ use serde::Deserialize;
 use crate::{
     app::{AppConfigFields, data::StoredData},
     canvas::components::data_table::{
-        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
-        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell,
+        PickableColumns, SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
     },
     options::config::style::Styles,
     utils::{
-        conversion::dec_bytes_per_second_string, data_units::get_decimal_bytes,
-        general::sort_partial_fn,
+        clipboard::CopyTarget, conversion::dec_bytes_per_second_string,
+        data_units::get_decimal_bytes, general::sort_partial_fn,
     },
 };
 
@@ -25,6 +25,8 @@ pub struct DiskWidgetData {
     pub summed_total_bytes: Option<u64>,
     pub io_read_rate_bytes: Option<u64>,
     pub io_write_rate_bytes: Option<u64>,
+    /// Whether reading this disk's usage timed out, such as with a dead network mount.
+    pub unresponsive: bool,
 }
 
 impl DiskWidgetData {
@@ -96,12 +98,11 @@ impl DiskWidgetData {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(
     feature = "generate_schema",
     derive(schemars::JsonSchema, strum::VariantArray)
 )]
-#[cfg_attr(test, derive(PartialEq, Eq))]
 pub enum DiskColumn {
     Disk,
     Mount,
@@ -138,6 +139,34 @@ impl<'de> Deserialize<'de> for DiskColumn {
 }
 
 impl DiskColumn {
+    /// Every column the disk widget can show.
+    const ALL: [DiskColumn; 9] = [
+        DiskColumn::Disk,
+        DiskColumn::Mount,
+        DiskColumn::Used,
+        DiskColumn::Free,
+        DiskColumn::Total,
+        DiskColumn::UsedPercent,
+        DiskColumn::FreePercent,
+        DiskColumn::IoRead,
+        DiskColumn::IoWrite,
+    ];
+
+    /// The column's name as used in the config file.
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            DiskColumn::Disk => "Disk",
+            DiskColumn::Mount => "Mount",
+            DiskColumn::Used => "Used",
+            DiskColumn::Free => "Free",
+            DiskColumn::Total => "Total",
+            DiskColumn::UsedPercent => "Used%",
+            DiskColumn::FreePercent => "Free%",
+            DiskColumn::IoRead => "R/s",
+            DiskColumn::IoWrite => "W/s",
+        }
+    }
+
     /// An ugly hack to generate the JSON schema.
     #[cfg(feature = "generate_schema")]
     pub fn get_schema_names(&self) -> &[&'static str] {
@@ -187,6 +216,15 @@ impl DataToCell<DiskColumn> for DiskWidgetData {
         let text = match column {
             DiskColumn::Disk => self.name.clone().into(),
             DiskColumn::Mount => self.mount_point.clone().into(),
+            DiskColumn::Used
+            | DiskColumn::Free
+            | DiskColumn::UsedPercent
+            | DiskColumn::FreePercent
+            | DiskColumn::Total
+                if self.unresponsive =>
+            {
+                "unresponsive".into()
+            }
             DiskColumn::Used => self.used_space(),
             DiskColumn::Free => self.free_space(),
             DiskColumn::UsedPercent => percent_string(self.used_percent()),
@@ -199,18 +237,22 @@ impl DataToCell<DiskColumn> for DiskWidgetData {
         Some(text)
     }
 
-    fn column_widths<C: DataTableColumn<DiskColumn>>(data: &[Self], _columns: &[C]) -> Vec<u16>
+    fn column_widths<C: DataTableColumn<DiskColumn>>(data: &[Self], columns: &[C]) -> Vec<u16>
     where
         Self: Sized,
     {
-        let mut widths = vec![0; 7];
-
-        data.iter().for_each(|row| {
-            widths[0] = max(widths[0], row.name.len() as u16);
-            widths[1] = max(widths[1], row.mount_point.len() as u16);
-        });
-
-        widths
+        columns
+            .iter()
+            .map(|column| match column.inner() {
+                DiskColumn::Disk => data
+                    .iter()
+                    .fold(0, |acc, row| max(acc, row.name.len() as u16)),
+                DiskColumn::Mount => data
+                    .iter()
+                    .fold(0, |acc, row| max(acc, row.mount_point.len() as u16)),
+                _ => 0,
+            })
+            .collect()
     }
 }
 
@@ -281,18 +323,17 @@ const fn create_column(column_type: &DiskColumn) -> SortColumn<DiskColumn> {
     }
 }
 
-const fn default_disk_columns() -> [SortColumn<DiskColumn>; 8] {
-    [
-        create_column(&DiskColumn::Disk),
-        create_column(&DiskColumn::Mount),
-        create_column(&DiskColumn::Used),
-        create_column(&DiskColumn::Free),
-        create_column(&DiskColumn::Total),
-        create_column(&DiskColumn::UsedPercent),
-        create_column(&DiskColumn::IoRead),
-        create_column(&DiskColumn::IoWrite),
-    ]
-}
+/// The columns shown if the config doesn't set them.
+pub(crate) const DEFAULT_DISK_COLUMNS: [DiskColumn; 8] = [
+    DiskColumn::Disk,
+    DiskColumn::Mount,
+    DiskColumn::Used,
+    DiskColumn::Free,
+    DiskColumn::Total,
+    DiskColumn::UsedPercent,
+    DiskColumn::IoRead,
+    DiskColumn::IoWrite,
+];
 
 impl DiskTableWidget {
     pub fn new(config: &AppConfigFields, palette: &Styles, columns: Option<&[DiskColumn]>) -> Self {
@@ -311,18 +352,15 @@ impl DiskTableWidget {
 
         let styling = DataTableStyling::from_palette(palette);
 
-        match columns {
-            Some(columns) => {
-                let columns = columns.iter().map(create_column).collect::<Vec<_>>();
-                Self {
-                    table: SortDataTable::new_sortable(columns, props, styling),
-                    force_update_data: false,
-                }
-            }
-            None => Self {
-                table: SortDataTable::new_sortable(default_disk_columns(), props, styling),
-                force_update_data: false,
-            },
+        let columns = columns
+            .unwrap_or(&DEFAULT_DISK_COLUMNS)
+            .iter()
+            .map(create_column)
+            .collect::<Vec<_>>();
+
+        Self {
+            table: SortDataTable::new_sortable(columns, props, styling),
+            force_update_data: false,
         }
     }
 
@@ -332,6 +370,12 @@ impl DiskTableWidget {
         self.force_update_data = true;
     }
 
+    /// Keeps the column widths, sorting, and scroll position of `old`, the
+    /// state of this widget before the config was reloaded.
+    pub(crate) fn keep_runtime_state(&mut self, old: Self) {
+        self.table.keep_state_from(&old.table);
+    }
+
     /// Update the current table data.
     pub fn set_table_data(&mut self, data: &StoredData) {
         let mut data = data.disk_harvest.clone();
@@ -343,8 +387,66 @@ impl DiskTableWidget {
         self.force_update_data = false;
     }
 
-    pub fn set_index(&mut self, index: usize) {
-        self.table.set_sort_index(index);
+    /// Sorts by `column`, or toggles the sort order if it is already sorted by.
+    pub fn select_column(&mut self, column: DiskColumn) {
+        self.table.select_column(&column);
         self.force_data_update();
     }
+
+    /// Returns the text to copy from the selected row. As there are no PIDs,
+    /// the disk name is copied instead.
+    pub fn copy_text(&self, target: CopyTarget) -> Option<String> {
+        match target {
+            CopyTarget::Pid => self.table.current_item().map(|row| row.name.clone()),
+            CopyTarget::Row => self.table.current_row_text(),
+            CopyTarget::Cell => self
+                .table
+                .current_cell_text(self.table.sort_index())
+                .map(Cow::into_owned),
+        }
+    }
+}
+
+impl PickableColumns for DiskTableWidget {
+    type Column = DiskColumn;
+
+    fn available_columns(&self) -> Vec<DiskColumn> {
+        DiskColumn::ALL.to_vec()
+    }
+
+    fn shown_columns(&self) -> Vec<DiskColumn> {
+        self.table
+            .columns
+            .iter()
+            .map(|column| *column.inner())
+            .collect()
+    }
+
+    fn set_shown_columns(&mut self, columns: Vec<DiskColumn>) {
+        self.table
+            .set_columns(columns.iter().map(create_column).collect());
+        self.force_data_update();
+    }
+
+    fn column_name(&self, column: DiskColumn) -> Cow<'static, str> {
+        column.config_name().into()
+    }
+
+    fn column_width(&self, column: DiskColumn) -> Option<u16> {
+        self.table
+            .columns
+            .iter()
+            .position(|c| *c.inner() == column)
+            .and_then(|index| self.table.column_width(index))
+    }
+
+    fn resize_column(&mut self, column: DiskColumn, delta: i16) {
+        if let Some(index) = self.table.columns.iter().position(|c| *c.inner() == column) {
+            self.table.resize_column(index, delta);
+        }
+    }
+
+    fn sort_by_column(&mut self, column: DiskColumn) {
+        self.select_column(column);
+    }
 }
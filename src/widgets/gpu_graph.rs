@@ -208,6 +208,13 @@ impl GpuWidgetState {
         self.force_update_data = true;
     }
 
+    /// Keeps the zoom and selected legend row of `old`, the state of this
+    /// widget before the config was reloaded.
+    pub(crate) fn keep_runtime_state(&mut self, old: Self) {
+        self.current_display_time = old.current_display_time;
+        self.table.keep_position_from(&old.table);
+    }
+
     /// Sets the legend data from GPU data.
     pub fn set_legend_data(&mut self, data: &[GpuData]) {
         self.table.set_data(
@@ -0,0 +1,25 @@
+use crate::canvas::components::time_graph::LegendPosition;
+
+/// Where a graph widget's legend is, and whether it is shown. Both can be
+/// changed while running.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GraphLegend {
+    pub position: LegendPosition,
+    pub is_hidden: bool,
+}
+
+impl GraphLegend {
+    /// Creates a legend at `position`, or a hidden one at the default position
+    /// if there is none.
+    pub fn new(position: Option<LegendPosition>) -> Self {
+        GraphLegend {
+            position: position.unwrap_or_default(),
+            is_hidden: position.is_none(),
+        }
+    }
+
+    /// Returns where the legend is drawn, or [`None`] if it is hidden.
+    pub fn shown_position(&self) -> Option<LegendPosition> {
+        (!self.is_hidden).then_some(self.position)
+    }
+}
@@ -1,19 +1,29 @@
 pub mod battery_info;
+pub mod connection_table;
 pub mod cpu_graph;
+pub mod custom_graph;
 pub mod disk_table;
 #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
 pub mod gpu_graph;
+pub mod graph_legend;
 pub mod mem_graph;
 pub mod network_graph;
+pub mod power_graph;
+pub mod process_log;
 pub mod process_table;
 pub mod temperature_table;
 
 pub use battery_info::*;
+pub use connection_table::*;
 pub use cpu_graph::*;
+pub use custom_graph::*;
 pub use disk_table::*;
 #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
 pub use gpu_graph::*;
+pub use graph_legend::*;
 pub use mem_graph::*;
 pub use network_graph::*;
+pub use power_graph::*;
+pub use process_log::*;
 pub use process_table::*;
 pub use temperature_table::*;
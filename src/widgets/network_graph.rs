@@ -1,9 +1,12 @@
 use std::time::Instant;
 
+use super::GraphLegend;
+
 pub struct NetWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
     pub height_cache: Option<NetWidgetHeightCache>,
+    pub legend: GraphLegend,
 }
 
 pub struct NetWidgetHeightCache {
@@ -13,11 +16,21 @@ pub struct NetWidgetHeightCache {
 }
 
 impl NetWidgetState {
-    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+    pub fn init(
+        current_display_time: u64, autohide_timer: Option<Instant>, legend: GraphLegend,
+    ) -> Self {
         NetWidgetState {
             current_display_time,
             autohide_timer,
             height_cache: None,
+            legend,
         }
     }
+
+    /// Keeps the zoom and legend of `old`, the state of this widget before the
+    /// config was reloaded.
+    pub(crate) fn keep_runtime_state(&mut self, old: Self) {
+        self.current_display_time = old.current_display_time;
+        self.legend = old.legend;
+    }
 }
@@ -0,0 +1,28 @@
+use std::time::Instant;
+
+use super::GraphLegend;
+
+pub struct PowerWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+    pub legend: GraphLegend,
+}
+
+impl PowerWidgetState {
+    pub fn init(
+        current_display_time: u64, autohide_timer: Option<Instant>, legend: GraphLegend,
+    ) -> Self {
+        PowerWidgetState {
+            current_display_time,
+            autohide_timer,
+            legend,
+        }
+    }
+
+    /// Keeps the zoom and legend of `old`, the state of this widget before the
+    /// config was reloaded.
+    pub(crate) fn keep_runtime_state(&mut self, old: Self) {
+        self.current_display_time = old.current_display_time;
+        self.legend = old.legend;
+    }
+}
@@ -0,0 +1,354 @@
+use std::{borrow::Cow, cmp::max, num::NonZeroU16, sync::Arc};
+
+use tui::widgets::Row;
+
+use crate::{
+    app::{
+        AppConfigFields,
+        data::{ProcessEvent, ProcessLog},
+    },
+    canvas::{
+        Painter,
+        components::data_table::{
+            ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell,
+            SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        },
+    },
+    options::config::style::Styles,
+    utils::{
+        clipboard::CopyTarget,
+        general::{sort_partial_fn, utc_time_of_day},
+    },
+    widgets::{
+        binary_byte_string, format_time,
+        query::{ProcessQuery, QueryOptions, parse_query},
+    },
+};
+
+#[derive(Clone, Debug)]
+pub struct ProcessLogWidgetData {
+    pub event: Arc<ProcessEvent>,
+
+    /// The time of day the event was noticed at.
+    pub time: String,
+}
+
+impl ProcessLogWidgetData {
+    fn parent(&self) -> String {
+        match self.event.process.parent_pid {
+            Some(parent_pid) => parent_pid.to_string(),
+            None => "N/A".to_string(),
+        }
+    }
+
+    fn user(&self) -> String {
+        match &self.event.process.user {
+            Some(user) => user.to_string(),
+            None => "N/A".to_string(),
+        }
+    }
+
+    fn text(&self, column: &ProcessLogColumn) -> String {
+        let process = &self.event.process;
+
+        match column {
+            ProcessLogColumn::Time => self.time.clone(),
+            ProcessLogColumn::Event => self.event.kind.as_str().to_string(),
+            ProcessLogColumn::Pid => process.pid.to_string(),
+            ProcessLogColumn::Parent => self.parent(),
+            ProcessLogColumn::Name => process.name.clone(),
+            ProcessLogColumn::User => self.user(),
+            ProcessLogColumn::Lifetime => format_time(process.time),
+            ProcessLogColumn::PeakCpu => format!("{:.1}%", process.cpu_usage_percent),
+            ProcessLogColumn::PeakMem => binary_byte_string(process.mem_usage),
+            ProcessLogColumn::Command => process.command.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessLogColumn {
+    Time,
+    Event,
+    Pid,
+    Parent,
+    Name,
+    User,
+    Lifetime,
+    PeakCpu,
+    PeakMem,
+    Command,
+}
+
+impl ColumnHeader for ProcessLogColumn {
+    fn text(&self) -> Cow<'static, str> {
+        match self {
+            ProcessLogColumn::Time => "Time".into(),
+            ProcessLogColumn::Event => "Event".into(),
+            ProcessLogColumn::Pid => "PID".into(),
+            ProcessLogColumn::Parent => "Parent".into(),
+            ProcessLogColumn::Name => "Name".into(),
+            ProcessLogColumn::User => "User".into(),
+            ProcessLogColumn::Lifetime => "Lifetime".into(),
+            ProcessLogColumn::PeakCpu => "Peak CPU%".into(),
+            ProcessLogColumn::PeakMem => "Peak Mem".into(),
+            ProcessLogColumn::Command => "Command".into(),
+        }
+    }
+}
+
+impl DataToCell<ProcessLogColumn> for ProcessLogWidgetData {
+    fn to_cell_text(
+        &self, column: &ProcessLogColumn, _calculated_width: NonZeroU16,
+    ) -> Option<Cow<'static, str>> {
+        Some(self.text(column).into())
+    }
+
+    #[inline(always)]
+    fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
+        if self.event.is_restarting() {
+            row.style(painter.styles.restarting_text_style)
+        } else {
+            row
+        }
+    }
+
+    fn column_widths<C: DataTableColumn<ProcessLogColumn>>(
+        data: &[ProcessLogWidgetData], columns: &[C],
+    ) -> Vec<u16>
+    where
+        Self: Sized,
+    {
+        columns
+            .iter()
+            .map(|column| {
+                data.iter().fold(0, |acc, row| {
+                    max(acc, row.text(column.inner()).len() as u16)
+                })
+            })
+            .collect()
+    }
+}
+
+impl SortsRow for ProcessLogColumn {
+    type DataType = ProcessLogWidgetData;
+
+    fn sort_data(&self, data: &mut [Self::DataType], descending: bool) {
+        match self {
+            ProcessLogColumn::Time => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.event.time, b.event.time))
+            }
+            ProcessLogColumn::Event => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.event.kind, b.event.kind))
+            }
+            ProcessLogColumn::Pid => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(a.event.process.pid, b.event.process.pid)
+            }),
+            ProcessLogColumn::Parent => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(a.event.process.parent_pid, b.event.process.parent_pid)
+            }),
+            ProcessLogColumn::Name => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(&a.event.process.name, &b.event.process.name)
+            }),
+            ProcessLogColumn::User => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(&a.event.process.user, &b.event.process.user)
+            }),
+            ProcessLogColumn::Lifetime => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(a.event.process.time, b.event.process.time)
+            }),
+            ProcessLogColumn::PeakCpu => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(
+                    a.event.process.cpu_usage_percent,
+                    b.event.process.cpu_usage_percent,
+                )
+            }),
+            ProcessLogColumn::PeakMem => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(a.event.process.mem_usage, b.event.process.mem_usage)
+            }),
+            ProcessLogColumn::Command => data.sort_by(|a, b| {
+                sort_partial_fn(descending)(&a.event.process.command, &b.event.process.command)
+            }),
+        }
+    }
+}
+
+fn create_column(column: ProcessLogColumn) -> SortColumn<ProcessLogColumn> {
+    match column {
+        ProcessLogColumn::Name => SortColumn::soft(column, Some(0.2)),
+        ProcessLogColumn::User => SortColumn::soft(column, Some(0.1)),
+        ProcessLogColumn::Command => SortColumn::soft(column, Some(0.5)),
+        ProcessLogColumn::Time => SortColumn::hard(column, 9).default_descending(),
+        ProcessLogColumn::Lifetime | ProcessLogColumn::PeakCpu | ProcessLogColumn::PeakMem => {
+            SortColumn::new(column).default_descending()
+        }
+        ProcessLogColumn::Event | ProcessLogColumn::Pid | ProcessLogColumn::Parent => {
+            SortColumn::new(column)
+        }
+    }
+}
+
+/// A table of processes that recently started or exited.
+pub struct ProcessLogWidgetState {
+    pub table: SortDataTable<ProcessLogWidgetData, ProcessLogColumn>,
+    pub force_update_data: bool,
+
+    /// The query that shown events must match, and the text it was parsed from.
+    query: Option<(String, ProcessQuery)>,
+}
+
+impl ProcessLogWidgetState {
+    pub(crate) fn new(config: &AppConfigFields, palette: &Styles) -> Self {
+        let columns = [
+            ProcessLogColumn::Time,
+            ProcessLogColumn::Event,
+            ProcessLogColumn::Pid,
+            ProcessLogColumn::Parent,
+            ProcessLogColumn::Name,
+            ProcessLogColumn::User,
+            ProcessLogColumn::Lifetime,
+            ProcessLogColumn::PeakCpu,
+            ProcessLogColumn::PeakMem,
+            ProcessLogColumn::Command,
+        ]
+        .map(create_column);
+
+        let props = SortDataTableProps {
+            inner: DataTableProps {
+                title: Some(" Process Log ".into()),
+                table_gap: config.table_gap,
+                left_to_right: true,
+                is_basic: config.use_basic_mode,
+                show_table_scroll_position: config.show_table_scroll_position,
+                show_current_entry_when_unfocused: false,
+            },
+            sort_index: 0,
+            order: SortOrder::Descending,
+        };
+
+        let styling = DataTableStyling::from_palette(palette);
+
+        Self {
+            table: SortDataTable::new_sortable(columns, props, styling),
+            force_update_data: false,
+            query: None,
+        }
+    }
+
+    /// Forces an update of the data stored.
+    #[inline]
+    pub fn force_data_update(&mut self) {
+        self.force_update_data = true;
+    }
+
+    /// Keeps the query, sorting, and scroll position of `old`, the state of
+    /// this widget before the config was reloaded.
+    pub(crate) fn keep_runtime_state(&mut self, old: Self) {
+        if let Some(query) = old.query() {
+            // The query was already parsed once, so this can't fail.
+            let _ = self.set_query(query);
+        }
+        self.table.keep_state_from(&old.table);
+    }
+
+    /// Returns the text of the query that shown events must match, if any.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_ref().map(|(text, _)| text.as_str())
+    }
+
+    /// Only shows the events of processes matching `query`, in the same syntax
+    /// as process searches. An empty query shows every event.
+    pub fn set_query(&mut self, query: &str) -> Result<(), String> {
+        let query = query.trim();
+        self.query = if query.is_empty() {
+            None
+        } else {
+            let parsed =
+                parse_query(query, &QueryOptions::default()).map_err(|err| err.to_string())?;
+            Some((query.to_string(), parsed))
+        };
+
+        self.table.props.title = Some(match self.query() {
+            Some(query) => format!(" Process Log: {query} ").into(),
+            None => " Process Log ".into(),
+        });
+        self.force_data_update();
+
+        Ok(())
+    }
+
+    /// Update the current table data.
+    pub fn set_table_data(&mut self, log: &ProcessLog) {
+        let mut data = log
+            .events()
+            .filter(|event| match &self.query {
+                Some((_, query)) => query.check(&event.process, false),
+                None => true,
+            })
+            .map(|event| ProcessLogWidgetData {
+                event: event.clone(),
+                time: utc_time_of_day(event.time),
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(column) = self.table.columns.get(self.table.sort_index()) {
+            column.sort_by(&mut data, self.table.order());
+        }
+        self.table.set_data(data);
+        self.force_update_data = false;
+    }
+
+    /// Returns the text to copy from the selected row.
+    pub fn copy_text(&self, target: CopyTarget) -> Option<String> {
+        match target {
+            CopyTarget::Pid => self
+                .table
+                .current_item()
+                .map(|row| row.event.process.pid.to_string()),
+            CopyTarget::Row => self.table.current_row_text(),
+            CopyTarget::Cell => self
+                .table
+                .current_cell_text(self.table.sort_index())
+                .map(Cow::into_owned),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::collection::processes::ProcessHarvest;
+
+    #[test]
+    fn filters_events_with_queries() {
+        let start = Instant::now();
+        let mut log = ProcessLog::default();
+        let process = |pid, name: &str| ProcessHarvest {
+            pid,
+            name: name.to_string(),
+            command: name.to_string(),
+            ..Default::default()
+        };
+
+        log.add(start, &[]);
+        log.add(
+            start + Duration::from_secs(1),
+            &[process(1, "cc1"), process(2, "cron")],
+        );
+
+        let mut state = ProcessLogWidgetState::new(&AppConfigFields::default(), &Styles::default());
+        state.set_table_data(&log);
+        assert_eq!(state.table.shown_rows().1.len(), 2);
+
+        state.set_query("cron").unwrap();
+        state.set_table_data(&log);
+        let (_, rows) = state.table.shown_rows();
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].iter().any(|cell| cell == "cron"));
+        assert!(state.set_query("cpu >").is_err());
+
+        state.set_query("").unwrap();
+        assert_eq!(state.query(), None);
+    }
+}
@@ -15,16 +15,15 @@ use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use sort_table::SortTableColumn;
 
 use crate::{
-    app::{
-        AppConfigFields, AppSearchState,
-        data::{ProcessData, StoredData},
-    },
+    app::{AppConfigFields, AppSearchState, data::ProcessData},
     canvas::components::data_table::{
         Column, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataTableProps,
-        DataTableStyling, SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        DataTableStyling, PickableColumns, SortColumn, SortDataTable, SortDataTableProps,
+        SortOrder, SortsRow,
     },
-    collection::processes::{Pid, ProcessHarvest},
+    collection::processes::{Pid, ProcessFields, ProcessHarvest},
     options::config::style::Styles,
+    utils::clipboard::CopyTarget,
     widgets::query::QueryOptions,
 };
 
@@ -155,6 +154,47 @@ fn make_column(column: ProcColumn) -> SortColumn<ProcColumn> {
         State => SortColumn::hard(State, 9),
         Time => SortColumn::new(Time),
         Priority => SortColumn::new(Priority).default_descending(),
+        #[cfg(target_os = "linux")]
+        Ports => SortColumn::soft(Ports, Some(0.1)),
+        #[cfg(target_os = "linux")]
+        TcpRxPerSecond => SortColumn::hard(TcpRxPerSecond, 8).default_descending(),
+        #[cfg(target_os = "linux")]
+        TcpTxPerSecond => SortColumn::hard(TcpTxPerSecond, 8).default_descending(),
+        #[cfg(target_os = "linux")]
+        TotalTcpRx => SortColumn::hard(TotalTcpRx, 8).default_descending(),
+        #[cfg(target_os = "linux")]
+        TotalTcpTx => SortColumn::hard(TotalTcpTx, 8).default_descending(),
+        Ppid => SortColumn::new(Ppid),
+        #[cfg(target_os = "linux")]
+        Threads => SortColumn::new(Threads).default_descending(),
+        #[cfg(target_os = "linux")]
+        Fds => SortColumn::hard(Fds, 6).default_descending(),
+        #[cfg(target_os = "linux")]
+        VoluntaryCtxSwitches => SortColumn::hard(VoluntaryCtxSwitches, 8).default_descending(),
+        #[cfg(target_os = "linux")]
+        InvoluntaryCtxSwitches => SortColumn::hard(InvoluntaryCtxSwitches, 8).default_descending(),
+        #[cfg(target_os = "linux")]
+        MinorFaultsPerSecond => SortColumn::hard(MinorFaultsPerSecond, 8).default_descending(),
+        #[cfg(target_os = "linux")]
+        MajorFaultsPerSecond => SortColumn::hard(MajorFaultsPerSecond, 8).default_descending(),
+        #[cfg(target_os = "linux")]
+        StartTime => SortColumn::hard(StartTime, 19),
+        #[cfg(target_os = "linux")]
+        Tty => SortColumn::hard(Tty, 6),
+        #[cfg(target_os = "linux")]
+        Exe => SortColumn::soft(Exe, Some(0.2)),
+        #[cfg(target_os = "linux")]
+        OomScore => SortColumn::new(OomScore).default_descending(),
+        #[cfg(target_os = "linux")]
+        LastCpu => SortColumn::new(LastCpu),
+        #[cfg(target_os = "linux")]
+        Pss => SortColumn::hard(Pss, 8).default_descending(),
+        #[cfg(target_os = "linux")]
+        Uss => SortColumn::hard(Uss, 8).default_descending(),
+        #[cfg(target_os = "linux")]
+        SharedMem => SortColumn::hard(SharedMem, 8).default_descending(),
+        #[cfg(target_os = "linux")]
+        Swap => SortColumn::hard(Swap, 8).default_descending(),
         #[cfg(unix)]
         Nice => SortColumn::new(Nice),
         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -193,12 +233,204 @@ pub enum ProcWidgetColumn {
     Priority,
     #[cfg(unix)]
     Nice,
+    #[cfg(target_os = "linux")]
+    Ports,
+    #[cfg(target_os = "linux")]
+    TcpRxPerSecond,
+    #[cfg(target_os = "linux")]
+    TcpTxPerSecond,
+    #[cfg(target_os = "linux")]
+    TotalTcpRx,
+    #[cfg(target_os = "linux")]
+    TotalTcpTx,
+    Ppid,
+    #[cfg(target_os = "linux")]
+    Threads,
+    #[cfg(target_os = "linux")]
+    Fds,
+    #[cfg(target_os = "linux")]
+    VoluntaryCtxSwitches,
+    #[cfg(target_os = "linux")]
+    InvoluntaryCtxSwitches,
+    #[cfg(target_os = "linux")]
+    MinorFaultsPerSecond,
+    #[cfg(target_os = "linux")]
+    MajorFaultsPerSecond,
+    #[cfg(target_os = "linux")]
+    StartTime,
+    #[cfg(target_os = "linux")]
+    Tty,
+    #[cfg(target_os = "linux")]
+    Exe,
+    #[cfg(target_os = "linux")]
+    OomScore,
+    #[cfg(target_os = "linux")]
+    LastCpu,
+    #[cfg(target_os = "linux")]
+    Pss,
+    #[cfg(target_os = "linux")]
+    Uss,
+    #[cfg(target_os = "linux")]
+    SharedMem,
+    #[cfg(target_os = "linux")]
+    Swap,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     GpuMem,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     GpuUtil,
 }
 
+impl ProcWidgetColumn {
+    /// The name used for this column in the config file.
+    pub(crate) fn config_name(&self) -> &'static str {
+        match self {
+            ProcWidgetColumn::PidOrCount => "PID",
+            ProcWidgetColumn::ProcNameOrCommand => "Name",
+            ProcWidgetColumn::Cpu => "CPU%",
+            ProcWidgetColumn::Mem => "Mem%",
+            ProcWidgetColumn::VirtualMem => "Virt",
+            ProcWidgetColumn::ReadPerSecond => "R/s",
+            ProcWidgetColumn::WritePerSecond => "W/s",
+            ProcWidgetColumn::TotalRead => "T.Read",
+            ProcWidgetColumn::TotalWrite => "T.Write",
+            ProcWidgetColumn::User => "User",
+            ProcWidgetColumn::State => "State",
+            ProcWidgetColumn::Time => "Time",
+            ProcWidgetColumn::Priority => "Priority",
+            #[cfg(unix)]
+            ProcWidgetColumn::Nice => "Nice",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Ports => "Ports",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::TcpRxPerSecond => "TCP Rx/s",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::TcpTxPerSecond => "TCP Tx/s",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::TotalTcpRx => "T.TCP Rx",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::TotalTcpTx => "T.TCP Tx",
+            ProcWidgetColumn::Ppid => "PPID",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Threads => "Threads",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Fds => "FDs",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::VoluntaryCtxSwitches => "VCSW",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::InvoluntaryCtxSwitches => "IVCSW",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::MinorFaultsPerSecond => "MinFlt/s",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::MajorFaultsPerSecond => "MajFlt/s",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::StartTime => "Start",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Tty => "TTY",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Exe => "Exe",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::OomScore => "OOM",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::LastCpu => "Last CPU",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Pss => "PSS",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Uss => "USS",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::SharedMem => "Shared",
+            #[cfg(target_os = "linux")]
+            ProcWidgetColumn::Swap => "Swap",
+            #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+            ProcWidgetColumn::GpuMem => "GMem%",
+            #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+            ProcWidgetColumn::GpuUtil => "GPU%",
+        }
+    }
+}
+
+/// The columns shown if none are set in the config.
+///
+/// Maybe add nice back as a default when I can figure out how to do the default
+/// configs better for Windows? As currently otherwise there's a mismatch.
+pub(crate) const DEFAULT_COLUMNS: [ProcWidgetColumn; 12] = [
+    ProcWidgetColumn::PidOrCount,
+    ProcWidgetColumn::ProcNameOrCommand,
+    ProcWidgetColumn::Cpu,
+    ProcWidgetColumn::Mem,
+    ProcWidgetColumn::ReadPerSecond,
+    ProcWidgetColumn::WritePerSecond,
+    ProcWidgetColumn::TotalRead,
+    ProcWidgetColumn::TotalWrite,
+    ProcWidgetColumn::User,
+    ProcWidgetColumn::State,
+    ProcWidgetColumn::Time,
+    ProcWidgetColumn::Priority,
+];
+
+/// Every column the process widget can show.
+const ALL_COLUMNS: &[ProcWidgetColumn] = &[
+    ProcWidgetColumn::PidOrCount,
+    ProcWidgetColumn::ProcNameOrCommand,
+    ProcWidgetColumn::Cpu,
+    ProcWidgetColumn::Mem,
+    ProcWidgetColumn::VirtualMem,
+    ProcWidgetColumn::ReadPerSecond,
+    ProcWidgetColumn::WritePerSecond,
+    ProcWidgetColumn::TotalRead,
+    ProcWidgetColumn::TotalWrite,
+    ProcWidgetColumn::User,
+    ProcWidgetColumn::State,
+    ProcWidgetColumn::Time,
+    ProcWidgetColumn::Priority,
+    #[cfg(unix)]
+    ProcWidgetColumn::Nice,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::Ports,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::TcpRxPerSecond,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::TcpTxPerSecond,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::TotalTcpRx,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::TotalTcpTx,
+    ProcWidgetColumn::Ppid,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::Threads,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::Fds,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::VoluntaryCtxSwitches,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::InvoluntaryCtxSwitches,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::MinorFaultsPerSecond,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::MajorFaultsPerSecond,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::StartTime,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::Tty,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::Exe,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::OomScore,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::LastCpu,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::Pss,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::Uss,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::SharedMem,
+    #[cfg(target_os = "linux")]
+    ProcWidgetColumn::Swap,
+    #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+    ProcWidgetColumn::GpuMem,
+    #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+    ProcWidgetColumn::GpuUtil,
+];
+
 // This is temporary. Switch back to `ProcColumn` later!
 
 pub struct ProcWidgetState {
@@ -272,110 +504,112 @@ impl ProcWidgetState {
         DataTable::new_sortable(columns, props, styling)
     }
 
-    pub(crate) fn new(
-        config: &AppConfigFields, mode: ProcWidgetMode, table_config: ProcTableConfig,
-        colours: &Styles, config_columns: &Option<IndexSet<ProcWidgetColumn>>,
-    ) -> Self {
-        let process_search_state = {
-            let mut pss = ProcessSearchState::default();
+    /// Creates the table's columns, given the current mode and whether the
+    /// command and memory values are shown.
+    fn build_columns(
+        columns: &[ProcWidgetColumn], mode: &ProcWidgetMode, is_command: bool, mem_as_values: bool,
+    ) -> Vec<SortColumn<ProcColumn>> {
+        use ProcColumn::*;
 
-            if table_config.is_case_sensitive {
-                // By default it's off.
-                pss.search_toggle_ignore_case();
-            }
-            if table_config.is_match_whole_word {
-                pss.search_toggle_whole_word();
-            }
-            if table_config.is_use_regex {
-                pss.search_toggle_regex();
-            }
+        let is_count = matches!(mode, ProcWidgetMode::Grouped);
 
-            pss
-        };
+        columns
+            .iter()
+            .map(|c| {
+                let col = match c {
+                    ProcWidgetColumn::PidOrCount => {
+                        if is_count {
+                            Count
+                        } else {
+                            Pid
+                        }
+                    }
+                    ProcWidgetColumn::ProcNameOrCommand => {
+                        if is_command {
+                            Command
+                        } else {
+                            Name
+                        }
+                    }
+                    ProcWidgetColumn::Cpu => CpuPercent,
+                    ProcWidgetColumn::Mem => {
+                        if mem_as_values {
+                            MemValue
+                        } else {
+                            MemPercent
+                        }
+                    }
+                    ProcWidgetColumn::VirtualMem => VirtualMem,
+                    ProcWidgetColumn::ReadPerSecond => ReadPerSecond,
+                    ProcWidgetColumn::WritePerSecond => WritePerSecond,
+                    ProcWidgetColumn::TotalRead => TotalRead,
+                    ProcWidgetColumn::TotalWrite => TotalWrite,
+                    ProcWidgetColumn::User => User,
+                    ProcWidgetColumn::State => State,
+                    ProcWidgetColumn::Time => Time,
+                    ProcWidgetColumn::Priority => Priority,
+                    #[cfg(unix)]
+                    ProcWidgetColumn::Nice => Nice,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::Ports => Ports,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::TcpRxPerSecond => TcpRxPerSecond,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::TcpTxPerSecond => TcpTxPerSecond,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::TotalTcpRx => TotalTcpRx,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::TotalTcpTx => TotalTcpTx,
+                    ProcWidgetColumn::Ppid => Ppid,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::Threads => Threads,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::Fds => Fds,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::VoluntaryCtxSwitches => VoluntaryCtxSwitches,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::InvoluntaryCtxSwitches => InvoluntaryCtxSwitches,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::MinorFaultsPerSecond => MinorFaultsPerSecond,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::MajorFaultsPerSecond => MajorFaultsPerSecond,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::StartTime => StartTime,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::Tty => Tty,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::Exe => Exe,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::OomScore => OomScore,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::LastCpu => LastCpu,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::Pss => Pss,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::Uss => Uss,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::SharedMem => SharedMem,
+                    #[cfg(target_os = "linux")]
+                    ProcWidgetColumn::Swap => Swap,
+                    #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+                    ProcWidgetColumn::GpuMem => {
+                        if mem_as_values {
+                            GpuMemValue
+                        } else {
+                            GpuMemPercent
+                        }
+                    }
+                    #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+                    ProcWidgetColumn::GpuUtil => GpuUtilPercent,
+                };
 
-        let columns: Vec<SortColumn<ProcColumn>> = {
-            use ProcColumn::*;
-
-            let is_count = matches!(mode, ProcWidgetMode::Grouped);
-            let is_command = table_config.is_command;
-            let mem_as_values = table_config.show_memory_as_values;
-
-            match config_columns {
-                Some(columns) if !columns.is_empty() => columns
-                    .into_iter()
-                    .map(|c| {
-                        let col = match c {
-                            ProcWidgetColumn::PidOrCount => {
-                                if is_count {
-                                    Count
-                                } else {
-                                    Pid
-                                }
-                            }
-                            ProcWidgetColumn::ProcNameOrCommand => {
-                                if is_command {
-                                    Command
-                                } else {
-                                    Name
-                                }
-                            }
-                            ProcWidgetColumn::Cpu => CpuPercent,
-                            ProcWidgetColumn::Mem => {
-                                if mem_as_values {
-                                    MemValue
-                                } else {
-                                    MemPercent
-                                }
-                            }
-                            ProcWidgetColumn::VirtualMem => VirtualMem,
-                            ProcWidgetColumn::ReadPerSecond => ReadPerSecond,
-                            ProcWidgetColumn::WritePerSecond => WritePerSecond,
-                            ProcWidgetColumn::TotalRead => TotalRead,
-                            ProcWidgetColumn::TotalWrite => TotalWrite,
-                            ProcWidgetColumn::User => User,
-                            ProcWidgetColumn::State => State,
-                            ProcWidgetColumn::Time => Time,
-                            ProcWidgetColumn::Priority => Priority,
-                            #[cfg(unix)]
-                            ProcWidgetColumn::Nice => Nice,
-                            #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
-                            ProcWidgetColumn::GpuMem => {
-                                if mem_as_values {
-                                    GpuMemValue
-                                } else {
-                                    GpuMemPercent
-                                }
-                            }
-                            #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
-                            ProcWidgetColumn::GpuUtil => GpuUtilPercent,
-                        };
-
-                        make_column(col)
-                    })
-                    .collect(),
-                _ => {
-                    let default_columns = [
-                        if is_count { Count } else { Pid },
-                        if is_command { Command } else { Name },
-                        CpuPercent,
-                        if mem_as_values { MemValue } else { MemPercent },
-                        ReadPerSecond,
-                        WritePerSecond,
-                        TotalRead,
-                        TotalWrite,
-                        User,
-                        State,
-                        Time,
-                        Priority,
-                        // Maybe add nice back as a default when I can figure out how to do the default configs better for Windows? As currently otherwise there's a mismatch.
-                    ];
-
-                    default_columns.into_iter().map(make_column).collect()
-                }
-            }
-        };
+                make_column(col)
+            })
+            .collect()
+    }
 
-        let column_mapping = columns
+    fn build_column_mapping(columns: &[SortColumn<ProcColumn>]) -> IndexSet<ProcWidgetColumn> {
+        columns
             .iter()
             .map(|col| {
                 use ProcColumn::*;
@@ -396,26 +630,107 @@ impl ProcWidgetState {
                     Priority => ProcWidgetColumn::Priority,
                     #[cfg(unix)]
                     Nice => ProcWidgetColumn::Nice,
+                    #[cfg(target_os = "linux")]
+                    Ports => ProcWidgetColumn::Ports,
+                    #[cfg(target_os = "linux")]
+                    TcpRxPerSecond => ProcWidgetColumn::TcpRxPerSecond,
+                    #[cfg(target_os = "linux")]
+                    TcpTxPerSecond => ProcWidgetColumn::TcpTxPerSecond,
+                    #[cfg(target_os = "linux")]
+                    TotalTcpRx => ProcWidgetColumn::TotalTcpRx,
+                    #[cfg(target_os = "linux")]
+                    TotalTcpTx => ProcWidgetColumn::TotalTcpTx,
+                    Ppid => ProcWidgetColumn::Ppid,
+                    #[cfg(target_os = "linux")]
+                    Threads => ProcWidgetColumn::Threads,
+                    #[cfg(target_os = "linux")]
+                    Fds => ProcWidgetColumn::Fds,
+                    #[cfg(target_os = "linux")]
+                    VoluntaryCtxSwitches => ProcWidgetColumn::VoluntaryCtxSwitches,
+                    #[cfg(target_os = "linux")]
+                    InvoluntaryCtxSwitches => ProcWidgetColumn::InvoluntaryCtxSwitches,
+                    #[cfg(target_os = "linux")]
+                    MinorFaultsPerSecond => ProcWidgetColumn::MinorFaultsPerSecond,
+                    #[cfg(target_os = "linux")]
+                    MajorFaultsPerSecond => ProcWidgetColumn::MajorFaultsPerSecond,
+                    #[cfg(target_os = "linux")]
+                    StartTime => ProcWidgetColumn::StartTime,
+                    #[cfg(target_os = "linux")]
+                    Tty => ProcWidgetColumn::Tty,
+                    #[cfg(target_os = "linux")]
+                    Exe => ProcWidgetColumn::Exe,
+                    #[cfg(target_os = "linux")]
+                    OomScore => ProcWidgetColumn::OomScore,
+                    #[cfg(target_os = "linux")]
+                    LastCpu => ProcWidgetColumn::LastCpu,
+                    #[cfg(target_os = "linux")]
+                    Pss => ProcWidgetColumn::Pss,
+                    #[cfg(target_os = "linux")]
+                    Uss => ProcWidgetColumn::Uss,
+                    #[cfg(target_os = "linux")]
+                    SharedMem => ProcWidgetColumn::SharedMem,
+                    #[cfg(target_os = "linux")]
+                    Swap => ProcWidgetColumn::Swap,
                     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
                     GpuMemValue | GpuMemPercent => ProcWidgetColumn::GpuMem,
                     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
                     GpuUtilPercent => ProcWidgetColumn::GpuUtil,
                 }
             })
-            .collect::<IndexSet<_>>();
+            .collect()
+    }
 
-        let (default_sort_index, default_sort_order) =
-            if matches!(mode, ProcWidgetMode::Tree { .. }) {
-                if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::PidOrCount) {
-                    (index, columns[index].default_order)
-                } else {
-                    (0, columns[0].default_order)
-                }
-            } else if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::Cpu) {
+    fn default_sort(
+        mode: &ProcWidgetMode, column_mapping: &IndexSet<ProcWidgetColumn>,
+        columns: &[SortColumn<ProcColumn>],
+    ) -> (usize, SortOrder) {
+        if matches!(mode, ProcWidgetMode::Tree { .. }) {
+            if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::PidOrCount) {
                 (index, columns[index].default_order)
             } else {
                 (0, columns[0].default_order)
-            };
+            }
+        } else if let Some(index) = column_mapping.get_index_of(&ProcWidgetColumn::Cpu) {
+            (index, columns[index].default_order)
+        } else {
+            (0, columns[0].default_order)
+        }
+    }
+
+    pub(crate) fn new(
+        config: &AppConfigFields, mode: ProcWidgetMode, table_config: ProcTableConfig,
+        colours: &Styles, config_columns: &Option<IndexSet<ProcWidgetColumn>>,
+    ) -> Self {
+        let process_search_state = {
+            let mut pss = ProcessSearchState::default();
+
+            if table_config.is_case_sensitive {
+                // By default it's off.
+                pss.search_toggle_ignore_case();
+            }
+            if table_config.is_match_whole_word {
+                pss.search_toggle_whole_word();
+            }
+            if table_config.is_use_regex {
+                pss.search_toggle_regex();
+            }
+
+            pss
+        };
+
+        let columns = match config_columns {
+            Some(columns) if !columns.is_empty() => columns.iter().copied().collect(),
+            _ => DEFAULT_COLUMNS.to_vec(),
+        };
+        let columns = Self::build_columns(
+            &columns,
+            &mode,
+            table_config.is_command,
+            table_config.show_memory_as_values,
+        );
+        let column_mapping = Self::build_column_mapping(&columns);
+        let (default_sort_index, default_sort_order) =
+            Self::default_sort(&mode, &column_mapping, &columns);
 
         let sort_table = Self::new_sort_table(config, colours);
         let table = Self::new_process_table(
@@ -476,24 +791,32 @@ impl ProcWidgetState {
         }
     }
 
+    /// Sets the table's title to say which earlier time it shows, if any.
+    pub fn set_shown_time(&mut self, label: Option<&str>) {
+        self.table.props.title = Some(match label {
+            Some(label) => format!(" Processes at {label} ").into(),
+            None => " Processes ".into(),
+        });
+    }
+
     /// Update the current table data.
     ///
     /// This function *only* updates the displayed process data. If there is a
     /// need to update the actual *stored* data, call it before this
     /// function.
-    pub fn set_table_data(&mut self, stored_data: &StoredData) {
+    pub fn set_table_data(&mut self, process_data: &ProcessData) {
         let data = match &self.mode {
             ProcWidgetMode::Grouped | ProcWidgetMode::Normal => {
-                self.get_normal_data(&stored_data.process_data.process_harvest)
+                self.get_normal_data(&process_data.process_harvest)
             }
-            ProcWidgetMode::Tree(collapse) => self.get_tree_data(collapse, stored_data),
+            ProcWidgetMode::Tree(collapse) => self.get_tree_data(collapse, process_data),
         };
         self.table.set_data(data);
         self.force_update_data = false;
     }
 
     fn get_tree_data(
-        &self, collapsed: &TreeCollapsed, stored_data: &StoredData,
+        &self, collapsed: &TreeCollapsed, process_data: &ProcessData,
     ) -> Vec<ProcWidgetData> {
         const BRANCH_END: char = '└';
         const BRANCH_SPLIT: char = '├';
@@ -509,11 +832,10 @@ impl ProcWidgetState {
             process_parent_mapping,
             orphan_pids,
             ..
-        } = &stored_data.process_data;
+        } = process_data;
 
         // Only keep a set of the kept PIDs.
-        let kept_pids = stored_data
-            .process_data
+        let kept_pids = process_data
             .process_harvest
             .iter()
             .filter_map(|(pid, process)| {
@@ -805,42 +1127,12 @@ impl ProcWidgetState {
                     id_pid_map.insert(id.clone(), vec![pid]);
                 }
 
+                let data = ProcWidgetData::from_data(process, is_using_command, is_mem_percent);
                 if let Some(pwd) = id_process_mapping.get_mut(id) {
-                    pwd.cpu_usage_percent += process.cpu_usage_percent;
-
-                    match &mut pwd.mem_usage {
-                        MemUsage::Percent(usage) => {
-                            *usage += process.mem_usage_percent;
-                        }
-                        MemUsage::Bytes(usage) => {
-                            *usage += process.mem_usage;
-                        }
-                    }
-
-                    pwd.rps += process.read_per_sec;
-                    pwd.wps += process.write_per_sec;
-                    pwd.total_read += process.total_read;
-                    pwd.total_write += process.total_write;
-                    pwd.time = pwd.time.max(process.time);
-                    #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
-                    {
-                        pwd.gpu_usage += process.gpu_util;
-                        match &mut pwd.gpu_mem_usage {
-                            MemUsage::Percent(usage) => {
-                                *usage += process.gpu_mem_percent;
-                            }
-                            MemUsage::Bytes(usage) => {
-                                *usage += process.gpu_mem;
-                            }
-                        }
-                    }
-
+                    pwd.add(&data);
                     pwd.num_similar += 1;
                 } else {
-                    id_process_mapping.insert(
-                        id,
-                        ProcWidgetData::from_data(process, is_using_command, is_mem_percent),
-                    );
+                    id_process_mapping.insert(id, data);
                 }
             }
 
@@ -953,6 +1245,145 @@ impl ProcWidgetState {
         }
     }
 
+    /// Returns the column being sorted by, and whether it is sorted in
+    /// descending order.
+    pub(crate) fn sort_column(&self) -> Option<(ProcWidgetColumn, bool)> {
+        self.column_mapping
+            .get_index(self.table.sort_index())
+            .map(|column| (*column, self.table.order() == SortOrder::Descending))
+    }
+
+    /// Sorts by `column` in the given order, if it is one of the shown columns.
+    pub(crate) fn set_sort_column(&mut self, column: ProcWidgetColumn, descending: bool) {
+        if let Some(index) = self.column_mapping.get_index_of(&column) {
+            if self
+                .table
+                .columns
+                .get(index)
+                .is_some_and(|col| !col.is_hidden)
+            {
+                self.table.set_sort_index(index);
+                self.table.set_order(if descending {
+                    SortOrder::Descending
+                } else {
+                    SortOrder::Ascending
+                });
+                self.force_data_update();
+            }
+        }
+    }
+
+    /// Sorts by `column` if it is one of the shown columns, in the given order or
+    /// otherwise the column's usual order.
+    pub(crate) fn set_sort_from_config(
+        &mut self, column: ProcWidgetColumn, descending: Option<bool>,
+    ) {
+        let Some(index) = self.column_mapping.get_index_of(&column) else {
+            return;
+        };
+
+        let descending = descending.unwrap_or_else(|| {
+            self.table
+                .columns
+                .get(index)
+                .is_some_and(|col| col.default_order == SortOrder::Descending)
+        });
+        self.set_sort_column(column, descending);
+    }
+
+    /// Whether the widget is sorted by its default column and order.
+    pub(crate) fn is_default_sort(&self) -> bool {
+        self.table.sort_index() == self.default_sort_index
+            && self.table.order() == self.default_sort_order
+    }
+
+    /// Whether the sort order is the sorted column's usual order.
+    pub(crate) fn is_default_sort_order(&self) -> bool {
+        self.table
+            .columns
+            .get(self.table.sort_index())
+            .is_none_or(|col| col.default_order == self.table.order())
+    }
+
+    /// Replaces the shown columns, such as after picking them at runtime. The
+    /// sort column and any widths set by the user are kept for columns that
+    /// are still shown.
+    pub(crate) fn set_columns(&mut self, columns: &[ProcWidgetColumn]) {
+        if columns.is_empty() {
+            return;
+        }
+
+        let is_command = self.is_using_command();
+        let mem_as_values = self.is_mem_values();
+        let sorted = self.sort_column();
+
+        let columns = Self::build_columns(columns, &self.mode, is_command, mem_as_values);
+        self.column_mapping = Self::build_column_mapping(&columns);
+        (self.default_sort_index, self.default_sort_order) =
+            Self::default_sort(&self.mode, &self.column_mapping, &columns);
+        self.table.set_columns(columns);
+
+        if !sorted.is_some_and(|(column, _)| self.column_mapping.contains(&column)) {
+            self.table.set_sort_index(self.default_sort_index);
+            self.table.set_order(self.default_sort_order);
+        }
+
+        if let ProcWidgetMode::Grouped = self.mode {
+            self.hide_column(ProcWidgetColumn::User);
+            self.hide_column(ProcWidgetColumn::State);
+        }
+
+        self.sort_table.set_data(self.column_text());
+        self.force_rerender_and_update();
+    }
+
+    /// Returns whether memory is shown as values rather than percentages.
+    fn is_mem_values(&self) -> bool {
+        self.table.columns.iter().any(|col| match col.inner() {
+            ProcColumn::MemValue => true,
+            #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
+            ProcColumn::GpuMemValue => true,
+            _ => false,
+        })
+    }
+
+    /// Returns the slower process details needed by the shown columns and the
+    /// current search.
+    pub(crate) fn process_fields(&self) -> ProcessFields {
+        let mut fields = self
+            .table
+            .columns
+            .iter()
+            .filter(|col| !col.is_hidden)
+            .fold(ProcessFields::default(), |fields, col| {
+                fields.union(col.inner().process_fields())
+            });
+
+        // Sorting or searching by detailed memory needs it for every process,
+        // not just the shown ones.
+        if let Some(sorted) = self.table.columns.get(self.table.sort_index()) {
+            fields.all_detailed_memory |= sorted.inner().process_fields().detailed_memory;
+        }
+        if let Some(query) = self.get_query() {
+            let query_fields = query.process_fields();
+            fields = fields.union(query_fields);
+            fields.all_detailed_memory |= query_fields.detailed_memory;
+        }
+
+        fields
+    }
+
+    /// Returns the PIDs of the processes shown as of the last draw, including
+    /// every process in a shown group.
+    pub(crate) fn visible_pids(&self) -> impl Iterator<Item = Pid> + '_ {
+        self.table.visible_data().iter().flat_map(|row| {
+            match self.id_pid_map.get(row.id.as_str()) {
+                Some(pids) if matches!(self.mode, ProcWidgetMode::Grouped) => pids.clone(),
+                _ => vec![row.pid],
+            }
+        })
+    }
+
     pub fn collapse_current_tree_branch_entry(&mut self) {
         if let ProcWidgetMode::Tree(collapsed) = &mut self.mode {
             if let Some(process) = self.table.current_item() {
@@ -1131,15 +1562,63 @@ impl ProcWidgetState {
         self.proc_search.search_state.walk_backward();
     }
 
+    /// Returns the text to copy from the selected process. Its PIDs are those of
+    /// every process in it if grouped.
+    pub fn copy_text(&self, target: CopyTarget) -> Option<String> {
+        match target {
+            CopyTarget::Pid => {
+                let current = self.table.current_item()?;
+                let pids = self
+                    .id_pid_map
+                    .get(current.id.as_str())
+                    .cloned()
+                    .unwrap_or_else(|| vec![current.pid]);
+
+                Some(pids.iter().map(|pid| pid.to_string()).join(" "))
+            }
+            CopyTarget::Row => self.table.current_row_text(),
+            CopyTarget::Cell => self
+                .table
+                .current_cell_text(self.table.sort_index())
+                .map(Cow::into_owned),
+        }
+    }
+
     /// Sets the [`ProcWidgetState`]'s current sort index to whatever was in the
     /// sort table if possible, then closes the sort table.
     pub(crate) fn use_sort_table_value(&mut self) {
-        self.table.set_sort_index(self.sort_table.current_index());
+        if let Some(index) = self
+            .table
+            .shown_column_index(self.sort_table.current_index())
+        {
+            self.table.set_sort_index(index);
+        }
 
         self.is_sort_open = false;
         self.force_rerender_and_update();
     }
 
+    /// Keeps the search, column widths, sorting, scroll position, and expanded
+    /// tree branches of `old`, the state of this widget before the config was
+    /// reloaded.
+    pub(crate) fn keep_runtime_state(&mut self, old: Self) {
+        self.proc_search = old.proc_search;
+
+        if let (ProcWidgetMode::Tree(collapsed), ProcWidgetMode::Tree(old_collapsed)) =
+            (&mut self.mode, old.mode)
+        {
+            // Only keep the branches if they were toggled from the same default.
+            if std::mem::discriminant(collapsed) == std::mem::discriminant(&old_collapsed) {
+                *collapsed = old_collapsed;
+            }
+        }
+
+        self.table.keep_state_from(&old.table);
+        self.sort_table.keep_position_from(&old.sort_table);
+        self.is_sort_open = old.is_sort_open;
+        self.force_rerender_and_update();
+    }
+
     #[cfg(test)]
     pub(crate) fn test_equality(&self, other: &Self) -> bool {
         self.mode == other.mode
@@ -1159,6 +1638,47 @@ impl ProcWidgetState {
     }
 }
 
+impl PickableColumns for ProcWidgetState {
+    type Column = ProcWidgetColumn;
+
+    fn available_columns(&self) -> Vec<ProcWidgetColumn> {
+        ALL_COLUMNS.to_vec()
+    }
+
+    fn shown_columns(&self) -> Vec<ProcWidgetColumn> {
+        self.column_mapping.iter().copied().collect()
+    }
+
+    fn set_shown_columns(&mut self, columns: Vec<ProcWidgetColumn>) {
+        self.set_columns(&columns);
+    }
+
+    fn column_name(&self, column: ProcWidgetColumn) -> Cow<'static, str> {
+        self.column_mapping
+            .get_index_of(&column)
+            .and_then(|index| self.table.columns.get(index))
+            .map(|col| col.inner().text())
+            .unwrap_or_else(|| column.config_name().into())
+    }
+
+    fn column_width(&self, column: ProcWidgetColumn) -> Option<u16> {
+        self.column_mapping
+            .get_index_of(&column)
+            .and_then(|index| self.table.column_width(index))
+    }
+
+    fn resize_column(&mut self, column: ProcWidgetColumn, delta: i16) {
+        if let Some(index) = self.column_mapping.get_index_of(&column) {
+            self.table.resize_column(index, delta);
+            self.force_rerender = true;
+        }
+    }
+
+    fn sort_by_column(&mut self, column: ProcWidgetColumn) {
+        self.select_column(column);
+    }
+}
+
 #[inline]
 fn sort_skip_pid_asc(column: &ProcColumn, data: &mut [ProcWidgetData], order: SortOrder) {
     let descending = matches!(order, SortOrder::Descending);
@@ -1177,7 +1697,7 @@ mod test {
     use super::*;
     #[cfg(target_os = "linux")]
     use crate::collection::processes::ProcessType;
-    use crate::widgets::MemUsage;
+    use crate::{canvas::components::data_table::ColumnPicker, widgets::MemUsage};
 
     #[test]
     fn test_proc_sort() {
@@ -1210,6 +1730,46 @@ mod test {
             #[cfg(unix)]
             nice: 0,
             priority: -20,
+            #[cfg(target_os = "linux")]
+            ports: Vec::new(),
+            #[cfg(target_os = "linux")]
+            tcp_rx: 0,
+            #[cfg(target_os = "linux")]
+            tcp_tx: 0,
+            #[cfg(target_os = "linux")]
+            total_tcp_rx: 0,
+            #[cfg(target_os = "linux")]
+            total_tcp_tx: 0,
+            #[cfg(target_os = "linux")]
+            num_threads: 1,
+            #[cfg(target_os = "linux")]
+            fds: None,
+            #[cfg(target_os = "linux")]
+            vcsw: None,
+            #[cfg(target_os = "linux")]
+            ivcsw: None,
+            #[cfg(target_os = "linux")]
+            minflt: 0,
+            #[cfg(target_os = "linux")]
+            majflt: 0,
+            #[cfg(target_os = "linux")]
+            start_time: 0,
+            #[cfg(target_os = "linux")]
+            tty: None,
+            #[cfg(target_os = "linux")]
+            exe: None,
+            #[cfg(target_os = "linux")]
+            oom_score: None,
+            #[cfg(target_os = "linux")]
+            last_cpu: 0,
+            #[cfg(target_os = "linux")]
+            pss: None,
+            #[cfg(target_os = "linux")]
+            uss: None,
+            #[cfg(target_os = "linux")]
+            shared_mem: None,
+            #[cfg(target_os = "linux")]
+            swap: None,
         };
 
         let b = ProcWidgetData {
@@ -1322,6 +1882,59 @@ mod test {
         assert_eq!(get_columns(&state.table), columns);
     }
 
+    #[test]
+    fn pick_columns() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::Mem,
+        ];
+        let mut state = init_default_state(&init_columns);
+        state.select_column(ProcWidgetColumn::Mem);
+        let sorted = state.sort_column();
+
+        // Show the CPU column, then move it to the front.
+        let cpu = state.picker_position(ProcWidgetColumn::Cpu, 0);
+        assert_eq!(state.toggle_picked_column(cpu), 3);
+        assert_eq!(state.move_picked_column(3, true), 2);
+        assert_eq!(state.move_picked_column(2, true), 1);
+        assert_eq!(state.move_picked_column(1, true), 0);
+        assert_eq!(
+            get_columns(&state.table),
+            vec![
+                ProcColumn::CpuPercent,
+                ProcColumn::Pid,
+                ProcColumn::Name,
+                ProcColumn::MemPercent,
+            ]
+        );
+        assert_eq!(state.sort_column(), sorted);
+
+        // Hiding the sorted column falls back to the default sort.
+        state.toggle_picked_column(3);
+        assert_eq!(
+            get_columns(&state.table),
+            vec![ProcColumn::CpuPercent, ProcColumn::Pid, ProcColumn::Name]
+        );
+        assert_eq!(state.sort_column(), Some((ProcWidgetColumn::Cpu, true)));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn sorting_by_detailed_memory_reads_every_process() {
+        let mut state = init_default_state(&[
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::Cpu,
+            ProcWidgetColumn::Pss,
+        ]);
+        let fields = state.process_fields();
+        assert!(fields.detailed_memory);
+        assert!(!fields.all_detailed_memory);
+
+        state.select_column(ProcWidgetColumn::Pss);
+        assert!(state.process_fields().all_detailed_memory);
+    }
+
     #[test]
     fn toggle_count_pid() {
         let init_columns = [
@@ -1692,6 +2305,45 @@ mod test {
             assert!(!expanded_by_default.is_collapsed(1));
         }
     }
+    #[test]
+    fn copy_selected_process() {
+        let init_columns = [
+            ProcWidgetColumn::PidOrCount,
+            ProcWidgetColumn::ProcNameOrCommand,
+            ProcWidgetColumn::State,
+        ];
+        let mut state = init_default_state(&init_columns);
+        assert_eq!(state.copy_text(CopyTarget::Pid), None);
+
+        let mut harvest: BTreeMap<Pid, ProcessHarvest> = BTreeMap::new();
+        for pid in [7, 9] {
+            harvest.insert(
+                pid,
+                ProcessHarvest {
+                    pid,
+                    name: "postgres".into(),
+                    process_state: ("Sleeping", 'S'),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let data = state.get_normal_data(&harvest);
+        state.table.set_data(data);
+        assert_eq!(state.copy_text(CopyTarget::Pid).as_deref(), Some("7"));
+        assert_eq!(
+            state.copy_text(CopyTarget::Row).as_deref(),
+            Some("7\tpostgres\tSleeping")
+        );
+        assert_eq!(state.copy_text(CopyTarget::Cell).as_deref(), Some("7"));
+
+        // Grouped processes copy every PID.
+        state.mode = ProcWidgetMode::Grouped;
+        let data = state.get_normal_data(&harvest);
+        state.table.set_data(data);
+        assert_eq!(state.copy_text(CopyTarget::Pid).as_deref(), Some("7 9"));
+    }
+
     #[cfg(target_os = "linux")]
     /// Sanity test to ensure kernel thread processes are toggled
     #[test]
@@ -1734,18 +2386,10 @@ mod test {
         tree_proc_data.process_harvest.insert(1, process_harvest);
         tree_proc_data.process_harvest.insert(2, k_process_harvest);
         tree_proc_data.orphan_pids = vec![1, 2];
-        let tree_stored_data = StoredData {
-            process_data: tree_proc_data,
-            ..Default::default()
-        };
-        let default_tree_results = state
-            .get_tree_data(&tree_collapsed, &tree_stored_data)
-            .len();
+        let default_tree_results = state.get_tree_data(&tree_collapsed, &tree_proc_data).len();
         assert!(default_tree_results == 2);
         state.toggle_k_thread();
-        let filtered_tree_results = state
-            .get_tree_data(&tree_collapsed, &tree_stored_data)
-            .len();
+        let filtered_tree_results = state.get_tree_data(&tree_collapsed, &tree_proc_data).len();
         assert!(filtered_tree_results == 1);
     }
 }
@@ -5,6 +5,7 @@ use serde::Deserialize;
 use super::{ProcWidgetColumn, ProcWidgetData};
 use crate::{
     canvas::components::data_table::{ColumnHeader, SortsRow},
+    collection::processes::ProcessFields,
     utils::general::sort_partial_fn,
 };
 
@@ -33,6 +34,47 @@ pub enum ProcColumn {
     #[cfg(unix)]
     Nice,
     Priority,
+    #[cfg(target_os = "linux")]
+    Ports,
+    #[cfg(target_os = "linux")]
+    TcpRxPerSecond,
+    #[cfg(target_os = "linux")]
+    TcpTxPerSecond,
+    #[cfg(target_os = "linux")]
+    TotalTcpRx,
+    #[cfg(target_os = "linux")]
+    TotalTcpTx,
+    Ppid,
+    #[cfg(target_os = "linux")]
+    Threads,
+    #[cfg(target_os = "linux")]
+    Fds,
+    #[cfg(target_os = "linux")]
+    VoluntaryCtxSwitches,
+    #[cfg(target_os = "linux")]
+    InvoluntaryCtxSwitches,
+    #[cfg(target_os = "linux")]
+    MinorFaultsPerSecond,
+    #[cfg(target_os = "linux")]
+    MajorFaultsPerSecond,
+    #[cfg(target_os = "linux")]
+    StartTime,
+    #[cfg(target_os = "linux")]
+    Tty,
+    #[cfg(target_os = "linux")]
+    Exe,
+    #[cfg(target_os = "linux")]
+    OomScore,
+    #[cfg(target_os = "linux")]
+    LastCpu,
+    #[cfg(target_os = "linux")]
+    Pss,
+    #[cfg(target_os = "linux")]
+    Uss,
+    #[cfg(target_os = "linux")]
+    SharedMem,
+    #[cfg(target_os = "linux")]
+    Swap,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     GpuMemValue,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -69,6 +111,96 @@ impl ProcColumn {
             #[cfg(unix)]
             ProcColumn::Nice => &["Nice"],
             ProcColumn::Priority => &["Priority"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::Ports => &["Ports"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpRxPerSecond => &["TCP Rx/s", "TcpRx"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpTxPerSecond => &["TCP Tx/s", "TcpTx"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpRx => &["T.TCP Rx", "TTcpRx"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpTx => &["T.TCP Tx", "TTcpTx"],
+            ProcColumn::Ppid => &["PPID"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::Threads => &["Threads"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::Fds => &["FDs"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::VoluntaryCtxSwitches => &["VCSW"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::InvoluntaryCtxSwitches => &["IVCSW"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::MinorFaultsPerSecond => &["MinFlt/s", "MinFlt"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::MajorFaultsPerSecond => &["MajFlt/s", "MajFlt"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::StartTime => &["Start"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::Tty => &["TTY"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::Exe => &["Exe"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::OomScore => &["OOM", "OOM Score"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => &["Last CPU", "LastCPU"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::Pss => &["PSS"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::Uss => &["USS"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::SharedMem => &["Shared"],
+            #[cfg(target_os = "linux")]
+            ProcColumn::Swap => &["Swap"],
+        }
+    }
+
+    /// Returns the slower process details needed to show this column.
+    pub(crate) fn process_fields(&self) -> ProcessFields {
+        match self {
+            #[cfg(target_os = "linux")]
+            ProcColumn::Ports => ProcessFields {
+                listening_ports: true,
+                ..Default::default()
+            },
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpRxPerSecond
+            | ProcColumn::TcpTxPerSecond
+            | ProcColumn::TotalTcpRx
+            | ProcColumn::TotalTcpTx => ProcessFields {
+                tcp_traffic: true,
+                ..Default::default()
+            },
+            #[cfg(target_os = "linux")]
+            ProcColumn::Fds => ProcessFields {
+                fd_count: true,
+                ..Default::default()
+            },
+            #[cfg(target_os = "linux")]
+            ProcColumn::VoluntaryCtxSwitches | ProcColumn::InvoluntaryCtxSwitches => {
+                ProcessFields {
+                    context_switches: true,
+                    ..Default::default()
+                }
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Exe => ProcessFields {
+                exe: true,
+                ..Default::default()
+            },
+            #[cfg(target_os = "linux")]
+            ProcColumn::OomScore => ProcessFields {
+                oom_score: true,
+                ..Default::default()
+            },
+            #[cfg(target_os = "linux")]
+            ProcColumn::Pss | ProcColumn::Uss | ProcColumn::SharedMem | ProcColumn::Swap => {
+                ProcessFields {
+                    detailed_memory: true,
+                    ..Default::default()
+                }
+            }
+            _ => ProcessFields::default(),
         }
     }
 }
@@ -94,6 +226,47 @@ impl ColumnHeader for ProcColumn {
             #[cfg(unix)]
             ProcColumn::Nice => "Nice",
             ProcColumn::Priority => "Priority",
+            #[cfg(target_os = "linux")]
+            ProcColumn::Ports => "Ports",
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpRxPerSecond => "TCP Rx/s",
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpTxPerSecond => "TCP Tx/s",
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpRx => "T.TCP Rx",
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpTx => "T.TCP Tx",
+            ProcColumn::Ppid => "PPID",
+            #[cfg(target_os = "linux")]
+            ProcColumn::Threads => "Threads",
+            #[cfg(target_os = "linux")]
+            ProcColumn::Fds => "FDs",
+            #[cfg(target_os = "linux")]
+            ProcColumn::VoluntaryCtxSwitches => "VCSW",
+            #[cfg(target_os = "linux")]
+            ProcColumn::InvoluntaryCtxSwitches => "IVCSW",
+            #[cfg(target_os = "linux")]
+            ProcColumn::MinorFaultsPerSecond => "MinFlt/s",
+            #[cfg(target_os = "linux")]
+            ProcColumn::MajorFaultsPerSecond => "MajFlt/s",
+            #[cfg(target_os = "linux")]
+            ProcColumn::StartTime => "Start",
+            #[cfg(target_os = "linux")]
+            ProcColumn::Tty => "TTY",
+            #[cfg(target_os = "linux")]
+            ProcColumn::Exe => "Exe",
+            #[cfg(target_os = "linux")]
+            ProcColumn::OomScore => "OOM",
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => "Last CPU",
+            #[cfg(target_os = "linux")]
+            ProcColumn::Pss => "PSS",
+            #[cfg(target_os = "linux")]
+            ProcColumn::Uss => "USS",
+            #[cfg(target_os = "linux")]
+            ProcColumn::SharedMem => "Shared",
+            #[cfg(target_os = "linux")]
+            ProcColumn::Swap => "Swap",
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
             ProcColumn::GpuMemValue => "GMem",
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -182,6 +355,89 @@ impl SortsRow for ProcColumn {
             ProcColumn::Priority => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.priority, b.priority));
             }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Ports => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.ports, &b.ports));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpRxPerSecond => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.tcp_rx, b.tcp_rx));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpTxPerSecond => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.tcp_tx, b.tcp_tx));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpRx => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.total_tcp_rx, b.total_tcp_rx));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpTx => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.total_tcp_tx, b.total_tcp_tx));
+            }
+            ProcColumn::Ppid => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.ppid, b.ppid));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Threads => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.num_threads, b.num_threads));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Fds => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.fds, b.fds));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::VoluntaryCtxSwitches => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.vcsw, b.vcsw));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::InvoluntaryCtxSwitches => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.ivcsw, b.ivcsw));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::MinorFaultsPerSecond => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.minflt, b.minflt));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::MajorFaultsPerSecond => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.majflt, b.majflt));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::StartTime => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.start_time, b.start_time));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Tty => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.tty, &b.tty));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Exe => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.exe, &b.exe));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::OomScore => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.oom_score, b.oom_score));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.last_cpu, b.last_cpu));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Pss => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.pss, b.pss));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Uss => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.uss, b.uss));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::SharedMem => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.shared_mem, b.shared_mem));
+            }
+            #[cfg(target_os = "linux")]
+            ProcColumn::Swap => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.swap, b.swap));
+            }
             #[cfg(unix)]
             ProcColumn::Nice => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(a.nice, b.nice));
@@ -224,6 +480,47 @@ impl<'de> Deserialize<'de> for ProcColumn {
             #[cfg(unix)]
             "nice" => Ok(ProcColumn::Nice),
             "priority" => Ok(ProcColumn::Priority),
+            #[cfg(target_os = "linux")]
+            "ports" => Ok(ProcColumn::Ports),
+            #[cfg(target_os = "linux")]
+            "tcp rx/s" | "tcprx" => Ok(ProcColumn::TcpRxPerSecond),
+            #[cfg(target_os = "linux")]
+            "tcp tx/s" | "tcptx" => Ok(ProcColumn::TcpTxPerSecond),
+            #[cfg(target_os = "linux")]
+            "t.tcp rx" | "ttcprx" => Ok(ProcColumn::TotalTcpRx),
+            #[cfg(target_os = "linux")]
+            "t.tcp tx" | "ttcptx" => Ok(ProcColumn::TotalTcpTx),
+            "ppid" => Ok(ProcColumn::Ppid),
+            #[cfg(target_os = "linux")]
+            "threads" => Ok(ProcColumn::Threads),
+            #[cfg(target_os = "linux")]
+            "fds" => Ok(ProcColumn::Fds),
+            #[cfg(target_os = "linux")]
+            "vcsw" => Ok(ProcColumn::VoluntaryCtxSwitches),
+            #[cfg(target_os = "linux")]
+            "ivcsw" => Ok(ProcColumn::InvoluntaryCtxSwitches),
+            #[cfg(target_os = "linux")]
+            "minflt/s" | "minflt" => Ok(ProcColumn::MinorFaultsPerSecond),
+            #[cfg(target_os = "linux")]
+            "majflt/s" | "majflt" => Ok(ProcColumn::MajorFaultsPerSecond),
+            #[cfg(target_os = "linux")]
+            "start" => Ok(ProcColumn::StartTime),
+            #[cfg(target_os = "linux")]
+            "tty" => Ok(ProcColumn::Tty),
+            #[cfg(target_os = "linux")]
+            "exe" => Ok(ProcColumn::Exe),
+            #[cfg(target_os = "linux")]
+            "oom" | "oom score" => Ok(ProcColumn::OomScore),
+            #[cfg(target_os = "linux")]
+            "last cpu" | "lastcpu" => Ok(ProcColumn::LastCpu),
+            #[cfg(target_os = "linux")]
+            "pss" => Ok(ProcColumn::Pss),
+            #[cfg(target_os = "linux")]
+            "uss" => Ok(ProcColumn::Uss),
+            #[cfg(target_os = "linux")]
+            "shared" => Ok(ProcColumn::SharedMem),
+            #[cfg(target_os = "linux")]
+            "swap" => Ok(ProcColumn::Swap),
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
             "gmem" | "gmem%" => Ok(ProcColumn::GpuMemPercent),
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -251,6 +548,47 @@ impl From<&ProcColumn> for ProcWidgetColumn {
             ProcColumn::User => ProcWidgetColumn::User,
             ProcColumn::Time => ProcWidgetColumn::Time,
             ProcColumn::Priority => ProcWidgetColumn::Priority,
+            #[cfg(target_os = "linux")]
+            ProcColumn::Ports => ProcWidgetColumn::Ports,
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpRxPerSecond => ProcWidgetColumn::TcpRxPerSecond,
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpTxPerSecond => ProcWidgetColumn::TcpTxPerSecond,
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpRx => ProcWidgetColumn::TotalTcpRx,
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpTx => ProcWidgetColumn::TotalTcpTx,
+            ProcColumn::Ppid => ProcWidgetColumn::Ppid,
+            #[cfg(target_os = "linux")]
+            ProcColumn::Threads => ProcWidgetColumn::Threads,
+            #[cfg(target_os = "linux")]
+            ProcColumn::Fds => ProcWidgetColumn::Fds,
+            #[cfg(target_os = "linux")]
+            ProcColumn::VoluntaryCtxSwitches => ProcWidgetColumn::VoluntaryCtxSwitches,
+            #[cfg(target_os = "linux")]
+            ProcColumn::InvoluntaryCtxSwitches => ProcWidgetColumn::InvoluntaryCtxSwitches,
+            #[cfg(target_os = "linux")]
+            ProcColumn::MinorFaultsPerSecond => ProcWidgetColumn::MinorFaultsPerSecond,
+            #[cfg(target_os = "linux")]
+            ProcColumn::MajorFaultsPerSecond => ProcWidgetColumn::MajorFaultsPerSecond,
+            #[cfg(target_os = "linux")]
+            ProcColumn::StartTime => ProcWidgetColumn::StartTime,
+            #[cfg(target_os = "linux")]
+            ProcColumn::Tty => ProcWidgetColumn::Tty,
+            #[cfg(target_os = "linux")]
+            ProcColumn::Exe => ProcWidgetColumn::Exe,
+            #[cfg(target_os = "linux")]
+            ProcColumn::OomScore => ProcWidgetColumn::OomScore,
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => ProcWidgetColumn::LastCpu,
+            #[cfg(target_os = "linux")]
+            ProcColumn::Pss => ProcWidgetColumn::Pss,
+            #[cfg(target_os = "linux")]
+            ProcColumn::Uss => ProcWidgetColumn::Uss,
+            #[cfg(target_os = "linux")]
+            ProcColumn::SharedMem => ProcWidgetColumn::SharedMem,
+            #[cfg(target_os = "linux")]
+            ProcColumn::Swap => ProcWidgetColumn::Swap,
             #[cfg(unix)]
             ProcColumn::Nice => ProcWidgetColumn::Nice,
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
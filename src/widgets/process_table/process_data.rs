@@ -11,6 +11,8 @@ use concat_string::concat_string;
 use tui::widgets::Row;
 
 use super::process_columns::ProcColumn;
+#[cfg(target_os = "linux")]
+use crate::utils::general::utc_date_time;
 use crate::{
     canvas::{
         Painter,
@@ -141,7 +143,7 @@ impl DurationExt for Duration {
     }
 }
 
-fn format_time(dur: Duration) -> String {
+pub(crate) fn format_time(dur: Duration) -> String {
     if dur.num_days() > 0 {
         format!(
             "{}d {}h {}m",
@@ -172,7 +174,7 @@ fn format_time(dur: Duration) -> String {
 /// variant. If the value is greater than a gibibyte, then it will return a
 /// decimal place.
 #[inline]
-fn binary_byte_string(value: u64) -> String {
+pub(crate) fn binary_byte_string(value: u64) -> String {
     let converted_values = get_binary_bytes(value);
     if value >= GIBI_LIMIT {
         format!("{:.1}{}", converted_values.0, converted_values.1)
@@ -195,7 +197,6 @@ fn dec_bytes_string(value: u64) -> String {
 #[derive(Clone)]
 pub struct ProcWidgetData {
     pub pid: Pid,
-    #[allow(dead_code)]
     pub ppid: Option<Pid>,
     pub id: Id,
     pub cpu_usage_percent: f32,
@@ -221,6 +222,81 @@ pub struct ProcWidgetData {
     #[cfg(unix)]
     pub nice: i32,
     pub priority: i32,
+    /// The ports listened on, sorted.
+    #[cfg(target_os = "linux")]
+    pub ports: Vec<u16>,
+    #[cfg(target_os = "linux")]
+    pub tcp_rx: u64,
+    #[cfg(target_os = "linux")]
+    pub tcp_tx: u64,
+    #[cfg(target_os = "linux")]
+    pub total_tcp_rx: u64,
+    #[cfg(target_os = "linux")]
+    pub total_tcp_tx: u64,
+    #[cfg(target_os = "linux")]
+    pub num_threads: u64,
+    #[cfg(target_os = "linux")]
+    pub fds: Option<u64>,
+    /// Voluntary context switches.
+    #[cfg(target_os = "linux")]
+    pub vcsw: Option<u64>,
+    /// Involuntary context switches.
+    #[cfg(target_os = "linux")]
+    pub ivcsw: Option<u64>,
+    /// Minor faults per second.
+    #[cfg(target_os = "linux")]
+    pub minflt: u64,
+    /// Major faults per second.
+    #[cfg(target_os = "linux")]
+    pub majflt: u64,
+    /// The start time in seconds since the Unix epoch.
+    #[cfg(target_os = "linux")]
+    pub start_time: u64,
+    #[cfg(target_os = "linux")]
+    pub tty: Option<String>,
+    #[cfg(target_os = "linux")]
+    pub exe: Option<String>,
+    #[cfg(target_os = "linux")]
+    pub oom_score: Option<u32>,
+    #[cfg(target_os = "linux")]
+    pub last_cpu: u32,
+    /// Proportional set size.
+    #[cfg(target_os = "linux")]
+    pub pss: Option<u64>,
+    /// Unique set size.
+    #[cfg(target_os = "linux")]
+    pub uss: Option<u64>,
+    #[cfg(target_os = "linux")]
+    pub shared_mem: Option<u64>,
+    #[cfg(target_os = "linux")]
+    pub swap: Option<u64>,
+}
+
+/// Adds two optional counts, treating a missing count as zero unless both are
+/// missing.
+#[cfg(target_os = "linux")]
+fn add_counts(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Returns an optional value as a string, or `N/A` if it is missing.
+#[cfg(target_os = "linux")]
+fn optional_string<T: ToString>(value: Option<T>) -> String {
+    value
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+/// Returns an optional amount of memory as a string, or `N/A` if it is
+/// missing.
+#[cfg(target_os = "linux")]
+fn optional_bytes_string(value: Option<u64>) -> String {
+    value
+        .map(binary_byte_string)
+        .unwrap_or_else(|| "N/A".to_string())
 }
 
 impl ProcWidgetData {
@@ -270,6 +346,46 @@ impl ProcWidgetData {
             #[cfg(unix)]
             nice: process.nice,
             priority: process.priority,
+            #[cfg(target_os = "linux")]
+            ports: process.listening_ports.clone(),
+            #[cfg(target_os = "linux")]
+            tcp_rx: process.tcp_rx_per_sec,
+            #[cfg(target_os = "linux")]
+            tcp_tx: process.tcp_tx_per_sec,
+            #[cfg(target_os = "linux")]
+            total_tcp_rx: process.total_tcp_rx,
+            #[cfg(target_os = "linux")]
+            total_tcp_tx: process.total_tcp_tx,
+            #[cfg(target_os = "linux")]
+            num_threads: process.num_threads,
+            #[cfg(target_os = "linux")]
+            fds: process.fd_count,
+            #[cfg(target_os = "linux")]
+            vcsw: process.voluntary_ctx_switches,
+            #[cfg(target_os = "linux")]
+            ivcsw: process.involuntary_ctx_switches,
+            #[cfg(target_os = "linux")]
+            minflt: process.minor_faults_per_sec,
+            #[cfg(target_os = "linux")]
+            majflt: process.major_faults_per_sec,
+            #[cfg(target_os = "linux")]
+            start_time: process.start_time,
+            #[cfg(target_os = "linux")]
+            tty: process.tty.clone(),
+            #[cfg(target_os = "linux")]
+            exe: process.exe.clone(),
+            #[cfg(target_os = "linux")]
+            oom_score: process.oom_score,
+            #[cfg(target_os = "linux")]
+            last_cpu: process.last_cpu,
+            #[cfg(target_os = "linux")]
+            pss: process.memory_details.map(|details| details.pss),
+            #[cfg(target_os = "linux")]
+            uss: process.memory_details.map(|details| details.uss),
+            #[cfg(target_os = "linux")]
+            shared_mem: process.memory_details.map(|details| details.shared),
+            #[cfg(target_os = "linux")]
+            swap: process.memory_details.map(|details| details.swap),
         }
     }
 
@@ -298,6 +414,28 @@ impl ProcWidgetData {
         self.total_read += other.total_read;
         self.total_write += other.total_write;
         self.time = self.time.max(other.time);
+        #[cfg(target_os = "linux")]
+        {
+            self.ports.extend_from_slice(&other.ports);
+            self.ports.sort_unstable();
+            self.ports.dedup();
+            self.tcp_rx += other.tcp_rx;
+            self.tcp_tx += other.tcp_tx;
+            self.total_tcp_rx += other.total_tcp_rx;
+            self.total_tcp_tx += other.total_tcp_tx;
+            self.num_threads += other.num_threads;
+            self.fds = add_counts(self.fds, other.fds);
+            self.vcsw = add_counts(self.vcsw, other.vcsw);
+            self.ivcsw = add_counts(self.ivcsw, other.ivcsw);
+            self.minflt += other.minflt;
+            self.majflt += other.majflt;
+            self.start_time = self.start_time.min(other.start_time);
+            self.oom_score = self.oom_score.max(other.oom_score);
+            self.pss = add_counts(self.pss, other.pss);
+            self.uss = add_counts(self.uss, other.uss);
+            self.shared_mem = add_counts(self.shared_mem, other.shared_mem);
+            self.swap = add_counts(self.swap, other.swap);
+        }
         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
         {
             self.gpu_mem_usage = match (&self.gpu_mem_usage, &other.gpu_mem_usage) {
@@ -312,9 +450,63 @@ impl ProcWidgetData {
         }
     }
 
+    /// Returns the ports listened on as a comma-separated list.
+    #[cfg(target_os = "linux")]
+    fn ports_string(&self) -> String {
+        self.ports
+            .iter()
+            .map(|port| port.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
     fn to_string(&self, column: &ProcColumn) -> String {
         match column {
+            ProcColumn::Ppid => self
+                .ppid
+                .map(|ppid| ppid.to_string())
+                .unwrap_or_else(|| "N/A".to_string()),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Threads => self.num_threads.to_string(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Fds => optional_string(self.fds),
+            #[cfg(target_os = "linux")]
+            ProcColumn::VoluntaryCtxSwitches => optional_string(self.vcsw),
+            #[cfg(target_os = "linux")]
+            ProcColumn::InvoluntaryCtxSwitches => optional_string(self.ivcsw),
+            #[cfg(target_os = "linux")]
+            ProcColumn::MinorFaultsPerSecond => self.minflt.to_string(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::MajorFaultsPerSecond => self.majflt.to_string(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::StartTime => utc_date_time(self.start_time),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Tty => self.tty.clone().unwrap_or_else(|| "?".to_string()),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Exe => optional_string(self.exe.as_ref()),
+            #[cfg(target_os = "linux")]
+            ProcColumn::OomScore => optional_string(self.oom_score),
+            #[cfg(target_os = "linux")]
+            ProcColumn::LastCpu => self.last_cpu.to_string(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Pss => optional_bytes_string(self.pss),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Uss => optional_bytes_string(self.uss),
+            #[cfg(target_os = "linux")]
+            ProcColumn::SharedMem => optional_bytes_string(self.shared_mem),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Swap => optional_bytes_string(self.swap),
             &ProcColumn::Priority => self.priority.to_string(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Ports => self.ports_string(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpRxPerSecond => dec_bytes_per_second_string(self.tcp_rx),
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpTxPerSecond => dec_bytes_per_second_string(self.tcp_tx),
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpRx => dec_bytes_string(self.total_tcp_rx),
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpTx => dec_bytes_string(self.total_tcp_tx),
             #[cfg(unix)]
             ProcColumn::Nice => self.nice.to_string(),
             ProcColumn::CpuPercent => format!("{:.1}%", self.cpu_usage_percent),
@@ -350,9 +542,36 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
         // TODO: Also maybe just pull in the to_string call but add a variable for the
         // differences.
         Some(match column {
+            ProcColumn::Ppid => self.to_string(column).into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Threads
+            | ProcColumn::Fds
+            | ProcColumn::VoluntaryCtxSwitches
+            | ProcColumn::InvoluntaryCtxSwitches
+            | ProcColumn::MinorFaultsPerSecond
+            | ProcColumn::MajorFaultsPerSecond
+            | ProcColumn::StartTime
+            | ProcColumn::Tty
+            | ProcColumn::Exe
+            | ProcColumn::OomScore
+            | ProcColumn::LastCpu
+            | ProcColumn::Pss
+            | ProcColumn::Uss
+            | ProcColumn::SharedMem
+            | ProcColumn::Swap => self.to_string(column).into(),
             #[cfg(unix)]
             ProcColumn::Nice => self.nice.to_string().into(),
             &ProcColumn::Priority => self.priority.to_string().into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::Ports => self.ports_string().into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpRxPerSecond => dec_bytes_per_second_string(self.tcp_rx).into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::TcpTxPerSecond => dec_bytes_per_second_string(self.tcp_tx).into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpRx => dec_bytes_string(self.total_tcp_rx).into(),
+            #[cfg(target_os = "linux")]
+            ProcColumn::TotalTcpTx => dec_bytes_string(self.total_tcp_tx).into(),
             ProcColumn::CpuPercent => format!("{:.1}%", self.cpu_usage_percent).into(),
             ProcColumn::MemValue | ProcColumn::MemPercent => self.mem_usage.to_string().into(),
             ProcColumn::VirtualMem => binary_byte_string(self.virtual_mem).into(),
@@ -385,6 +604,15 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
         })
     }
 
+    fn to_copy_text(&self, column: &ProcColumn) -> Option<Cow<'static, str>> {
+        // Leave out the tree prefix, and use the full state name.
+        match column {
+            ProcColumn::Name | ProcColumn::Command => Some(self.id.as_str().to_string().into()),
+            ProcColumn::State => Some(self.process_state.into()),
+            _ => self.to_cell_text(column, NonZeroU16::MAX),
+        }
+    }
+
     #[cfg(target_os = "linux")]
     #[inline(always)]
     fn style_cell(&self, column: &ProcColumn, painter: &Painter) -> Option<tui::style::Style> {
@@ -18,7 +18,10 @@ use or::Or;
 use prefix::Prefix;
 use regex::Regex;
 
-use crate::{collection::processes::ProcessHarvest, multi_eq_ignore_ascii_case};
+use crate::{
+    collection::processes::{ProcessFields, ProcessHarvest},
+    multi_eq_ignore_ascii_case,
+};
 
 const DELIMITER_LIST: [char; 6] = ['=', '>', '<', '(', ')', '\"'];
 const COMPARISON_LIST: [&str; 3] = [">", "=", "<"];
@@ -148,6 +151,15 @@ impl ProcessQuery {
             .iter()
             .all(|ok| ok.check(process, is_using_command))
     }
+
+    /// Returns the slower process details needed to check this query.
+    pub(crate) fn process_fields(&self) -> ProcessFields {
+        self.query
+            .iter()
+            .fold(ProcessFields::default(), |fields, or| {
+                fields.union(or.process_fields())
+            })
+    }
 }
 
 #[derive(Debug)]
@@ -167,6 +179,45 @@ enum PrefixType {
     #[cfg(unix)]
     Nice,
     Priority,
+    #[cfg(target_os = "linux")]
+    TcpRxPerSecond,
+    #[cfg(target_os = "linux")]
+    TcpTxPerSecond,
+    #[cfg(target_os = "linux")]
+    TotalTcpRx,
+    #[cfg(target_os = "linux")]
+    TotalTcpTx,
+    Ppid,
+    #[cfg(target_os = "linux")]
+    Threads,
+    #[cfg(target_os = "linux")]
+    Fds,
+    #[cfg(target_os = "linux")]
+    VoluntaryCtxSwitches,
+    #[cfg(target_os = "linux")]
+    InvoluntaryCtxSwitches,
+    #[cfg(target_os = "linux")]
+    MinorFaultsPerSecond,
+    #[cfg(target_os = "linux")]
+    MajorFaultsPerSecond,
+    #[cfg(target_os = "linux")]
+    StartTime,
+    #[cfg(target_os = "linux")]
+    Tty,
+    #[cfg(target_os = "linux")]
+    Exe,
+    #[cfg(target_os = "linux")]
+    OomScore,
+    #[cfg(target_os = "linux")]
+    LastCpu,
+    #[cfg(target_os = "linux")]
+    Pss,
+    #[cfg(target_os = "linux")]
+    Uss,
+    #[cfg(target_os = "linux")]
+    SharedMem,
+    #[cfg(target_os = "linux")]
+    Swap,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     GpuPercentage,
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -214,6 +265,50 @@ impl std::str::FromStr for PrefixType {
             }
         } else if multi_eq_ignore_ascii_case!(s, "priority") {
             result = Priority;
+        } else if multi_eq_ignore_ascii_case!(s, "ppid") {
+            result = Ppid;
+        }
+        #[cfg(target_os = "linux")]
+        {
+            if multi_eq_ignore_ascii_case!(s, "tcprx" | "tcprx/s") {
+                result = TcpRxPerSecond;
+            } else if multi_eq_ignore_ascii_case!(s, "tcptx" | "tcptx/s") {
+                result = TcpTxPerSecond;
+            } else if multi_eq_ignore_ascii_case!(s, "ttcprx") {
+                result = TotalTcpRx;
+            } else if multi_eq_ignore_ascii_case!(s, "ttcptx") {
+                result = TotalTcpTx;
+            } else if multi_eq_ignore_ascii_case!(s, "threads") {
+                result = Threads;
+            } else if multi_eq_ignore_ascii_case!(s, "fds") {
+                result = Fds;
+            } else if multi_eq_ignore_ascii_case!(s, "vcsw") {
+                result = VoluntaryCtxSwitches;
+            } else if multi_eq_ignore_ascii_case!(s, "ivcsw") {
+                result = InvoluntaryCtxSwitches;
+            } else if multi_eq_ignore_ascii_case!(s, "minflt" | "minflt/s") {
+                result = MinorFaultsPerSecond;
+            } else if multi_eq_ignore_ascii_case!(s, "majflt" | "majflt/s") {
+                result = MajorFaultsPerSecond;
+            } else if multi_eq_ignore_ascii_case!(s, "start") {
+                result = StartTime;
+            } else if multi_eq_ignore_ascii_case!(s, "tty") {
+                result = Tty;
+            } else if multi_eq_ignore_ascii_case!(s, "exe") {
+                result = Exe;
+            } else if multi_eq_ignore_ascii_case!(s, "oom") {
+                result = OomScore;
+            } else if multi_eq_ignore_ascii_case!(s, "lastcpu") {
+                result = LastCpu;
+            } else if multi_eq_ignore_ascii_case!(s, "pss") {
+                result = Pss;
+            } else if multi_eq_ignore_ascii_case!(s, "uss") {
+                result = Uss;
+            } else if multi_eq_ignore_ascii_case!(s, "shared") {
+                result = SharedMem;
+            } else if multi_eq_ignore_ascii_case!(s, "swap") {
+                result = Swap;
+            }
         }
         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
         {
@@ -229,6 +324,18 @@ impl std::str::FromStr for PrefixType {
     }
 }
 
+impl PrefixType {
+    /// Whether this prefix matches text rather than comparing a number.
+    fn is_string(&self) -> bool {
+        match self {
+            PrefixType::Pid | PrefixType::Ppid | PrefixType::State | PrefixType::User => true,
+            #[cfg(target_os = "linux")]
+            PrefixType::StartTime | PrefixType::Tty | PrefixType::Exe => true,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug)]
 enum QueryComparison {
     Equal,
@@ -848,6 +955,116 @@ mod tests {
         parse_query_no_options("gpu% = \"heck\"").unwrap_err();
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn tcp_traffic_queries() {
+        let rx = parse_query_no_options("tcprx > 1 mb").unwrap();
+        let total_tx = parse_query_no_options("a or ttcptx >= 10 kb").unwrap();
+
+        let mut process = simple_process("test");
+        process.tcp_rx_per_sec = 2_000_000;
+        process.total_tcp_tx = 5_000;
+
+        assert!(rx.check(&process, false));
+        assert!(!total_tx.check(&process, false));
+        assert!(rx.process_fields().tcp_traffic);
+        assert!(total_tx.process_fields().tcp_traffic);
+        assert!(
+            !parse_query_no_options("cpu > 5")
+                .unwrap()
+                .process_fields()
+                .tcp_traffic
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn proc_detail_queries() {
+        let mut process = simple_process("test");
+        process.parent_pid = Some(1);
+        process.num_threads = 8;
+        process.fd_count = Some(100);
+        process.voluntary_ctx_switches = Some(5000);
+        process.major_faults_per_sec = 3;
+        process.start_time = 1_700_000_000;
+        process.tty = Some("pts/3".into());
+        process.exe = Some("/usr/bin/test".into());
+        process.oom_score = Some(667);
+        process.last_cpu = 2;
+
+        for query in [
+            "ppid = 1",
+            "threads > 4",
+            "fds >= 100",
+            "vcsw > 1000",
+            "majflt/s = 3",
+            "start = 2023-11-14",
+            "tty = pts",
+            "exe = /usr/bin",
+            "oom > 500",
+            "lastcpu = 2",
+        ] {
+            assert!(
+                parse_query_no_options(query)
+                    .unwrap()
+                    .check(&process, false),
+                "{query} should match"
+            );
+        }
+
+        // Details that weren't collected never match.
+        assert!(
+            !parse_query_no_options("ivcsw >= 0")
+                .unwrap()
+                .check(&process, false)
+        );
+        assert!(
+            !parse_query_no_options("tty = pts/4")
+                .unwrap()
+                .check(&process, false)
+        );
+
+        let fields = parse_query_no_options("fds > 10 or (vcsw > 1 and exe = test) or oom > 1")
+            .unwrap()
+            .process_fields();
+        assert!(fields.fd_count && fields.context_switches && fields.exe && fields.oom_score);
+        assert_eq!(
+            parse_query_no_options("threads > 1 and start = 2023")
+                .unwrap()
+                .process_fields(),
+            ProcessFields::default()
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn detailed_memory_queries() {
+        use crate::collection::processes::MemoryDetails;
+
+        let pss = parse_query_no_options("pss > 100 mib").unwrap();
+        let swap = parse_query_no_options("a or swap >= 1 gib").unwrap();
+        assert!(pss.process_fields().detailed_memory);
+        assert!(swap.process_fields().detailed_memory);
+
+        // Memory details that weren't read never match.
+        let mut process = simple_process("test");
+        assert!(!pss.check(&process, false));
+
+        process.memory_details = Some(MemoryDetails {
+            pss: 200 * 1024 * 1024,
+            uss: 150 * 1024 * 1024,
+            shared: 50 * 1024 * 1024,
+            swap: 0,
+        });
+        assert!(pss.check(&process, false));
+        assert!(!swap.check(&process, false));
+        assert!(
+            parse_query_no_options("uss < 200 mib and shared = 50 mib")
+                .unwrap()
+                .check(&process, false)
+        );
+    }
+
     // TODO: Test all attribute keywords (e.g. cpu, mem, etc.)
     // #[test]
     // fn test_all_attribute_keywords() {}
@@ -2,8 +2,10 @@
 
 use regex::Regex;
 
+#[cfg(target_os = "linux")]
+use crate::utils::general::utc_date_time;
 use crate::{
-    collection::processes::ProcessHarvest,
+    collection::processes::{ProcessFields, ProcessHarvest},
     widgets::query::{
         NumericalQuery, PrefixType, QueryOptions, TimeQuery,
         error::{QueryError, QueryResult},
@@ -34,6 +36,46 @@ pub(super) enum ProcessAttribute {
     #[cfg(unix)]
     Nice(NumericalQuery),
     Priority(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    TcpRxPerSecond(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    TcpTxPerSecond(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    TotalTcpRx(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    TotalTcpTx(NumericalQuery),
+    Ppid(Regex),
+    #[cfg(target_os = "linux")]
+    Threads(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    Fds(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    VoluntaryCtxSwitches(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    InvoluntaryCtxSwitches(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    MinorFaultsPerSecond(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    MajorFaultsPerSecond(NumericalQuery),
+    /// Matched against the start time as shown in the table.
+    #[cfg(target_os = "linux")]
+    StartTime(Regex),
+    #[cfg(target_os = "linux")]
+    Tty(Regex),
+    #[cfg(target_os = "linux")]
+    Exe(Regex),
+    #[cfg(target_os = "linux")]
+    OomScore(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    LastCpu(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    Pss(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    Uss(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    SharedMem(NumericalQuery),
+    #[cfg(target_os = "linux")]
+    Swap(NumericalQuery),
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
     GpuPercentage(NumericalQuery),
     #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -69,6 +111,66 @@ impl ProcessAttribute {
             #[cfg(unix)]
             ProcessAttribute::Nice(cmp) => cmp.check(process.nice as f64),
             ProcessAttribute::Priority(cmp) => cmp.check(process.priority as f64),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::TcpRxPerSecond(cmp) => cmp.check(process.tcp_rx_per_sec as f64),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::TcpTxPerSecond(cmp) => cmp.check(process.tcp_tx_per_sec as f64),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::TotalTcpRx(cmp) => cmp.check(process.total_tcp_rx as f64),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::TotalTcpTx(cmp) => cmp.check(process.total_tcp_tx as f64),
+            ProcessAttribute::Ppid(re) => match process.parent_pid {
+                Some(ppid) => re.is_match(ppid.to_string().as_str()),
+                None => re.is_match("N/A"),
+            },
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::Threads(cmp) => cmp.check(process.num_threads as f64),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::Fds(cmp) => process.fd_count.is_some_and(|fds| cmp.check(fds as f64)),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::VoluntaryCtxSwitches(cmp) => process
+                .voluntary_ctx_switches
+                .is_some_and(|count| cmp.check(count as f64)),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::InvoluntaryCtxSwitches(cmp) => process
+                .involuntary_ctx_switches
+                .is_some_and(|count| cmp.check(count as f64)),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::MinorFaultsPerSecond(cmp) => {
+                cmp.check(process.minor_faults_per_sec as f64)
+            }
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::MajorFaultsPerSecond(cmp) => {
+                cmp.check(process.major_faults_per_sec as f64)
+            }
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::StartTime(re) => re.is_match(&utc_date_time(process.start_time)),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::Tty(re) => re.is_match(process.tty.as_deref().unwrap_or("?")),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::Exe(re) => re.is_match(process.exe.as_deref().unwrap_or("N/A")),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::OomScore(cmp) => process
+                .oom_score
+                .is_some_and(|score| cmp.check(score as f64)),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::LastCpu(cmp) => cmp.check(process.last_cpu as f64),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::Pss(cmp) => process
+                .memory_details
+                .is_some_and(|details| cmp.check(details.pss as f64)),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::Uss(cmp) => process
+                .memory_details
+                .is_some_and(|details| cmp.check(details.uss as f64)),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::SharedMem(cmp) => process
+                .memory_details
+                .is_some_and(|details| cmp.check(details.shared as f64)),
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::Swap(cmp) => process
+                .memory_details
+                .is_some_and(|details| cmp.check(details.swap as f64)),
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
             ProcessAttribute::GpuPercentage(cmp) => cmp.check(process.gpu_util as f64),
             #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
@@ -77,6 +179,50 @@ impl ProcessAttribute {
             ProcessAttribute::GpuMemoryBytes(cmp) => cmp.check(process.gpu_mem as f64),
         }
     }
+
+    /// Returns the slower process details needed to check this attribute.
+    pub(super) fn process_fields(&self) -> ProcessFields {
+        match self {
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::TcpRxPerSecond(_)
+            | ProcessAttribute::TcpTxPerSecond(_)
+            | ProcessAttribute::TotalTcpRx(_)
+            | ProcessAttribute::TotalTcpTx(_) => ProcessFields {
+                tcp_traffic: true,
+                ..Default::default()
+            },
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::Fds(_) => ProcessFields {
+                fd_count: true,
+                ..Default::default()
+            },
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::VoluntaryCtxSwitches(_)
+            | ProcessAttribute::InvoluntaryCtxSwitches(_) => ProcessFields {
+                context_switches: true,
+                ..Default::default()
+            },
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::Exe(_) => ProcessFields {
+                exe: true,
+                ..Default::default()
+            },
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::OomScore(_) => ProcessFields {
+                oom_score: true,
+                ..Default::default()
+            },
+            #[cfg(target_os = "linux")]
+            ProcessAttribute::Pss(_)
+            | ProcessAttribute::Uss(_)
+            | ProcessAttribute::SharedMem(_)
+            | ProcessAttribute::Swap(_) => ProcessFields {
+                detailed_memory: true,
+                ..Default::default()
+            },
+            _ => ProcessFields::default(),
+        }
+    }
 }
 
 /// Given a string prefix type, obtain the appropriate [`ProcessAttribute`].
@@ -84,17 +230,17 @@ pub(super) fn new_string_attribute(
     prefix_type: PrefixType, base: &str, regex_options: &QueryOptions,
 ) -> QueryResult<ProcessAttribute> {
     match prefix_type {
-        PrefixType::Pid | PrefixType::Name | PrefixType::State | PrefixType::User => {
-            let re = new_regex(base, regex_options)?;
-
-            match prefix_type {
-                PrefixType::Pid => Ok(ProcessAttribute::Pid(re)),
-                PrefixType::Name => Ok(ProcessAttribute::Name(re)),
-                PrefixType::State => Ok(ProcessAttribute::State(re)),
-                PrefixType::User => Ok(ProcessAttribute::User(re)),
-                _ => unreachable!(),
-            }
-        }
+        PrefixType::Pid => Ok(ProcessAttribute::Pid(new_regex(base, regex_options)?)),
+        PrefixType::Name => Ok(ProcessAttribute::Name(new_regex(base, regex_options)?)),
+        PrefixType::State => Ok(ProcessAttribute::State(new_regex(base, regex_options)?)),
+        PrefixType::User => Ok(ProcessAttribute::User(new_regex(base, regex_options)?)),
+        PrefixType::Ppid => Ok(ProcessAttribute::Ppid(new_regex(base, regex_options)?)),
+        #[cfg(target_os = "linux")]
+        PrefixType::StartTime => Ok(ProcessAttribute::StartTime(new_regex(base, regex_options)?)),
+        #[cfg(target_os = "linux")]
+        PrefixType::Tty => Ok(ProcessAttribute::Tty(new_regex(base, regex_options)?)),
+        #[cfg(target_os = "linux")]
+        PrefixType::Exe => Ok(ProcessAttribute::Exe(new_regex(base, regex_options)?)),
         _ => Err(QueryError::new(format!(
             "process attribute type {prefix_type:?} is not a supported string attribute"
         ))),
@@ -128,6 +274,38 @@ pub(super) fn new_numerical_attribute(
         #[cfg(unix)]
         PrefixType::Nice => Ok(ProcessAttribute::Nice(query)),
         PrefixType::Priority => Ok(ProcessAttribute::Priority(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::TcpRxPerSecond => Ok(ProcessAttribute::TcpRxPerSecond(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::TcpTxPerSecond => Ok(ProcessAttribute::TcpTxPerSecond(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::TotalTcpRx => Ok(ProcessAttribute::TotalTcpRx(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::TotalTcpTx => Ok(ProcessAttribute::TotalTcpTx(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::Threads => Ok(ProcessAttribute::Threads(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::Fds => Ok(ProcessAttribute::Fds(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::VoluntaryCtxSwitches => Ok(ProcessAttribute::VoluntaryCtxSwitches(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::InvoluntaryCtxSwitches => Ok(ProcessAttribute::InvoluntaryCtxSwitches(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::MinorFaultsPerSecond => Ok(ProcessAttribute::MinorFaultsPerSecond(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::MajorFaultsPerSecond => Ok(ProcessAttribute::MajorFaultsPerSecond(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::OomScore => Ok(ProcessAttribute::OomScore(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::LastCpu => Ok(ProcessAttribute::LastCpu(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::Pss => Ok(ProcessAttribute::Pss(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::Uss => Ok(ProcessAttribute::Uss(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::SharedMem => Ok(ProcessAttribute::SharedMem(query)),
+        #[cfg(target_os = "linux")]
+        PrefixType::Swap => Ok(ProcessAttribute::Swap(query)),
         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
         PrefixType::GpuPercentage => Ok(ProcessAttribute::GpuPercentage(query)),
         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
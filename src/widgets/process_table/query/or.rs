@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 
 use crate::{
-    collection::processes::ProcessHarvest,
+    collection::processes::{ProcessFields, ProcessHarvest},
     widgets::query::{
         And, COMPARISON_LIST, Prefix, QueryOptions, QueryProcessor, QueryResult, error::QueryError,
     },
@@ -24,6 +24,13 @@ impl Or {
             self.lhs.check(process, is_using_command)
         }
     }
+
+    pub(super) fn process_fields(&self) -> ProcessFields {
+        match &self.rhs {
+            Some(rhs) => self.lhs.process_fields().union(rhs.process_fields()),
+            None => self.lhs.process_fields(),
+        }
+    }
 }
 
 impl QueryProcessor for Or {
@@ -3,7 +3,7 @@ use std::{collections::VecDeque, fmt::Debug};
 use humantime::parse_duration;
 
 use crate::{
-    collection::processes::ProcessHarvest,
+    collection::processes::{ProcessFields, ProcessHarvest},
     utils::data_units::*,
     widgets::query::{
         And, NumericalQuery, Or, PrefixType, QueryComparison, QueryOptions, QueryProcessor,
@@ -72,6 +72,13 @@ impl Prefix {
         }
     }
 
+    pub(super) fn process_fields(&self) -> ProcessFields {
+        match self {
+            Prefix::Or(or) => or.process_fields(),
+            Prefix::Attribute(attribute) => attribute.process_fields(),
+        }
+    }
+
     fn process_in_quotes(
         query: &mut VecDeque<String>, options: &QueryOptions,
     ) -> QueryResult<Self> {
@@ -199,7 +206,7 @@ impl QueryProcessor for Prefix {
                                 options,
                             )?));
                         }
-                        PrefixType::Pid | PrefixType::State | PrefixType::User => {
+                        _ if prefix_type.is_string() => {
                             // We have to check if someone put an "="...
                             if content == "=" {
                                 // Check next string if possible
@@ -352,6 +359,20 @@ impl QueryProcessor for Prefix {
                                         | PrefixType::TotalWrite => {
                                             process_prefix_units(query, &mut value);
                                         }
+                                        #[cfg(target_os = "linux")]
+                                        PrefixType::TcpRxPerSecond
+                                        | PrefixType::TcpTxPerSecond
+                                        | PrefixType::TotalTcpRx
+                                        | PrefixType::TotalTcpTx => {
+                                            process_prefix_units(query, &mut value);
+                                        }
+                                        #[cfg(target_os = "linux")]
+                                        PrefixType::Pss
+                                        | PrefixType::Uss
+                                        | PrefixType::SharedMem
+                                        | PrefixType::Swap => {
+                                            process_prefix_units(query, &mut value);
+                                        }
                                         #[cfg(any(feature = "gpu", feature = "apple-gpu"))]
                                         PrefixType::GpuMemoryBytes => {
                                             process_prefix_units(query, &mut value);
@@ -3,19 +3,23 @@ use std::{borrow::Cow, cmp::max, num::NonZeroU16};
 use crate::{
     app::{AppConfigFields, data::TypedTemperature},
     canvas::components::data_table::{
-        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
-        SortDataTable, SortDataTableProps, SortOrder, SortsRow,
+        ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell,
+        PickableColumns, SortColumn, SortDataTable, SortDataTableProps, SortOrder, SortsRow,
     },
     options::config::style::Styles,
-    utils::general::sort_partial_fn,
+    utils::{clipboard::CopyTarget, general::sort_partial_fn},
 };
 
 #[derive(Clone, Debug)]
 pub struct TempWidgetData {
     pub sensor: String,
+
+    /// The reading in Celsius, kept to convert it if the unit is changed.
+    pub celsius: Option<f32>,
     pub temperature: Option<TypedTemperature>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TempWidgetColumn {
     Sensor,
     Temp,
@@ -50,19 +54,23 @@ impl DataToCell<TempWidgetColumn> for TempWidgetData {
     }
 
     fn column_widths<C: DataTableColumn<TempWidgetColumn>>(
-        data: &[TempWidgetData], _columns: &[C],
+        data: &[TempWidgetData], columns: &[C],
     ) -> Vec<u16>
     where
         Self: Sized,
     {
-        let mut widths = vec![0; 2];
-
-        data.iter().for_each(|row| {
-            widths[0] = max(widths[0], row.sensor.len() as u16);
-            widths[1] = max(widths[1], row.temperature().len() as u16);
-        });
-
-        widths
+        columns
+            .iter()
+            .map(|column| {
+                data.iter().fold(0, |acc, row| {
+                    let len = match column.inner() {
+                        TempWidgetColumn::Sensor => row.sensor.len(),
+                        TempWidgetColumn::Temp => row.temperature().len(),
+                    };
+                    max(acc, len as u16)
+                })
+            })
+            .collect()
     }
 }
 
@@ -81,6 +89,15 @@ impl SortsRow for TempWidgetColumn {
     }
 }
 
+fn create_column(column: TempWidgetColumn) -> SortColumn<TempWidgetColumn> {
+    match column {
+        TempWidgetColumn::Sensor => SortColumn::soft(TempWidgetColumn::Sensor, Some(0.8)),
+        TempWidgetColumn::Temp => {
+            SortColumn::soft(TempWidgetColumn::Temp, None).default_descending()
+        }
+    }
+}
+
 pub struct TempWidgetState {
     pub table: SortDataTable<TempWidgetData, TempWidgetColumn>,
     pub force_update_data: bool,
@@ -89,8 +106,8 @@ pub struct TempWidgetState {
 impl TempWidgetState {
     pub(crate) fn new(config: &AppConfigFields, palette: &Styles) -> Self {
         let columns = [
-            SortColumn::soft(TempWidgetColumn::Sensor, Some(0.8)),
-            SortColumn::soft(TempWidgetColumn::Temp, None).default_descending(),
+            create_column(TempWidgetColumn::Sensor),
+            create_column(TempWidgetColumn::Temp),
         ];
 
         let props = SortDataTableProps {
@@ -120,6 +137,12 @@ impl TempWidgetState {
         self.force_update_data = true;
     }
 
+    /// Keeps the column widths, sorting, and scroll position of `old`, the
+    /// state of this widget before the config was reloaded.
+    pub(crate) fn keep_runtime_state(&mut self, old: Self) {
+        self.table.keep_state_from(&old.table);
+    }
+
     /// Update the current table data.
     pub fn set_table_data(&mut self, data: &[TempWidgetData]) {
         let mut data = data.to_vec();
@@ -129,4 +152,65 @@ impl TempWidgetState {
         self.table.set_data(data);
         self.force_update_data = false;
     }
+
+    /// Returns the text to copy from the selected row. As there are no PIDs,
+    /// the sensor name is copied instead.
+    pub fn copy_text(&self, target: CopyTarget) -> Option<String> {
+        match target {
+            CopyTarget::Pid => self.table.current_item().map(|row| row.sensor.clone()),
+            CopyTarget::Row => self.table.current_row_text(),
+            CopyTarget::Cell => self
+                .table
+                .current_cell_text(self.table.sort_index())
+                .map(Cow::into_owned),
+        }
+    }
+}
+
+impl PickableColumns for TempWidgetState {
+    type Column = TempWidgetColumn;
+
+    fn available_columns(&self) -> Vec<TempWidgetColumn> {
+        vec![TempWidgetColumn::Sensor, TempWidgetColumn::Temp]
+    }
+
+    fn shown_columns(&self) -> Vec<TempWidgetColumn> {
+        self.table
+            .columns
+            .iter()
+            .map(|column| *column.inner())
+            .collect()
+    }
+
+    fn set_shown_columns(&mut self, columns: Vec<TempWidgetColumn>) {
+        self.table
+            .set_columns(columns.into_iter().map(create_column).collect());
+        self.force_data_update();
+    }
+
+    fn column_name(&self, column: TempWidgetColumn) -> Cow<'static, str> {
+        match column {
+            TempWidgetColumn::Sensor => "Sensor".into(),
+            TempWidgetColumn::Temp => "Temp".into(),
+        }
+    }
+
+    fn column_width(&self, column: TempWidgetColumn) -> Option<u16> {
+        self.table
+            .columns
+            .iter()
+            .position(|c| *c.inner() == column)
+            .and_then(|index| self.table.column_width(index))
+    }
+
+    fn resize_column(&mut self, column: TempWidgetColumn, delta: i16) {
+        if let Some(index) = self.table.columns.iter().position(|c| *c.inner() == column) {
+            self.table.resize_column(index, delta);
+        }
+    }
+
+    fn sort_by_column(&mut self, column: TempWidgetColumn) {
+        self.table.select_column(&column);
+        self.force_data_update();
+    }
 }
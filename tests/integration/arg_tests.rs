@@ -107,6 +107,83 @@ fn test_conflicting_temps() {
         .stderr(predicate::str::contains("cannot be used with"));
 }
 
+#[test]
+fn test_zero_inline_height() {
+    no_cfg_btm_command()
+        .arg("--inline")
+        .arg("0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'--inline' was set with an invalid value",
+        ));
+}
+
+#[test]
+fn test_invalid_capture_size() {
+    for size in ["120", "0x0", "65535x65535"] {
+        no_cfg_btm_command()
+            .arg("--capture")
+            .arg("frame.txt")
+            .arg("--capture_size")
+            .arg(size)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(format!(
+                "invalid value '{size}' for '--capture_size"
+            )));
+    }
+}
+
+#[test]
+fn test_capture() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("frame.txt");
+
+    no_cfg_btm_command()
+        .arg("--capture")
+        .arg(&path)
+        .arg("--capture_size")
+        .arg("80x24")
+        .arg("--capture_after")
+        .arg("1")
+        .assert()
+        .success();
+
+    let frame = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(frame.lines().count(), 24);
+    assert!(frame.contains("CPU"));
+}
+
+#[test]
+fn test_batch() {
+    no_cfg_btm_command()
+        .arg("--batch")
+        .arg("--batch_iterations")
+        .arg("2")
+        .arg("--batch_rows")
+        .arg("3")
+        .arg("--rate")
+        .arg("250")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::starts_with("PID").and(predicate::function(|output: &str| {
+                output.matches("PID").count() == 2 && output.lines().count() <= 9
+            })),
+        );
+}
+
+#[test]
+fn test_invalid_batch_query() {
+    no_cfg_btm_command()
+        .arg("--batch")
+        .arg("--batch_query")
+        .arg("cpu >")
+        .assert()
+        .failure();
+}
+
 #[test]
 fn test_invalid_default_widget_1() {
     no_cfg_btm_command()
@@ -175,6 +252,19 @@ fn test_gpu_flag() {
         ));
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn test_invalid_procfs_root() {
+    no_cfg_btm_command()
+        .arg("--procfs_root")
+        .arg("./tests/fixtures/linux/does_not_exist")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'--procfs_root' must be a directory",
+        ));
+}
+
 /// Sanity test due to <https://github.com/ClementTsang/bottom/pull/1478>.
 #[test]
 fn test_version() {